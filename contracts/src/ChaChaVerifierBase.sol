@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {IChaChaVerifier} from "./IChaChaVerifier.sol";
+
+/// @title ChaCha Proof Verifier Base
+/// @notice Emits `ProofVerified` from the public values of the encryption program, laid out as
+/// documented in `SP1Chacha`. `verify` is a view and can not emit, so implementations call
+/// `_emitProofVerified` from the function that accepts a proof.
+abstract contract ChaChaVerifierBase is IChaChaVerifier {
+    error PublicValuesTooShort();
+
+    /// @notice Emit `ProofVerified` on this chain for `publicValues`, whose ciphertext follows a
+    /// header of `headerLength` bytes.
+    function _emitProofVerified(bytes calldata publicValues, uint256 headerLength) internal {
+        if (headerLength < 65 || publicValues.length < headerLength) revert PublicValuesTooShort();
+        emit ProofVerified(
+            bytes32(publicValues[33:65]),
+            bytes32(publicValues[1:33]),
+            sha256(publicValues[headerLength:]),
+            block.chainid
+        );
+    }
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @title ChaCha Proof Verifier Interface
+/// @notice Implemented by every contract verifying proofs of the encryption program, so an
+/// integration can switch between them.
+interface IChaChaVerifier {
+    /// @notice Emitted when a proof is accepted.
+    /// @param nullifier The committed `sha256(key || nonce)`.
+    /// @param plaintextHash The committed SHA256 hash of the plaintext.
+    /// @param ciphertextHash The SHA256 hash of the ciphertext.
+    /// @param chainId The chain the proof was accepted on, as a proof commits to no chain.
+    event ProofVerified(bytes32 indexed nullifier, bytes32 plaintextHash, bytes32 ciphertextHash, uint256 chainId);
+
+    /// @notice Whether `proof` is a valid proof of `publicValues` for the program with `vkHash`.
+    /// @dev Does not revert for an invalid proof, and does not record the nullifier.
+    /// @param proof The proof encoded as bytes, e.g. by `SP1ProofWithPublicValues::bytes`.
+    /// @param vkHash The verification key hash of the program, printed by `cargo run --bin vkey`.
+    /// @param publicValues The public values committed by the program.
+    function verify(bytes calldata proof, bytes32 vkHash, bytes calldata publicValues)
+        external
+        view
+        returns (bool);
+}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @title Nullifier Registry
+/// @notice Records the nullifiers of verified encryptions so each proof is only accepted once.
+abstract contract NullifierRegistry {
+    /// @notice Nullifiers of every proof accepted so far.
+    mapping(bytes32 => bool) public used;
+
+    error NullifierUsed(bytes32 nullifier);
+
+    /// @notice Reverts if `nullifier` was already used, otherwise marks it as used.
+    modifier notUsed(bytes32 nullifier) {
+        if (used[nullifier]) revert NullifierUsed(nullifier);
+        used[nullifier] = true;
+        _;
+    }
+}
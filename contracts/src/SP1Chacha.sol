@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {ISP1Verifier} from "./ISP1Verifier.sol";
+import {NullifierRegistry} from "./NullifierRegistry.sol";
+
+/// @title SP1 ChaCha20 encryption verifier
+/// @notice Verifies proofs that a ciphertext is the ChaCha20 encryption of a plaintext with a
+/// committed SHA256 hash. Public values are laid out as:
+///
+///     version (1) | plaintext_hash (32) | nullifier (32) | key_hash (32) | segments_root (32) |
+///     timestamp_commitment (32) | custody_hash (32) | lineage_commitment (32) |
+///     plaintext_size (8) | ciphertext_is_hash (1) | input_hmac_key_hash (32) | ciphertext
+///
+/// Version 1 programs commit no custody hash, version 1 and 2 programs no lineage commitment,
+/// version 1 to 3 programs no plaintext size, version 1 to 5 programs no `ciphertext_is_hash` and
+/// version 1 to 6 programs no `input_hmac_key_hash`.
+/// Proofs are verified against the key registered for their committed program version.
+contract SP1Chacha is NullifierRegistry {
+    /// @notice The SP1 verifier gateway.
+    ISP1Verifier public immutable verifier;
+
+    /// @notice May register verification keys.
+    address public immutable owner;
+
+    /// @notice The verification key of each program version, printed by `cargo run --bin vk_registry`.
+    mapping(uint8 => bytes32) public vkByVersion;
+
+    /// @notice The verification key of the upgrade program, see `program/src/main_upgrade.rs`.
+    bytes32 public upgradeVKey;
+
+    event EncryptionVerified(bytes32 indexed plaintextHash, bytes32 indexed nullifier, bytes ciphertext);
+    event VKeySet(uint8 indexed version, bytes32 vkey);
+    event Upgraded(uint8 indexed oldVersion, uint8 indexed newVersion, bytes32 vkey);
+
+    error PublicValuesTooShort();
+    /// @notice The ciphertext does not match the committed hash.
+    error CiphertextMismatch();
+    error UnknownVersion(uint8 version);
+    /// @notice Programs before version 4 commit no plaintext size.
+    error NoPlaintextSize(uint8 version);
+    /// @notice The program committed the ciphertext hash, use `verifyEncryptionWithCiphertext`.
+    error CiphertextIsHash();
+    error NotOwner();
+    /// @notice No upgrade program key is set.
+    error UpgradeVKeyNotSet();
+    error MalformedUpgrade();
+    /// @notice The upgrade starts from a key that is not registered for its version.
+    error StaleUpgrade(uint8 version);
+    error VersionRegistered(uint8 version);
+
+    constructor(address _verifier, uint8 _version, bytes32 _programVKey) {
+        verifier = ISP1Verifier(_verifier);
+        owner = msg.sender;
+        vkByVersion[_version] = _programVKey;
+        emit VKeySet(_version, _programVKey);
+    }
+
+    /// @notice Register the verification key of a program version. Setting it to zero stops
+    /// accepting proofs of that version.
+    function setVKey(uint8 version, bytes32 vkey) external {
+        if (msg.sender != owner) revert NotOwner();
+        vkByVersion[version] = vkey;
+        emit VKeySet(version, vkey);
+    }
+
+    /// @notice Set the verification key of the upgrade program, printed by `cargo run --bin upgrade`.
+    function setUpgradeVKey(bytes32 vkey) external {
+        if (msg.sender != owner) revert NotOwner();
+        upgradeVKey = vkey;
+    }
+
+    /// @notice Register the key of a new program version with a proof that it encrypts like the
+    /// registered key of the version it upgrades. The proof does not show the new program is
+    /// correct for every input, so only the owner may upgrade.
+    /// @param publicValues `oldVersion (1) | oldVKey (32) | newVersion (1) | newVKey (32)`.
+    function upgrade(bytes calldata publicValues, bytes calldata proofBytes) external {
+        if (msg.sender != owner) revert NotOwner();
+        if (upgradeVKey == bytes32(0)) revert UpgradeVKeyNotSet();
+        if (publicValues.length != 66) revert MalformedUpgrade();
+        uint8 oldVersion = uint8(publicValues[0]);
+        uint8 newVersion = uint8(publicValues[33]);
+        bytes32 newVKey = bytes32(publicValues[34:66]);
+        if (vkByVersion[oldVersion] != bytes32(publicValues[1:33])) revert StaleUpgrade(oldVersion);
+        if (vkByVersion[newVersion] != bytes32(0)) revert VersionRegistered(newVersion);
+
+        verifier.verifyProof(upgradeVKey, publicValues, proofBytes);
+        vkByVersion[newVersion] = newVKey;
+        emit VKeySet(newVersion, newVKey);
+        emit Upgraded(oldVersion, newVersion, newVKey);
+    }
+
+    /// @notice Verify an encryption proof. Each proof is accepted only once. Programs before
+    /// version 6 do not commit whether the ciphertext is hashed, for those a 32 byte
+    /// `ciphertext` may be the hash of the ciphertext.
+    function verifyEncryption(bytes calldata publicValues, bytes calldata proofBytes)
+        external
+        notUsed(nullifierOf(publicValues))
+        returns (bytes32 plaintextHash, bytes calldata ciphertext)
+    {
+        if (ciphertextIsHashOf(publicValues)) revert CiphertextIsHash();
+        verifier.verifyProof(vkeyOf(publicValues), publicValues, proofBytes);
+        plaintextHash = bytes32(publicValues[1:33]);
+        ciphertext = publicValues[headerLengthOf(publicValues):];
+        emit EncryptionVerified(plaintextHash, bytes32(publicValues[33:65]), ciphertext);
+    }
+
+    /// @notice Verify a proof made with `--commit-ciphertext-hash`, checking the separately
+    /// submitted `ciphertext` against the committed hash. Each proof is accepted only once.
+    function verifyEncryptionWithCiphertext(
+        bytes calldata publicValues,
+        bytes calldata proofBytes,
+        bytes calldata ciphertext
+    ) external notUsed(nullifierOf(publicValues)) returns (bytes32 plaintextHash) {
+        // Only 32 bytes follow the header if the program committed the ciphertext hash
+        uint256 headerLength = headerLengthOf(publicValues);
+        if (publicValues.length != headerLength + 32) revert CiphertextMismatch();
+        if (uint8(publicValues[0]) >= 6 && !ciphertextIsHashOf(publicValues)) revert CiphertextMismatch();
+        if (sha256(ciphertext) != bytes32(publicValues[headerLength:])) revert CiphertextMismatch();
+        verifier.verifyProof(vkeyOf(publicValues), publicValues, proofBytes);
+        plaintextHash = bytes32(publicValues[1:33]);
+        emit EncryptionVerified(plaintextHash, bytes32(publicValues[33:65]), ciphertext);
+    }
+
+    /// @notice Whether `leaf` is at `index` in the tree of plaintext segments with `root`, the
+    /// `segments_root` of a verified proof. `leaf` must be computed as `sha256(0x00 || segment)`
+    /// from the revealed segment, and `proof` holds the sibling hashes from the leaf up to the
+    /// root, see `chacha_lib::merkle::merkle_prove`. The tree is ordered, so `index` picks the side
+    /// of each sibling and a proof verifies at one index only.
+    function verifyChunk(bytes32 root, bytes32 leaf, uint256 index, bytes32[] calldata proof)
+        public
+        pure
+        returns (bool)
+    {
+        if (index >> proof.length != 0) return false;
+        bytes32 node = leaf;
+        for (uint256 i = 0; i < proof.length; i++) {
+            node = ((index >> i) & 1) == 0
+                ? sha256(abi.encodePacked(bytes1(0x01), node, proof[i]))
+                : sha256(abi.encodePacked(bytes1(0x01), proof[i], node));
+        }
+        return node == root;
+    }
+
+    /// @notice The nullifier committed in `publicValues`.
+    function nullifierOf(bytes calldata publicValues) public pure returns (bytes32) {
+        if (publicValues.length < headerLengthOf(publicValues)) revert PublicValuesTooShort();
+        return bytes32(publicValues[33:65]);
+    }
+
+    /// @notice Length of the fields before the ciphertext for the program version committed in
+    /// `publicValues`.
+    function headerLengthOf(bytes calldata publicValues) public pure returns (uint256) {
+        if (publicValues.length == 0) revert PublicValuesTooShort();
+        uint8 version = uint8(publicValues[0]);
+        if (version == 1) return 161;
+        if (version == 2) return 193;
+        if (version == 3) return 225;
+        if (version == 4 || version == 5) return 233;
+        if (version == 6) return 234;
+        return 266;
+    }
+
+    /// @notice The length of the uncompressed plaintext committed in `publicValues`.
+    function plaintextSizeOf(bytes calldata publicValues) public pure returns (uint64) {
+        if (publicValues.length < headerLengthOf(publicValues)) revert PublicValuesTooShort();
+        uint8 version = uint8(publicValues[0]);
+        if (version < 4) revert NoPlaintextSize(version);
+        return uint64(bytes8(publicValues[225:233]));
+    }
+
+    /// @notice Whether the program committed `sha256(ciphertext)` instead of the ciphertext,
+    /// false for programs before version 6, which do not commit it.
+    function ciphertextIsHashOf(bytes calldata publicValues) public pure returns (bool) {
+        if (publicValues.length < headerLengthOf(publicValues)) revert PublicValuesTooShort();
+        return uint8(publicValues[0]) >= 6 && uint8(publicValues[233]) != 0;
+    }
+
+    /// @notice `sha256(hmacKey)` of the `--hmac-key` the inputs were authenticated with, zero
+    /// without input authentication and for programs before version 7, which do not commit it.
+    function inputHmacKeyHashOf(bytes calldata publicValues) public pure returns (bytes32) {
+        if (publicValues.length < headerLengthOf(publicValues)) revert PublicValuesTooShort();
+        if (uint8(publicValues[0]) < 7) return bytes32(0);
+        return bytes32(publicValues[234:266]);
+    }
+
+    /// @notice The verification key for the program version committed in `publicValues`.
+    function vkeyOf(bytes calldata publicValues) public view returns (bytes32 vkey) {
+        if (publicValues.length == 0) revert PublicValuesTooShort();
+        uint8 version = uint8(publicValues[0]);
+        vkey = vkByVersion[version];
+        if (vkey == bytes32(0)) revert UnknownVersion(version);
+    }
+}
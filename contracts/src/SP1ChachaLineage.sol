@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {ISP1Verifier} from "./ISP1Verifier.sol";
+
+/// @title SP1 ChaCha20 data lineage registry
+/// @notice Records the lineage commitments of encryption proofs, linking each pipeline stage to the
+/// stage before. A version 3 or later encryption proof commits
+///
+///     lineage_commitment = sha256(lineage_hash || plaintext_hash)
+///
+/// at bytes 193..225 of its public values, where `lineage_hash` is the lineage commitment of the
+/// previous stage, or zero for the first stage.
+contract SP1ChachaLineage {
+    /// @notice The SP1 verifier gateway.
+    ISP1Verifier public immutable verifier;
+
+    /// @notice The verification key of the encryption program.
+    bytes32 public immutable programVKey;
+
+    /// @notice The lineage hash of each recorded lineage commitment.
+    mapping(bytes32 => bytes32) public parentOf;
+
+    /// @notice Whether a lineage commitment was recorded.
+    mapping(bytes32 => bool) public recorded;
+
+    event StageRecorded(bytes32 indexed commitment, bytes32 indexed parent, bytes32 plaintextHash);
+
+    error PublicValuesTooShort();
+    /// @notice The program version commits no lineage.
+    error NoLineage(uint8 version);
+    /// @notice `parent` is not zero and was never recorded.
+    error UnknownParent(bytes32 parent);
+    /// @notice The committed lineage does not link `parent` to the plaintext hash.
+    error LineageMismatch();
+    error AlreadyRecorded(bytes32 commitment);
+
+    constructor(address _verifier, bytes32 _programVKey) {
+        verifier = ISP1Verifier(_verifier);
+        programVKey = _programVKey;
+    }
+
+    /// @notice Verify an encryption proof of the stage after `parent` and record its lineage
+    /// commitment.
+    function recordStage(bytes calldata publicValues, bytes calldata proofBytes, bytes32 parent)
+        external
+        returns (bytes32 commitment)
+    {
+        if (publicValues.length < 225) revert PublicValuesTooShort();
+        uint8 version = uint8(publicValues[0]);
+        if (version < 3) revert NoLineage(version);
+        if (parent != bytes32(0) && !recorded[parent]) revert UnknownParent(parent);
+        bytes32 plaintextHash = bytes32(publicValues[1:33]);
+        commitment = bytes32(publicValues[193:225]);
+        if (sha256(abi.encodePacked(parent, plaintextHash)) != commitment) revert LineageMismatch();
+        if (recorded[commitment]) revert AlreadyRecorded(commitment);
+
+        verifier.verifyProof(programVKey, publicValues, proofBytes);
+        recorded[commitment] = true;
+        parentOf[commitment] = parent;
+        emit StageRecorded(commitment, parent, plaintextHash);
+    }
+}
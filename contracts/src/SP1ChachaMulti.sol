@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {ISP1Verifier} from "./ISP1Verifier.sol";
+
+/// @title SP1 ChaCha20 multi-recipient verifier
+/// @notice Verifies proofs that one plaintext was encrypted for several keys. Public values are
+/// laid out as:
+///
+///     sha256(plaintext) | (sha256(key_i) | sha256(ciphertext_i)) for each recipient
+contract SP1ChachaMulti {
+    /// @notice The SP1 verifier gateway.
+    ISP1Verifier public immutable verifier;
+
+    /// @notice The verification key of the multi-recipient program.
+    bytes32 public immutable programVKey;
+
+    error MalformedPublicValues();
+    error NotARecipient(bytes32 keyHash);
+    error CiphertextMismatch();
+
+    constructor(address _verifier, bytes32 _programVKey) {
+        verifier = ISP1Verifier(_verifier);
+        programVKey = _programVKey;
+    }
+
+    /// @notice Verify that `ciphertext` is the encryption for the recipient with `keyHash`, so the
+    /// recipient can decrypt it to the plaintext with the returned hash.
+    function verifyForRecipient(
+        bytes calldata publicValues,
+        bytes calldata proofBytes,
+        bytes32 keyHash,
+        bytes calldata ciphertext
+    ) external view returns (bytes32 plaintextHash) {
+        if (publicValues.length < 32 || (publicValues.length - 32) % 64 != 0) {
+            revert MalformedPublicValues();
+        }
+        verifier.verifyProof(programVKey, publicValues, proofBytes);
+
+        for (uint256 i = 32; i < publicValues.length; i += 64) {
+            if (bytes32(publicValues[i:i + 32]) != keyHash) continue;
+            if (bytes32(publicValues[i + 32:i + 64]) != sha256(ciphertext)) revert CiphertextMismatch();
+            return bytes32(publicValues[0:32]);
+        }
+        revert NotARecipient(keyHash);
+    }
+
+    /// @notice Number of recipients committed in `publicValues`.
+    function recipientCount(bytes calldata publicValues) external pure returns (uint256) {
+        return (publicValues.length - 32) / 64;
+    }
+}
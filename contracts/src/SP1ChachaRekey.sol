@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {ISP1Verifier} from "./ISP1Verifier.sol";
+
+/// @title SP1 ChaCha20 re-keying verifier
+/// @notice Verifies proofs that a stored ciphertext was re-encrypted from an expiring key to a new
+/// one. Unlike `SP1ChachaRotation` the new ciphertext stays off-chain, public values are laid out
+/// as:
+///
+///     sha256(old_key) | sha256(new_key) | sha256(plaintext) | sha256(ciphertext_new)
+contract SP1ChachaRekey {
+    /// @notice The SP1 verifier gateway.
+    ISP1Verifier public immutable verifier;
+
+    /// @notice The verification key of the re-keying program.
+    bytes32 public immutable programVKey;
+
+    event Rekeyed(bytes32 oldKeyHash, bytes32 newKeyHash, bytes32 plaintextHash, bytes32 ciphertextHash);
+
+    error InvalidPublicValuesLength();
+
+    constructor(address _verifier, bytes32 _programVKey) {
+        verifier = ISP1Verifier(_verifier);
+        programVKey = _programVKey;
+    }
+
+    /// @notice Verify a re-keying proof and emit `Rekeyed`. Check the stored ciphertext against
+    /// `ciphertextHash` before deleting the old one.
+    function verifyRekey(bytes calldata publicValues, bytes calldata proofBytes)
+        external
+        returns (bytes32 oldKeyHash, bytes32 newKeyHash, bytes32 plaintextHash, bytes32 ciphertextHash)
+    {
+        if (publicValues.length != 128) revert InvalidPublicValuesLength();
+        verifier.verifyProof(programVKey, publicValues, proofBytes);
+        oldKeyHash = bytes32(publicValues[0:32]);
+        newKeyHash = bytes32(publicValues[32:64]);
+        plaintextHash = bytes32(publicValues[64:96]);
+        ciphertextHash = bytes32(publicValues[96:128]);
+        emit Rekeyed(oldKeyHash, newKeyHash, plaintextHash, ciphertextHash);
+    }
+}
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {ISP1Verifier} from "./ISP1Verifier.sol";
+
+/// @title SP1 ChaCha20 key rotation verifier
+/// @notice Verifies proofs that a ciphertext was re-encrypted from one key to another. Public
+/// values are laid out as:
+///
+///     sha256(old_key) | sha256(new_key) | sha256(plaintext) | ciphertext_new
+contract SP1ChachaRotation {
+    /// @notice The SP1 verifier gateway.
+    ISP1Verifier public immutable verifier;
+
+    /// @notice The verification key of the rotation program.
+    bytes32 public immutable programVKey;
+
+    event KeyRotated(bytes32 oldKeyHash, bytes32 newKeyHash);
+
+    error PublicValuesTooShort();
+
+    constructor(address _verifier, bytes32 _programVKey) {
+        verifier = ISP1Verifier(_verifier);
+        programVKey = _programVKey;
+    }
+
+    /// @notice Verify a key rotation proof and emit `KeyRotated`.
+    function verifyRotation(bytes calldata publicValues, bytes calldata proofBytes)
+        external
+        returns (bytes32 oldKeyHash, bytes32 newKeyHash, bytes32 plaintextHash)
+    {
+        if (publicValues.length < 96) revert PublicValuesTooShort();
+        verifier.verifyProof(programVKey, publicValues, proofBytes);
+        oldKeyHash = bytes32(publicValues[0:32]);
+        newKeyHash = bytes32(publicValues[32:64]);
+        plaintextHash = bytes32(publicValues[64:96]);
+        emit KeyRotated(oldKeyHash, newKeyHash);
+    }
+}
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {MockVerifier} from "./MockVerifier.sol";
+import {ChaChaVerifierBase} from "../src/ChaChaVerifierBase.sol";
+import {IChaChaVerifier} from "../src/IChaChaVerifier.sol";
+import {ISP1Verifier} from "../src/ISP1Verifier.sol";
+
+/// Rejects every proof.
+contract RejectingVerifier is ISP1Verifier {
+    function verifyProof(bytes32, bytes calldata, bytes calldata) external pure {
+        revert("invalid proof");
+    }
+}
+
+/// A minimal implementation accepting proofs of one program.
+contract MockChaChaVerifier is ChaChaVerifierBase {
+    ISP1Verifier immutable verifier;
+    bytes32 immutable vkey;
+
+    constructor(ISP1Verifier _verifier, bytes32 _vkey) {
+        verifier = _verifier;
+        vkey = _vkey;
+    }
+
+    function verify(bytes calldata proof, bytes32 vkHash, bytes calldata publicValues)
+        external
+        view
+        returns (bool)
+    {
+        if (vkHash != vkey) return false;
+        try verifier.verifyProof(vkHash, publicValues, proof) {
+            return true;
+        } catch {
+            return false;
+        }
+    }
+
+    function accept(bytes calldata proof, bytes calldata publicValues) external {
+        require(this.verify(proof, vkey, publicValues), "rejected");
+        _emitProofVerified(publicValues, 233);
+    }
+}
+
+contract ChaChaVerifierBaseTest {
+    bytes32 constant VKEY = keccak256("vkey");
+
+    function publicValues() internal pure returns (bytes memory) {
+        return abi.encodePacked(uint8(4), keccak256("plaintext"), keccak256("nullifier"), new bytes(199), hex"deadbeef");
+    }
+
+    function testInterfaceSelectors() public pure {
+        require(
+            IChaChaVerifier.verify.selector == bytes4(keccak256("verify(bytes,bytes32,bytes)")), "verify selector"
+        );
+        require(type(IChaChaVerifier).interfaceId == IChaChaVerifier.verify.selector, "interface id");
+        require(
+            IChaChaVerifier.ProofVerified.selector == keccak256("ProofVerified(bytes32,bytes32,bytes32,uint256)"),
+            "event selector"
+        );
+    }
+
+    function testVerify() public {
+        IChaChaVerifier chacha = new MockChaChaVerifier(new MockVerifier(), VKEY);
+        require(chacha.verify("", VKEY, publicValues()), "valid proof");
+        require(!chacha.verify("", keccak256("other vkey"), publicValues()), "other key");
+
+        IChaChaVerifier rejecting = new MockChaChaVerifier(new RejectingVerifier(), VKEY);
+        require(!rejecting.verify("", VKEY, publicValues()), "invalid proof");
+    }
+
+    function testAcceptChecksLength() public {
+        MockChaChaVerifier chacha = new MockChaChaVerifier(new MockVerifier(), VKEY);
+        chacha.accept("", publicValues());
+
+        try chacha.accept("", new bytes(100)) {
+            revert("short public values were accepted");
+        } catch (bytes memory reason) {
+            require(
+                keccak256(reason) == keccak256(abi.encodeWithSelector(ChaChaVerifierBase.PublicValuesTooShort.selector)),
+                "unexpected revert reason"
+            );
+        }
+    }
+}
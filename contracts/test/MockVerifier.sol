@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {ISP1Verifier} from "../src/ISP1Verifier.sol";
+
+/// Accepts every proof, the zkVM proof itself is covered by the SP1 verifier contracts.
+contract MockVerifier is ISP1Verifier {
+    function verifyProof(bytes32, bytes calldata, bytes calldata) external pure {}
+}
@@ -0,0 +1,281 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {NullifierRegistry} from "../src/NullifierRegistry.sol";
+import {MockVerifier} from "./MockVerifier.sol";
+import {SP1Chacha} from "../src/SP1Chacha.sol";
+
+contract SP1ChachaTest {
+    SP1Chacha chacha;
+
+    bytes32 constant PLAINTEXT_HASH = keccak256("plaintext");
+    bytes32 constant NULLIFIER = keccak256("nullifier");
+    bytes32 constant KEY_HASH = keccak256("key");
+    bytes32 constant SEGMENTS_ROOT = bytes32(0);
+    bytes32 constant TIMESTAMP = bytes32(0);
+    bytes32 constant CUSTODY_HASH = keccak256("custody");
+    bytes32 constant LINEAGE = keccak256("lineage");
+    uint64 constant PLAINTEXT_SIZE = 4;
+    uint8 constant CIPHERTEXT = 0;
+    uint8 constant CIPHERTEXT_HASH = 1;
+    bytes32 constant HMAC_KEY_HASH = keccak256("hmac key");
+    uint8 constant VERSION = 7;
+    bytes32 constant VKEY = keccak256("vkey");
+
+    function setUp() public {
+        chacha = new SP1Chacha(address(new MockVerifier()), VERSION, VKEY);
+    }
+
+    function publicValues() internal pure returns (bytes memory) {
+        return abi.encodePacked(VERSION, PLAINTEXT_HASH, NULLIFIER, KEY_HASH, SEGMENTS_ROOT, TIMESTAMP, CUSTODY_HASH, LINEAGE, PLAINTEXT_SIZE, CIPHERTEXT, HMAC_KEY_HASH, hex"deadbeef");
+    }
+
+    function testVerifyEncryption() public {
+        (bytes32 plaintextHash, bytes memory ciphertext) = chacha.verifyEncryption(publicValues(), "");
+        require(plaintextHash == PLAINTEXT_HASH, "plaintext hash");
+        require(keccak256(ciphertext) == keccak256(hex"deadbeef"), "ciphertext");
+        require(chacha.used(NULLIFIER), "nullifier not recorded");
+    }
+
+    function testVerifyEncryptionWithCiphertext() public {
+        bytes memory ciphertext = hex"deadbeef";
+        bytes memory hashed =
+            abi.encodePacked(VERSION, PLAINTEXT_HASH, NULLIFIER, KEY_HASH, SEGMENTS_ROOT, TIMESTAMP, CUSTODY_HASH, LINEAGE, PLAINTEXT_SIZE, CIPHERTEXT_HASH, HMAC_KEY_HASH, sha256(ciphertext));
+        require(chacha.verifyEncryptionWithCiphertext(hashed, "", ciphertext) == PLAINTEXT_HASH, "plaintext hash");
+    }
+
+    function testWrongCiphertextReverts() public {
+        bytes memory hashed =
+            abi.encodePacked(VERSION, PLAINTEXT_HASH, NULLIFIER, KEY_HASH, SEGMENTS_ROOT, TIMESTAMP, CUSTODY_HASH, LINEAGE, PLAINTEXT_SIZE, CIPHERTEXT_HASH, HMAC_KEY_HASH, sha256(hex"deadbeef"));
+        try chacha.verifyEncryptionWithCiphertext(hashed, "", hex"c0ffee") {
+            revert("wrong ciphertext was accepted");
+        } catch (bytes memory reason) {
+            require(bytes4(reason) == SP1Chacha.CiphertextMismatch.selector, "unexpected revert reason");
+        }
+    }
+
+    function testCiphertextHashIsNotTheCiphertext() public {
+        bytes memory hashed = abi.encodePacked(
+            VERSION, PLAINTEXT_HASH, NULLIFIER, KEY_HASH, SEGMENTS_ROOT, TIMESTAMP, CUSTODY_HASH, LINEAGE, PLAINTEXT_SIZE, CIPHERTEXT_HASH, HMAC_KEY_HASH, sha256(hex"deadbeef")
+        );
+        require(chacha.ciphertextIsHashOf(hashed), "hashed");
+        require(!chacha.ciphertextIsHashOf(publicValues()), "not hashed");
+        try chacha.verifyEncryption(hashed, "") {
+            revert("ciphertext hash was accepted as the ciphertext");
+        } catch (bytes memory reason) {
+            require(bytes4(reason) == SP1Chacha.CiphertextIsHash.selector, "unexpected revert reason");
+        }
+    }
+
+    function testCiphertextIsNotItsHash() public {
+        // A 32 byte ciphertext that happens to be the hash of the submitted one
+        bytes memory ciphertext = hex"deadbeef";
+        bytes memory full = abi.encodePacked(
+            VERSION, PLAINTEXT_HASH, NULLIFIER, KEY_HASH, SEGMENTS_ROOT, TIMESTAMP, CUSTODY_HASH, LINEAGE, PLAINTEXT_SIZE, CIPHERTEXT, HMAC_KEY_HASH, sha256(ciphertext)
+        );
+        try chacha.verifyEncryptionWithCiphertext(full, "", ciphertext) {
+            revert("ciphertext was accepted as a hash");
+        } catch (bytes memory reason) {
+            require(bytes4(reason) == SP1Chacha.CiphertextMismatch.selector, "unexpected revert reason");
+        }
+    }
+
+    function testReplayReverts() public {
+        chacha.verifyEncryption(publicValues(), "");
+        try chacha.verifyEncryption(publicValues(), "") {
+            revert("replayed proof was accepted");
+        } catch (bytes memory reason) {
+            require(
+                keccak256(reason)
+                    == keccak256(abi.encodeWithSelector(NullifierRegistry.NullifierUsed.selector, NULLIFIER)),
+                "unexpected revert reason"
+            );
+        }
+    }
+
+    function testUnknownVersionReverts() public {
+        bytes memory v8 = abi.encodePacked(
+            uint8(8), PLAINTEXT_HASH, NULLIFIER, KEY_HASH, SEGMENTS_ROOT, TIMESTAMP, CUSTODY_HASH, LINEAGE, PLAINTEXT_SIZE, CIPHERTEXT, HMAC_KEY_HASH
+        );
+        try chacha.verifyEncryption(v8, "") {
+            revert("unknown version was accepted");
+        } catch (bytes memory reason) {
+            require(
+                keccak256(reason) == keccak256(abi.encodeWithSelector(SP1Chacha.UnknownVersion.selector, uint8(8))),
+                "unexpected revert reason"
+            );
+        }
+
+        chacha.setVKey(8, keccak256("vkey v8"));
+        require(chacha.vkeyOf(v8) == keccak256("vkey v8"), "registered key");
+        chacha.verifyEncryption(v8, "");
+    }
+
+    function testVersion1HasNoCustodyHash() public {
+        chacha.setVKey(1, keccak256("vkey v1"));
+        bytes memory v1 =
+            abi.encodePacked(uint8(1), PLAINTEXT_HASH, NULLIFIER, KEY_HASH, SEGMENTS_ROOT, TIMESTAMP, hex"deadbeef");
+        (, bytes memory ciphertext) = chacha.verifyEncryption(v1, "");
+        require(keccak256(ciphertext) == keccak256(hex"deadbeef"), "ciphertext");
+    }
+
+    function testVersion2HasNoLineage() public {
+        chacha.setVKey(2, keccak256("vkey v2"));
+        bytes memory v2 = abi.encodePacked(
+            uint8(2), PLAINTEXT_HASH, NULLIFIER, KEY_HASH, SEGMENTS_ROOT, TIMESTAMP, CUSTODY_HASH, hex"deadbeef"
+        );
+        (, bytes memory ciphertext) = chacha.verifyEncryption(v2, "");
+        require(keccak256(ciphertext) == keccak256(hex"deadbeef"), "ciphertext");
+    }
+
+    function testVersion3HasNoPlaintextSize() public {
+        chacha.setVKey(3, keccak256("vkey v3"));
+        bytes memory v3 = abi.encodePacked(
+            uint8(3), PLAINTEXT_HASH, NULLIFIER, KEY_HASH, SEGMENTS_ROOT, TIMESTAMP, CUSTODY_HASH, LINEAGE, hex"deadbeef"
+        );
+        (, bytes memory ciphertext) = chacha.verifyEncryption(v3, "");
+        require(keccak256(ciphertext) == keccak256(hex"deadbeef"), "ciphertext");
+        try chacha.plaintextSizeOf(v3) {
+            revert("version 3 has a plaintext size");
+        } catch (bytes memory reason) {
+            require(
+                keccak256(reason) == keccak256(abi.encodeWithSelector(SP1Chacha.NoPlaintextSize.selector, uint8(3))),
+                "unexpected revert reason"
+            );
+        }
+    }
+
+    function testVersion5HasNoCiphertextKind() public {
+        chacha.setVKey(5, keccak256("vkey v5"));
+        bytes32 ciphertextHash = sha256(hex"deadbeef");
+        bytes memory v5 = abi.encodePacked(
+            uint8(5), PLAINTEXT_HASH, NULLIFIER, KEY_HASH, SEGMENTS_ROOT, TIMESTAMP, CUSTODY_HASH, LINEAGE, PLAINTEXT_SIZE, ciphertextHash
+        );
+        require(!chacha.ciphertextIsHashOf(v5), "version 5 has a ciphertext kind");
+        require(chacha.inputHmacKeyHashOf(v5) == bytes32(0), "version 5 has an input HMAC key hash");
+        require(chacha.plaintextSizeOf(v5) == PLAINTEXT_SIZE, "plaintext size");
+        (, bytes memory ciphertext) = chacha.verifyEncryption(v5, "");
+        require(keccak256(ciphertext) == keccak256(abi.encodePacked(ciphertextHash)), "ciphertext");
+    }
+
+    function testInputHmacKeyHashOf() public view {
+        require(chacha.inputHmacKeyHashOf(publicValues()) == HMAC_KEY_HASH, "input HMAC key hash");
+        require(chacha.headerLengthOf(publicValues()) == 266, "header length");
+    }
+
+    function testPlaintextSizeOf() public view {
+        require(chacha.plaintextSizeOf(publicValues()) == PLAINTEXT_SIZE, "plaintext size");
+    }
+
+    function testUpgradeCycle() public {
+        SP1Chacha v1Chacha = new SP1Chacha(address(new MockVerifier()), 1, keccak256("vkey v1"));
+        bytes memory upgradeValues = abi.encodePacked(uint8(1), keccak256("vkey v1"), VERSION, VKEY);
+
+        try v1Chacha.upgrade(upgradeValues, "") {
+            revert("upgrade without an upgrade key was accepted");
+        } catch (bytes memory reason) {
+            require(bytes4(reason) == SP1Chacha.UpgradeVKeyNotSet.selector, "unexpected revert reason");
+        }
+        v1Chacha.setUpgradeVKey(keccak256("upgrade vkey"));
+
+        try v1Chacha.verifyEncryption(publicValues(), "") {
+            revert("v2 proof was accepted before the upgrade");
+        } catch {}
+
+        v1Chacha.upgrade(upgradeValues, "");
+        require(v1Chacha.vkByVersion(VERSION) == VKEY, "upgraded key");
+        require(v1Chacha.vkByVersion(1) == keccak256("vkey v1"), "old key kept");
+        (bytes32 plaintextHash,) = v1Chacha.verifyEncryption(publicValues(), "");
+        require(plaintextHash == PLAINTEXT_HASH, "plaintext hash");
+
+        try v1Chacha.upgrade(upgradeValues, "") {
+            revert("registered version was upgraded again");
+        } catch (bytes memory reason) {
+            require(
+                keccak256(reason) == keccak256(abi.encodeWithSelector(SP1Chacha.VersionRegistered.selector, VERSION)),
+                "unexpected revert reason"
+            );
+        }
+    }
+
+    function testUpgradeFromUnregisteredKeyReverts() public {
+        chacha.setUpgradeVKey(keccak256("upgrade vkey"));
+        bytes memory upgradeValues = abi.encodePacked(VERSION, keccak256("other vkey"), uint8(8), keccak256("vkey v8"));
+        try chacha.upgrade(upgradeValues, "") {
+            revert("upgrade from an unregistered key was accepted");
+        } catch (bytes memory reason) {
+            require(
+                keccak256(reason) == keccak256(abi.encodeWithSelector(SP1Chacha.StaleUpgrade.selector, VERSION)),
+                "unexpected revert reason"
+            );
+        }
+    }
+
+    function testUpgradeOnlyOwner() public {
+        chacha.setUpgradeVKey(keccak256("upgrade vkey"));
+        Stranger stranger = new Stranger();
+        try stranger.upgrade(chacha, abi.encodePacked(VERSION, VKEY, uint8(8), keccak256("vkey v8"))) {
+            revert("non-owner upgraded");
+        } catch (bytes memory reason) {
+            require(bytes4(reason) == SP1Chacha.NotOwner.selector, "unexpected revert reason");
+        }
+    }
+
+    function testSetVKeyOnlyOwner() public {
+        Stranger stranger = new Stranger();
+        try stranger.setVKey(chacha) {
+            revert("non-owner registered a key");
+        } catch (bytes memory reason) {
+            require(bytes4(reason) == SP1Chacha.NotOwner.selector, "unexpected revert reason");
+        }
+    }
+
+    function node(bytes32 left, bytes32 right) internal pure returns (bytes32) {
+        return sha256(abi.encodePacked(bytes1(0x01), left, right));
+    }
+
+    function testVerifyEveryChunkOf8() public view {
+        bytes32[8] memory leaves;
+        for (uint256 i = 0; i < 8; i++) {
+            leaves[i] = sha256(abi.encodePacked(bytes1(0x00), uint8(i)));
+        }
+        bytes32[4] memory level1;
+        for (uint256 i = 0; i < 4; i++) {
+            level1[i] = node(leaves[2 * i], leaves[2 * i + 1]);
+        }
+        bytes32[2] memory level2 = [node(level1[0], level1[1]), node(level1[2], level1[3])];
+        bytes32 root = node(level2[0], level2[1]);
+
+        for (uint256 i = 0; i < 8; i++) {
+            bytes32[] memory proof = new bytes32[](3);
+            proof[0] = leaves[i ^ 1];
+            proof[1] = level1[(i / 2) ^ 1];
+            proof[2] = level2[(i / 4) ^ 1];
+            require(chacha.verifyChunk(root, leaves[i], i, proof), "leaf rejected");
+            require(!chacha.verifyChunk(root, leaves[i ^ 1], i, proof), "sibling accepted");
+            require(!chacha.verifyChunk(root, leaves[i], i ^ 1, proof), "wrong index accepted");
+            require(!chacha.verifyChunk(root, leaves[i], i + 8, proof), "index past the tree accepted");
+        }
+    }
+
+    /// @dev The 8 leaf tree of `lib/tests/merkle.rs`.
+    function testVerifyChunkMatchesMerkleProve() public view {
+        bytes32[] memory proof = new bytes32[](3);
+        proof[0] = 0x141db44e3d5d5a2c8bad02261c410cc5c263fb28454da025131fef20fd97711d;
+        proof[1] = 0xe2febd190f5e73d04ceed5421518a8efa7ce48b95dc0b30e909931b13bf3b89b;
+        proof[2] = 0xa4ec702e032356865a9f6114baeb74fffe4ffd313719e39e84ed2d47da54b702;
+        bytes32 root = 0x55a1a2cee6c5f7cebfd2afd3315ebd643abe51814dee90dafc92e84ade888136;
+        bytes32 leaf = sha256(abi.encodePacked(bytes1(0x00), "segment 5"));
+        require(chacha.verifyChunk(root, leaf, 5, proof), "leaf 5 rejected");
+    }
+}
+
+contract Stranger {
+    function setVKey(SP1Chacha chacha) external {
+        chacha.setVKey(7, keccak256("vkey v7"));
+    }
+
+    function upgrade(SP1Chacha chacha, bytes calldata publicValues) external {
+        chacha.upgrade(publicValues, "");
+    }
+}
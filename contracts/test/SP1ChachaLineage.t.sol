@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {MockVerifier} from "./MockVerifier.sol";
+import {SP1ChachaLineage} from "../src/SP1ChachaLineage.sol";
+
+contract SP1ChachaLineageTest {
+    SP1ChachaLineage lineage;
+
+    function setUp() public {
+        lineage = new SP1ChachaLineage(address(new MockVerifier()), bytes32(0));
+    }
+
+    /// Version 3 public values of a stage after `parent`, with only the fields the registry reads.
+    function stage(bytes32 parent, bytes32 plaintextHash) internal pure returns (bytes memory) {
+        bytes32 commitment = sha256(abi.encodePacked(parent, plaintextHash));
+        return abi.encodePacked(uint8(3), plaintextHash, new bytes(160), commitment, hex"deadbeef");
+    }
+
+    function testThreeStagePipeline() public {
+        bytes32 first = lineage.recordStage(stage(bytes32(0), keccak256("raw")), "", bytes32(0));
+        bytes32 second = lineage.recordStage(stage(first, keccak256("cleaned")), "", first);
+        bytes32 third = lineage.recordStage(stage(second, keccak256("aggregated")), "", second);
+
+        require(lineage.recorded(third), "last stage recorded");
+        require(lineage.parentOf(third) == second, "third links to second");
+        require(lineage.parentOf(second) == first, "second links to first");
+        require(lineage.parentOf(first) == bytes32(0), "first is the start");
+    }
+
+    function testUnknownParentReverts() public {
+        bytes32 parent = keccak256("never recorded");
+        try lineage.recordStage(stage(parent, keccak256("raw")), "", parent) {
+            revert("unknown parent was accepted");
+        } catch (bytes memory reason) {
+            require(
+                keccak256(reason) == keccak256(abi.encodeWithSelector(SP1ChachaLineage.UnknownParent.selector, parent)),
+                "unexpected revert reason"
+            );
+        }
+    }
+
+    function testWrongParentReverts() public {
+        bytes32 first = lineage.recordStage(stage(bytes32(0), keccak256("raw")), "", bytes32(0));
+        bytes32 other = lineage.recordStage(stage(bytes32(0), keccak256("other")), "", bytes32(0));
+        try lineage.recordStage(stage(first, keccak256("cleaned")), "", other) {
+            revert("stage was linked to the wrong parent");
+        } catch (bytes memory reason) {
+            require(bytes4(reason) == SP1ChachaLineage.LineageMismatch.selector, "unexpected revert reason");
+        }
+    }
+
+    function testRecordedTwiceReverts() public {
+        bytes memory values = stage(bytes32(0), keccak256("raw"));
+        bytes32 first = lineage.recordStage(values, "", bytes32(0));
+        try lineage.recordStage(values, "", bytes32(0)) {
+            revert("stage was recorded twice");
+        } catch (bytes memory reason) {
+            require(
+                keccak256(reason) == keccak256(abi.encodeWithSelector(SP1ChachaLineage.AlreadyRecorded.selector, first)),
+                "unexpected revert reason"
+            );
+        }
+    }
+
+    function testVersion2Reverts() public {
+        bytes memory values = stage(bytes32(0), keccak256("raw"));
+        values[0] = bytes1(uint8(2));
+        try lineage.recordStage(values, "", bytes32(0)) {
+            revert("version 2 proof was accepted");
+        } catch (bytes memory reason) {
+            require(
+                keccak256(reason) == keccak256(abi.encodeWithSelector(SP1ChachaLineage.NoLineage.selector, uint8(2))),
+                "unexpected revert reason"
+            );
+        }
+    }
+}
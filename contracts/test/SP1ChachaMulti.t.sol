@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {MockVerifier} from "./MockVerifier.sol";
+import {SP1ChachaMulti} from "../src/SP1ChachaMulti.sol";
+
+contract SP1ChachaMultiTest {
+    SP1ChachaMulti multi;
+
+    bytes32 constant PLAINTEXT_HASH = keccak256("plaintext");
+
+    function setUp() public {
+        multi = new SP1ChachaMulti(address(new MockVerifier()), bytes32(0));
+    }
+
+    function publicValues() internal pure returns (bytes memory values) {
+        values = abi.encodePacked(PLAINTEXT_HASH);
+        for (uint8 i = 0; i < 3; i++) {
+            values = abi.encodePacked(values, keyHash(i), sha256(ciphertext(i)));
+        }
+    }
+
+    function keyHash(uint8 i) internal pure returns (bytes32) {
+        return keccak256(abi.encodePacked("key", i));
+    }
+
+    function ciphertext(uint8 i) internal pure returns (bytes memory) {
+        return abi.encodePacked("ciphertext", i);
+    }
+
+    function testEveryRecipient() public view {
+        require(multi.recipientCount(publicValues()) == 3, "recipient count");
+        for (uint8 i = 0; i < 3; i++) {
+            bytes32 plaintextHash = multi.verifyForRecipient(publicValues(), "", keyHash(i), ciphertext(i));
+            require(plaintextHash == PLAINTEXT_HASH, "plaintext hash");
+        }
+    }
+
+    function testOtherRecipientsCiphertextReverts() public view {
+        try multi.verifyForRecipient(publicValues(), "", keyHash(0), ciphertext(1)) {
+            revert("ciphertext of another recipient was accepted");
+        } catch (bytes memory reason) {
+            require(bytes4(reason) == SP1ChachaMulti.CiphertextMismatch.selector, "unexpected revert reason");
+        }
+    }
+
+    function testUnknownKeyReverts() public view {
+        try multi.verifyForRecipient(publicValues(), "", keyHash(3), ciphertext(0)) {
+            revert("unknown key was accepted");
+        } catch (bytes memory reason) {
+            require(bytes4(reason) == SP1ChachaMulti.NotARecipient.selector, "unexpected revert reason");
+        }
+    }
+}
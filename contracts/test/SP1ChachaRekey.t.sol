@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {MockVerifier} from "./MockVerifier.sol";
+import {SP1ChachaRekey} from "../src/SP1ChachaRekey.sol";
+
+contract SP1ChachaRekeyTest {
+    SP1ChachaRekey rekey;
+
+    bytes32 constant OLD_KEY_HASH = keccak256("old key");
+    bytes32 constant NEW_KEY_HASH = keccak256("new key");
+    bytes32 constant PLAINTEXT_HASH = keccak256("plaintext");
+    bytes32 constant CIPHERTEXT_HASH = keccak256("ciphertext");
+
+    function setUp() public {
+        rekey = new SP1ChachaRekey(address(new MockVerifier()), bytes32(0));
+    }
+
+    function testHashes() public {
+        bytes memory publicValues = abi.encodePacked(OLD_KEY_HASH, NEW_KEY_HASH, PLAINTEXT_HASH, CIPHERTEXT_HASH);
+        (bytes32 oldKeyHash, bytes32 newKeyHash, bytes32 plaintextHash, bytes32 ciphertextHash) =
+            rekey.verifyRekey(publicValues, "");
+        require(oldKeyHash == OLD_KEY_HASH, "old key hash");
+        require(newKeyHash == NEW_KEY_HASH, "new key hash");
+        require(plaintextHash == PLAINTEXT_HASH, "plaintext hash");
+        require(ciphertextHash == CIPHERTEXT_HASH, "ciphertext hash");
+    }
+
+    function testWrongLengthReverts() public {
+        try rekey.verifyRekey(abi.encodePacked(OLD_KEY_HASH, NEW_KEY_HASH, PLAINTEXT_HASH), "") {
+            revert("short public values were accepted");
+        } catch {}
+        bytes memory rotation = abi.encodePacked(OLD_KEY_HASH, NEW_KEY_HASH, PLAINTEXT_HASH, CIPHERTEXT_HASH, hex"00");
+        try rekey.verifyRekey(rotation, "") {
+            revert("rotation public values were accepted");
+        } catch {}
+    }
+}
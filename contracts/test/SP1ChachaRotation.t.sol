@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {MockVerifier} from "./MockVerifier.sol";
+import {SP1ChachaRotation} from "../src/SP1ChachaRotation.sol";
+
+contract SP1ChachaRotationTest {
+    SP1ChachaRotation rotation;
+
+    bytes32 constant OLD_KEY_HASH = keccak256("old key");
+    bytes32 constant NEW_KEY_HASH = keccak256("new key");
+    bytes32 constant PLAINTEXT_HASH = keccak256("plaintext");
+
+    function setUp() public {
+        rotation = new SP1ChachaRotation(address(new MockVerifier()), bytes32(0));
+    }
+
+    function testKeyHashes() public {
+        bytes memory publicValues = abi.encodePacked(OLD_KEY_HASH, NEW_KEY_HASH, PLAINTEXT_HASH, hex"deadbeef");
+        (bytes32 oldKeyHash, bytes32 newKeyHash, bytes32 plaintextHash) = rotation.verifyRotation(publicValues, "");
+        require(oldKeyHash == OLD_KEY_HASH, "old key hash");
+        require(newKeyHash == NEW_KEY_HASH, "new key hash");
+        require(plaintextHash == PLAINTEXT_HASH, "plaintext hash");
+    }
+
+    function testShortPublicValuesRevert() public {
+        try rotation.verifyRotation(abi.encodePacked(OLD_KEY_HASH), "") {
+            revert("short public values were accepted");
+        } catch {}
+    }
+}
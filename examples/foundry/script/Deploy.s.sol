@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {SP1Chacha} from "sp1-chacha/SP1Chacha.sol";
+import {vm} from "./Vm.sol";
+
+/// @notice Deploy SP1Chacha against the SP1 verifier gateway of the chain:
+///
+///     SP1_VERIFIER_GATEWAY=0x... PROGRAM_VKEY=0x... forge script script/Deploy.s.sol \
+///         --rpc-url $RPC_URL --private-key $PRIVATE_KEY --broadcast
+///
+/// `PROGRAM_VKEY` is printed by `cargo run --bin vkey`, `PROGRAM_VERSION` defaults to 3.
+contract Deploy {
+    function run() external returns (SP1Chacha chacha) {
+        address gateway = vm.envAddress("SP1_VERIFIER_GATEWAY");
+        bytes32 vkey = vm.envBytes32("PROGRAM_VKEY");
+        uint8 version = uint8(vm.envOr("PROGRAM_VERSION", uint256(3)));
+
+        vm.startBroadcast();
+        chacha = new SP1Chacha(gateway, version, vkey);
+        vm.stopBroadcast();
+    }
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {SP1Chacha} from "sp1-chacha/SP1Chacha.sol";
+import {vm} from "./Vm.sol";
+
+/// @notice Submit a proof to a deployed SP1Chacha, from the JSON fixture written by
+/// `cargo run --bin evm_fixture`:
+///
+///     CONTRACT=0x... FIXTURE=fixture.json forge script script/SubmitProof.s.sol \
+///         --rpc-url $RPC_URL --private-key $PRIVATE_KEY --broadcast
+///
+/// The simulation reverts without broadcasting if the proof is invalid or already used.
+contract SubmitProof {
+    function run() external returns (bytes32 plaintextHash, bytes memory ciphertext) {
+        SP1Chacha chacha = SP1Chacha(vm.envAddress("CONTRACT"));
+        string memory fixture = vm.readFile(vm.envString("FIXTURE"));
+        bytes memory publicValues = vm.parseJsonBytes(fixture, ".publicValues");
+        bytes memory proof = vm.parseJsonBytes(fixture, ".proof");
+
+        vm.startBroadcast();
+        (plaintextHash, ciphertext) = chacha.verifyEncryption(publicValues, proof);
+        vm.stopBroadcast();
+    }
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {ISP1Verifier} from "sp1-chacha/ISP1Verifier.sol";
+import {SP1Chacha} from "sp1-chacha/SP1Chacha.sol";
+
+/// Accepts one proof of one set of public values, standing in for a verifier that only accepts
+/// valid proofs.
+contract StrictMockVerifier is ISP1Verifier {
+    bytes32 immutable vkey;
+    bytes32 immutable digest;
+
+    error InvalidProof();
+
+    constructor(bytes32 _vkey, bytes memory publicValues, bytes memory proofBytes) {
+        vkey = _vkey;
+        digest = keccak256(abi.encode(publicValues, proofBytes));
+    }
+
+    function verifyProof(bytes32 programVKey, bytes calldata publicValues, bytes calldata proofBytes)
+        external
+        view
+    {
+        if (programVKey != vkey || keccak256(abi.encode(publicValues, proofBytes)) != digest) revert InvalidProof();
+    }
+}
+
+contract SP1ChachaFuzzTest {
+    SP1Chacha chacha;
+    bytes valid;
+
+    uint8 constant VERSION = 3;
+    bytes32 constant VKEY = keccak256("vkey");
+    bytes32 constant NULLIFIER = keccak256("nullifier");
+    bytes constant PROOF = hex"c0ffee";
+
+    function setUp() public {
+        valid = publicValues(keccak256("plaintext"), NULLIFIER, keccak256("key"));
+        chacha = new SP1Chacha(address(new StrictMockVerifier(VKEY, valid, PROOF)), VERSION, VKEY);
+    }
+
+    function publicValues(bytes32 plaintextHash, bytes32 nullifier, bytes32 keyHash)
+        internal
+        pure
+        returns (bytes memory)
+    {
+        return abi.encodePacked(
+            VERSION, plaintextHash, nullifier, keyHash, bytes32(0), bytes32(0), bytes32(0), bytes32(0), hex"deadbeef"
+        );
+    }
+
+    /// Whether the proof is accepted.
+    function verify(bytes memory values, bytes memory proofBytes) internal returns (bool) {
+        try chacha.verifyEncryption(values, proofBytes) {
+            return true;
+        } catch {
+            return false;
+        }
+    }
+
+    function testValidProof() public {
+        require(verify(valid, PROOF), "valid proof rejected");
+        require(chacha.used(NULLIFIER), "nullifier not recorded");
+    }
+
+    function testFuzzRandomPublicValues(bytes32 plaintextHash, bytes32 nullifier, bytes32 keyHash) public {
+        bytes memory values = publicValues(plaintextHash, nullifier, keyHash);
+        if (keccak256(values) == keccak256(valid)) return;
+        require(!verify(values, PROOF), "invalid public values accepted");
+        require(!chacha.used(nullifier), "nullifier of a rejected proof recorded");
+    }
+
+    function testFuzzRandomProof(bytes memory proofBytes) public {
+        if (keccak256(proofBytes) == keccak256(PROOF)) return;
+        require(!verify(valid, proofBytes), "invalid proof accepted");
+        // A rejected proof does not use up the nullifier of the valid one
+        require(!chacha.used(NULLIFIER), "nullifier of a rejected proof recorded");
+        require(verify(valid, PROOF), "valid proof rejected");
+    }
+}
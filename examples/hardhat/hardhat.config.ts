@@ -0,0 +1,29 @@
+import "@nomicfoundation/hardhat-toolbox";
+import "dotenv/config";
+import { HardhatUserConfig } from "hardhat/config";
+
+const accounts = process.env.PRIVATE_KEY ? [process.env.PRIVATE_KEY] : [];
+
+const config: HardhatUserConfig = {
+  // Matches contracts/foundry.toml
+  solidity: "0.8.20",
+  paths: {
+    sources: "../../contracts/src",
+  },
+  networks: {
+    // A fork has the deployed SP1 verifier the tests verify against
+    hardhat: process.env.FORK_URL ? { forking: { url: process.env.FORK_URL } } : {},
+    sepolia: {
+      url: process.env.SEPOLIA_RPC_URL ?? "",
+      chainId: 11155111,
+      accounts,
+    },
+    mainnet: {
+      url: process.env.MAINNET_RPC_URL ?? "",
+      chainId: 1,
+      accounts,
+    },
+  },
+};
+
+export default config;
@@ -0,0 +1,55 @@
+// Deploy SP1Chacha against the SP1 verifier gateway of the network, and record its address in
+// deployed_contracts.json.
+//
+//   SP1_VERIFIER_GATEWAY=0x... PROGRAM_VKEY=0x... npx hardhat run scripts/deploy.ts --network sepolia
+
+import { promises as fs } from "node:fs";
+import { ethers, network } from "hardhat";
+
+// `chacha_lib::PROGRAM_VERSION` of the program PROGRAM_VKEY belongs to
+const PROGRAM_VERSION = Number(process.env.PROGRAM_VERSION ?? 3);
+const OUTPUT = "deployed_contracts.json";
+
+function env(name: string): string {
+  const value = process.env[name];
+  if (!value) throw new Error(`Missing ${name} env var`);
+  return value;
+}
+
+async function readDeployments(): Promise<Record<string, unknown>> {
+  try {
+    return JSON.parse(await fs.readFile(OUTPUT, "utf8"));
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === "ENOENT") return {};
+    throw error;
+  }
+}
+
+async function main() {
+  const gateway = env("SP1_VERIFIER_GATEWAY");
+  const vkey = env("PROGRAM_VKEY");
+  const [deployer] = await ethers.getSigners();
+  console.log(`Deploying SP1Chacha to ${network.name} from ${deployer.address}`);
+
+  const chacha = await ethers.deployContract("SP1Chacha", [gateway, PROGRAM_VERSION, vkey]);
+  await chacha.waitForDeployment();
+  const address = await chacha.getAddress();
+  console.log(`SP1Chacha deployed to ${address}`);
+
+  // One entry per network, so deployments to other networks are kept
+  const deployments = await readDeployments();
+  deployments[network.name] = {
+    SP1Chacha: address,
+    verifier: gateway,
+    programVersion: PROGRAM_VERSION,
+    vkey,
+    transaction: chacha.deploymentTransaction()?.hash,
+  };
+  await fs.writeFile(OUTPUT, JSON.stringify(deployments, null, 2) + "\n");
+  console.log(`Saved to ${OUTPUT}`);
+}
+
+main().catch((error) => {
+  console.error(error);
+  process.exitCode = 1;
+});
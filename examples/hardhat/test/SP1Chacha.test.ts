@@ -0,0 +1,56 @@
+// Verify a saved proof with SP1Chacha, against the SP1 verifier gateway of a forked chain.
+//
+//   FORK_URL=... SP1_VERIFIER_GATEWAY=0x... FIXTURE=fixture.json npx hardhat test
+
+import { expect } from "chai";
+import { readFileSync } from "node:fs";
+import { ethers } from "hardhat";
+
+interface Fixture {
+  vkey: string;
+  publicValues: string;
+  proof: string;
+}
+
+describe("SP1Chacha", function () {
+  let fixture: Fixture;
+
+  before(function () {
+    // A local chain has no SP1 verifier to check the proof against
+    if (!process.env.FORK_URL || !process.env.SP1_VERIFIER_GATEWAY) this.skip();
+    fixture = JSON.parse(readFileSync(process.env.FIXTURE ?? "fixture.json", "utf8"));
+  });
+
+  async function deploy() {
+    // The first byte of the public values is the program version
+    const version = parseInt(fixture.publicValues.slice(2, 4), 16);
+    return ethers.deployContract("SP1Chacha", [
+      process.env.SP1_VERIFIER_GATEWAY!,
+      version,
+      fixture.vkey,
+    ]);
+  }
+
+  it("verifies the saved proof", async function () {
+    const chacha = await deploy();
+    const [plaintextHash] = await chacha.verifyEncryption.staticCall(
+      fixture.publicValues,
+      fixture.proof,
+    );
+    expect(plaintextHash).to.equal("0x" + fixture.publicValues.slice(4, 68));
+  });
+
+  it("rejects tampered public values", async function () {
+    const chacha = await deploy();
+    // Flip the last bit of the plaintext hash
+    const bytes = ethers.getBytes(fixture.publicValues);
+    bytes[32] ^= 1;
+    await expect(chacha.verifyEncryption(bytes, fixture.proof)).to.be.reverted;
+  });
+
+  it("accepts the proof only once", async function () {
+    const chacha = await deploy();
+    await chacha.verifyEncryption(fixture.publicValues, fixture.proof);
+    await expect(chacha.verifyEncryption(fixture.publicValues, fixture.proof)).to.be.reverted;
+  });
+});
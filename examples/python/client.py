@@ -0,0 +1,76 @@
+"""Encrypt a test vector through the proving server and print the public values of its proof.
+
+    cargo run --release --bin server -- --port 3000
+    pip install httpx
+    SERVER_URL=http://localhost:3000 python client.py
+"""
+
+import hashlib
+import json
+import os
+import sys
+import time
+
+import httpx
+
+# RFC 8439 section 2.4.2.
+PLAINTEXT = (
+    b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, "
+    b"sunscreen would be it."
+)
+
+POLL_INTERVAL_SECS = 2
+TIMEOUT_SECS = 30 * 60
+
+
+def parse_public_values(public_values: bytes) -> dict:
+    """Layout of `chacha_lib::public_values::PublicValues`, version 7."""
+    return {
+        "version": public_values[0],
+        "plaintext_hash": public_values[1:33],
+        "nullifier": public_values[33:65],
+        "key_hash": public_values[65:97],
+        "plaintext_size": int.from_bytes(public_values[225:233], "big"),
+        "ciphertext_is_hash": public_values[233] == 1,
+        "input_hmac_key_hash": public_values[234:266],
+        "ciphertext": public_values[266:],
+    }
+
+
+def main() -> None:
+    server = os.environ.get("SERVER_URL", "http://localhost:3000")
+    with httpx.Client(base_url=server) as client:
+        response = client.post("/prove", content=PLAINTEXT)
+        response.raise_for_status()
+        job_id = response.json()["job_id"]
+        print(f"Submitted job {job_id}")
+
+        start = time.monotonic()
+        while True:
+            response = client.get(f"/status/{job_id}")
+            response.raise_for_status()
+            status = response.json()
+            if status["state"] == "Complete":
+                break
+            if status["state"] == "Failed":
+                sys.exit(f"Job failed: {status['result']}")
+            print(f"Job is {status['state']}")
+            if time.monotonic() - start > TIMEOUT_SECS:
+                sys.exit("Timed out waiting for the proof")
+            time.sleep(POLL_INTERVAL_SECS)
+
+    result = json.loads(status["result"])
+    public_values = parse_public_values(bytes.fromhex(result["public_values"]))
+    print(f"Nonce:           0x{result['nonce']}")
+    print(f"Program version: {public_values['version']}")
+    print(f"Plaintext hash:  0x{public_values['plaintext_hash'].hex()}")
+    print(f"Nullifier:       0x{public_values['nullifier'].hex()}")
+    print(f"Key hash:        0x{public_values['key_hash'].hex()}")
+    print(f"Ciphertext:      0x{public_values['ciphertext'].hex()}")
+
+    if public_values["plaintext_hash"] != hashlib.sha256(PLAINTEXT).digest():
+        sys.exit("The proof is for another plaintext")
+
+
+if __name__ == "__main__":
+    main()
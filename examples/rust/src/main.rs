@@ -0,0 +1,124 @@
+//! Encrypt a test vector through the proving server and print the public values of its proof.
+//!
+//! ```shell
+//! cargo run --release --bin server -- --port 3000
+//! SERVER_URL=http://localhost:3000 cargo run --release -p chacha-client-example
+//! ```
+//!
+//! Against a server with mutual TLS, pass the CA and a client certificate signed by it, e.g. of
+//! `scripts/gen_certs.sh`:
+//!
+//! ```shell
+//! SERVER_URL=https://localhost:3000 cargo run --release -p chacha-client-example -- \
+//!     --tls-ca-cert certs/ca.pem --tls-client-cert certs/client.pem --tls-client-key certs/client.key
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use reqwest::blocking::Client;
+use reqwest::{Certificate, Identity};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use chacha_lib::public_values::PublicValues;
+
+/// RFC 8439 section 2.4.2.
+const PLAINTEXT: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Parser, Debug)]
+#[clap(about, long_about = None)]
+struct Args {
+    #[clap(long, env = "SERVER_URL", default_value = "http://localhost:3000")]
+    server_url: String,
+
+    /// CA certificate, PEM, the server certificate is signed by.
+    #[clap(long)]
+    tls_ca_cert: Option<PathBuf>,
+
+    /// Client certificate, PEM, presented to a server with mutual TLS.
+    #[clap(long, requires = "tls_client_key")]
+    tls_client_cert: Option<PathBuf>,
+
+    /// Private key, PEM, of `--tls-client-cert`.
+    #[clap(long, requires = "tls_client_cert")]
+    tls_client_key: Option<PathBuf>,
+}
+
+fn read(path: &Path) -> Vec<u8> {
+    std::fs::read(path).unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()))
+}
+
+fn main() {
+    let args = Args::parse();
+    let server = args.server_url;
+
+    let mut client = Client::builder();
+    if let Some(ca_cert) = &args.tls_ca_cert {
+        client = client.add_root_certificate(
+            Certificate::from_pem(&read(ca_cert)).expect("Malformed CA certificate"),
+        );
+    }
+    if let (Some(cert), Some(key)) = (&args.tls_client_cert, &args.tls_client_key) {
+        let identity = [read(cert), read(key)].concat();
+        client =
+            client.identity(Identity::from_pem(&identity).expect("Malformed client certificate"));
+    }
+    let client = client.build().expect("Failed to build the HTTP client");
+
+    let job: Value = client
+        .post(format!("{server}/prove"))
+        .body(PLAINTEXT)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.json())
+        .expect("Failed to submit the job");
+    let job_id = job["job_id"].as_str().expect("No job_id in the response");
+    println!("Submitted job {job_id}");
+
+    let start = Instant::now();
+    let result = loop {
+        let status: Value = client
+            .get(format!("{server}/status/{job_id}"))
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.json())
+            .expect("Failed to poll the job");
+        match status["state"].as_str() {
+            Some("Complete") => break status["result"].as_str().unwrap().to_owned(),
+            Some("Failed") => panic!("Job failed: {}", status["result"]),
+            state => println!("Job is {}", state.unwrap_or("unknown")),
+        }
+        assert!(start.elapsed() < TIMEOUT, "Timed out waiting for the proof");
+        std::thread::sleep(POLL_INTERVAL);
+    };
+
+    let result: Value = serde_json::from_str(&result).expect("Malformed job result");
+    let public_values = hex::decode(result["public_values"].as_str().unwrap()).unwrap();
+    let public_values = PublicValues::parse(&public_values).expect("Public values too short");
+
+    println!("Nonce:           0x{}", result["nonce"].as_str().unwrap());
+    println!("Program version: {}", public_values.version);
+    println!(
+        "Plaintext hash:  0x{}",
+        hex::encode(public_values.plaintext_hash)
+    );
+    println!(
+        "Nullifier:       0x{}",
+        hex::encode(public_values.nullifier)
+    );
+    println!("Key hash:        0x{}", hex::encode(public_values.key_hash));
+    println!(
+        "Ciphertext:      0x{}",
+        hex::encode(public_values.ciphertext)
+    );
+    assert_eq!(
+        public_values.plaintext_hash,
+        Sha256::digest(PLAINTEXT)[..],
+        "The proof is for another plaintext"
+    );
+}
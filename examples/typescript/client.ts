@@ -0,0 +1,93 @@
+// Encrypt a test vector through the proving server and print the public values of its proof.
+//
+//   cargo run --release --bin server -- --port 3000
+//   SERVER_URL=http://localhost:3000 npx tsx client.ts
+//
+// Needs Node.js 18 or later for the global `fetch`.
+
+import { createHash } from "node:crypto";
+
+// RFC 8439 section 2.4.2.
+const PLAINTEXT = new TextEncoder().encode(
+  "Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.",
+);
+
+const POLL_INTERVAL_MS = 2_000;
+const TIMEOUT_MS = 30 * 60 * 1_000;
+
+interface JobStatus {
+  job_id: string;
+  state: "Pending" | "Running" | "Complete" | "Failed";
+  elapsed_secs: number;
+  result: string | null;
+}
+
+interface JobResult {
+  nonce: string;
+  public_values: string;
+  proof: string;
+}
+
+/** Layout of `chacha_lib::public_values::PublicValues`, version 7. */
+function parsePublicValues(hex: string) {
+  const bytes = Buffer.from(hex, "hex");
+  const field = (start: number, end?: number) => "0x" + bytes.subarray(start, end).toString("hex");
+  return {
+    version: bytes[0],
+    plaintextHash: field(1, 33),
+    nullifier: field(33, 65),
+    keyHash: field(65, 97),
+    plaintextSize: bytes.readBigUInt64BE(225),
+    ciphertextIsHash: bytes[233] === 1,
+    inputHmacKeyHash: field(234, 266),
+    ciphertext: field(266),
+  };
+}
+
+async function request<T>(url: string, init?: RequestInit): Promise<T> {
+  const response = await fetch(url, init);
+  if (!response.ok) {
+    throw new Error(`${init?.method ?? "GET"} ${url}: ${response.status} ${response.statusText}`);
+  }
+  return (await response.json()) as T;
+}
+
+async function main() {
+  const server = process.env.SERVER_URL ?? "http://localhost:3000";
+
+  const { job_id } = await request<{ job_id: string }>(`${server}/prove`, {
+    method: "POST",
+    body: PLAINTEXT,
+  });
+  console.log(`Submitted job ${job_id}`);
+
+  const start = Date.now();
+  let status: JobStatus;
+  for (;;) {
+    status = await request<JobStatus>(`${server}/status/${job_id}`);
+    if (status.state === "Complete") break;
+    if (status.state === "Failed") throw new Error(`Job failed: ${status.result}`);
+    console.log(`Job is ${status.state}`);
+    if (Date.now() - start > TIMEOUT_MS) throw new Error("Timed out waiting for the proof");
+    await new Promise((resolve) => setTimeout(resolve, POLL_INTERVAL_MS));
+  }
+
+  const result = JSON.parse(status.result!) as JobResult;
+  const publicValues = parsePublicValues(result.public_values);
+  console.log(`Nonce:           0x${result.nonce}`);
+  console.log(`Program version: ${publicValues.version}`);
+  console.log(`Plaintext hash:  ${publicValues.plaintextHash}`);
+  console.log(`Nullifier:       ${publicValues.nullifier}`);
+  console.log(`Key hash:        ${publicValues.keyHash}`);
+  console.log(`Ciphertext:      ${publicValues.ciphertext}`);
+
+  const expected = "0x" + createHash("sha256").update(PLAINTEXT).digest("hex");
+  if (publicValues.plaintextHash !== expected) {
+    throw new Error("The proof is for another plaintext");
+  }
+}
+
+main().catch((error) => {
+  console.error(error);
+  process.exit(1);
+});
@@ -0,0 +1,72 @@
+//! Host throughput of encrypting a 100 MB file through a memory map, against streaming it through
+//! buffered reads and writes.
+//!
+//! ```shell
+//! cargo bench -p chacha-lib --bench mmap
+//! ```
+//!
+//! The input is in the page cache after the first round, so this measures copying rather than
+//! the disk.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chacha_lib::io::ChaChaWriter;
+use chacha_lib::mmap::chacha_mmap_encrypt;
+use chacha_lib::{chacha, Key, Nonce};
+
+const FILE_LEN: usize = 100 * 1024 * 1024;
+const ROUNDS: u32 = 5;
+
+fn temp_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("chacha-bench-mmap-{name}-{}", std::process::id()))
+}
+
+fn buffered(key: &Key, nonce: &Nonce, input: &Path, output: &Path) {
+    let mut reader = BufReader::new(File::open(input).unwrap());
+    let mut writer = ChaChaWriter::new(BufWriter::new(File::create(output).unwrap()), key, nonce);
+    std::io::copy(&mut reader, &mut writer).unwrap();
+    writer.flush().unwrap();
+}
+
+/// MiB per second of `encrypt` on the file, best of [`ROUNDS`].
+fn throughput(encrypt: impl Fn()) -> f64 {
+    let best = (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            encrypt();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or(Duration::MAX);
+    (FILE_LEN as f64 / (1 << 20) as f64) / best.as_secs_f64()
+}
+
+fn main() {
+    let (key, nonce) = (Key([0x42; 32]), Nonce([0x24; 12]));
+    let (input, output) = (temp_file("in"), temp_file("out"));
+    let mut plaintext = vec![0; FILE_LEN];
+    chacha(&[1; 32], &[0; 12], &mut plaintext);
+    std::fs::write(&input, &plaintext).unwrap();
+
+    let mapped = throughput(|| {
+        chacha_mmap_encrypt(&key, &nonce, &input, &output).unwrap();
+    });
+    let mapped_output = std::fs::read(&output).unwrap();
+    let streamed = throughput(|| buffered(&key, &nonce, &input, &output));
+    assert!(
+        std::fs::read(&output).unwrap() == mapped_output,
+        "the outputs differ"
+    );
+
+    println!("{:>8}: {mapped:>8.1} MiB/s", "mmap");
+    println!(
+        "{:>8}: {streamed:>8.1} MiB/s ({:.2}x mmap)",
+        "buffered",
+        streamed / mapped
+    );
+    std::fs::remove_file(&input).unwrap();
+    std::fs::remove_file(&output).unwrap();
+}
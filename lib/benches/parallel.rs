@@ -0,0 +1,57 @@
+//! Host throughput of `chacha_lanes` with 1, 4 and 8 lanes, against `chacha`.
+//!
+//! ```shell
+//! RUSTFLAGS="-C target-cpu=native" cargo bench -p chacha-lib --bench parallel
+//! ```
+//!
+//! Without `target-cpu=native` the lanes are built for baseline SSE2, see `chacha_lib::parallel`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use chacha_lib::parallel::chacha_lanes;
+use chacha_lib::{chacha, Key, Nonce};
+
+const BUFFER_LEN: usize = 1 << 20;
+const ROUNDS: u32 = 64;
+
+/// MiB per second of `encrypt` on a 1 MiB buffer, best of [`ROUNDS`].
+fn throughput(encrypt: impl Fn(&mut [u8])) -> f64 {
+    let mut buffer = vec![0u8; BUFFER_LEN];
+    encrypt(&mut buffer);
+    let best = (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            encrypt(black_box(&mut buffer));
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or(Duration::MAX);
+    (BUFFER_LEN as f64 / (1 << 20) as f64) / best.as_secs_f64()
+}
+
+fn main() {
+    let (key, nonce) = (Key([0x42; 32]), Nonce([0x24; 12]));
+    let single = throughput(|buffer| chacha_lanes::<1>(&key, &nonce, buffer));
+    let results = [
+        ("1 lane", single),
+        (
+            "4 lanes",
+            throughput(|buffer| chacha_lanes::<4>(&key, &nonce, buffer)),
+        ),
+        (
+            "8 lanes",
+            throughput(|buffer| chacha_lanes::<8>(&key, &nonce, buffer)),
+        ),
+        (
+            "chacha20 crate",
+            throughput(|buffer| chacha(&key.0, &nonce.0, buffer)),
+        ),
+    ];
+    for (name, mib_per_sec) in results {
+        println!(
+            "{name:>14}: {mib_per_sec:>8.1} MiB/s ({:.2}x 1 lane)",
+            mib_per_sec / single
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! With the `libsodium-compat` feature, link libsodium if `pkg-config` finds it, enabling the live
+//! comparison in `tests/libsodium_compat.rs`.
+//!
+//! Host builds on Linux without the `secure-memory` feature warn that keys may be swapped to disk.
+
+fn main() {
+    println!("cargo::rustc-check-cfg=cfg(libsodium)");
+    #[cfg(feature = "libsodium-compat")]
+    match pkg_config::probe_library("libsodium") {
+        Ok(_) => println!("cargo::rustc-cfg=libsodium"),
+        Err(e) => println!("cargo::warning=libsodium not found, skipping the live comparison: {e}"),
+    }
+
+    let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let feature = |name: &str| std::env::var_os(format!("CARGO_FEATURE_{name}")).is_some();
+    if target_os == "linux" && feature("STD") && !feature("SECURE_MEMORY") {
+        println!(
+            "cargo::warning=built without the `secure-memory` feature, keys may be swapped to disk"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! AES-256-CTR, the cipher of `--fips-mode`.
+//!
+//! ChaCha20 is not a FIPS 140-2 approved algorithm. AES-256 in counter mode (NIST SP 800-38A)
+//! is, as is the SHA-256 plaintext hash the programs already commit. Using approved algorithms
+//! does not make a deployment FIPS 140-2 compliant by itself: that also takes a validated
+//! cryptographic module, which neither this crate nor the `aes` crate is.
+//!
+//! The zkVM has no AES precompile, so the cipher runs as the constant time software implementation
+//! of the `aes` crate. Compare its cycles against ChaCha20 with `--execute`.
+
+use aes::Aes256;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+
+/// AES with a 128 bit big endian counter, as in NIST SP 800-38A appendix B.1.
+type Aes256Ctr = ctr::Ctr128BE<Aes256>;
+
+/// Length of the initial counter block.
+pub const IV_LEN: usize = 16;
+
+/// Apply the AES-256-CTR keystream to `buffer` in place, which both encrypts and decrypts. `iv` is
+/// the initial counter block, incremented as a 128 bit big endian integer per 16 byte block.
+pub fn aes_ctr_encrypt(key: &[u8; 32], iv: &[u8; IV_LEN], buffer: &mut [u8]) {
+    Aes256Ctr::new(key.into(), iv.into()).apply_keystream(buffer);
+}
+
+/// The initial counter block of a 12 byte nonce: the nonce followed by a 32 bit block counter
+/// starting at 0, which only wraps after 64 GiB.
+pub fn iv_from_nonce(nonce: &[u8; 12]) -> [u8; IV_LEN] {
+    let mut iv = [0u8; IV_LEN];
+    iv[..12].copy_from_slice(nonce);
+    iv
+}
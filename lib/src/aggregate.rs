@@ -0,0 +1,188 @@
+//! Aggregating the chunk proofs of one stream into a single proof of a Merkle root.
+//!
+//! `chunk_prove` proves a stream as N independent chunk proofs, each committing a
+//! [`ChunkPublicValues`]. The aggregation program (`program/src/main_aggregate.rs`) verifies all of
+//! them, checks they continue each other like `chunk_verify` does, and commits an [`Aggregate`]:
+//! the root of a [`MerkleTree`] over the chunk plaintext hashes and one over the chunk ciphertext
+//! hashes. An on-chain verifier checks this one proof instead of N, and any single chunk is then
+//! opened against a root with `SP1Chacha.verifyChunk` and [`chunk_leaf`].
+
+use sha2::{Digest, Sha256};
+
+use crate::merkle::{leaf_hash, MerkleTree};
+
+/// Length of the chunk program's public values before the ciphertext:
+/// `nullifier (32) | counter (4, LE) | chunks (4, LE) | sha256(chunk) (32)`.
+pub const CHUNK_HEADER_LEN: usize = 72;
+
+/// Length of the aggregation program's public values: `chunk_vkey (32) | nullifier (32) |
+/// chunks (4, LE) | plaintext_root (32) | ciphertext_root (32)`.
+pub const AGGREGATE_LEN: usize = 132;
+
+/// Size of a ChaCha20 keystream block, the unit of the chunk counters.
+const BLOCK_LEN: usize = 64;
+
+/// Public values of one chunk proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkPublicValues {
+    pub nullifier: [u8; 32],
+    /// Keystream block the chunk starts at.
+    pub counter: u32,
+    /// Number of chunks in the stream, so a verifier notices missing trailing chunks.
+    pub chunks: u32,
+    pub plaintext_hash: [u8; 32],
+    pub ciphertext: Vec<u8>,
+}
+
+impl ChunkPublicValues {
+    /// `None` if `bytes` is shorter than [`CHUNK_HEADER_LEN`].
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < CHUNK_HEADER_LEN {
+            return None;
+        }
+        Some(Self {
+            nullifier: bytes[..32].try_into().unwrap(),
+            counter: u32::from_le_bytes(bytes[32..36].try_into().unwrap()),
+            chunks: u32::from_le_bytes(bytes[36..40].try_into().unwrap()),
+            plaintext_hash: bytes[40..72].try_into().unwrap(),
+            ciphertext: bytes[CHUNK_HEADER_LEN..].to_vec(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(CHUNK_HEADER_LEN + self.ciphertext.len());
+        bytes.extend_from_slice(&self.nullifier);
+        bytes.extend_from_slice(&self.counter.to_le_bytes());
+        bytes.extend_from_slice(&self.chunks.to_le_bytes());
+        bytes.extend_from_slice(&self.plaintext_hash);
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+}
+
+/// Leaf of a chunk in the plaintext tree, `sha256(0x00 || sha256(chunk))`. The ciphertext tree has
+/// the leaves `chunk_leaf(&sha256(chunk_ciphertext))`.
+pub fn chunk_leaf(chunk_hash: &[u8; 32]) -> [u8; 32] {
+    leaf_hash(chunk_hash)
+}
+
+/// Public values of the aggregation program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aggregate {
+    /// `bytes32` key of the chunk program whose proofs were aggregated, see
+    /// [`crate::upgrade::vkey_bytes32`].
+    pub chunk_vkey: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub chunks: u32,
+    pub plaintext_root: [u8; 32],
+    pub ciphertext_root: [u8; 32],
+}
+
+impl Aggregate {
+    pub fn to_bytes(&self) -> [u8; AGGREGATE_LEN] {
+        let mut bytes = [0u8; AGGREGATE_LEN];
+        bytes[..32].copy_from_slice(&self.chunk_vkey);
+        bytes[32..64].copy_from_slice(&self.nullifier);
+        bytes[64..68].copy_from_slice(&self.chunks.to_le_bytes());
+        bytes[68..100].copy_from_slice(&self.plaintext_root);
+        bytes[100..].copy_from_slice(&self.ciphertext_root);
+        bytes
+    }
+
+    /// `None` unless `bytes` is exactly [`AGGREGATE_LEN`] long.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != AGGREGATE_LEN {
+            return None;
+        }
+        Some(Self {
+            chunk_vkey: bytes[..32].try_into().unwrap(),
+            nullifier: bytes[32..64].try_into().unwrap(),
+            chunks: u32::from_le_bytes(bytes[64..68].try_into().unwrap()),
+            plaintext_root: bytes[68..100].try_into().unwrap(),
+            ciphertext_root: bytes[100..].try_into().unwrap(),
+        })
+    }
+}
+
+/// Why chunk proofs cannot be aggregated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateError {
+    /// There are no chunks.
+    Empty,
+    /// The chunk at this index has the nullifier of another stream.
+    OtherStream(usize),
+    /// The chunk at this index does not start where the previous one ended.
+    Discontinuous(usize),
+    /// The chunk at this index commits a stream of another number of chunks.
+    Incomplete(usize),
+}
+
+impl core::fmt::Display for AggregateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AggregateError::Empty => write!(f, "no chunks to aggregate"),
+            AggregateError::OtherStream(i) => {
+                write!(f, "chunk {i} belongs to another stream")
+            }
+            AggregateError::Discontinuous(i) => {
+                write!(f, "chunk {i} does not continue the previous chunks")
+            }
+            AggregateError::Incomplete(i) => {
+                write!(
+                    f,
+                    "chunk {i} belongs to a stream of another number of chunks"
+                )
+            }
+        }
+    }
+}
+
+/// Leaves of the plaintext tree of `chunks`, for opening a chunk with
+/// [`crate::merkle::merkle_prove`].
+pub fn plaintext_leaves(chunks: &[ChunkPublicValues]) -> Vec<[u8; 32]> {
+    chunks
+        .iter()
+        .map(|chunk| chunk_leaf(&chunk.plaintext_hash))
+        .collect()
+}
+
+/// Leaves of the ciphertext tree of `chunks`.
+pub fn ciphertext_leaves(chunks: &[ChunkPublicValues]) -> Vec<[u8; 32]> {
+    chunks
+        .iter()
+        .map(|chunk| chunk_leaf(&Sha256::digest(&chunk.ciphertext).into()))
+        .collect()
+}
+
+/// Check that `chunks` are a whole stream in order, and build its [`Aggregate`] under the chunk
+/// program key `chunk_vkey`. Only the last chunk may end within a keystream block, and every
+/// chunk must commit `chunks.len()` as the stream's chunk count.
+pub fn aggregate(
+    chunk_vkey: [u8; 32],
+    chunks: &[ChunkPublicValues],
+) -> Result<Aggregate, AggregateError> {
+    let first = chunks.first().ok_or(AggregateError::Empty)?;
+    let mut offset = 0usize;
+    for (i, chunk) in chunks.iter().enumerate() {
+        if chunk.nullifier != first.nullifier {
+            return Err(AggregateError::OtherStream(i));
+        }
+        if chunk.counter as usize * BLOCK_LEN != offset {
+            return Err(AggregateError::Discontinuous(i));
+        }
+        offset += chunk.ciphertext.len();
+    }
+    if let Some(i) = chunks
+        .iter()
+        .position(|chunk| chunk.chunks as usize != chunks.len())
+    {
+        return Err(AggregateError::Incomplete(i));
+    }
+    Ok(Aggregate {
+        chunk_vkey,
+        nullifier: first.nullifier,
+        chunks: u32::try_from(chunks.len()).expect("more than 2^32 chunks"),
+        plaintext_root: MerkleTree::from_leaves(plaintext_leaves(chunks)).root(),
+        ciphertext_root: MerkleTree::from_leaves(ciphertext_leaves(chunks)).root(),
+    })
+}
@@ -0,0 +1,31 @@
+//! BLAKE2b-256 plaintext hashes (RFC 7693), for proofs checked against Zcash state.
+//!
+//! Zcash hashes with BLAKE2b throughout, each use under its own 16 byte personalization such as
+//! `ZcashPoW` or `Zcash_PH`, padded with zeros like Zcash pads it. The output is 32 bytes, the
+//! same size as the SHA-256 plaintext hash it replaces in `program/src/main_blake2b.rs`.
+
+use blake2::digest::core_api::{Buffer, UpdateCore, VariableOutputCore};
+use blake2::digest::Output;
+use blake2::Blake2bVarCore;
+
+/// Length of a BLAKE2b personalization.
+pub const PERSONAL_LEN: usize = 16;
+
+/// BLAKE2b with a 32 byte output, unkeyed and personalized with `personal`. All zeros is no
+/// personalization, i.e. plain BLAKE2b-256.
+pub fn blake2b_256(personal: &[u8; PERSONAL_LEN], data: &[u8]) -> [u8; 32] {
+    // `Blake2bMac` would take a personalization, but hashes a key block even for an empty key
+    let mut core = Blake2bVarCore::new_with_params(&[], personal, 0, 32);
+    let mut buffer = Buffer::<Blake2bVarCore>::default();
+    buffer.digest_blocks(data, |blocks| core.update_blocks(blocks));
+    let mut output = Output::<Blake2bVarCore>::default();
+    core.finalize_variable_core(&mut buffer, &mut output);
+    output[..32].try_into().unwrap()
+}
+
+/// `personal` zero padded to [`PERSONAL_LEN`], `None` if longer.
+pub fn personalization(personal: &[u8]) -> Option<[u8; PERSONAL_LEN]> {
+    let mut padded = [0u8; PERSONAL_LEN];
+    padded.get_mut(..personal.len())?.copy_from_slice(personal);
+    Some(padded)
+}
@@ -0,0 +1,104 @@
+//! Commit to a plaintext now and reveal it later, e.g. sealed bids or votes.
+//!
+//! [`commit`] encrypts the plaintext and binds the ciphertext to the key and nonce with
+//! `SHA256(key || nonce || ciphertext)`. The commitment is published, the key and nonce are kept
+//! until the reveal phase, when [`reveal`] checks them against the binding and decrypts.
+//! `program/src/main_reveal.rs` proves a reveal without publishing the key.
+//!
+//! # Equivocation resistance
+//!
+//! ChaCha20 alone does not commit to a plaintext: the same ciphertext decrypts to a different
+//! plaintext under every key, so a committer holding only the ciphertext could reveal any value by
+//! choosing a key. The binding hash rules this out, opening the same commitment with another key or
+//! nonce needs a SHA-256 second preimage. A committer can therefore only reveal the committed
+//! plaintext, or not reveal at all.
+//!
+//! Not revealing is the remaining strategy. A bidder who sees the other reveals first and would
+//! lose, or a voter who dislikes the tally, can abort selectively. Protocols should make aborting
+//! cost more than it gains, typically a deposit forfeited when no valid reveal arrives by a
+//! deadline, at least as large as the value of the best outcome an abort can force.
+//!
+//! Two further attacks are outside the commitment itself:
+//!
+//! - Copying: anyone can republish a commitment and, once it is revealed, its reveal. Include the
+//!   committer's identity in the plaintext, or bind commitments to senders on-chain.
+//! - Leakage: the ciphertext has the length of the plaintext, pad values to a fixed length. Use a
+//!   fresh key per commitment, a revealed key opens every commitment made with it.
+//!
+//! Hiding relies on the key staying secret until the reveal, the binding hash leaks nothing more as
+//! the key has 256 bits of entropy.
+
+use constant_time_eq::constant_time_eq_32;
+use sha2::{Digest, Sha256};
+
+use crate::{chacha, Key, Nonce};
+
+/// A published commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commitment {
+    pub ciphertext: Vec<u8>,
+    /// `SHA256(key || nonce || ciphertext)`.
+    pub binding: [u8; 32],
+}
+
+/// Why a reveal was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevealError {
+    /// The key and nonce do not open the commitment.
+    BindingMismatch,
+}
+
+impl core::fmt::Display for RevealError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RevealError::BindingMismatch => {
+                f.write_str("key and nonce do not match the commitment's binding hash")
+            }
+        }
+    }
+}
+
+/// `SHA256(key || nonce || ciphertext)`.
+pub fn binding(key: &Key, nonce: &Nonce, ciphertext: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(key.0);
+    hasher.update(nonce.0);
+    hasher.update(ciphertext);
+    hasher.finalize().into()
+}
+
+/// Commit to `plaintext`. Use a fresh key for every commitment.
+///
+/// ```
+/// use chacha_lib::commit_reveal::{commit, reveal, RevealError};
+/// use chacha_lib::{Key, Nonce};
+///
+/// let (key, nonce) = (Key([7; 32]), Nonce([1; 12]));
+/// let commitment = commit(&key, &nonce, b"bid: 100");
+/// assert_eq!(reveal(&commitment, &key, &nonce).unwrap(), b"bid: 100");
+/// assert_eq!(
+///     reveal(&commitment, &Key([8; 32]), &nonce),
+///     Err(RevealError::BindingMismatch)
+/// );
+/// ```
+pub fn commit(key: &Key, nonce: &Nonce, plaintext: &[u8]) -> Commitment {
+    let mut ciphertext = plaintext.to_vec();
+    chacha(&key.0, &nonce.0, &mut ciphertext);
+    Commitment {
+        binding: binding(key, nonce, &ciphertext),
+        ciphertext,
+    }
+}
+
+/// Check `key` and `nonce` open `commitment` and decrypt it.
+pub fn reveal(commitment: &Commitment, key: &Key, nonce: &Nonce) -> Result<Vec<u8>, RevealError> {
+    if !constant_time_eq_32(
+        &binding(key, nonce, &commitment.ciphertext),
+        &commitment.binding,
+    ) {
+        return Err(RevealError::BindingMismatch);
+    }
+    let mut plaintext = commitment.ciphertext.clone();
+    chacha(&key.0, &nonce.0, &mut plaintext);
+    Ok(plaintext)
+}
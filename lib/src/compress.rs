@@ -0,0 +1,176 @@
+//! Compress-then-encrypt.
+//!
+//! Compressing the plaintext before encrypting reduces both the zkVM input (fewer cycles spent
+//! hashing and encrypting) and the committed ciphertext.
+//!
+//! ## Important Notice
+//!
+//! The ciphertext length reveals how well the plaintext compresses. Do not compress plaintexts
+//! that mix secrets with attacker controlled data.
+//!
+
+use crate::{chacha, Key, Nonce};
+
+/// Compression applied to the plaintext before encryption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+#[repr(u8)]
+pub enum CompressionType {
+    #[default]
+    None = 0,
+    /// [LZ4](https://lz4.org/) block format with the decompressed size prepended.
+    Lz4 = 1,
+    /// [Zstandard](https://facebook.github.io/zstd/) frame. Better ratios than LZ4 on text.
+    Zstd = 2,
+}
+
+/// Zstandard level used when none is given, out of 1 (fastest) to 22 (smallest).
+pub const ZSTD_DEFAULT_LEVEL: i32 = 3;
+
+impl TryFrom<u8> for CompressionType {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CompressionType::None),
+            1 => Ok(CompressionType::Lz4),
+            2 => Ok(CompressionType::Zstd),
+            other => Err(other),
+        }
+    }
+}
+
+impl core::fmt::Display for CompressionType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            CompressionType::None => "none",
+            CompressionType::Lz4 => "lz4",
+            CompressionType::Zstd => "zstd",
+        })
+    }
+}
+
+impl core::str::FromStr for CompressionType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(CompressionType::None),
+            "lz4" => Ok(CompressionType::Lz4),
+            "zstd" => Ok(CompressionType::Zstd),
+            other => Err(format!(
+                "unknown compression `{other}`, expected none|lz4|zstd"
+            )),
+        }
+    }
+}
+
+/// The compressed data is malformed.
+#[derive(Debug)]
+pub enum DecompressError {
+    Lz4(lz4_flex::block::DecompressError),
+    Zstd(String),
+}
+
+impl core::fmt::Display for DecompressError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecompressError::Lz4(e) => write!(f, "lz4: {e}"),
+            DecompressError::Zstd(e) => write!(f, "zstd: {e}"),
+        }
+    }
+}
+
+/// Compress with the default level of `compression`.
+///
+/// Zstandard compression is only available on the host, the zkVM only decompresses.
+#[cfg(feature = "std")]
+pub fn compress(compression: CompressionType, plaintext: &[u8]) -> Vec<u8> {
+    match compression {
+        CompressionType::None => plaintext.to_vec(),
+        CompressionType::Lz4 => lz4_flex::compress_prepend_size(plaintext),
+        CompressionType::Zstd => zstd_compress(plaintext, ZSTD_DEFAULT_LEVEL),
+    }
+}
+
+/// Compress into a single Zstandard frame at `level` (1-22).
+#[cfg(feature = "std")]
+pub fn zstd_compress(plaintext: &[u8], level: i32) -> Vec<u8> {
+    zstd::encode_all(plaintext, level).expect("in-memory zstd compression")
+}
+
+pub fn decompress(compression: CompressionType, data: &[u8]) -> Result<Vec<u8>, DecompressError> {
+    match compression {
+        CompressionType::None => Ok(data.to_vec()),
+        CompressionType::Lz4 => {
+            lz4_flex::decompress_size_prepended(data).map_err(DecompressError::Lz4)
+        }
+        CompressionType::Zstd => zstd_decompress(data),
+    }
+}
+
+// Pure Rust decoder, so it also runs inside the zkVM.
+fn zstd_decompress(data: &[u8]) -> Result<Vec<u8>, DecompressError> {
+    use ruzstd::decoding::StreamingDecoder;
+    use ruzstd::io::Read;
+
+    let mut decoder =
+        StreamingDecoder::new(data).map_err(|e| DecompressError::Zstd(e.to_string()))?;
+    let mut plaintext = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = decoder
+            .read(&mut chunk)
+            .map_err(|e| DecompressError::Zstd(e.to_string()))?;
+        if read == 0 {
+            return Ok(plaintext);
+        }
+        plaintext.extend_from_slice(&chunk[..read]);
+    }
+}
+
+/// Compress `plaintext` with LZ4, then encrypt it with [`chacha`].
+pub fn lz4_compress_then_chacha(key: &Key, nonce: &Nonce, plaintext: &[u8]) -> Vec<u8> {
+    let mut buffer = lz4_flex::compress_prepend_size(plaintext);
+    chacha(&key.0, &nonce.0, &mut buffer);
+    buffer
+}
+
+/// Inverse of [`lz4_compress_then_chacha`].
+pub fn chacha_then_lz4_decompress(
+    key: &Key,
+    nonce: &Nonce,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, DecompressError> {
+    let mut buffer = ciphertext.to_vec();
+    chacha(&key.0, &nonce.0, &mut buffer);
+    decompress(CompressionType::Lz4, &buffer)
+}
+
+/// Compress `plaintext` with Zstandard at `level` (1-22), then encrypt it with [`chacha`].
+#[cfg(feature = "std")]
+pub fn zstd_compress_then_chacha(
+    key: &Key,
+    nonce: &Nonce,
+    plaintext: &[u8],
+    level: i32,
+) -> Vec<u8> {
+    let mut buffer = zstd_compress(plaintext, level);
+    chacha(&key.0, &nonce.0, &mut buffer);
+    buffer
+}
+
+/// Inverse of [`zstd_compress_then_chacha`].
+pub fn chacha_then_zstd_decompress(
+    key: &Key,
+    nonce: &Nonce,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, DecompressError> {
+    let mut buffer = ciphertext.to_vec();
+    chacha(&key.0, &nonce.0, &mut buffer);
+    zstd_decompress(&buffer)
+}
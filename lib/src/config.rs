@@ -0,0 +1,171 @@
+//! All cipher options in one place.
+//!
+//! A [`ChaCha20Config`] can be stored with serde, or written inline as comma separated
+//! `name=value` pairs, where omitted options keep their default:
+//!
+//! ```
+//! use chacha_lib::config::{ChaCha20Config, ChaChaRounds};
+//!
+//! let config = ChaCha20Config::try_from("rounds=12,counter=1").unwrap();
+//! assert_eq!(config.rounds, ChaChaRounds::R12);
+//! assert_eq!(config.to_string(), "rounds=12,counter=1,nonce=ietf,compression=none");
+//! ```
+
+use chacha20::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+use chacha20::{ChaCha12, ChaCha20, ChaCha8};
+
+use crate::{CompressionType, NonceFormat};
+
+/// Number of ChaCha rounds. Only 20 rounds is ChaCha20 as used by the zkVM programs, the reduced
+/// round variants trade security margin for speed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
+pub enum ChaChaRounds {
+    R8,
+    R12,
+    #[default]
+    R20,
+}
+
+impl From<ChaChaRounds> for u8 {
+    fn from(rounds: ChaChaRounds) -> Self {
+        match rounds {
+            ChaChaRounds::R8 => 8,
+            ChaChaRounds::R12 => 12,
+            ChaChaRounds::R20 => 20,
+        }
+    }
+}
+
+impl TryFrom<u8> for ChaChaRounds {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            8 => Ok(ChaChaRounds::R8),
+            12 => Ok(ChaChaRounds::R12),
+            20 => Ok(ChaChaRounds::R20),
+            other => Err(format!("unsupported rounds `{other}`, expected 8|12|20")),
+        }
+    }
+}
+
+/// Cipher options, defaulting to what [`crate::chacha`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ChaCha20Config {
+    pub rounds: ChaChaRounds,
+    /// 64 byte keystream block to start at.
+    pub initial_counter: u32,
+    pub nonce_format: NonceFormat,
+    /// Applied by the caller before encrypting, see [`crate::compress`].
+    pub compression: CompressionType,
+}
+
+impl ChaCha20Config {
+    /// Apply the keystream for `key` and `nonce` to `buffer` in place.
+    ///
+    /// In the [`NonceFormat::Original`] format the initial counter is the low half of the 64 bit
+    /// counter, see [`crate::chacha_original`]. Compression is not applied.
+    pub fn apply_keystream(
+        &self,
+        key: &[u8; 32],
+        nonce: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), String> {
+        if nonce.len() != self.nonce_format.nonce_len() {
+            return Err(format!(
+                "{:?} nonce must be {} bytes, got {}",
+                self.nonce_format,
+                self.nonce_format.nonce_len(),
+                nonce.len()
+            ));
+        }
+        let mut ietf_nonce = [0u8; 12];
+        ietf_nonce[12 - nonce.len()..].copy_from_slice(nonce);
+
+        match self.rounds {
+            ChaChaRounds::R8 => apply::<ChaCha8>(key, &ietf_nonce, self.initial_counter, buffer),
+            ChaChaRounds::R12 => apply::<ChaCha12>(key, &ietf_nonce, self.initial_counter, buffer),
+            ChaChaRounds::R20 => apply::<ChaCha20>(key, &ietf_nonce, self.initial_counter, buffer),
+        }
+        Ok(())
+    }
+}
+
+fn apply<C: KeyIvInit + StreamCipher + StreamCipherSeek>(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    counter: u32,
+    buffer: &mut [u8],
+) {
+    let mut cipher = C::new_from_slices(key, nonce).expect("key and nonce lengths are fixed");
+    cipher.seek(u64::from(counter) * 64);
+    cipher.apply_keystream(buffer);
+}
+
+impl core::fmt::Display for ChaCha20Config {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let nonce_format = match self.nonce_format {
+            NonceFormat::Ietf => "ietf",
+            NonceFormat::Original => "original",
+        };
+        write!(
+            f,
+            "rounds={},counter={},nonce={nonce_format},compression={}",
+            u8::from(self.rounds),
+            self.initial_counter,
+            self.compression
+        )
+    }
+}
+
+impl TryFrom<&str> for ChaCha20Config {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let mut config = ChaCha20Config::default();
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected `name=value`, got `{pair}`"))?;
+            match name.trim() {
+                "rounds" => {
+                    let rounds: u8 = value
+                        .trim()
+                        .parse()
+                        .map_err(|e| format!("invalid rounds: {e}"))?;
+                    config.rounds = rounds.try_into()?;
+                }
+                "counter" => {
+                    config.initial_counter = value
+                        .trim()
+                        .parse()
+                        .map_err(|e| format!("invalid counter: {e}"))?;
+                }
+                "nonce" => {
+                    config.nonce_format = match value.trim().to_ascii_lowercase().as_str() {
+                        "ietf" => NonceFormat::Ietf,
+                        "original" => NonceFormat::Original,
+                        other => {
+                            return Err(format!(
+                                "unknown nonce format `{other}`, expected ietf|original"
+                            ))
+                        }
+                    };
+                }
+                "compression" => config.compression = value.trim().parse()?,
+                other => return Err(format!("unknown option `{other}`")),
+            }
+        }
+        Ok(config)
+    }
+}
@@ -0,0 +1,25 @@
+//! Chain-of-custody commitment, attesting who generated a proof and when.
+//!
+//! The host passes a 16 byte operator ID and its system time to the program, which commits to
+//! [`custody_hash`] over them and the plaintext hash. Neither is revealed: a verifier who knows the
+//! operator ID and the time recomputes the hash to hold that operator accountable for the proof.
+//! The time is read by the host and not attested by anyone, unlike a
+//! [`crate::timestamp_commitment`], and a low entropy operator ID can be guessed from the hash.
+
+use sha2::{Digest, Sha256};
+
+/// Length of an operator ID.
+pub const OPERATOR_ID_LEN: usize = 16;
+
+/// `SHA256(operator_id || timestamp || input_hash)`, with the timestamp as unix seconds u64 LE.
+pub fn custody_hash(
+    operator_id: &[u8; OPERATOR_ID_LEN],
+    timestamp: u64,
+    input_hash: &[u8; 32],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(operator_id);
+    hasher.update(timestamp.to_le_bytes());
+    hasher.update(input_hash);
+    hasher.finalize().into()
+}
@@ -0,0 +1,34 @@
+//! Nonces derived from the plaintext, for idempotent encryption.
+//!
+//! [`deterministic_nonce`] is a PRF of the key and the plaintext, so encrypting the same plaintext
+//! twice under the same key gives the same nonce and so the same ciphertext, nullifier and proof
+//! public values, which lets a store deduplicate ciphertexts without decrypting them.
+//!
+//! The trade-off is that equal ciphertexts now mean equal plaintexts: anyone seeing two
+//! ciphertexts of the same key learns whether they encrypt the same plaintext, and a
+//! `NullifierRegistry` accepts only the first proof of a plaintext. Nonces of different plaintexts
+//! only collide by chance, which after 2^48 plaintexts under one key is about as likely as with
+//! [`crate::random_nonce`]. Use random nonces unless deduplication is needed.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{Key, Nonce};
+
+/// `HMAC-SHA256(key, plaintext)[0..12]`.
+///
+/// ```
+/// use chacha_lib::deterministic_nonce::deterministic_nonce;
+/// use chacha_lib::Key;
+///
+/// let key = Key([7; 32]);
+/// assert_eq!(deterministic_nonce(&key, b"report"), deterministic_nonce(&key, b"report"));
+/// assert_ne!(deterministic_nonce(&key, b"report"), deterministic_nonce(&key, b"reports"));
+/// ```
+pub fn deterministic_nonce(key: &Key, plaintext: &[u8]) -> Nonce {
+    let mut mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(&key.0).expect("HMAC accepts any key length");
+    mac.update(plaintext);
+    let tag = mac.finalize().into_bytes();
+    Nonce(tag[..12].try_into().unwrap())
+}
@@ -0,0 +1,19 @@
+//! Domain separation of keys used in more than one context.
+//!
+//! Using the same key for encryption and, say, authentication risks cross-protocol attacks.
+//! Derive an independent subkey per context with [`domain_separate`] instead.
+
+use crate::kdf::hkdf_derive_key;
+use crate::Key;
+
+/// Subkey for ChaCha20 encryption.
+pub const DOMAIN_ENCRYPT: &[u8] = b"encrypt";
+/// Subkey to derive further keys from.
+pub const DOMAIN_DERIVE: &[u8] = b"derive";
+/// Subkey for MACs and other authentication.
+pub const DOMAIN_AUTH: &[u8] = b"auth";
+
+/// Derive the subkey of `key` for `domain` with HKDF-SHA256, using `domain` as the info string.
+pub fn domain_separate(key: &Key, domain: &[u8]) -> Key {
+    hkdf_derive_key(&key.0, None, domain)
+}
@@ -0,0 +1,146 @@
+//! A self-describing JSON envelope for proof artifacts.
+//!
+//! New fields go into [`ProofEnvelope::extensions`], so parsers of older versions keep working.
+//! Bytes are hex encoded. Envelopes can be signed with ed25519 to attest who produced them.
+
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ed25519_dalek::{Signature, SignatureError, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::{ProofType, PublicValues};
+
+pub use ed25519_dalek;
+
+/// Version of envelopes created by this crate. Version 1 are the raw proof bytes, see
+/// [`ProofEnvelope::from_v1`].
+pub const ENVELOPE_VERSION: u8 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofEnvelope {
+    pub version: u8,
+    pub proof_type: ProofType,
+    /// Seconds since the unix epoch.
+    pub created_at: u64,
+    /// Verification key hash of the program, as printed by the `vkey` binary.
+    #[serde(with = "hex")]
+    pub program_hash: [u8; 32],
+    /// Raw committed bytes, see [`ProofEnvelope::public_values`].
+    #[serde(with = "hex")]
+    pub public_values: Vec<u8>,
+    #[serde(with = "hex")]
+    pub proof_bytes: Vec<u8>,
+    /// Optional fields such as a chain ID or an expiry. Ordered so signatures are deterministic.
+    #[serde(default, with = "hex_map")]
+    pub extensions: BTreeMap<String, Vec<u8>>,
+}
+
+impl ProofEnvelope {
+    pub fn new(
+        proof_type: ProofType,
+        program_hash: [u8; 32],
+        public_values: Vec<u8>,
+        proof_bytes: Vec<u8>,
+    ) -> Self {
+        Self {
+            version: ENVELOPE_VERSION,
+            proof_type,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock before unix epoch")
+                .as_secs(),
+            program_hash,
+            public_values,
+            proof_bytes,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    /// Upgrade a v1 proof, which was only the proof and public values bytes, to an envelope.
+    ///
+    /// v1 proofs record neither the proof type nor the program, so both must be supplied.
+    pub fn from_v1(
+        proof_type: ProofType,
+        program_hash: [u8; 32],
+        public_values: &[u8],
+        proof_bytes: &[u8],
+    ) -> Self {
+        Self::new(
+            proof_type,
+            program_hash,
+            public_values.to_vec(),
+            proof_bytes.to_vec(),
+        )
+    }
+
+    /// The parsed public values, `None` if they are too short.
+    pub fn public_values(&self) -> Option<PublicValues<'_>> {
+        PublicValues::parse(&self.public_values)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("envelope serializes")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Sign the JSON serialization of this envelope.
+    pub fn sign(&self, signing_key: &SigningKey) -> SignedEnvelope {
+        SignedEnvelope {
+            envelope: self.clone(),
+            public_key: signing_key.verifying_key().to_bytes(),
+            signature: signing_key.sign(self.to_json().as_bytes()).to_bytes(),
+        }
+    }
+}
+
+/// A [`ProofEnvelope`] with an ed25519 signature by `public_key`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedEnvelope {
+    pub envelope: ProofEnvelope,
+    #[serde(with = "hex")]
+    pub public_key: [u8; 32],
+    #[serde(with = "hex")]
+    pub signature: [u8; 64],
+}
+
+impl SignedEnvelope {
+    /// Check the signature against `public_key`. Callers must still check that they trust the key.
+    pub fn verify_signature(&self) -> Result<(), SignatureError> {
+        let public_key = VerifyingKey::from_bytes(&self.public_key)?;
+        public_key.verify_strict(
+            self.envelope.to_json().as_bytes(),
+            &Signature::from_bytes(&self.signature),
+        )
+    }
+}
+
+/// Hex encode the values of the extensions map.
+mod hex_map {
+    use std::collections::BTreeMap;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        map: &BTreeMap<String, Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        map.iter()
+            .map(|(k, v)| (k, hex::encode(v)))
+            .collect::<BTreeMap<_, _>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<String, Vec<u8>>, D::Error> {
+        BTreeMap::<String, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(k, v)| Ok((k, hex::decode(v).map_err(D::Error::custom)?)))
+            .collect()
+    }
+}
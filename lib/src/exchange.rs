@@ -0,0 +1,140 @@
+//! X25519 key exchange, to encrypt to a recipient's public key.
+//!
+//! The sender picks an ephemeral secret `r` and publishes `R = r * G` with the ciphertext. Both
+//! sides derive the ChaCha20 key and nonce from the shared point `r * P = p * R` with HKDF-SHA256,
+//! salted with both public keys. The nonce can be derived since every ephemeral key is used once.
+//!
+//! ## Relation to ElGamal
+//!
+//! This is hashed ElGamal (DHIES/ECIES): the ephemeral public key is the first ElGamal component,
+//! and the key derived from the shared point replaces the multiplicative mask. A zkVM proof of the
+//! derivation and encryption turns it into verifiable encryption: anyone can check that the
+//! ciphertext decrypts under the recipient's key to a plaintext with the committed hash, without
+//! learning the plaintext.
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+use x25519_dalek::{x25519, X25519_BASEPOINT_BYTES};
+
+use crate::{Key, KeyError, Nonce};
+
+/// HKDF info string of the derived key and nonce.
+const INFO: &[u8] = b"sp1-chacha/x25519";
+
+/// The little endian u-coordinates of small order rejected by [`validate_x25519_public_key`],
+/// besides 0. These are the list of libsodium's `crypto_scalarmult_curve25519`:
+///
+/// - 1, the two points of order 4
+/// - 325606250916557431795983626356110631294008115727848805560023387167927233504 and
+///   39382357235489614581723060781553021112529911719440698176882885853963445705823, the four
+///   points of order 8
+/// - p - 1, with p = 2^255 - 19, of order 2 on the twist
+/// - p and p + 1, the non-canonical encodings of 0 and 1
+const LOW_ORDER_POINTS: [[u8; 32]; 6] = [
+    [
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ],
+    [
+        0xe0, 0xeb, 0x7a, 0x7c, 0x3b, 0x41, 0xb8, 0xae, 0x16, 0x56, 0xe3, 0xfa, 0xf1, 0x9f, 0xc4,
+        0x6a, 0xda, 0x09, 0x8d, 0xeb, 0x9c, 0x32, 0xb1, 0xfd, 0x86, 0x62, 0x05, 0x16, 0x5f, 0x49,
+        0xb8, 0x00,
+    ],
+    [
+        0x5f, 0x9c, 0x95, 0xbc, 0xa3, 0x50, 0x8c, 0x24, 0xb1, 0xd0, 0xb1, 0x55, 0x9c, 0x83, 0xef,
+        0x5b, 0x04, 0x44, 0x5c, 0xc4, 0x58, 0x1c, 0x8e, 0x86, 0xd8, 0x22, 0x4e, 0xdd, 0xd0, 0x9f,
+        0x11, 0x57,
+    ],
+    [
+        0xec, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x7f,
+    ],
+    [
+        0xed, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x7f,
+    ],
+    [
+        0xee, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x7f,
+    ],
+];
+
+/// Reject public keys whose shared secret with any secret key is one of a few values known to an
+/// attacker, before using them in [`sender_key`] or [`recipient_key`].
+///
+/// Curve25519 has a cofactor of 8, so its 8 points of small order, whose u-coordinates are 0, 1
+/// and the two above of order 8, multiply to the point at infinity under every clamped scalar
+/// (RFC 7748 section 5 clears the 3 low bits). X25519 outputs all zeros for the point at infinity,
+/// so these keys are rejected, as are the points of small order on the twist and the
+/// non-canonical encodings of small order points, since X25519 accepts u-coordinates that are
+/// not reduced mod p. The most significant bit is masked first, as X25519 ignores it
+/// (RFC 7748 section 5).
+pub fn validate_x25519_public_key(pk: &[u8; 32]) -> Result<(), KeyError> {
+    let mut u = *pk;
+    u[31] &= 0x7f;
+    if u == [0; 32] {
+        return Err(KeyError::PointAtInfinity);
+    }
+    if LOW_ORDER_POINTS.contains(&u) {
+        return Err(KeyError::WeakPublicKey);
+    }
+    Ok(())
+}
+
+/// The X25519 public key of `secret`.
+pub fn public_key(secret: &[u8; 32]) -> [u8; 32] {
+    x25519(*secret, X25519_BASEPOINT_BYTES)
+}
+
+/// Derive the ephemeral public key and the key and nonce to encrypt to `recipient_public`.
+///
+/// `None` if `recipient_public` is a low order point, as the shared secret would be known, see
+/// [`validate_x25519_public_key`].
+pub fn sender_key(
+    ephemeral_secret: &[u8; 32],
+    recipient_public: &[u8; 32],
+) -> Option<([u8; 32], Key, Nonce)> {
+    validate_x25519_public_key(recipient_public).ok()?;
+    let ephemeral_public = public_key(ephemeral_secret);
+    let shared = x25519(*ephemeral_secret, *recipient_public);
+    let (key, nonce) = derive(&shared, &ephemeral_public, recipient_public)?;
+    Some((ephemeral_public, key, nonce))
+}
+
+/// Derive the key and nonce of a message encrypted with [`sender_key`].
+///
+/// `None` if `ephemeral_public` is a low order point.
+pub fn recipient_key(
+    recipient_secret: &[u8; 32],
+    ephemeral_public: &[u8; 32],
+) -> Option<(Key, Nonce)> {
+    validate_x25519_public_key(ephemeral_public).ok()?;
+    let shared = x25519(*recipient_secret, *ephemeral_public);
+    derive(&shared, ephemeral_public, &public_key(recipient_secret))
+}
+
+fn derive(
+    shared: &[u8; 32],
+    ephemeral_public: &[u8; 32],
+    recipient_public: &[u8; 32],
+) -> Option<(Key, Nonce)> {
+    if shared == &[0u8; 32] {
+        return None;
+    }
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(ephemeral_public);
+    salt[32..].copy_from_slice(recipient_public);
+
+    let mut okm = [0u8; 44];
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(INFO, &mut okm)
+        .expect("44 bytes is a valid HKDF-SHA256 output length");
+    Some((
+        Key(okm[..32].try_into().unwrap()),
+        Nonce(okm[32..].try_into().unwrap()),
+    ))
+}
@@ -0,0 +1,112 @@
+//! A seekable encrypted file format.
+//!
+//! ChaCha20 can start at any 64 byte keystream block, so any range of the plaintext can be
+//! decrypted without processing the bytes before it. A file is a 64 byte header followed by the
+//! ciphertext:
+//!
+//! ```text
+//! version (u32 LE) | plaintext_size (u64 LE) | sha256(plaintext) (32) | nonce (12) | reserved (8)
+//! ```
+
+use sha2::{Digest, Sha256};
+
+use crate::{chacha, chacha_at, Key, Nonce};
+
+pub const HEADER_LEN: usize = 64;
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub version: u32,
+    pub plaintext_size: u64,
+    /// SHA256 of the full plaintext, to check a complete decryption.
+    pub plaintext_hash: [u8; 32],
+    pub nonce: Nonce,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileFormatError {
+    TooShort,
+    UnsupportedVersion(u32),
+    /// The header size does not match the ciphertext length.
+    SizeMismatch,
+}
+
+impl core::fmt::Display for FileFormatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FileFormatError::TooShort => f.write_str("file is shorter than its header"),
+            FileFormatError::UnsupportedVersion(v) => write!(f, "unsupported format version {v}"),
+            FileFormatError::SizeMismatch => {
+                f.write_str("plaintext size does not match the ciphertext length")
+            }
+        }
+    }
+}
+
+impl Header {
+    pub fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0u8; HEADER_LEN];
+        bytes[..4].copy_from_slice(&self.version.to_le_bytes());
+        bytes[4..12].copy_from_slice(&self.plaintext_size.to_le_bytes());
+        bytes[12..44].copy_from_slice(&self.plaintext_hash);
+        bytes[44..56].copy_from_slice(&self.nonce.0);
+        bytes
+    }
+
+    /// Parse the header of `file` and check it matches the ciphertext length.
+    pub fn parse(file: &[u8]) -> Result<Self, FileFormatError> {
+        if file.len() < HEADER_LEN {
+            return Err(FileFormatError::TooShort);
+        }
+        let version = u32::from_le_bytes(file[..4].try_into().unwrap());
+        if version != FORMAT_VERSION {
+            return Err(FileFormatError::UnsupportedVersion(version));
+        }
+        let header = Header {
+            version,
+            plaintext_size: u64::from_le_bytes(file[4..12].try_into().unwrap()),
+            plaintext_hash: file[12..44].try_into().unwrap(),
+            nonce: Nonce(file[44..56].try_into().unwrap()),
+        };
+        if header.plaintext_size != (file.len() - HEADER_LEN) as u64 {
+            return Err(FileFormatError::SizeMismatch);
+        }
+        Ok(header)
+    }
+}
+
+/// Encrypt `plaintext` into the seekable file format. `nonce` MUST be unique for `key`.
+pub fn encrypt_file(key: &Key, nonce: &Nonce, plaintext: &[u8]) -> Vec<u8> {
+    let header = Header {
+        version: FORMAT_VERSION,
+        plaintext_size: plaintext.len() as u64,
+        plaintext_hash: Sha256::digest(plaintext).into(),
+        nonce: nonce.clone(),
+    };
+    let mut file = header.to_bytes().to_vec();
+    file.extend_from_slice(plaintext);
+    chacha(&key.0, &nonce.0, &mut file[HEADER_LEN..]);
+    file
+}
+
+/// Decrypt `len` plaintext bytes starting at `offset`, truncated at the end of the plaintext.
+pub fn seek_decrypt(
+    key: &Key,
+    file: &[u8],
+    offset: u64,
+    len: usize,
+) -> Result<Vec<u8>, FileFormatError> {
+    let header = Header::parse(file)?;
+    let ciphertext = &file[HEADER_LEN..];
+    let start = offset.min(header.plaintext_size) as usize;
+    let end = start.saturating_add(len).min(ciphertext.len());
+
+    // Start at the block containing `offset`, then drop the keystream bytes before it.
+    let skip = start % 64;
+    let mut buffer = ciphertext[start - skip..end].to_vec();
+    let counter = u32::try_from(start / 64).expect("offset within the ChaCha20 keystream");
+    chacha_at(&key.0, &header.nonce.0, counter, &mut buffer);
+    buffer.drain(..skip);
+    Ok(buffer)
+}
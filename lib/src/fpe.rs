@@ -0,0 +1,92 @@
+//! Format-preserving encryption over an alphabet, e.g. a 16 digit card number to another 16 digit
+//! number.
+//!
+//! Each character is shifted within the alphabet by an offset drawn from the ChaCha20 keystream:
+//! keystream bytes at or above the largest multiple of the alphabet size below 256 are skipped,
+//! walking the keystream until a byte maps to an offset without modulo bias. Characters outside
+//! the alphabet, such as the dashes of `4111-1111-1111-1111`, are kept as they are.
+//!
+//! This is a stream cipher restricted to an alphabet, not a format-preserving block cipher such as
+//! FF1 or FF3-1 from NIST SP 800-38G: every key and nonce pair must encrypt a single value, as
+//! reusing the nonce reveals the differences between values, and a ciphertext character can be
+//! changed to decrypt to any other character. The characters outside the alphabet and the length
+//! are not hidden.
+
+use crate::{chacha_at, Key, Nonce};
+
+/// Largest alphabet, as each offset is drawn from one keystream byte.
+pub const MAX_ALPHABET_LEN: usize = 256;
+
+/// Offsets in `0..alphabet_len`, uniformly distributed.
+fn offsets(key: &Key, nonce: &Nonce, alphabet_len: usize) -> impl Iterator<Item = usize> {
+    let (key, nonce) = (key.0, nonce.0);
+    // The largest multiple of the alphabet size that fits in a byte
+    let limit = MAX_ALPHABET_LEN - MAX_ALPHABET_LEN % alphabet_len;
+    (0u32..)
+        .flat_map(move |counter| {
+            let mut block = [0; 64];
+            chacha_at(&key, &nonce, counter, &mut block);
+            block
+        })
+        .map(usize::from)
+        .filter(move |&byte| byte < limit)
+        .map(move |byte| byte % alphabet_len)
+}
+
+fn shift(key: &Key, nonce: &Nonce, text: &str, alphabet: &str, decrypt: bool) -> String {
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    let len = alphabet.len();
+    assert!(
+        (2..=MAX_ALPHABET_LEN).contains(&len),
+        "alphabet of {len} characters, expected 2 to {MAX_ALPHABET_LEN}"
+    );
+    assert!(
+        alphabet
+            .iter()
+            .enumerate()
+            .all(|(i, c)| !alphabet[..i].contains(c)),
+        "alphabet has repeated characters"
+    );
+
+    let mut offsets = offsets(key, nonce, len);
+    text.chars()
+        .map(|c| match alphabet.iter().position(|&a| a == c) {
+            Some(index) => {
+                let offset = offsets.next().expect("the keystream is endless");
+                let offset = if decrypt { len - offset } else { offset };
+                alphabet[(index + offset) % len]
+            }
+            None => c,
+        })
+        .collect()
+}
+
+/// Encrypt the characters of `plaintext` that are in `alphabet` to characters of `alphabet`.
+///
+/// ```
+/// use chacha_lib::fpe::{chacha_fpe_decrypt, chacha_fpe_encrypt};
+/// use chacha_lib::{Key, Nonce};
+///
+/// let (key, nonce) = (Key([1; 32]), Nonce([2; 12]));
+/// let ciphertext = chacha_fpe_encrypt(&key, &nonce, "4111-1111-1111-1111", "0123456789");
+/// assert_eq!(ciphertext.len(), 19);
+/// assert_eq!(ciphertext.matches('-').count(), 3);
+/// assert_eq!(chacha_fpe_decrypt(&key, &nonce, &ciphertext, "0123456789"), "4111-1111-1111-1111");
+/// ```
+///
+/// # Panics
+///
+/// If `alphabet` repeats a character, or has fewer than 2 or more than [`MAX_ALPHABET_LEN`]
+/// characters.
+pub fn chacha_fpe_encrypt(key: &Key, nonce: &Nonce, plaintext: &str, alphabet: &str) -> String {
+    shift(key, nonce, plaintext, alphabet, false)
+}
+
+/// The inverse of [`chacha_fpe_encrypt`].
+///
+/// # Panics
+///
+/// Like [`chacha_fpe_encrypt`].
+pub fn chacha_fpe_decrypt(key: &Key, nonce: &Nonce, ciphertext: &str, alphabet: &str) -> String {
+    shift(key, nonce, ciphertext, alphabet, true)
+}
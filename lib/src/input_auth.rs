@@ -0,0 +1,115 @@
+//! HMAC-SHA256 authentication of the encryption program inputs.
+//!
+//! The host writes the inputs to the zkVM, so a bug in the host that changes any of them would be
+//! proven as faithfully as the intended inputs. With a pre-shared HMAC key, separate from the
+//! encryption key, whoever prepares the inputs tags every input the program reads before the tag,
+//! [`AuthenticatedInputs`], with [`input_hmac`]. The buffer is the plaintext as the program reads
+//! it, after compression. The program checks the tag before anything else and only commits
+//! [`INPUT_HMAC_FAILED`] if it does not match. Otherwise it commits [`hmac_key_hash`], or zeros
+//! without authentication, so a verifier can check the pre-shared key was used.
+//!
+//! Version 5 and 6 programs only tag `key (32) | nonce (12) | buffer`, see [`input_hmac_v6`].
+//!
+//! The HMAC key is an input too, so this catches inputs changed after they were tagged, such as
+//! a host corrupting them or reading the wrong file. It cannot stop a malicious host that holds the
+//! HMAC key, as it can tag whatever inputs it likes.
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::custody::OPERATOR_ID_LEN;
+use crate::{CompressionType, Key, Nonce};
+
+/// The only public values of a proof whose inputs failed authentication, too short to parse as
+/// [`crate::PublicValues`].
+pub const INPUT_HMAC_FAILED: [u8; 32] = [0; 32];
+
+/// SHA256 of the HMAC key, committed by the program as
+/// [`crate::PublicValues::input_hmac_key_hash`].
+pub fn hmac_key_hash(hmac_key: &[u8; 32]) -> [u8; 32] {
+    Sha256::digest(hmac_key).into()
+}
+
+/// The inputs the program reads before the tag, in that order.
+pub struct AuthenticatedInputs<'a> {
+    pub key: &'a Key,
+    pub nonce: &'a Nonce,
+    pub compression: CompressionType,
+    pub commit_segments: bool,
+    pub commit_ciphertext_hash: bool,
+    pub timestamp: Option<&'a [u8]>,
+    pub custody: Option<([u8; OPERATOR_ID_LEN], u64)>,
+    pub lineage: Option<[u8; 32]>,
+    pub buffer: &'a [u8],
+}
+
+fn new_mac(hmac_key: &[u8; 32]) -> Hmac<Sha256> {
+    <Hmac<Sha256> as Mac>::new_from_slice(hmac_key).expect("HMAC accepts any key length")
+}
+
+/// MAC over `key (32) | nonce (12) | compression (1) | commit_segments (1) |
+/// commit_ciphertext_hash (1) | timestamp | custody | lineage | buffer length (8) | buffer`.
+///
+/// Each option is a 1 byte flag followed, if set, by the timestamp length (8) and bytes, the
+/// operator ID (16) and time (8), or the lineage (32). Lengths and times are u64 LE. Every
+/// variable length value is prefixed with its length, so the encoding is unambiguous.
+fn hmac(hmac_key: &[u8; 32], inputs: &AuthenticatedInputs) -> Hmac<Sha256> {
+    let mut mac = new_mac(hmac_key);
+    mac.update(&inputs.key.0);
+    mac.update(&inputs.nonce.0);
+    mac.update(&[
+        inputs.compression as u8,
+        inputs.commit_segments as u8,
+        inputs.commit_ciphertext_hash as u8,
+    ]);
+    match inputs.timestamp {
+        Some(timestamp) => {
+            mac.update(&[1]);
+            mac.update(&(timestamp.len() as u64).to_le_bytes());
+            mac.update(timestamp);
+        }
+        None => mac.update(&[0]),
+    }
+    match &inputs.custody {
+        Some((operator_id, time)) => {
+            mac.update(&[1]);
+            mac.update(operator_id);
+            mac.update(&time.to_le_bytes());
+        }
+        None => mac.update(&[0]),
+    }
+    match &inputs.lineage {
+        Some(lineage) => {
+            mac.update(&[1]);
+            mac.update(lineage);
+        }
+        None => mac.update(&[0]),
+    }
+    mac.update(&(inputs.buffer.len() as u64).to_le_bytes());
+    mac.update(inputs.buffer);
+    mac
+}
+
+/// The tag of `inputs`.
+pub fn input_hmac(hmac_key: &[u8; 32], inputs: &AuthenticatedInputs) -> [u8; 32] {
+    hmac(hmac_key, inputs).finalize().into_bytes().into()
+}
+
+/// Whether `tag` is the tag of `inputs`, compared in constant time.
+pub fn verify_input_hmac(
+    hmac_key: &[u8; 32],
+    inputs: &AuthenticatedInputs,
+    tag: &[u8; 32],
+) -> bool {
+    hmac(hmac_key, inputs).verify_slice(tag).is_ok()
+}
+
+/// The tag version 5 and 6 programs check, over `key (32) | nonce (12) | buffer` only.
+pub fn input_hmac_v6(hmac_key: &[u8; 32], key: &Key, nonce: &Nonce, buffer: &[u8]) -> [u8; 32] {
+    let mut mac = new_mac(hmac_key);
+    // The key and nonce are fixed length, so the concatenation is unambiguous
+    mac.update(&key.0);
+    mac.update(&nonce.0);
+    mac.update(buffer);
+    mac.finalize().into_bytes().into()
+}
@@ -0,0 +1,77 @@
+//! Streaming encryption and decryption, for files too large to load into memory.
+//!
+//! The output is identical to [`chacha`](crate::chacha) over the whole stream.
+
+use std::io::{self, Read, Write};
+
+use chacha20::cipher::{KeyIvInit, StreamCipher};
+use chacha20::ChaCha20;
+
+use crate::{Key, Nonce};
+
+/// Size of the scratch buffer [`ChaChaWriter`] encrypts into.
+const CHUNK_SIZE: usize = 4096;
+
+/// Decrypts (or encrypts) everything read from `inner`.
+pub struct ChaChaReader<R: Read> {
+    inner: R,
+    cipher: ChaCha20,
+}
+
+impl<R: Read> ChaChaReader<R> {
+    pub fn new(inner: R, key: &Key, nonce: &Nonce) -> Self {
+        Self {
+            inner,
+            cipher: ChaCha20::new(&key.0.into(), &nonce.0.into()),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for ChaChaReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.cipher.apply_keystream(&mut buf[..read]);
+        Ok(read)
+    }
+}
+
+/// Encrypts (or decrypts) everything written to `inner`.
+///
+/// The keystream advances as soon as bytes are accepted, so the stream is unusable after an I/O
+/// error.
+pub struct ChaChaWriter<W: Write> {
+    inner: W,
+    cipher: ChaCha20,
+}
+
+impl<W: Write> ChaChaWriter<W> {
+    pub fn new(inner: W, key: &Key, nonce: &Nonce) -> Self {
+        Self {
+            inner,
+            cipher: ChaCha20::new(&key.0.into(), &nonce.0.into()),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for ChaChaWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(CHUNK_SIZE);
+        let mut chunk = [0u8; CHUNK_SIZE];
+        chunk[..len].copy_from_slice(&buf[..len]);
+        self.cipher.apply_keystream(&mut chunk[..len]);
+        self.inner.write_all(&chunk[..len])?;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,37 @@
+//! HKDF-SHA256 (RFC 5869) derivation of ChaCha20 keys and nonces from a secret.
+//!
+//! `ikm` is the input key material, such as a shared secret or master key. `salt` is optional,
+//! `None` is the same as a salt of 32 zero bytes. `info` binds the output to its use, outputs for
+//! different `info` values are independent.
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+use crate::{Key, Nonce};
+
+fn derive<const N: usize>(ikm: &[u8], salt: Option<&[u8]>, info: &[u8]) -> [u8; N] {
+    let mut okm = [0u8; N];
+    Hkdf::<Sha256>::new(salt, ikm)
+        .expand(info, &mut okm)
+        .expect("keys and nonces are valid HKDF-SHA256 output lengths");
+    okm
+}
+
+/// Derive a ChaCha20 key.
+///
+/// ```
+/// use chacha_lib::kdf::hkdf_derive_key;
+///
+/// let encrypt = hkdf_derive_key(b"master secret", None, b"encrypt");
+/// let auth = hkdf_derive_key(b"master secret", None, b"auth");
+/// assert_ne!(encrypt, auth);
+/// ```
+pub fn hkdf_derive_key(ikm: &[u8], salt: Option<&[u8]>, info: &[u8]) -> Key {
+    Key(derive(ikm, salt, info))
+}
+
+/// Derive a ChaCha20 nonce. Use a different `info` than for the key, and never derive the same
+/// nonce twice for one key.
+pub fn hkdf_derive_nonce(ikm: &[u8], salt: Option<&[u8]>, info: &[u8]) -> Nonce {
+    Nonce(derive(ikm, salt, info))
+}
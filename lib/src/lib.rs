@@ -1,11 +1,213 @@
 // Include the binary input file
 pub const INPUT_BYTES: &[u8] = include_bytes!("../../static/proof_input_example.bin");
 
-use chacha20::cipher::{KeyIvInit, StreamCipher};
+use chacha20::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
 use chacha20::ChaCha20;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+pub mod aes_mode;
+pub mod aggregate;
+pub mod blake2b;
+pub mod commit_reveal;
+pub mod compress;
+pub mod config;
+pub mod custody;
+pub mod deterministic_nonce;
+pub mod domain;
+#[cfg(feature = "std")]
+pub mod envelope;
+pub mod exchange;
+pub mod file_format;
+pub mod fpe;
+pub mod input_auth;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod kdf;
+pub mod lineage;
+pub mod mac;
+pub mod merkle;
+pub mod mode;
+#[cfg(feature = "std")]
+pub mod mmap;
+pub mod noise_compat;
+#[cfg(feature = "std")]
+pub mod nonce_tracker;
+pub mod nullifier;
+#[cfg(feature = "std")]
+pub mod parallel;
+pub mod pedersen;
+pub mod policy;
+pub mod public_values;
+pub mod rekey;
+#[cfg(all(feature = "secure-memory", unix))]
+pub mod secure_memory;
+pub mod selective;
+pub mod shamir;
+pub mod signal_compat;
+pub mod ssh_compat;
+pub mod timelock;
+pub mod timestamp;
+pub mod tls13;
+pub mod upgrade;
+pub mod vk_registry;
+pub mod wireguard;
+
+#[doc = include_str!("security.md")]
+pub mod security {}
+
+pub use compress::CompressionType;
+pub use custody::custody_hash;
+pub use lineage::lineage_commitment;
+pub use nullifier::compute_nullifier;
+pub use public_values::PublicValues;
+pub use timestamp::timestamp_commitment;
+pub use vk_registry::PROGRAM_VERSION;
+
+/// A 256 bit ChaCha20 key.
+///
+/// Compared in constant time and zeroized on drop, see [`security`].
+///
+/// # Example
+///
+/// ```
+/// use chacha_lib::Key;
+///
+/// let key = Key::from([7u8; 32]);
+/// assert_eq!(key, Key([7u8; 32]));
+/// // Key material is never printed
+/// assert_eq!(format!("{key:?}"), "Key(..)");
+/// ```
+#[derive(Clone)]
+pub struct Key(pub [u8; 32]);
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq::constant_time_eq_32(&self.0, &other.0)
+    }
+}
+
+impl Eq for Key {}
+
+impl Zeroize for Key {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for Key {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Key {}
+
+impl From<[u8; 32]> for Key {
+    fn from(bytes: [u8; 32]) -> Self {
+        Key(bytes)
+    }
+}
+
+// Never print key material
+impl core::fmt::Debug for Key {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+/// A 96 bit ChaCha20 nonce. It MUST never be reused with the same [`Key`].
+///
+/// Nonces are not secret, but are zeroized on drop like keys since they are often derived from
+/// key material.
+///
+/// # Example
+///
+/// ```
+/// use chacha_lib::{chacha, Key, Nonce};
+///
+/// let (key, nonce) = (Key([1u8; 32]), Nonce([2u8; 12]));
+/// let mut buffer = *b"hello";
+/// chacha(&key.0, &nonce.0, &mut buffer);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nonce(pub [u8; 12]);
+
+impl Zeroize for Nonce {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for Nonce {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Nonce {}
+
+impl From<[u8; 12]> for Nonce {
+    fn from(bytes: [u8; 12]) -> Self {
+        Nonce(bytes)
+    }
+}
+
+/// How the last 16 bytes of the ChaCha20 state are split between block counter and nonce.
+///
+/// In both formats the counter is stored as little-endian 32 bit words, like the rest of the
+/// state, and starts at the block given by the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum NonceFormat {
+    /// [RFC 8439](https://www.rfc-editor.org/rfc/rfc8439#section-2.3): a 32 bit counter and a
+    /// 96 bit nonce, limiting a single nonce to 256 GiB. Used by [`chacha`] and the zkVM program.
+    #[default]
+    Ietf,
+    /// The original construction by D. J. Bernstein: a 64 bit counter and a 64 bit nonce, used by
+    /// e.g. libsodium's `crypto_stream_chacha20`. See [`chacha_original`].
+    Original,
+}
+
+impl NonceFormat {
+    /// Length of the nonce in bytes.
+    ///
+    /// ```
+    /// use chacha_lib::NonceFormat;
+    ///
+    /// assert_eq!(NonceFormat::Ietf.nonce_len(), 12);
+    /// assert_eq!(NonceFormat::Original.nonce_len(), 8);
+    /// ```
+    pub const fn nonce_len(self) -> usize {
+        match self {
+            NonceFormat::Ietf => 12,
+            NonceFormat::Original => 8,
+        }
+    }
+
+    /// Width of the block counter in bits.
+    ///
+    /// ```
+    /// use chacha_lib::NonceFormat;
+    ///
+    /// assert_eq!(NonceFormat::default().counter_bits(), 32);
+    /// ```
+    pub const fn counter_bits(self) -> u32 {
+        match self {
+            NonceFormat::Ietf => 32,
+            NonceFormat::Original => 64,
+        }
+    }
+}
 
 /// Encrypt a buffer in-place using [ChaCha20](https://en.wikipedia.org/wiki/Salsa20#ChaCha_variant).
 ///
+/// The key and nonce are used in the [`NonceFormat::Ietf`] format of the `chacha20` crate, and the
+/// block counter starts at 0.
+///
 /// ## Important Notice
 ///
 /// This intentionally omits the Poly1305 MAC steps to reduce cycle count.
@@ -13,22 +215,475 @@ use chacha20::ChaCha20;
 /// man-in-the-middle manipulation of the ciphertext.
 /// A zkVM proving correct execution of this function provides these properties.
 ///
+/// Outside the zkVM, see [`security`] for timing side-channels and handling key material.
+///
+/// # Example
+///
+/// ```
+/// use chacha_lib::chacha;
+///
+/// let (key, nonce) = ([1u8; 32], [2u8; 12]);
+/// let mut buffer = *b"attack at dawn";
+/// chacha(&key, &nonce, &mut buffer);
+/// assert_ne!(&buffer, b"attack at dawn");
+///
+/// // Decrypting is encrypting again
+/// chacha(&key, &nonce, &mut buffer);
+/// assert_eq!(&buffer, b"attack at dawn");
+/// ```
 pub fn chacha(key: &[u8; 32], nonce: &[u8; 12], buffer: &mut [u8]) {
     let mut cipher = ChaCha20::new(key.into(), nonce.into());
     cipher.apply_keystream(buffer);
 }
 
-// Helper to format bytes as hex for pretty printing
+/// Like [`chacha`], but returns the ciphertext in a new `Vec` instead of encrypting in place.
+///
+/// # Example
+///
+/// ```
+/// use chacha_lib::{chacha, chacha_encrypt};
+///
+/// let (key, nonce) = ([1u8; 32], [2u8; 12]);
+/// let ciphertext = chacha_encrypt(&key, &nonce, b"attack at dawn");
+///
+/// let mut buffer = *b"attack at dawn";
+/// chacha(&key, &nonce, &mut buffer);
+/// assert_eq!(ciphertext, buffer);
+/// ```
+#[must_use = "the encrypted bytes are returned as a new Vec"]
+pub fn chacha_encrypt(key: &[u8; 32], nonce: &[u8; 12], plaintext: &[u8]) -> Vec<u8> {
+    let mut buffer = plaintext.to_vec();
+    chacha(key, nonce, &mut buffer);
+    buffer
+}
+
+/// Like [`chacha`], but starting at the 64 byte keystream block `counter` instead of 0.
+///
+/// `chacha_at(key, nonce, n, buf)` equals bytes `64 * n..` of encrypting a longer buffer.
+///
+/// # Example
+///
+/// ```
+/// use chacha_lib::{chacha, chacha_at};
+///
+/// let (key, nonce) = ([1u8; 32], [2u8; 12]);
+/// let mut whole = [0u8; 128];
+/// chacha(&key, &nonce, &mut whole);
+///
+/// let mut second_block = [0u8; 64];
+/// chacha_at(&key, &nonce, 1, &mut second_block);
+/// assert_eq!(second_block, whole[64..]);
+/// ```
+pub fn chacha_at(key: &[u8; 32], nonce: &[u8; 12], counter: u32, buffer: &mut [u8]) {
+    let mut cipher = ChaCha20::new(key.into(), nonce.into());
+    cipher.seek(u64::from(counter) * 64);
+    cipher.apply_keystream(buffer);
+}
+
+/// Like [`chacha`], but in the [`NonceFormat::Original`] format with a 64 bit nonce, starting at
+/// the 64 byte keystream block `counter64`.
+///
+/// Both formats produce the same state for `counter64 = hi << 32 | lo`, IETF counter `lo` and IETF
+/// nonce `hi (LE) || nonce64`, which is how this is computed.
+///
+/// # Panics
+///
+/// If the low 32 bits of the block counter would reach `u32::MAX`, so a single call can not cross
+/// a 256 GiB boundary of the keystream.
+///
+/// # Example
+///
+/// ```
+/// use chacha_lib::{chacha_at, chacha_original};
+///
+/// let key = [1u8; 32];
+/// let mut original = [0u8; 64];
+/// chacha_original(&key, &[9u8; 8], (5 << 32) | 3, &mut original);
+///
+/// let mut ietf = [0u8; 64];
+/// let nonce = [5, 0, 0, 0, 9, 9, 9, 9, 9, 9, 9, 9];
+/// chacha_at(&key, &nonce, 3, &mut ietf);
+/// assert_eq!(original, ietf);
+/// ```
+pub fn chacha_original(key: &[u8; 32], nonce64: &[u8; 8], counter64: u64, buffer: &mut [u8]) {
+    let mut nonce = [0u8; 12];
+    nonce[..4].copy_from_slice(&((counter64 >> 32) as u32).to_le_bytes());
+    nonce[4..].copy_from_slice(nonce64);
+    chacha_at(key, &nonce, counter64 as u32, buffer);
+}
+
+/// The raw keystream of `len` bytes starting at block `counter`, i.e. the encryption of zeros.
+///
+/// # Panics
+///
+/// If the keystream would reach block `u32::MAX`, which the `chacha20` crate never produces
+/// (libsodium does).
+///
+/// # Example
+///
+/// ```
+/// use chacha_lib::{chacha, chacha_keystream};
+///
+/// let (key, nonce) = ([1u8; 32], [2u8; 12]);
+/// let mut zeros = [0u8; 16];
+/// chacha(&key, &nonce, &mut zeros);
+/// assert_eq!(chacha_keystream(&key, &nonce, 0, 16), zeros);
+/// ```
+pub fn chacha_keystream(key: &[u8; 32], nonce: &[u8; 12], counter: u32, len: usize) -> Vec<u8> {
+    let mut keystream = vec![0u8; len];
+    chacha_at(key, nonce, counter, &mut keystream);
+    keystream
+}
+
+/// The SP1 proof systems an encryption can be proved with.
+///
+/// See the [SP1 proof types](https://docs.succinct.xyz/docs/sp1/generating-proofs/proof-types).
+///
+/// # Example
+///
+/// ```
+/// use chacha_lib::ProofType;
+///
+/// let proof_type: ProofType = "plonk".parse().unwrap();
+/// assert_eq!(proof_type.to_string(), "plonk");
+/// assert_eq!(ProofType::default(), ProofType::Groth16);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum ProofType {
+    Core,
+    Compressed,
+    /// Smallest on-chain verification cost, the default.
+    #[default]
+    Groth16,
+    Plonk,
+}
+
+impl core::fmt::Display for ProofType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            ProofType::Core => "core",
+            ProofType::Compressed => "compressed",
+            ProofType::Groth16 => "groth16",
+            ProofType::Plonk => "plonk",
+        })
+    }
+}
+
+impl core::str::FromStr for ProofType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "core" => Ok(ProofType::Core),
+            "compressed" => Ok(ProofType::Compressed),
+            "groth16" => Ok(ProofType::Groth16),
+            "plonk" => Ok(ProofType::Plonk),
+            other => Err(format!(
+                "unknown proof type `{other}`, expected core|compressed|groth16|plonk"
+            )),
+        }
+    }
+}
+
+/// Helper to format bytes as hex for pretty printing.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(chacha_lib::bytes_to_hex(&[0xde, 0xad]), "dead");
+/// ```
 pub fn bytes_to_hex(bytes: &[u8]) -> String {
     let digest_hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
     digest_hex
 }
 
+/// Decode a 64 character hex string into key bytes at compile time, e.g. for test keys:
+///
+/// ```
+/// use chacha_lib::{key_from_hex_const, Key};
+///
+/// const TEST_KEY: Key = Key(key_from_hex_const(
+///     "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+/// ));
+/// ```
+///
+/// # Panics
+///
+/// If `s` is not 64 hex characters, which fails the build when evaluated in a const context.
+pub const fn key_from_hex_const(s: &str) -> [u8; 32] {
+    match decode_hex(s) {
+        Ok(key) => key,
+        Err(KeyError::Length { .. }) => panic!("hex key must be 64 characters"),
+        Err(_) => panic!("invalid hex character in key"),
+    }
+}
+
+/// A key from a hex literal, checked at compile time.
+///
+/// ```
+/// use chacha_lib::{key, Key};
+///
+/// let key: Key = key!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+/// assert_eq!(key.0[31], 0x1f);
+/// ```
+///
+/// An invalid literal fails the build:
+///
+/// ```compile_fail
+/// let key = chacha_lib::key!("not hex");
+/// ```
+#[macro_export]
+macro_rules! key {
+    ($hex:literal) => {{
+        const KEY: [u8; 32] = $crate::key_from_hex_const($hex);
+        $crate::Key(KEY)
+    }};
+}
+
+/// Decode exactly `N` bytes of hex, in a const context if needed.
+const fn decode_hex<const N: usize>(s: &str) -> Result<[u8; N], KeyError> {
+    const fn nibble(c: u8) -> Result<u8, KeyError> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(KeyError::InvalidHex),
+        }
+    }
+
+    let hex = s.as_bytes();
+    if hex.len() != 2 * N {
+        return Err(KeyError::Length {
+            expected: N,
+            got: hex.len() / 2,
+        });
+    }
+    let mut bytes = [0u8; N];
+    let mut i = 0;
+    while i < N {
+        let (high, low) = match (nibble(hex[2 * i]), nibble(hex[2 * i + 1])) {
+            (Ok(high), Ok(low)) => (high, low),
+            _ => return Err(KeyError::InvalidHex),
+        };
+        bytes[i] = (high << 4) | low;
+        i += 1;
+    }
+    Ok(bytes)
+}
+
+fn decode_base64<const N: usize>(s: &str) -> Result<[u8; N], KeyError> {
+    use base64::Engine;
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(s)
+        .map_err(|_| KeyError::InvalidBase64)?;
+    let got = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| KeyError::Length { expected: N, got })
+}
+
+/// A [`Key`] or [`Nonce`] string could not be decoded, or an X25519 public key is unsafe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyError {
+    /// Decoded to the wrong number of bytes.
+    Length {
+        expected: usize,
+        got: usize,
+    },
+    InvalidHex,
+    InvalidBase64,
+    /// An X25519 public key of small order, see [`exchange::validate_x25519_public_key`].
+    WeakPublicKey,
+    /// The all-zero X25519 public key, which X25519 also outputs for the point at infinity.
+    PointAtInfinity,
+}
+
+impl core::fmt::Display for KeyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KeyError::Length { expected, got } => {
+                write!(f, "expected {expected} bytes, got {got}")
+            }
+            KeyError::InvalidHex => f.write_str("invalid hex"),
+            KeyError::InvalidBase64 => f.write_str("invalid base64"),
+            KeyError::WeakPublicKey => f.write_str("X25519 public key of small order"),
+            KeyError::PointAtInfinity => f.write_str("X25519 public key is the point at infinity"),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+impl Key {
+    /// `BASE58(SHA256(key)[..8])`, a short name to tell keys apart by, like an SSH key
+    /// fingerprint. It is a prefix of the committed key hash, so it reveals nothing more.
+    ///
+    /// ```
+    /// use chacha_lib::Key;
+    ///
+    /// assert_eq!(Key([1; 32]).fingerprint(), "LCjD8QTpHRt");
+    /// ```
+    pub fn fingerprint(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let hash = Sha256::digest(self.0);
+        base58(hash[..8].try_into().unwrap())
+    }
+
+    /// Decode 64 hex characters.
+    ///
+    /// ```
+    /// use chacha_lib::Key;
+    ///
+    /// let key = Key::from_hex("0101010101010101010101010101010101010101010101010101010101010101");
+    /// assert_eq!(key, Ok(Key([1; 32])));
+    /// assert!(Key::from_hex("0101").is_err());
+    /// ```
+    pub fn from_hex(s: &str) -> Result<Self, KeyError> {
+        decode_hex(s).map(Key)
+    }
+
+    /// Decode standard, padded base64.
+    ///
+    /// ```
+    /// use chacha_lib::Key;
+    ///
+    /// let key = Key::from_base64("AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=");
+    /// assert_eq!(key, Ok(Key([1; 32])));
+    /// ```
+    pub fn from_base64(s: &str) -> Result<Self, KeyError> {
+        decode_base64(s).map(Key)
+    }
+}
+
+/// Whether `fingerprint` is the [`Key::fingerprint`] of `key`.
+pub fn fingerprint_matches(key: &Key, fingerprint: &str) -> bool {
+    key.fingerprint() == fingerprint
+}
+
+/// Bitcoin's base58 alphabet, leaving out `0`, `O`, `I` and `l`.
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn base58(bytes: [u8; 8]) -> String {
+    let mut n = u64::from_be_bytes(bytes);
+    let mut digits = Vec::new();
+    while n > 0 {
+        digits.push(BASE58_ALPHABET[(n % 58) as usize]);
+        n /= 58;
+    }
+    // Every leading zero byte is a `1`
+    digits.extend(bytes.iter().take_while(|&&b| b == 0).map(|_| b'1'));
+    digits.reverse();
+    String::from_utf8(digits).unwrap()
+}
+
+/// Hex (64 characters) or base64 (44 characters), told apart by length.
+///
+/// ```
+/// use chacha_lib::Key;
+///
+/// let hex: Key = "0101010101010101010101010101010101010101010101010101010101010101".parse().unwrap();
+/// let base64: Key = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=".parse().unwrap();
+/// assert_eq!(hex, base64);
+/// ```
+impl core::str::FromStr for Key {
+    type Err = KeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() == 64 {
+            Key::from_hex(s)
+        } else {
+            Key::from_base64(s)
+        }
+    }
+}
+
+impl Nonce {
+    /// Decode 24 hex characters.
+    ///
+    /// ```
+    /// use chacha_lib::Nonce;
+    ///
+    /// assert_eq!(Nonce::from_hex("000000000000004a00000000").unwrap().0[7], 0x4a);
+    /// ```
+    pub fn from_hex(s: &str) -> Result<Self, KeyError> {
+        decode_hex(s).map(Nonce)
+    }
+
+    /// Decode standard, padded base64.
+    ///
+    /// ```
+    /// use chacha_lib::Nonce;
+    ///
+    /// assert_eq!(Nonce::from_base64("AAAAAAAAAEoAAAAA"), Nonce::from_hex("000000000000004a00000000"));
+    /// ```
+    pub fn from_base64(s: &str) -> Result<Self, KeyError> {
+        decode_base64(s).map(Nonce)
+    }
+}
+
+/// Hex (24 characters) or base64 (16 characters), told apart by length.
+impl core::str::FromStr for Nonce {
+    type Err = KeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() == 24 {
+            Nonce::from_hex(s)
+        } else {
+            Nonce::from_base64(s)
+        }
+    }
+}
+
+// The RFC 8439 section 2.3.2 test key decodes to 0x00..=0x1f
+const _: () = {
+    let key =
+        key_from_hex_const("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+    let mut i = 0;
+    while i < 32 {
+        assert!(key[i] == i as u8);
+        i += 1;
+    }
+};
+
 // Only compile this when the standard library is available
 #[cfg(feature = "std")]
 mod std_only {
     use rand::{rngs::OsRng, TryRngCore};
 
+    use crate::Key;
+
+    impl Key {
+        /// A fresh key from the OS random number generator.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use chacha_lib::Key;
+        ///
+        /// assert_ne!(Key::random(), Key::random());
+        /// ```
+        pub fn random() -> Self {
+            let mut key = [0u8; 32];
+            OsRng.try_fill_bytes(&mut key).expect("Rng->buffer");
+            Key(key)
+        }
+    }
+
+    /// A fresh nonce from the OS random number generator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let nonce: [u8; 12] = chacha_lib::random_nonce();
+    /// assert_ne!(nonce, chacha_lib::random_nonce());
+    /// ```
     pub fn random_nonce() -> [u8; 12] {
         let mut nonce = [0u8; 12];
         OsRng.try_fill_bytes(&mut nonce).expect("Rng->buffer");
@@ -0,0 +1,19 @@
+//! Data lineage commitment, linking the proofs of consecutive pipeline stages.
+//!
+//! Each stage is proven with the lineage commitment of the stage before as its `lineage_hash`,
+//! and commits [`lineage_commitment`] over it and its own plaintext hash. The first stage uses
+//! [`GENESIS_LINEAGE_HASH`]. A verifier walks the chain back from the last stage's commitment
+//! with the plaintext hash and lineage hash of each stage.
+
+use sha2::{Digest, Sha256};
+
+/// `lineage_hash` of the first stage of a pipeline.
+pub const GENESIS_LINEAGE_HASH: [u8; 32] = [0; 32];
+
+/// `SHA256(lineage_hash || plaintext_hash)`.
+pub fn lineage_commitment(lineage_hash: &[u8; 32], plaintext_hash: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(lineage_hash);
+    hasher.update(plaintext_hash);
+    hasher.finalize().into()
+}
@@ -0,0 +1,63 @@
+//! Encrypt-then-MAC with a single key.
+//!
+//! Using one key for both HMAC-SHA256 and ChaCha20 is key reuse across two primitives. Instead
+//! HKDF-SHA256 derives an independent subkey for each, see [`crate::domain`], and the tag is an
+//! HMAC-SHA256 of `nonce | ciphertext` under the MAC subkey. The nonce is authenticated too, as
+//! decrypting under a changed nonce would otherwise yield a different plaintext for a valid tag.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::domain::{domain_separate, DOMAIN_ENCRYPT};
+use crate::{chacha, Key, Nonce};
+
+/// HKDF info string of the MAC subkey.
+pub const DOMAIN_MAC: &[u8] = b"mac";
+
+/// Length of an HMAC-SHA256 tag.
+pub const TAG_LEN: usize = 32;
+
+fn hmac(key: &Key, nonce: &Nonce, ciphertext: &[u8]) -> Hmac<Sha256> {
+    let mac_key = domain_separate(key, DOMAIN_MAC);
+    let mut mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(&mac_key.0).expect("HMAC accepts any key length");
+    mac.update(&nonce.0);
+    mac.update(ciphertext);
+    mac
+}
+
+/// Encrypt `plaintext` under the encryption subkey of `key`, returning the ciphertext and its
+/// tag.
+///
+/// ```
+/// use chacha_lib::mac::{chacha_with_hmac, open_with_hmac};
+/// use chacha_lib::{Key, Nonce};
+///
+/// let (key, nonce) = (Key([7; 32]), Nonce([1; 12]));
+/// let (mut ciphertext, tag) = chacha_with_hmac(&key, &nonce, b"attack at dawn");
+/// assert_eq!(open_with_hmac(&key, &nonce, &ciphertext, &tag).unwrap(), b"attack at dawn");
+///
+/// ciphertext[0] ^= 1;
+/// assert!(open_with_hmac(&key, &nonce, &ciphertext, &tag).is_none());
+/// ```
+pub fn chacha_with_hmac(key: &Key, nonce: &Nonce, plaintext: &[u8]) -> (Vec<u8>, [u8; TAG_LEN]) {
+    let encrypt_key = domain_separate(key, DOMAIN_ENCRYPT);
+    let mut ciphertext = plaintext.to_vec();
+    chacha(&encrypt_key.0, &nonce.0, &mut ciphertext);
+    let tag = hmac(key, nonce, &ciphertext).finalize().into_bytes().into();
+    (ciphertext, tag)
+}
+
+/// Check `tag` in constant time and decrypt, `None` if the tag is invalid.
+pub fn open_with_hmac(
+    key: &Key,
+    nonce: &Nonce,
+    ciphertext: &[u8],
+    tag: &[u8; TAG_LEN],
+) -> Option<Vec<u8>> {
+    hmac(key, nonce, ciphertext).verify_slice(tag).ok()?;
+    let encrypt_key = domain_separate(key, DOMAIN_ENCRYPT);
+    let mut plaintext = ciphertext.to_vec();
+    chacha(&encrypt_key.0, &nonce.0, &mut plaintext);
+    Some(plaintext)
+}
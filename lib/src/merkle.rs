@@ -0,0 +1,117 @@
+//! A binary SHA256 Merkle tree with inclusion proofs.
+//!
+//! Leaves and inner nodes are hashed with distinct prefixes, so an inner node can never be passed
+//! off as a leaf. The leaves are padded with [`EMPTY_LEAF`] to a power of two, which binds every
+//! proof to the index of its leaf.
+
+use sha2::{Digest, Sha256};
+
+/// Padding leaf, also the root of an empty tree.
+pub const EMPTY_LEAF: [u8; 32] = [0u8; 32];
+
+/// `SHA256(0x00 || data)`
+pub fn leaf_hash(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// `SHA256(0x01 || left || right)`
+pub fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+pub struct MerkleTree {
+    /// `levels[0]` are the padded leaves, the last level is the root.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Build a tree over leaf hashes, see [`leaf_hash`].
+    pub fn from_leaves(mut leaves: Vec<[u8; 32]>) -> Self {
+        leaves.resize(leaves.len().next_power_of_two(), EMPTY_LEAF);
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| node_hash(&pair[0], &pair[1]))
+                .collect();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    /// Sibling hashes from the leaf at `index` up to the root.
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut index = index;
+        let mut path = Vec::with_capacity(self.levels.len() - 1);
+        for level in &self.levels[..self.levels.len() - 1] {
+            path.push(level[index ^ 1]);
+            index /= 2;
+        }
+        path
+    }
+}
+
+/// Inclusion proof of the leaf at `index`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub index: usize,
+    /// Sibling hashes from the leaf up to the root.
+    pub path: Vec<[u8; 32]>,
+}
+
+/// Prove the leaf at `index` of the tree over `leaves`.
+///
+/// ```
+/// use chacha_lib::merkle::{leaf_hash, merkle_prove, merkle_verify, MerkleTree};
+///
+/// let leaves: Vec<_> = [b"a", b"b", b"c"].map(|data| leaf_hash(data)).into();
+/// let root = MerkleTree::from_leaves(leaves.clone()).root();
+/// let proof = merkle_prove(&leaves, 2);
+/// assert!(merkle_verify(&root, &leaves[2], &proof));
+/// assert!(!merkle_verify(&root, &leaves[1], &proof));
+/// ```
+///
+/// # Panics
+///
+/// If `index` is not the index of one of `leaves`.
+pub fn merkle_prove(leaves: &[[u8; 32]], index: usize) -> MerkleProof {
+    assert!(index < leaves.len(), "leaf {index} of {}", leaves.len());
+    MerkleProof {
+        index,
+        path: MerkleTree::from_leaves(leaves.to_vec()).proof(index),
+    }
+}
+
+/// Check that `leaf` is at `proof.index` in the tree with `root`.
+pub fn merkle_verify(root: &[u8; 32], leaf: &[u8; 32], proof: &MerkleProof) -> bool {
+    verify_proof(root, leaf, proof.index, &proof.path)
+}
+
+/// Check that `leaf` is at `index` in the tree with `root`.
+pub fn verify_proof(root: &[u8; 32], leaf: &[u8; 32], index: usize, path: &[[u8; 32]]) -> bool {
+    if index >> path.len() != 0 {
+        return false;
+    }
+    let mut node = *leaf;
+    for (level, sibling) in path.iter().enumerate() {
+        node = if (index >> level) & 1 == 0 {
+            node_hash(&node, sibling)
+        } else {
+            node_hash(sibling, &node)
+        };
+    }
+    &node == root
+}
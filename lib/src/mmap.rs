@@ -0,0 +1,83 @@
+//! Memory-mapped file encryption.
+//!
+//! For files of hundreds of MB, mapping lets the OS page data in and out lazily instead of copying
+//! it through read and write buffers. `benches/mmap.rs` compares the two on a 100 MB file, on a
+//! warm page cache buffered I/O can still be faster.
+//!
+//! ## Safety
+//!
+//! A memory map is only sound while no other process modifies or truncates the mapped file. The
+//! input and output must not be changed by anything else until [`chacha_mmap_encrypt`] returns,
+//! otherwise the behavior is undefined (and in practice a `SIGBUS` or a corrupt ciphertext).
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+
+use chacha20::cipher::{KeyIvInit, StreamCipher};
+use chacha20::ChaCha20;
+use memmap2::{MmapMut, MmapOptions};
+
+use crate::{Key, Nonce};
+
+/// Bytes encrypted per keystream application, one page.
+const CHUNK_SIZE: usize = 4096;
+
+#[derive(Debug)]
+pub enum MmapError {
+    Io(io::Error),
+}
+
+impl core::fmt::Display for MmapError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MmapError::Io(e) => write!(f, "mmap: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MmapError {}
+
+impl From<io::Error> for MmapError {
+    fn from(e: io::Error) -> Self {
+        MmapError::Io(e)
+    }
+}
+
+/// Encrypt the file at `input` into `output` (created or truncated), returning its size.
+///
+/// See the [module documentation](self) for the conditions under which this is sound.
+pub fn chacha_mmap_encrypt(
+    key: &Key,
+    nonce: &Nonce,
+    input: &Path,
+    output: &Path,
+) -> Result<u64, MmapError> {
+    let input = File::open(input)?;
+    let len = input.metadata()?.len();
+    let output = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(output)?;
+    // Mapping an empty file fails on some platforms, and there is nothing to encrypt.
+    if len == 0 {
+        return Ok(0);
+    }
+    output.set_len(len)?;
+
+    // SAFETY: both files are only accessed through these maps until they are dropped, see the
+    // module documentation.
+    let source = unsafe { MmapOptions::new().map(&input)? };
+    let mut dest: MmapMut = unsafe { MmapOptions::new().map_mut(&output)? };
+
+    let mut cipher = ChaCha20::new(&key.0.into(), &nonce.0.into());
+    for (src, dst) in source.chunks(CHUNK_SIZE).zip(dest.chunks_mut(CHUNK_SIZE)) {
+        cipher
+            .apply_keystream_b2b(src, dst)
+            .expect("chunks have equal lengths");
+    }
+    dest.flush()?;
+    Ok(len)
+}
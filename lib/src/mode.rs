@@ -0,0 +1,102 @@
+//! Block cipher mode framings of the ChaCha20 keystream, for legacy systems that expect them.
+//!
+//! # OFB and CTR
+//!
+//! A stream cipher already is output feedback (OFB): the keystream only depends on the key and
+//! nonce and is XORed onto the data, so [`chacha_ofb_encrypt`] is [`crate::chacha`]. ChaCha20
+//! derives each 64 byte block from a counter, like CTR mode, which unlike OFB allows seeking.
+//!
+//! # CFB
+//!
+//! In cipher feedback (CFB) mode, [`chacha_cfb_encrypt`], the keystream of each 64 byte block
+//! depends on the previous ciphertext block: block `i > 0` is encrypted with the ChaCha20 block `i`
+//! of the nonce `SHA256(nonce || ciphertext_{i-1})[..12]`. The first block is that of
+//! [`crate::chacha`]. Decrypting takes [`chacha_cfb_decrypt`], as the feedback is the ciphertext.
+//!
+//! # Decryption failures
+//!
+//! Neither mode authenticates, the properties differ when a ciphertext is corrupted or tampered
+//! with:
+//!
+//! - OFB/CTR: flipping a ciphertext bit flips the same plaintext bit and nothing else. An attacker
+//!   who knows a plaintext can rewrite it at will, and a corruption goes unnoticed unless the
+//!   plaintext is checked.
+//! - CFB: flipping a ciphertext bit flips that plaintext bit, and garbles the whole next block,
+//!   after which decryption recovers. Only the last block is malleable without side effects, the
+//!   garbled block makes other tampering visible to a plaintext check, but is not a MAC.
+//! - CFB encryption is sequential and cannot seek, only decryption can run blocks in parallel.
+//!   Reusing a nonce leaks the XOR of the first blocks, and of any blocks following equal
+//!   ciphertext blocks at the same position, where OFB leaks the XOR of the whole plaintexts.
+//!
+//! Use Encrypt-then-MAC ([`crate::mac`]) or ChaCha20-Poly1305 whenever a ciphertext can be
+//! tampered with.
+
+use sha2::{Digest, Sha256};
+
+use crate::{chacha, chacha_at, Key, Nonce};
+
+/// Size of a ChaCha20 keystream block, the feedback unit of CFB.
+pub const BLOCK_LEN: usize = 64;
+
+/// OFB mode, identical to [`crate::chacha`].
+///
+/// ```
+/// use chacha_lib::mode::chacha_ofb_encrypt;
+/// use chacha_lib::{chacha, Key, Nonce};
+///
+/// let (key, nonce) = (Key([1; 32]), Nonce([2; 12]));
+/// let mut ofb = *b"legacy framing";
+/// chacha_ofb_encrypt(&key, &nonce, &mut ofb);
+/// let mut ctr = *b"legacy framing";
+/// chacha(&key.0, &nonce.0, &mut ctr);
+/// assert_eq!(ofb, ctr);
+/// ```
+pub fn chacha_ofb_encrypt(key: &Key, nonce: &Nonce, buffer: &mut [u8]) {
+    chacha(&key.0, &nonce.0, buffer);
+}
+
+/// The nonce of the block after `ciphertext`.
+fn feedback_nonce(nonce: &Nonce, ciphertext: &[u8]) -> [u8; 12] {
+    let mut hasher = Sha256::new();
+    hasher.update(nonce.0);
+    hasher.update(ciphertext);
+    hasher.finalize()[..12].try_into().unwrap()
+}
+
+fn cfb(key: &Key, nonce: &Nonce, buffer: &mut [u8], encrypt: bool) {
+    let mut block_nonce = nonce.0;
+    let mut ciphertext = [0; BLOCK_LEN];
+    for (counter, block) in buffer.chunks_mut(BLOCK_LEN).enumerate() {
+        let counter = u32::try_from(counter).expect("CFB buffer longer than 256 GiB");
+        let ciphertext = &mut ciphertext[..block.len()];
+        if !encrypt {
+            ciphertext.copy_from_slice(block);
+        }
+        chacha_at(&key.0, &block_nonce, counter, block);
+        if encrypt {
+            ciphertext.copy_from_slice(block);
+        }
+        block_nonce = feedback_nonce(nonce, ciphertext);
+    }
+}
+
+/// CFB mode encryption in place, see the [module docs](self).
+///
+/// ```
+/// use chacha_lib::mode::{chacha_cfb_decrypt, chacha_cfb_encrypt};
+/// use chacha_lib::{Key, Nonce};
+///
+/// let (key, nonce) = (Key([1; 32]), Nonce([2; 12]));
+/// let mut buffer = [7u8; 200];
+/// chacha_cfb_encrypt(&key, &nonce, &mut buffer);
+/// chacha_cfb_decrypt(&key, &nonce, &mut buffer);
+/// assert_eq!(buffer, [7u8; 200]);
+/// ```
+pub fn chacha_cfb_encrypt(key: &Key, nonce: &Nonce, buffer: &mut [u8]) {
+    cfb(key, nonce, buffer, true);
+}
+
+/// CFB mode decryption in place, the inverse of [`chacha_cfb_encrypt`].
+pub fn chacha_cfb_decrypt(key: &Key, nonce: &Nonce, buffer: &mut [u8]) {
+    cfb(key, nonce, buffer, false);
+}
@@ -0,0 +1,231 @@
+//! The Noise protocol framework's symmetric state, for proving a handshake was carried out.
+//!
+//! [`CipherState`] and [`SymmetricState`] follow sections 5.1 and 5.2 of the Noise specification
+//! (revision 34) with the `25519`, `ChaChaPoly` and `SHA256` functions. [`noise_nn_handshake`] runs
+//! both sides of `Noise_NN_25519_ChaChaPoly_SHA256`:
+//!
+//! ```text
+//! -> e
+//! <- e, ee
+//! ```
+//!
+//! NN is unauthenticated: it proves the two messages form a handshake between the two ephemeral
+//! keys, not who held them.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::ChaCha20Poly1305;
+use hkdf::Hkdf;
+use sha2::{Digest, Sha256};
+use x25519_dalek::x25519;
+
+use crate::exchange::public_key;
+
+/// Protocol name hashed into the initial handshake hash of [`noise_nn_handshake`].
+pub const NOISE_NN: &[u8] = b"Noise_NN_25519_ChaChaPoly_SHA256";
+
+/// An optional key and a nonce counter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CipherState {
+    k: Option<[u8; 32]>,
+    n: u64,
+}
+
+impl CipherState {
+    pub fn new(k: [u8; 32]) -> Self {
+        Self { k: Some(k), n: 0 }
+    }
+
+    pub fn has_key(&self) -> bool {
+        self.k.is_some()
+    }
+
+    /// The ChaChaPoly nonce of counter `n`: 32 zero bits, then `n` as u64 LE.
+    fn nonce(&self) -> [u8; 12] {
+        let mut nonce = [0u8; 12];
+        nonce[4..].copy_from_slice(&self.n.to_le_bytes());
+        nonce
+    }
+
+    /// Encrypt with the next nonce, or return `plaintext` unchanged if there is no key.
+    ///
+    /// # Panics
+    ///
+    /// If the nonce counter is exhausted.
+    pub fn encrypt_with_ad(&mut self, ad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let Some(k) = self.k else {
+            return plaintext.to_vec();
+        };
+        assert!(self.n < u64::MAX, "nonce counter exhausted");
+        let msg = Payload {
+            msg: plaintext,
+            aad: ad,
+        };
+        let ciphertext = ChaCha20Poly1305::new(&k.into())
+            .encrypt(&self.nonce().into(), msg)
+            .expect("plaintext too large");
+        self.n += 1;
+        ciphertext
+    }
+
+    /// Decrypt with the next nonce, `None` if the tag is invalid. The nonce is only used up by a
+    /// valid ciphertext.
+    pub fn decrypt_with_ad(&mut self, ad: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        let Some(k) = self.k else {
+            return Some(ciphertext.to_vec());
+        };
+        if self.n == u64::MAX {
+            return None;
+        }
+        let msg = Payload {
+            msg: ciphertext,
+            aad: ad,
+        };
+        let plaintext = ChaCha20Poly1305::new(&k.into())
+            .decrypt(&self.nonce().into(), msg)
+            .ok()?;
+        self.n += 1;
+        Some(plaintext)
+    }
+}
+
+/// The chaining key, handshake hash and cipher of a handshake in progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymmetricState {
+    ck: [u8; 32],
+    h: [u8; 32],
+    cipher: CipherState,
+}
+
+impl SymmetricState {
+    /// `InitializeSymmetric(protocol_name)`.
+    pub fn new(protocol_name: &[u8]) -> Self {
+        let mut h = [0u8; 32];
+        if protocol_name.len() <= h.len() {
+            h[..protocol_name.len()].copy_from_slice(protocol_name);
+        } else {
+            h = Sha256::digest(protocol_name).into();
+        }
+        Self {
+            ck: h,
+            h,
+            cipher: CipherState::default(),
+        }
+    }
+
+    /// The handshake hash, unique to the handshake so far.
+    pub fn handshake_hash(&self) -> [u8; 32] {
+        self.h
+    }
+
+    pub fn mix_key(&mut self, input_key_material: &[u8]) {
+        let [ck, temp_k] = hkdf(&self.ck, input_key_material);
+        self.ck = ck;
+        self.cipher = CipherState::new(temp_k);
+    }
+
+    pub fn mix_hash(&mut self, data: &[u8]) {
+        self.h = Sha256::new()
+            .chain_update(self.h)
+            .chain_update(data)
+            .finalize()
+            .into();
+    }
+
+    pub fn encrypt_and_hash(&mut self, plaintext: &[u8]) -> Vec<u8> {
+        let ciphertext = self.cipher.encrypt_with_ad(&self.h, plaintext);
+        self.mix_hash(&ciphertext);
+        ciphertext
+    }
+
+    /// `None` if the tag is invalid, leaving the state unchanged.
+    pub fn decrypt_and_hash(&mut self, ciphertext: &[u8]) -> Option<Vec<u8>> {
+        let plaintext = self.cipher.decrypt_with_ad(&self.h, ciphertext)?;
+        self.mix_hash(ciphertext);
+        Some(plaintext)
+    }
+
+    /// The transport ciphers, initiator to responder first.
+    pub fn split(&self) -> (CipherState, CipherState) {
+        let [k1, k2] = hkdf(&self.ck, &[]);
+        (CipherState::new(k1), CipherState::new(k2))
+    }
+}
+
+/// Noise's `HKDF(chaining_key, input_key_material, 2)`, which is HKDF-SHA256 with the chaining
+/// key as salt and no info.
+fn hkdf(chaining_key: &[u8; 32], input_key_material: &[u8]) -> [[u8; 32]; 2] {
+    let mut okm = [0u8; 64];
+    Hkdf::<Sha256>::new(Some(chaining_key), input_key_material)
+        .expand(&[], &mut okm)
+        .expect("64 bytes is a valid HKDF-SHA256 output length");
+    [okm[..32].try_into().unwrap(), okm[32..].try_into().unwrap()]
+}
+
+/// Both messages of a completed [`noise_nn_handshake`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NnHandshake {
+    pub initiator_ephemeral: [u8; 32],
+    pub responder_ephemeral: [u8; 32],
+    /// `e` and the initiator's payload.
+    pub message1: Vec<u8>,
+    /// `e` and the responder's payload, encrypted after `ee`.
+    pub message2: Vec<u8>,
+    pub handshake_hash: [u8; 32],
+    /// The transport ciphers, initiator to responder first.
+    pub transport: (CipherState, CipherState),
+}
+
+/// Run both sides of a `Noise_NN_25519_ChaChaPoly_SHA256` handshake with the given ephemeral
+/// secrets, checking each side reads the other's message.
+///
+/// # Panics
+///
+/// If a side's message fails to decrypt or the sides end with different handshake hashes, which
+/// would be a bug.
+pub fn noise_nn_handshake(
+    initiator_secret: &[u8; 32],
+    responder_secret: &[u8; 32],
+    prologue: &[u8],
+    initiator_payload: &[u8],
+    responder_payload: &[u8],
+) -> NnHandshake {
+    let mut initiator = SymmetricState::new(NOISE_NN);
+    initiator.mix_hash(prologue);
+    let mut responder = initiator.clone();
+
+    // -> e
+    let initiator_ephemeral = public_key(initiator_secret);
+    initiator.mix_hash(&initiator_ephemeral);
+    let mut message1 = initiator_ephemeral.to_vec();
+    message1.extend(initiator.encrypt_and_hash(initiator_payload));
+
+    let (re, payload) = message1.split_at(32);
+    let re: [u8; 32] = re.try_into().unwrap();
+    responder.mix_hash(&re);
+    let payload = responder.decrypt_and_hash(payload);
+    assert_eq!(payload.as_deref(), Some(initiator_payload));
+
+    // <- e, ee
+    let responder_ephemeral = public_key(responder_secret);
+    responder.mix_hash(&responder_ephemeral);
+    responder.mix_key(&x25519(*responder_secret, re));
+    let mut message2 = responder_ephemeral.to_vec();
+    message2.extend(responder.encrypt_and_hash(responder_payload));
+
+    let (re, payload) = message2.split_at(32);
+    let re: [u8; 32] = re.try_into().unwrap();
+    initiator.mix_hash(&re);
+    initiator.mix_key(&x25519(*initiator_secret, re));
+    let payload = initiator.decrypt_and_hash(payload);
+    assert_eq!(payload.as_deref(), Some(responder_payload));
+
+    assert_eq!(initiator, responder, "handshake states diverged");
+    NnHandshake {
+        initiator_ephemeral,
+        responder_ephemeral,
+        message1,
+        message2,
+        handshake_hash: initiator.handshake_hash(),
+        transport: initiator.split(),
+    }
+}
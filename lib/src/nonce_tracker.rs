@@ -0,0 +1,38 @@
+//! Nonces that are unique per key within a process.
+//!
+//! [`tracked_nonce`] derives every nonce from a process-global counter, so concurrent callers never
+//! receive the same nonce for the same key, unlike independent draws of [`crate::random_nonce`]
+//! which only make a collision unlikely.
+//!
+//! This does NOT protect across process restarts: the counter starts over in every process. A
+//! random salt drawn once per process makes a repeat across processes as unlikely as with
+//! [`crate::random_nonce`], but not impossible. Nonces that must never repeat for a long lived key
+//! need persistent state, such as a counter stored and incremented alongside the key.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+use crate::Key;
+
+/// Incremented on every call, shared by all keys.
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+static PROCESS_SALT: OnceLock<[u8; 32]> = OnceLock::new();
+
+/// A nonce for the key with SHA256 `key_hash`, derived with HKDF-SHA256 from
+/// `key_hash || counter` (LE) and salted with a random value drawn once per process.
+pub fn tracked_nonce(key_hash: &[u8; 32]) -> [u8; 12] {
+    let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let salt = PROCESS_SALT.get_or_init(|| Key::random().0);
+
+    let mut ikm = [0u8; 40];
+    ikm[..32].copy_from_slice(key_hash);
+    ikm[32..].copy_from_slice(&counter.to_le_bytes());
+    let mut nonce = [0u8; 12];
+    Hkdf::<Sha256>::new(Some(salt), &ikm)
+        .expand(b"sp1-chacha/nonce", &mut nonce)
+        .expect("12 bytes is a valid HKDF-SHA256 output length");
+    nonce
+}
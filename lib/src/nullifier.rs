@@ -0,0 +1,17 @@
+//! Replay protection for on-chain verification.
+//!
+//! Each encryption commits to a nullifier derived from its key and nonce. A verifier contract that
+//! records used nullifiers (see `contracts/src/NullifierRegistry.sol`) accepts every proof at most
+//! once. Since a nonce is never reused with the same key, distinct encryptions never collide.
+
+use sha2::{Digest, Sha256};
+
+use crate::{Key, Nonce};
+
+/// `SHA256(key || nonce)`. The key stays hidden as long as it has full entropy.
+pub fn compute_nullifier(key: &Key, nonce: &Nonce) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(key.0);
+    hasher.update(nonce.0);
+    hasher.finalize().into()
+}
@@ -0,0 +1,138 @@
+//! ChaCha20 computing several keystream blocks at once, for encrypting on the host before proving.
+//!
+//! The rounds of one block depend on each other, but blocks with different counters do not.
+//! [`chacha_lanes`] keeps each state word of `LANES` blocks side by side in an array, so every
+//! addition, XOR and rotation is the same operation on all lanes, which the compiler vectorizes
+//! without `std::simd` or `unsafe`. [`chacha_parallel`] is the 4 lane version.
+//!
+//! `cargo bench --bench parallel` compares the throughput of 1, 4 and 8 lanes. Built for the host
+//! CPU with `RUSTFLAGS="-C target-cpu=native"`, 4 lanes run about twice as fast as 1 on AVX2
+//! hardware, and 8 lanes faster still. For the baseline x86-64 target, SSE2 with 16 vector
+//! registers, the 16 state words of 4 lanes leave no room and the gain mostly disappears. The
+//! `chacha20` crate behind [`crate::chacha`] picks hand written SSE2 or AVX2 code at runtime and
+//! stays faster than both, this module shows where that speed comes from.
+//!
+//! This only speeds up host side work such as encrypting inputs or checking ciphertexts. It does
+//! not change the zkVM cycle count: the zkVM has no SIMD, so a lane costs as many cycles as a
+//! block, and the programs keep using [`crate::chacha`].
+
+use crate::{Key, Nonce};
+
+/// Size of a ChaCha20 keystream block.
+const BLOCK_LEN: usize = 64;
+
+/// "expand 32-byte k"
+const CONSTANTS: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+
+/// One state word of every lane.
+type Word<const LANES: usize> = [u32; LANES];
+
+#[inline(always)]
+fn add<const LANES: usize>(mut a: Word<LANES>, b: Word<LANES>) -> Word<LANES> {
+    for i in 0..LANES {
+        a[i] = a[i].wrapping_add(b[i]);
+    }
+    a
+}
+
+#[inline(always)]
+fn xor_rotate<const LANES: usize>(mut a: Word<LANES>, b: Word<LANES>, n: u32) -> Word<LANES> {
+    for i in 0..LANES {
+        a[i] = (a[i] ^ b[i]).rotate_left(n);
+    }
+    a
+}
+
+#[inline(always)]
+fn quarter_round<const LANES: usize>(
+    state: &mut [Word<LANES>; 16],
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+) {
+    state[a] = add(state[a], state[b]);
+    state[d] = xor_rotate(state[d], state[a], 16);
+    state[c] = add(state[c], state[d]);
+    state[b] = xor_rotate(state[b], state[c], 12);
+    state[a] = add(state[a], state[b]);
+    state[d] = xor_rotate(state[d], state[a], 8);
+    state[c] = add(state[c], state[d]);
+    state[b] = xor_rotate(state[b], state[c], 7);
+}
+
+fn le_words<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    core::array::from_fn(|i| u32::from_le_bytes(bytes[4 * i..4 * i + 4].try_into().unwrap()))
+}
+
+/// XOR the keystream blocks `counter..counter + LANES` onto `chunk`, at most `LANES` blocks long.
+fn apply<const LANES: usize>(key: &[u32; 8], nonce: &[u32; 3], counter: u32, chunk: &mut [u8]) {
+    let mut initial = [[0; LANES]; 16];
+    for (word, &value) in initial.iter_mut().zip(CONSTANTS.iter().chain(key)) {
+        *word = [value; LANES];
+    }
+    initial[12] = core::array::from_fn(|i| {
+        counter
+            .checked_add(i as u32)
+            .expect("ChaCha20 buffer longer than 256 GiB")
+    });
+    for (word, &value) in initial[13..].iter_mut().zip(nonce) {
+        *word = [value; LANES];
+    }
+
+    let mut state = initial;
+    for _ in 0..10 {
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+    for (word, initial) in state.iter_mut().zip(initial) {
+        *word = add(*word, initial);
+    }
+
+    for (lane, block) in chunk.chunks_mut(BLOCK_LEN).enumerate() {
+        for (word, bytes) in state.iter().zip(block.chunks_mut(4)) {
+            for (byte, key_byte) in bytes.iter_mut().zip(word[lane].to_le_bytes()) {
+                *byte ^= key_byte;
+            }
+        }
+    }
+}
+
+/// Encrypt or decrypt `buffer` in place, `LANES` blocks at a time. The output is that of
+/// [`crate::chacha`] for every `LANES`, `chacha_lanes::<1>` is the one block at a time baseline.
+///
+/// ```
+/// use chacha_lib::parallel::chacha_lanes;
+/// use chacha_lib::{chacha, Key, Nonce};
+///
+/// let (key, nonce) = (Key([1; 32]), Nonce([2; 12]));
+/// let mut lanes = [7u8; 1000];
+/// chacha_lanes::<8>(&key, &nonce, &mut lanes);
+/// let mut expected = [7u8; 1000];
+/// chacha(&key.0, &nonce.0, &mut expected);
+/// assert_eq!(lanes, expected);
+/// ```
+///
+/// # Panics
+///
+/// If `LANES` is 0, or `buffer` is longer than the 256 GiB of keystream of a nonce.
+pub fn chacha_lanes<const LANES: usize>(key: &Key, nonce: &Nonce, buffer: &mut [u8]) {
+    assert!(LANES > 0, "at least one lane");
+    let key = le_words(&key.0);
+    let nonce = le_words(&nonce.0);
+    for (i, chunk) in buffer.chunks_mut(LANES * BLOCK_LEN).enumerate() {
+        let counter = u32::try_from(i * LANES).expect("ChaCha20 buffer longer than 256 GiB");
+        apply::<LANES>(&key, &nonce, counter, chunk);
+    }
+}
+
+/// [`chacha_lanes`] with 4 lanes, the width of a 128 bit SIMD register.
+pub fn chacha_parallel(key: &Key, nonce: &Nonce, buffer: &mut [u8]) {
+    chacha_lanes::<4>(key, nonce, buffer);
+}
@@ -0,0 +1,63 @@
+//! Pedersen commitments to a plaintext, statistically hiding where SHA256 is not.
+//!
+//! `SHA256(plaintext)` is binding, but anyone can test a guess against it: a plaintext from a small
+//! set, e.g. a vote or a salary band, is found by hashing every candidate. A Pedersen commitment
+//! `C = m·G + r·H` over the Ristretto group is hiding for every value: with a uniform `r`, `C` is
+//! uniform whatever the value, so even unbounded computation learns nothing. Binding is
+//! computational instead, opening `C` to two values takes the discrete logarithm of `H` to base
+//! `G`.
+//!
+//! - `G` is the Ristretto base point, `H` is hashed to the group from [`H_DOMAIN`], so nobody knows
+//!   its discrete logarithm.
+//! - `m` is the SHA-512 of the value reduced modulo the group order, values of any length map to
+//!   one scalar.
+//! - `r` is 32 bytes reduced modulo the group order, which is within `2^-127` of uniform for
+//!   uniformly random bytes. A reused or guessable `r` voids the hiding.
+//!
+//! `program/src/main_pedersen.rs` commits both: the SHA256 for EVM contracts, which have no cheap
+//! Ristretto arithmetic, and the Pedersen commitment. Publishing the SHA256 gives the guessing
+//! attack back, the program's public values only hide a plaintext from a small set when the
+//! verifier drops the hash.
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use sha2::{Digest, Sha512};
+
+/// Domain separation of the second generator `H`.
+pub const H_DOMAIN: &[u8] = b"sp1-chacha/pedersen/H";
+
+/// A commitment, the compressed Ristretto point `m·G + r·H`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PedersenCommitment(pub [u8; 32]);
+
+/// The generator `H`.
+fn generator_h() -> RistrettoPoint {
+    RistrettoPoint::from_uniform_bytes(&Sha512::digest(H_DOMAIN).into())
+}
+
+/// The value as a scalar.
+fn value_scalar(value: &[u8]) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&Sha512::digest(value).into())
+}
+
+/// Commit to `value` with the blinding factor `randomness`, which must be uniformly random and
+/// secret.
+///
+/// ```
+/// use chacha_lib::pedersen::{pedersen_commit, pedersen_verify};
+///
+/// let commitment = pedersen_commit(b"yes", &[7; 32]);
+/// assert!(pedersen_verify(&commitment, b"yes", &[7; 32]));
+/// assert!(!pedersen_verify(&commitment, b"no", &[7; 32]));
+/// ```
+pub fn pedersen_commit(value: &[u8], randomness: &[u8; 32]) -> PedersenCommitment {
+    let r = Scalar::from_bytes_mod_order(*randomness);
+    let point = RISTRETTO_BASEPOINT_POINT * value_scalar(value) + generator_h() * r;
+    PedersenCommitment(point.compress().to_bytes())
+}
+
+/// Whether `commitment` opens to `value` with `r`.
+pub fn pedersen_verify(commitment: &PedersenCommitment, value: &[u8], r: &[u8; 32]) -> bool {
+    pedersen_commit(value, r) == *commitment
+}
@@ -0,0 +1,203 @@
+//! Attribute based access policies, checked by `program/src/main_policy.rs` before it decrypts.
+//!
+//! A policy is a JSON expression over the attributes of a requester, themselves a flat JSON
+//! object:
+//!
+//! ```
+//! use chacha_lib::policy::evaluate;
+//!
+//! let policy = r#"{"and": [{"eq": ["role", "admin"]}, {"lt": ["time", 1767225600]}]}"#;
+//! assert_eq!(evaluate(policy, r#"{"role": "admin", "time": 1700000000}"#), Ok(true));
+//! assert_eq!(evaluate(policy, r#"{"role": "guest", "time": 1700000000}"#), Ok(false));
+//! ```
+//!
+//! An expression is an object with a single operator:
+//!
+//! - `{"and": [expr, ...]}` and `{"or": [expr, ...]}`, evaluated left to right until one operand
+//!   decides the result. An empty `and` holds, an empty `or` does not.
+//! - `{"not": expr}`.
+//! - `{"eq": [attribute, literal]}` and `ne`, for strings, unsigned integers and booleans of the
+//!   same type.
+//! - `{"lt": [attribute, literal]}`, `le`, `gt` and `ge`, for unsigned integers.
+//!
+//! A missing attribute or a comparison of different types is an error rather than `false`, so a
+//! typo cannot silently deny or, under a `not`, grant access.
+//!
+//! Both are parsed with `serde_json`. Numbers other than unsigned 64-bit integers cannot be
+//! compared, and duplicate attribute names are rejected, as JSON parsers resolve them differently.
+
+use core::fmt;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde_json::Value;
+
+/// Why a policy could not be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// Invalid JSON.
+    Syntax {
+        line: usize,
+        column: usize,
+    },
+    DuplicateKey(String),
+    /// The attributes are not a JSON object.
+    InvalidAttributes,
+    /// Not an object with a single known operator and its operands.
+    InvalidExpression,
+    MissingAttribute(String),
+    /// The attribute and the literal it is compared to have different or unordered types.
+    TypeMismatch(String),
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::Syntax { line, column } => {
+                write!(f, "invalid JSON at line {line}, column {column}")
+            }
+            PolicyError::DuplicateKey(key) => write!(f, "duplicate key `{key}`"),
+            PolicyError::InvalidAttributes => f.write_str("attributes must be a JSON object"),
+            PolicyError::InvalidExpression => {
+                f.write_str("expected an object with one of and, or, not, eq, ne, lt, le, gt or ge")
+            }
+            PolicyError::MissingAttribute(name) => write!(f, "missing attribute `{name}`"),
+            PolicyError::TypeMismatch(name) => {
+                write!(
+                    f,
+                    "attribute `{name}` cannot be compared to the policy's literal"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+impl From<serde_json::Error> for PolicyError {
+    fn from(e: serde_json::Error) -> Self {
+        PolicyError::Syntax {
+            line: e.line(),
+            column: e.column(),
+        }
+    }
+}
+
+/// The fields of the attributes object in their order, keeping duplicates that a map would merge.
+struct Attributes(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for Attributes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Fields;
+
+        impl<'de> Visitor<'de> for Fields {
+            type Value = Attributes;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Attributes, A::Error> {
+                let mut fields = Vec::new();
+                while let Some(field) = map.next_entry()? {
+                    fields.push(field);
+                }
+                Ok(Attributes(fields))
+            }
+        }
+
+        deserializer.deserialize_map(Fields)
+    }
+}
+
+/// Whether `attributes` satisfy `policy`, both JSON as described in the [module docs](self).
+pub fn evaluate(policy: &str, attributes: &str) -> Result<bool, PolicyError> {
+    let policy: Value = serde_json::from_str(policy)?;
+    let Attributes(attributes) =
+        serde_json::from_str(attributes).map_err(|e| match e.classify() {
+            serde_json::error::Category::Data => PolicyError::InvalidAttributes,
+            _ => e.into(),
+        })?;
+    for (i, (name, _)) in attributes.iter().enumerate() {
+        if attributes[..i].iter().any(|(existing, _)| existing == name) {
+            return Err(PolicyError::DuplicateKey(name.clone()));
+        }
+    }
+    eval(&policy, &attributes)
+}
+
+fn eval(expression: &Value, attributes: &[(String, Value)]) -> Result<bool, PolicyError> {
+    let Value::Object(fields) = expression else {
+        return Err(PolicyError::InvalidExpression);
+    };
+    let mut fields = fields.iter();
+    let (Some((operator, operand)), None) = (fields.next(), fields.next()) else {
+        return Err(PolicyError::InvalidExpression);
+    };
+    match (operator.as_str(), operand) {
+        ("and", Value::Array(operands)) => {
+            for operand in operands {
+                if !eval(operand, attributes)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        ("or", Value::Array(operands)) => {
+            for operand in operands {
+                if eval(operand, attributes)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        ("not", operand) => Ok(!eval(operand, attributes)?),
+        (operator, Value::Array(operands)) => {
+            let [Value::String(name), literal] = operands.as_slice() else {
+                return Err(PolicyError::InvalidExpression);
+            };
+            let value = attributes
+                .iter()
+                .find(|(attribute, _)| attribute == name)
+                .map(|(_, value)| value)
+                .ok_or_else(|| PolicyError::MissingAttribute(name.clone()))?;
+            compare(operator, name, value, literal)
+        }
+        _ => Err(PolicyError::InvalidExpression),
+    }
+}
+
+fn compare(
+    operator: &str,
+    name: &str,
+    value: &Value,
+    literal: &Value,
+) -> Result<bool, PolicyError> {
+    let mismatch = || PolicyError::TypeMismatch(name.to_string());
+    match operator {
+        "eq" | "ne" => {
+            let same_type = match (value, literal) {
+                (Value::Number(value), Value::Number(literal)) => {
+                    value.is_u64() && literal.is_u64()
+                }
+                (Value::Bool(_), Value::Bool(_)) | (Value::String(_), Value::String(_)) => true,
+                _ => false,
+            };
+            if !same_type {
+                return Err(mismatch());
+            }
+            Ok((value == literal) == (operator == "eq"))
+        }
+        "lt" | "le" | "gt" | "ge" => {
+            let (Some(value), Some(literal)) = (value.as_u64(), literal.as_u64()) else {
+                return Err(mismatch());
+            };
+            Ok(match operator {
+                "lt" => value < literal,
+                "le" => value <= literal,
+                "gt" => value > literal,
+                _ => value >= literal,
+            })
+        }
+        _ => Err(PolicyError::InvalidExpression),
+    }
+}
@@ -0,0 +1,142 @@
+//! Public values committed by the encryption program:
+//!
+//! ```text
+//! version (1) | plaintext_hash (32) | nullifier (32) | key_hash (32) | segments_root (32) |
+//! timestamp_commitment (32) | custody_hash (32) | lineage_commitment (32) |
+//! plaintext_size (8, BE) | ciphertext_is_hash (1) | input_hmac_key_hash (32) | ciphertext
+//! ```
+//!
+//! Disabled optional commitments are all zeros. Version 1 programs committed no custody hash,
+//! version 1 and 2 programs no lineage commitment and version 1 to 3 programs no plaintext size.
+//! The size is big endian, so Solidity reads it as `uint64(bytes8(...))`. `ciphertext_is_hash` is
+//! 1 if the program committed `SHA256(ciphertext)` instead of the ciphertext and 0 otherwise,
+//! version 1 to 5 programs did not commit it. `input_hmac_key_hash` is the SHA256 of the key the
+//! inputs were authenticated with, see [`crate::input_auth`], version 1 to 6 programs did not
+//! commit it.
+
+/// Length of the fixed size fields before the ciphertext.
+pub const HEADER_LEN: usize = 266;
+
+/// [`HEADER_LEN`] of version 1 programs.
+pub const V1_HEADER_LEN: usize = 161;
+
+/// [`HEADER_LEN`] of version 2 programs.
+pub const V2_HEADER_LEN: usize = 193;
+
+/// [`HEADER_LEN`] of version 3 programs.
+pub const V3_HEADER_LEN: usize = 225;
+
+/// [`HEADER_LEN`] of version 4 and 5 programs.
+pub const V4_HEADER_LEN: usize = 233;
+
+/// [`HEADER_LEN`] of version 6 programs.
+pub const V6_HEADER_LEN: usize = 234;
+
+/// Borrowed view of the encryption program's public values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicValues<'a> {
+    /// [`crate::PROGRAM_VERSION`] of the program that made the proof.
+    pub version: u8,
+    /// SHA256 of the uncompressed plaintext.
+    pub plaintext_hash: [u8; 32],
+    /// See [`crate::compute_nullifier`].
+    pub nullifier: [u8; 32],
+    /// SHA256 of the key, to check a key received out-of-band.
+    pub key_hash: [u8; 32],
+    /// Merkle root of the plaintext segments, see [`crate::selective`].
+    pub segments_root: [u8; 32],
+    /// See [`crate::timestamp_commitment`].
+    pub timestamp_commitment: [u8; 32],
+    /// See [`crate::custody_hash`], all zeros for version 1 programs.
+    pub custody_hash: [u8; 32],
+    /// See [`crate::lineage_commitment`], all zeros for version 1 and 2 programs.
+    pub lineage_commitment: [u8; 32],
+    /// Length of the uncompressed plaintext, `None` for version 1 to 3 programs.
+    pub plaintext_size: Option<u64>,
+    /// Whether [`Self::ciphertext`] is the SHA256 hash of the ciphertext, `None` for version 1 to
+    /// 5 programs.
+    pub ciphertext_is_hash: Option<bool>,
+    /// See [`crate::input_auth::hmac_key_hash`], all zeros without input authentication and for
+    /// version 1 to 6 programs.
+    pub input_hmac_key_hash: [u8; 32],
+    /// The ciphertext, or its 32 byte SHA256 hash if the program was asked to commit only that.
+    pub ciphertext: &'a [u8],
+}
+
+impl<'a> PublicValues<'a> {
+    /// Split committed bytes into their fields, `None` if too short or `ciphertext_is_hash` is
+    /// neither 0 nor 1.
+    pub fn parse(bytes: &'a [u8]) -> Option<Self> {
+        let header_len = match bytes.first() {
+            Some(1) => V1_HEADER_LEN,
+            Some(2) => V2_HEADER_LEN,
+            Some(3) => V3_HEADER_LEN,
+            Some(4 | 5) => V4_HEADER_LEN,
+            Some(6) => V6_HEADER_LEN,
+            _ => HEADER_LEN,
+        };
+        if bytes.len() < header_len {
+            return None;
+        }
+        let (header, ciphertext) = bytes.split_at(header_len);
+        let (version, header) = (header[0], &header[1..]);
+        Some(Self {
+            version,
+            plaintext_hash: header[..32].try_into().unwrap(),
+            nullifier: header[32..64].try_into().unwrap(),
+            key_hash: header[64..96].try_into().unwrap(),
+            segments_root: header[96..128].try_into().unwrap(),
+            timestamp_commitment: header[128..160].try_into().unwrap(),
+            custody_hash: header
+                .get(160..192)
+                .map_or([0; 32], |h| h.try_into().unwrap()),
+            lineage_commitment: header
+                .get(192..224)
+                .map_or([0; 32], |h| h.try_into().unwrap()),
+            plaintext_size: header
+                .get(224..232)
+                .map(|size| u64::from_be_bytes(size.try_into().unwrap())),
+            ciphertext_is_hash: match header.get(232) {
+                None => None,
+                Some(0) => Some(false),
+                Some(1) => Some(true),
+                Some(_) => return None,
+            },
+            input_hmac_key_hash: header
+                .get(233..265)
+                .map_or([0; 32], |h| h.try_into().unwrap()),
+            ciphertext,
+        })
+    }
+}
+
+/// Length of the SHA-512 plaintext hash committed by `program/src/main_sha512.rs`.
+pub const SHA512_HASH_LEN: usize = 64;
+
+/// Borrowed view of the public values of the SHA-512 program:
+///
+/// ```text
+/// plaintext_hash (64) | ciphertext
+/// ```
+///
+/// Solidity has no `bytes64`, a contract reads the hash as `bytes` or as two `bytes32` words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sha512PublicValues<'a> {
+    /// SHA512 of the plaintext.
+    pub plaintext_hash: [u8; SHA512_HASH_LEN],
+    pub ciphertext: &'a [u8],
+}
+
+impl<'a> Sha512PublicValues<'a> {
+    /// Split committed bytes into their fields, `None` if too short.
+    pub fn parse(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < SHA512_HASH_LEN {
+            return None;
+        }
+        let (plaintext_hash, ciphertext) = bytes.split_at(SHA512_HASH_LEN);
+        Some(Self {
+            plaintext_hash: plaintext_hash.try_into().unwrap(),
+            ciphertext,
+        })
+    }
+}
@@ -0,0 +1,86 @@
+//! Re-keying a ciphertext from an expiring key to a new one, committing only hashes.
+//!
+//! The rotation program (`program/src/main_rotate.rs`) reveals the new ciphertext in its public
+//! values. When every ciphertext under a key is re-encrypted, the ciphertexts stay in storage and
+//! the re-keying program (`program/src/main_rekey.rs`) commits a fixed size [`Rekey`] instead: the
+//! proof then costs the same to verify on-chain for any ciphertext length, and the stored
+//! ciphertext is checked against [`Rekey::ciphertext_hash`].
+//!
+//! The program is given the old key hash registered for the ciphertext and fails unless the old
+//! key it decrypts with hashes to it, so a proof can only re-key from the registered key.
+
+use sha2::{Digest, Sha256};
+
+use crate::{chacha, Key, Nonce};
+
+/// Length of the re-keying program's public values: `sha256(old_key) (32) | sha256(new_key) (32) |
+/// sha256(plaintext) (32) | sha256(ciphertext_new) (32)`.
+pub const REKEY_LEN: usize = 128;
+
+/// Public values of the re-keying program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rekey {
+    pub old_key_hash: [u8; 32],
+    pub new_key_hash: [u8; 32],
+    /// SHA256 of the plaintext both ciphertexts decrypt to, compressed plaintexts are not
+    /// decompressed.
+    pub plaintext_hash: [u8; 32],
+    /// SHA256 of the ciphertext under the new key.
+    pub ciphertext_hash: [u8; 32],
+}
+
+impl Rekey {
+    pub fn to_bytes(&self) -> [u8; REKEY_LEN] {
+        let mut bytes = [0u8; REKEY_LEN];
+        bytes[..32].copy_from_slice(&self.old_key_hash);
+        bytes[32..64].copy_from_slice(&self.new_key_hash);
+        bytes[64..96].copy_from_slice(&self.plaintext_hash);
+        bytes[96..].copy_from_slice(&self.ciphertext_hash);
+        bytes
+    }
+
+    /// `None` unless `bytes` is exactly [`REKEY_LEN`] long.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != REKEY_LEN {
+            return None;
+        }
+        Some(Self {
+            old_key_hash: bytes[..32].try_into().unwrap(),
+            new_key_hash: bytes[32..64].try_into().unwrap(),
+            plaintext_hash: bytes[64..96].try_into().unwrap(),
+            ciphertext_hash: bytes[96..].try_into().unwrap(),
+        })
+    }
+}
+
+/// Re-encrypt `buffer` in place from `old_key` and `old_nonce` to `new_key` and `new_nonce`.
+///
+/// ```
+/// use chacha_lib::rekey::rekey;
+/// use chacha_lib::{chacha, Key, Nonce};
+///
+/// let (old_key, old_nonce) = (Key([1; 32]), Nonce([2; 12]));
+/// let (new_key, new_nonce) = (Key([3; 32]), Nonce([4; 12]));
+/// let mut buffer = *b"stored record";
+/// chacha(&old_key.0, &old_nonce.0, &mut buffer);
+/// rekey(&old_key, &old_nonce, &new_key, &new_nonce, &mut buffer);
+/// chacha(&new_key.0, &new_nonce.0, &mut buffer);
+/// assert_eq!(&buffer, b"stored record");
+/// ```
+pub fn rekey(
+    old_key: &Key,
+    old_nonce: &Nonce,
+    new_key: &Key,
+    new_nonce: &Nonce,
+    buffer: &mut [u8],
+) -> Rekey {
+    chacha(&old_key.0, &old_nonce.0, buffer);
+    let plaintext_hash = Sha256::digest(&*buffer).into();
+    chacha(&new_key.0, &new_nonce.0, buffer);
+    Rekey {
+        old_key_hash: Sha256::digest(old_key.0).into(),
+        new_key_hash: Sha256::digest(new_key.0).into(),
+        plaintext_hash,
+        ciphertext_hash: Sha256::digest(&*buffer).into(),
+    }
+}
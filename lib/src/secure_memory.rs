@@ -0,0 +1,100 @@
+//! Key material locked in RAM with `mlock`, so it is never written to swap.
+//!
+//! A [`LockedMemory`] owns whole pages of its own: `munlock` unlocks every page in its range and
+//! locks do not nest, so sharing a page with another allocation would unlock it early. The bytes
+//! are zeroized before the pages are unlocked and freed. Locking is limited by `RLIMIT_MEMLOCK`,
+//! see `ulimit -l`.
+
+use std::alloc::{alloc_zeroed, dealloc, handle_alloc_error, Layout};
+use std::io;
+use std::ptr::NonNull;
+
+use zeroize::Zeroize;
+
+use crate::Key;
+
+/// A locked [`Key`].
+pub type LockedKey = LockedMemory<32>;
+
+/// `N` bytes on pages locked into RAM until dropped.
+pub struct LockedMemory<const N: usize> {
+    data: NonNull<[u8; N]>,
+    layout: Layout,
+}
+
+// SAFETY: the pages are owned exclusively, like a `Box<[u8; N]>`.
+unsafe impl<const N: usize> Send for LockedMemory<N> {}
+unsafe impl<const N: usize> Sync for LockedMemory<N> {}
+
+impl<const N: usize> LockedMemory<N> {
+    /// Move `data` onto freshly locked pages, zeroizing the passed array.
+    pub fn new(mut data: [u8; N]) -> io::Result<Self> {
+        // SAFETY: sysconf has no preconditions
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let layout = Layout::from_size_align(N.max(1).next_multiple_of(page_size), page_size)
+            .map_err(io::Error::other)?;
+        // SAFETY: the layout has a non-zero size
+        let Some(pages) = NonNull::new(unsafe { alloc_zeroed(layout) }) else {
+            handle_alloc_error(layout);
+        };
+
+        // SAFETY: the range is the allocation made above
+        if unsafe { libc::mlock(pages.as_ptr().cast(), layout.size()) } != 0 {
+            let error = io::Error::last_os_error();
+            data.zeroize();
+            // SAFETY: allocated above with this layout
+            unsafe { dealloc(pages.as_ptr(), layout) };
+            return Err(error);
+        }
+
+        let mut memory = Self {
+            data: pages.cast(),
+            layout,
+        };
+        memory.as_bytes_mut().copy_from_slice(&data);
+        data.zeroize();
+        Ok(memory)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; N] {
+        // SAFETY: valid and initialized for the lifetime of self
+        unsafe { self.data.as_ref() }
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8; N] {
+        // SAFETY: valid and initialized for the lifetime of self, borrowed mutably
+        unsafe { self.data.as_mut() }
+    }
+}
+
+impl<const N: usize> Drop for LockedMemory<N> {
+    fn drop(&mut self) {
+        self.as_bytes_mut().zeroize();
+        let pages = self.data.as_ptr().cast::<u8>();
+        // SAFETY: the pages were locked and allocated with this layout in `new`
+        unsafe {
+            libc::munlock(pages.cast(), self.layout.size());
+            dealloc(pages, self.layout);
+        }
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for LockedMemory<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("LockedMemory(..)")
+    }
+}
+
+impl Key {
+    /// Move the key onto locked pages.
+    ///
+    /// ```
+    /// use chacha_lib::Key;
+    ///
+    /// let key = Key([7; 32]).lock().unwrap();
+    /// assert_eq!(key.as_bytes(), &[7; 32]);
+    /// ```
+    pub fn lock(self) -> io::Result<LockedKey> {
+        LockedMemory::new(self.0)
+    }
+}
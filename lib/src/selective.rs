@@ -0,0 +1,112 @@
+//! Selective disclosure of plaintext segments.
+//!
+//! The plaintext is split into [`SEGMENT_SIZE`] byte segments, the leaves of a [`MerkleTree`]. When
+//! the encryption proof commits to the tree root, any segment can later be revealed together with
+//! its inclusion proof, without revealing the rest of the plaintext.
+
+use crate::merkle::{leaf_hash, verify_proof, MerkleTree};
+
+pub const SEGMENT_SIZE: usize = 32;
+
+/// The root and the openings of the requested segments.
+pub struct SegmentTree {
+    pub root: [u8; 32],
+    pub proofs: Vec<SegmentProof>,
+}
+
+/// Opening of a single segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentProof {
+    pub index: usize,
+    /// The plaintext bytes `index * SEGMENT_SIZE..`, shorter for the last segment.
+    pub segment: Vec<u8>,
+    pub path: Vec<[u8; 32]>,
+}
+
+impl SegmentProof {
+    pub fn verify(&self, root: &[u8; 32]) -> bool {
+        self.segment.len() <= SEGMENT_SIZE
+            && verify_proof(root, &leaf_hash(&self.segment), self.index, &self.path)
+    }
+
+    /// Offset of the segment in the plaintext.
+    pub fn offset(&self) -> usize {
+        self.index * SEGMENT_SIZE
+    }
+}
+
+fn tree(plaintext: &[u8]) -> MerkleTree {
+    MerkleTree::from_leaves(plaintext.chunks(SEGMENT_SIZE).map(leaf_hash).collect())
+}
+
+/// The root committed by the zkVM program.
+pub fn segments_root(plaintext: &[u8]) -> [u8; 32] {
+    tree(plaintext).root()
+}
+
+/// Open every segment overlapping the `(start, end)` byte ranges (end exclusive).
+pub fn segment_commit(plaintext: &[u8], segment_ranges: &[(usize, usize)]) -> SegmentTree {
+    let tree = tree(plaintext);
+    // Clamped first, so a range up to `usize::MAX` does not walk every index
+    let mut indices: Vec<usize> = segment_ranges
+        .iter()
+        .map(|&(start, end)| (start, end.min(plaintext.len())))
+        .filter(|(start, end)| start < end)
+        .flat_map(|(start, end)| start / SEGMENT_SIZE..=(end - 1) / SEGMENT_SIZE)
+        .collect();
+    indices.sort_unstable();
+    indices.dedup();
+
+    SegmentTree {
+        root: tree.root(),
+        proofs: indices
+            .into_iter()
+            .map(|index| SegmentProof {
+                index,
+                segment: plaintext.chunks(SEGMENT_SIZE).nth(index).unwrap().to_vec(),
+                path: tree.proof(index),
+            })
+            .collect(),
+    }
+}
+
+/// Size of the public values of `program/src/main_segment.rs` without a revealed segment.
+pub const SEGMENT_HEADER_LEN: usize = 96;
+
+/// The public values of `program/src/main_segment.rs`:
+///
+/// ```text
+/// plaintext_hash (32) | segments_root (32) | ciphertext_hash (32) | [index (4, LE) | segment]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentPublicValues<'a> {
+    pub plaintext_hash: [u8; 32],
+    pub segments_root: [u8; 32],
+    pub ciphertext_hash: [u8; 32],
+    /// Index and plaintext of the segment revealed with `reveal_index`.
+    pub revealed: Option<(u32, &'a [u8])>,
+}
+
+impl<'a> SegmentPublicValues<'a> {
+    /// Split committed bytes into their fields, `None` if malformed.
+    pub fn parse(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < SEGMENT_HEADER_LEN {
+            return None;
+        }
+        let (header, revealed) = bytes.split_at(SEGMENT_HEADER_LEN);
+        let revealed = if revealed.is_empty() {
+            None
+        } else if (5..=4 + SEGMENT_SIZE).contains(&revealed.len()) {
+            let (index, segment) = revealed.split_at(4);
+            Some((u32::from_le_bytes(index.try_into().unwrap()), segment))
+        } else {
+            return None;
+        };
+        Some(Self {
+            plaintext_hash: header[..32].try_into().unwrap(),
+            segments_root: header[32..64].try_into().unwrap(),
+            ciphertext_hash: header[64..].try_into().unwrap(),
+            revealed,
+        })
+    }
+}
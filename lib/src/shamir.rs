@@ -0,0 +1,252 @@
+//! (t, n) Shamir secret sharing of a [`Key`], so no single custodian holds it.
+//!
+//! Each key byte is the constant term of its own random polynomial of degree `t - 1` over GF(256),
+//! and share `x` holds the values of the polynomials at `x`, for `x` in `1..=n`. Any `t` shares
+//! recover the key by Lagrange interpolation at zero, fewer reveal nothing about it. The field is
+//! the AES field with the reduction polynomial `x^8 + x^4 + x^3 + x + 1`.
+//!
+//! A share is encoded as:
+//!
+//! ```text
+//! threshold (1) | index (1) | value (32)
+//! ```
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{bytes_to_hex, decode_hex, Key};
+
+pub const SHARE_LEN: usize = 34;
+
+/// One of the `n` shares of a split key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyShare {
+    /// Number of shares that reconstruct the key.
+    pub threshold: u8,
+    /// The point the share is evaluated at, from 1 to `n`.
+    pub index: u8,
+    pub value: [u8; 32],
+}
+
+impl Zeroize for KeyShare {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for KeyShare {}
+
+impl core::fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "KeyShare({}/{}, ..)", self.index, self.threshold)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShamirError {
+    NoShares,
+    /// Fewer shares than their threshold.
+    TooFewShares {
+        got: usize,
+        threshold: u8,
+    },
+    /// Two shares at the same index.
+    DuplicateIndex(u8),
+    /// The shares are of splits with different thresholds.
+    ThresholdMismatch,
+    /// A share at index 0, which would be the key itself, or with a threshold of 0.
+    InvalidShare,
+    /// Not the hex encoding of a share.
+    InvalidEncoding,
+}
+
+impl core::fmt::Display for ShamirError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ShamirError::NoShares => f.write_str("no key shares"),
+            ShamirError::TooFewShares { got, threshold } => {
+                write!(f, "{got} key shares, {threshold} are needed")
+            }
+            ShamirError::DuplicateIndex(index) => write!(f, "key share {index} is given twice"),
+            ShamirError::ThresholdMismatch => {
+                f.write_str("key shares of different splits, their thresholds differ")
+            }
+            ShamirError::InvalidShare => f.write_str("key share with index or threshold 0"),
+            ShamirError::InvalidEncoding => {
+                write!(f, "key share must be {} hex characters", SHARE_LEN * 2)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShamirError {}
+
+impl KeyShare {
+    pub fn to_bytes(&self) -> [u8; SHARE_LEN] {
+        let mut bytes = [0u8; SHARE_LEN];
+        bytes[0] = self.threshold;
+        bytes[1] = self.index;
+        bytes[2..].copy_from_slice(&self.value);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8; SHARE_LEN]) -> Result<Self, ShamirError> {
+        let share = KeyShare {
+            threshold: bytes[0],
+            index: bytes[1],
+            value: bytes[2..].try_into().unwrap(),
+        };
+        if share.threshold == 0 || share.index == 0 {
+            return Err(ShamirError::InvalidShare);
+        }
+        Ok(share)
+    }
+
+    /// The hex encoding written to share files.
+    pub fn to_hex(&self) -> String {
+        bytes_to_hex(&self.to_bytes())
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self, ShamirError> {
+        let mut bytes =
+            decode_hex::<SHARE_LEN>(s.trim()).map_err(|_| ShamirError::InvalidEncoding)?;
+        let share = KeyShare::from_bytes(&bytes);
+        bytes.zeroize();
+        share
+    }
+}
+
+/// Multiply in GF(256) without branching on the operands, as they are key material.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        a = (a << 1) ^ (0x1b & 0u8.wrapping_sub(a >> 7));
+        b >>= 1;
+    }
+    product
+}
+
+/// `a^254`, the inverse of a nonzero `a`.
+fn gf_inv(a: u8) -> u8 {
+    let mut inverse = 1;
+    let mut power = a;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            inverse = gf_mul(inverse, power);
+        }
+        power = gf_mul(power, power);
+        exponent >>= 1;
+    }
+    inverse
+}
+
+/// [`split_key`] with the polynomial coefficients from `fill`, which is called `t - 1` times with
+/// 32 bytes each, for the coefficients of degree 1 to `t - 1` of every key byte.
+///
+/// # Panics
+///
+/// Unless `1 <= t <= n`.
+pub fn split_key_with(key: &Key, n: u8, t: u8, mut fill: impl FnMut(&mut [u8])) -> Vec<KeyShare> {
+    assert!(
+        t >= 1 && t <= n,
+        "threshold must be between 1 and {n}, got {t}"
+    );
+    let mut coefficients = vec![[0u8; 32]; t as usize - 1];
+    for coefficient in &mut coefficients {
+        fill(coefficient);
+    }
+    let shares = (1..=n)
+        .map(|x| {
+            let mut value = [0u8; 32];
+            for (i, byte) in value.iter_mut().enumerate() {
+                // Horner's rule, from the highest degree down to the key byte
+                let y = coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |y, coefficient| gf_mul(y, x) ^ coefficient[i]);
+                *byte = gf_mul(y, x) ^ key.0[i];
+            }
+            KeyShare {
+                threshold: t,
+                index: x,
+                value,
+            }
+        })
+        .collect();
+    for coefficient in &mut coefficients {
+        coefficient.zeroize();
+    }
+    shares
+}
+
+/// Split `key` into `n` shares, any `t` of which reconstruct it.
+///
+/// # Panics
+///
+/// Unless `1 <= t <= n`.
+///
+/// # Example
+///
+/// ```
+/// use chacha_lib::shamir::{reconstruct_key, split_key};
+/// use chacha_lib::Key;
+///
+/// let key = Key::random();
+/// let shares = split_key(&key, 5, 3);
+/// assert_eq!(reconstruct_key(&shares[1..4]), Ok(key));
+/// ```
+#[cfg(feature = "std")]
+pub fn split_key(key: &Key, n: u8, t: u8) -> Vec<KeyShare> {
+    use rand::{rngs::OsRng, TryRngCore};
+
+    split_key_with(key, n, t, |bytes| {
+        OsRng.try_fill_bytes(bytes).expect("Rng->buffer")
+    })
+}
+
+/// Reconstruct a key from at least `threshold` of its shares. Only the first `threshold` shares
+/// are used, so a share beyond them that was tampered with goes unnoticed.
+pub fn reconstruct_key(shares: &[KeyShare]) -> Result<Key, ShamirError> {
+    let threshold = shares.first().ok_or(ShamirError::NoShares)?.threshold;
+    for (i, share) in shares.iter().enumerate() {
+        if share.threshold == 0 || share.index == 0 {
+            return Err(ShamirError::InvalidShare);
+        }
+        if share.threshold != threshold {
+            return Err(ShamirError::ThresholdMismatch);
+        }
+        if shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(ShamirError::DuplicateIndex(share.index));
+        }
+    }
+    if shares.len() < threshold as usize {
+        return Err(ShamirError::TooFewShares {
+            got: shares.len(),
+            threshold,
+        });
+    }
+
+    let shares = &shares[..threshold as usize];
+    let mut key = Key([0; 32]);
+    for share in shares {
+        // The Lagrange basis polynomial of the share at 0, where subtracting is xor
+        let mut basis = 1;
+        for other in shares.iter().filter(|other| other.index != share.index) {
+            basis = gf_mul(
+                basis,
+                gf_mul(other.index, gf_inv(other.index ^ share.index)),
+            );
+        }
+        for (byte, value) in key.0.iter_mut().zip(share.value) {
+            *byte ^= gf_mul(value, basis);
+        }
+    }
+    Ok(key)
+}
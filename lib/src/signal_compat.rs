@@ -0,0 +1,67 @@
+//! One step of the Signal double ratchet's sending chain, for proving a message was encrypted
+//! with the key a chain step derived.
+//!
+//! This follows the Double Ratchet specification's recommended `KDF_CK`, HMAC-SHA256 of the
+//! chain key with constant inputs, and `ENCRYPT`, HKDF-SHA256 of the message key with a zero salt.
+//! The specification pairs `ENCRYPT` with AES-256-CBC and HMAC-SHA256; here HKDF derives a
+//! ChaCha20-Poly1305 key and nonce instead, for the same one-time message key use. Messages are
+//! therefore NOT readable by libsignal, and there is no Diffie-Hellman ratchet, header
+//! encryption or skipped message handling.
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::ChaCha20Poly1305;
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// `KDF_CK` input deriving the message key.
+pub const MESSAGE_KEY_INPUT: u8 = 0x01;
+/// `KDF_CK` input deriving the next chain key.
+pub const CHAIN_KEY_INPUT: u8 = 0x02;
+/// `ENCRYPT` HKDF info string.
+pub const ENCRYPT_INFO: &[u8] = b"sp1-chacha/signal";
+
+/// `KDF_CK`: the next chain key and the message key of `chain_key`.
+pub fn kdf_ck(chain_key: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let hmac = |input: u8| -> [u8; 32] {
+        let mut mac =
+            <Hmac<Sha256> as Mac>::new_from_slice(chain_key).expect("HMAC accepts any key length");
+        mac.update(&[input]);
+        mac.finalize().into_bytes().into()
+    };
+    (hmac(CHAIN_KEY_INPUT), hmac(MESSAGE_KEY_INPUT))
+}
+
+fn message_cipher(message_key: &[u8; 32]) -> (ChaCha20Poly1305, [u8; 12]) {
+    let mut okm = [0u8; 44];
+    Hkdf::<Sha256>::new(Some(&[0u8; 32]), message_key)
+        .expand(ENCRYPT_INFO, &mut okm)
+        .expect("44 bytes is a valid HKDF-SHA256 output length");
+    let (key, nonce) = okm.split_at(32);
+    (
+        ChaCha20Poly1305::new_from_slice(key).expect("key is 32 bytes"),
+        nonce.try_into().unwrap(),
+    )
+}
+
+/// Encrypt `plaintext` with the message key of `sending_chain_key`, returning the ciphertext
+/// with its 16 byte tag and the next chain key.
+pub fn signal_encrypt(sending_chain_key: &[u8; 32], plaintext: &[u8]) -> (Vec<u8>, [u8; 32]) {
+    let (next_chain_key, message_key) = kdf_ck(sending_chain_key);
+    let (cipher, nonce) = message_cipher(&message_key);
+    let ciphertext = cipher
+        .encrypt(&nonce.into(), plaintext)
+        .expect("plaintext too large");
+    (ciphertext, next_chain_key)
+}
+
+/// Decrypt a [`signal_encrypt`] ciphertext, `None` if its tag is invalid.
+pub fn signal_decrypt(
+    receiving_chain_key: &[u8; 32],
+    ciphertext: &[u8],
+) -> Option<(Vec<u8>, [u8; 32])> {
+    let (next_chain_key, message_key) = kdf_ck(receiving_chain_key);
+    let (cipher, nonce) = message_cipher(&message_key);
+    let plaintext = cipher.decrypt(&nonce.into(), ciphertext).ok()?;
+    Some((plaintext, next_chain_key))
+}
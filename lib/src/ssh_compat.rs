@@ -0,0 +1,85 @@
+//! OpenSSH's `chacha20-poly1305@openssh.com` packet encryption, for proving what an SSH session
+//! carried.
+//!
+//! As specified in OpenSSH's `PROTOCOL.chacha20poly1305`, the cipher uses two keys and the packet
+//! sequence number as the 64 bit big endian nonce of original, 8 byte nonce ChaCha20:
+//!
+//! - `key1` (`K_1`, the second half of the 64 byte key) encrypts the 4 byte packet length at
+//!   block 0.
+//! - `key2` (`K_2`, the first half) gives the Poly1305 key at block 0 and encrypts the rest of the
+//!   packet from block 1.
+//!
+//! The tag covers the encrypted length and payload. This is for auditing only and does NOT replace
+//! an SSH implementation: there is no key exchange, padding or MAC-then-parse logic here.
+
+use constant_time_eq::constant_time_eq_16;
+use poly1305::universal_hash::KeyInit;
+use poly1305::Poly1305;
+
+use crate::chacha_original;
+
+/// Length of the Poly1305 tag appended to every packet.
+pub const TAG_LEN: usize = 16;
+
+fn tag(key2: &[u8; 32], nonce: &[u8; 8], encrypted: &[u8]) -> [u8; TAG_LEN] {
+    let mut poly1305_key = [0u8; 32];
+    chacha_original(key2, nonce, 0, &mut poly1305_key);
+    Poly1305::new(&poly1305_key.into())
+        .compute_unpadded(encrypted)
+        .into()
+}
+
+/// Encrypt the packet with `sequence_number`, returning the encrypted length, the encrypted
+/// `plaintext` and the tag.
+///
+/// `plaintext` is everything after the length field: the padding length, payload and padding.
+pub fn ssh_chacha_encrypt(
+    key1: &[u8; 32],
+    key2: &[u8; 32],
+    sequence_number: u32,
+    plaintext: &[u8],
+) -> Vec<u8> {
+    let nonce = u64::from(sequence_number).to_be_bytes();
+    let length = u32::try_from(plaintext.len()).expect("packet too large");
+
+    let mut packet = Vec::with_capacity(4 + plaintext.len() + TAG_LEN);
+    packet.extend_from_slice(&length.to_be_bytes());
+    packet.extend_from_slice(plaintext);
+    chacha_original(key1, &nonce, 0, &mut packet[..4]);
+    chacha_original(key2, &nonce, 1, &mut packet[4..]);
+
+    let tag = tag(key2, &nonce, &packet);
+    packet.extend_from_slice(&tag);
+    packet
+}
+
+/// Decrypt an [`ssh_chacha_encrypt`] packet, `None` if its length or tag is invalid.
+pub fn ssh_chacha_decrypt(
+    key1: &[u8; 32],
+    key2: &[u8; 32],
+    sequence_number: u32,
+    packet: &[u8],
+) -> Option<Vec<u8>> {
+    let nonce = u64::from(sequence_number).to_be_bytes();
+    if packet.len() < 4 + TAG_LEN {
+        return None;
+    }
+    let (encrypted, received_tag) = packet.split_at(packet.len() - TAG_LEN);
+
+    let mut length = [0u8; 4];
+    length.copy_from_slice(&encrypted[..4]);
+    chacha_original(key1, &nonce, 0, &mut length);
+    if u32::from_be_bytes(length) as usize != encrypted.len() - 4 {
+        return None;
+    }
+    if !constant_time_eq_16(
+        &tag(key2, &nonce, encrypted),
+        received_tag.try_into().unwrap(),
+    ) {
+        return None;
+    }
+
+    let mut plaintext = encrypted[4..].to_vec();
+    chacha_original(key2, &nonce, 1, &mut plaintext);
+    Some(plaintext)
+}
@@ -0,0 +1,113 @@
+//! Time-lock decryption, proven by `program/src/main_timelock.rs` only once a block at or after
+//! the unlock block is shown.
+//!
+//! The sender publishes the ciphertext, the unlock block and the SHA256 of the key, and hands the
+//! key to a custodian. Once the chain reaches the unlock block, the custodian proves the ciphertext
+//! decrypts under that key to the committed plaintext, against the header of a block at or after
+//! the unlock block.
+//!
+//! # Trusted block headers
+//!
+//! The program cannot see the chain, it takes the header as an input and commits its hash. A
+//! verifier must check that hash against a source it trusts, e.g. a block hash oracle contract or a
+//! light client. Without that check a custodian can make up a header with any block number and
+//! unlock early. [`BlockHeader`] is a fixed size stand-in for testing, a production deployment
+//! parses the header format of its chain, e.g. RLP encoded Ethereum headers hashed with
+//! Keccak-256, which `blockhash` returns for the last 256 blocks only.
+//!
+//! The key is only as locked as its custodian is honest: nothing stops it from decrypting before
+//! the unlock block, it just cannot prove it did so at the right time. Designs without a
+//! custodian replace the block with sequential work:
+//!
+//! - A verifiable delay function or time-lock puzzle (Rivest, Shamir and Wagner) derives the key
+//!   from squarings that cannot be parallelized. Anyone can unlock after enough sequential time,
+//!   at the cost of the computation and of a wall clock time that depends on hardware.
+//! - Threshold schemes such as drand's timelock encryption encrypt to a future beacon round,
+//!   whose signature is the decryption key, trusting a threshold of beacon nodes instead.
+
+use sha2::{Digest, Sha256};
+
+/// Size of an encoded [`BlockHeader`].
+pub const BLOCK_HEADER_LEN: usize = 80;
+
+/// Size of the public values before the plaintext.
+pub const TIMELOCK_HEADER_LEN: usize = 112;
+
+/// A mock block header, encoded as `parent_hash (32) | number (8) | timestamp (8) |
+/// state_root (32)` with little endian integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BlockHeader {
+    pub parent_hash: [u8; 32],
+    pub number: u64,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub state_root: [u8; 32],
+}
+
+impl BlockHeader {
+    pub fn to_bytes(&self) -> [u8; BLOCK_HEADER_LEN] {
+        let mut bytes = [0; BLOCK_HEADER_LEN];
+        bytes[..32].copy_from_slice(&self.parent_hash);
+        bytes[32..40].copy_from_slice(&self.number.to_le_bytes());
+        bytes[40..48].copy_from_slice(&self.timestamp.to_le_bytes());
+        bytes[48..].copy_from_slice(&self.state_root);
+        bytes
+    }
+
+    /// Decode a header, `None` unless it is [`BLOCK_HEADER_LEN`] bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; BLOCK_HEADER_LEN] = bytes.try_into().ok()?;
+        Some(Self {
+            parent_hash: bytes[..32].try_into().unwrap(),
+            number: u64::from_le_bytes(bytes[32..40].try_into().unwrap()),
+            timestamp: u64::from_le_bytes(bytes[40..48].try_into().unwrap()),
+            state_root: bytes[48..].try_into().unwrap(),
+        })
+    }
+
+    /// SHA256 of the encoded header.
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(self.to_bytes()).into()
+    }
+
+    /// Whether this block is at or after `unlock_block`.
+    pub fn unlocks(&self, unlock_block: u64) -> bool {
+        self.number >= unlock_block
+    }
+}
+
+/// The public values of `program/src/main_timelock.rs`:
+///
+/// ```text
+/// unlock_block (8) | block_number (8) | block_hash (32) | key_hash (32) | ciphertext_hash (32) |
+/// plaintext
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelockPublicValues<'a> {
+    pub unlock_block: u64,
+    /// Number of the header the decryption was proven against.
+    pub block_number: u64,
+    /// [`BlockHeader::hash`], to check against a trusted source.
+    pub block_hash: [u8; 32],
+    /// SHA256 of the key, as published with the ciphertext.
+    pub key_hash: [u8; 32],
+    pub ciphertext_hash: [u8; 32],
+    pub plaintext: &'a [u8],
+}
+
+impl<'a> TimelockPublicValues<'a> {
+    /// Split committed bytes into their fields, `None` if too short.
+    pub fn parse(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < TIMELOCK_HEADER_LEN {
+            return None;
+        }
+        Some(Self {
+            unlock_block: u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+            block_number: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            block_hash: bytes[16..48].try_into().unwrap(),
+            key_hash: bytes[48..80].try_into().unwrap(),
+            ciphertext_hash: bytes[80..112].try_into().unwrap(),
+            plaintext: &bytes[TIMELOCK_HEADER_LEN..],
+        })
+    }
+}
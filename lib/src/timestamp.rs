@@ -0,0 +1,18 @@
+//! Binding a proof to a signed timestamp from a time oracle.
+//!
+//! The host fetches a timestamp signed over the plaintext hash and passes its bytes to the
+//! program, which commits to [`timestamp_commitment`]. The signature is checked by the host, an
+//! on-chain verifier only sees the commitment. See `script/src/timestamp.rs` for the oracle side.
+
+use sha2::{Digest, Sha256};
+
+/// Length of the timestamp bytes: unix seconds as u64 LE (8) | ed25519 signature (64).
+pub const TIMESTAMP_LEN: usize = 72;
+
+/// `SHA256(timestamp_bytes || plaintext_hash)`.
+pub fn timestamp_commitment(timestamp_bytes: &[u8], plaintext_hash: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(timestamp_bytes);
+    hasher.update(plaintext_hash);
+    hasher.finalize().into()
+}
@@ -0,0 +1,89 @@
+//! TLS 1.3 record protection with `TLS_CHACHA20_POLY1305_SHA256`, for proving what a connection
+//! carried.
+//!
+//! Following RFC 8446 section 5, each record is encrypted under the traffic key with the
+//! [`tls13_nonce`] of its sequence number. The AEAD plaintext is the record content followed by
+//! its real content type, and the 5 byte record header, which claims `application_data`, is the
+//! associated data. The same construction protects encrypted handshake records, with the
+//! handshake traffic key and content type 22.
+//!
+//! This is for auditing only and does NOT replace a TLS implementation: there is no handshake,
+//! key schedule or record padding here.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::ChaCha20Poly1305;
+
+/// Length of the Poly1305 tag of every record.
+pub const TAG_LEN: usize = 16;
+/// Outer content type of every encrypted record.
+pub const APPLICATION_DATA: u8 = 23;
+/// Inner content type of encrypted handshake messages.
+pub const HANDSHAKE: u8 = 22;
+
+/// The per-record nonce: the sequence number as u64 BE, XORed into the last 8 bytes of `iv`.
+pub fn tls13_nonce(iv: &[u8; 12], seq: u64) -> [u8; 12] {
+    let mut nonce = *iv;
+    for (n, s) in nonce[4..].iter_mut().zip(seq.to_be_bytes()) {
+        *n ^= s;
+    }
+    nonce
+}
+
+fn header(ciphertext_len: usize) -> [u8; 5] {
+    let len = u16::try_from(ciphertext_len).expect("record too large");
+    let [hi, lo] = len.to_be_bytes();
+    [APPLICATION_DATA, 0x03, 0x03, hi, lo]
+}
+
+/// The encrypted record with sequence number `seq`, including its header.
+pub fn tls13_encrypt(
+    key: &[u8; 32],
+    iv: &[u8; 12],
+    seq: u64,
+    content_type: u8,
+    plaintext: &[u8],
+) -> Vec<u8> {
+    let mut inner = Vec::with_capacity(plaintext.len() + 1);
+    inner.extend_from_slice(plaintext);
+    inner.push(content_type);
+    let header = header(inner.len() + TAG_LEN);
+
+    let payload = Payload {
+        msg: &inner,
+        aad: &header,
+    };
+    let ciphertext = ChaCha20Poly1305::new(key.into())
+        .encrypt(&tls13_nonce(iv, seq).into(), payload)
+        .expect("record too large");
+
+    let mut record = header.to_vec();
+    record.extend(ciphertext);
+    record
+}
+
+/// Decrypt a [`tls13_encrypt`] record into its content type and content, `None` if its header or
+/// tag is invalid.
+pub fn tls13_decrypt(
+    key: &[u8; 32],
+    iv: &[u8; 12],
+    seq: u64,
+    record: &[u8],
+) -> Option<(u8, Vec<u8>)> {
+    if record.len() < 5 + TAG_LEN + 1 {
+        return None;
+    }
+    let (header, ciphertext) = record.split_at(5);
+    if header != self::header(ciphertext.len()) {
+        return None;
+    }
+
+    let payload = Payload {
+        msg: ciphertext,
+        aad: header,
+    };
+    let mut inner = ChaCha20Poly1305::new(key.into())
+        .decrypt(&tls13_nonce(iv, seq).into(), payload)
+        .ok()?;
+    let content_type = inner.pop()?;
+    Some((content_type, inner))
+}
@@ -0,0 +1,119 @@
+//! Linking an upgraded encryption program to the version it replaces.
+//!
+//! The upgrade program (`program/src/main_upgrade.rs`) verifies a proof of the old and a proof of
+//! the new program encrypting the same input, and commits an [`Upgrade`] to both verification keys.
+//! `SP1Chacha.upgrade` registers the new key only if the old one is the registered key of its
+//! version, so an upgrade can only register a program that encrypts like the one it replaces. One
+//! input does not show the new program is correct on every input, so the owner still authorizes it.
+
+use crate::PublicValues;
+
+/// Length of the upgrade program's public values:
+/// `old_version (1) | old_vkey (32) | new_version (1) | new_vkey (32)`.
+pub const UPGRADE_LEN: usize = 66;
+
+/// Public values of the upgrade program. Keys are the on-chain `bytes32` hashes, see
+/// [`vkey_bytes32`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Upgrade {
+    pub old_version: u8,
+    pub old_vkey: [u8; 32],
+    pub new_version: u8,
+    pub new_vkey: [u8; 32],
+}
+
+impl Upgrade {
+    pub fn to_bytes(&self) -> [u8; UPGRADE_LEN] {
+        let mut bytes = [0u8; UPGRADE_LEN];
+        bytes[0] = self.old_version;
+        bytes[1..33].copy_from_slice(&self.old_vkey);
+        bytes[33] = self.new_version;
+        bytes[34..].copy_from_slice(&self.new_vkey);
+        bytes
+    }
+
+    /// `None` unless `bytes` is exactly [`UPGRADE_LEN`] long.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != UPGRADE_LEN {
+            return None;
+        }
+        Some(Self {
+            old_version: bytes[0],
+            old_vkey: bytes[1..33].try_into().unwrap(),
+            new_version: bytes[33],
+            new_vkey: bytes[34..].try_into().unwrap(),
+        })
+    }
+}
+
+/// The `bytes32` verification key hash verified on-chain, from the `u32` digest the zkVM verifies
+/// proofs with, like `HashableKey::bytes32_raw` from `HashableKey::hash_u32`.
+///
+/// Each digest word is a BabyBear element below 2^31, packed big endian 31 bits at a time.
+pub fn vkey_bytes32(digest: &[u32; 8]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, &word) in digest.iter().enumerate() {
+        // Bit offset of the word's most significant bit from the end of `bytes`
+        let shift = 31 * (7 - i);
+        for bit in 0..31 {
+            if word >> bit & 1 == 1 {
+                let position = shift + bit;
+                bytes[31 - position / 8] |= 1 << (position % 8);
+            }
+        }
+    }
+    bytes
+}
+
+/// Why two encryption proofs do not make an upgrade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeError {
+    /// The new program's version is not above the old one's.
+    NotNewer { old: u8, new: u8 },
+    /// The programs committed a different value for this field.
+    Mismatch(&'static str),
+}
+
+impl core::fmt::Display for UpgradeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            UpgradeError::NotNewer { old, new } => {
+                write!(f, "version {new} does not upgrade version {old}")
+            }
+            UpgradeError::Mismatch(field) => {
+                write!(f, "the programs committed a different {field}")
+            }
+        }
+    }
+}
+
+/// Check the public values of encrypting the same input with the old and the new program.
+pub fn check_upgrade(old: &PublicValues, new: &PublicValues) -> Result<(), UpgradeError> {
+    if new.version <= old.version {
+        return Err(UpgradeError::NotNewer {
+            old: old.version,
+            new: new.version,
+        });
+    }
+    if new.plaintext_hash != old.plaintext_hash {
+        return Err(UpgradeError::Mismatch("plaintext hash"));
+    }
+    if new.nullifier != old.nullifier {
+        return Err(UpgradeError::Mismatch("nullifier"));
+    }
+    if new.key_hash != old.key_hash {
+        return Err(UpgradeError::Mismatch("key hash"));
+    }
+    if new.ciphertext != old.ciphertext {
+        return Err(UpgradeError::Mismatch("ciphertext"));
+    }
+    // Older programs did not commit the size
+    if matches!((old.plaintext_size, new.plaintext_size), (Some(old), Some(new)) if old != new) {
+        return Err(UpgradeError::Mismatch("plaintext size"));
+    }
+    let ciphertext_is_hash = (old.ciphertext_is_hash, new.ciphertext_is_hash);
+    if matches!(ciphertext_is_hash, (Some(old), Some(new)) if old != new) {
+        return Err(UpgradeError::Mismatch("ciphertext kind"));
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Verification keys of every released version of the encryption program.
+//!
+//! Any change to the program changes its verification key, so proofs of an older program no longer
+//! verify against the current key. The program commits [`PROGRAM_VERSION`] as the first public
+//! values byte, letting a verifier pick the matching key from a [`VkRegistry`]. The
+//! `vk_registry` binary keeps a JSON registry up to date, `SP1Chacha.vkByVersion` mirrors it
+//! on-chain.
+
+use std::collections::BTreeMap;
+
+/// Version of `program/src/main.rs`. Bump it whenever the program changes.
+///
+/// Version 0 programs committed no version byte, version 1 programs no custody hash, version 2
+/// programs no lineage commitment and version 3 programs no plaintext size. Version 4 programs
+/// read no input HMAC, see [`crate::input_auth`], version 5 programs did not commit whether the
+/// ciphertext is hashed and version 6 programs did not commit the input HMAC key hash.
+pub const PROGRAM_VERSION: u8 = 7;
+
+/// Maps program versions to verification key hashes, as printed by the `vkey` binary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct VkRegistry {
+    #[cfg_attr(feature = "std", serde(with = "hex_vks"))]
+    versions: BTreeMap<u8, [u8; 32]>,
+}
+
+impl VkRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the key of `version`, returning the key it replaces.
+    pub fn insert(&mut self, version: u8, vk_hash: [u8; 32]) -> Option<[u8; 32]> {
+        self.versions.insert(version, vk_hash)
+    }
+
+    pub fn get(&self, version: u8) -> Option<&[u8; 32]> {
+        self.versions.get(&version)
+    }
+
+    /// The highest registered version and its key.
+    pub fn latest(&self) -> Option<(u8, &[u8; 32])> {
+        self.versions.iter().next_back().map(|(v, vk)| (*v, vk))
+    }
+
+    /// All versions in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &[u8; 32])> {
+        self.versions.iter().map(|(v, vk)| (*v, vk))
+    }
+
+    /// The key for the version committed in `public_values`, `None` if empty or unregistered.
+    pub fn vk_for(&self, public_values: &[u8]) -> Option<&[u8; 32]> {
+        self.get(*public_values.first()?)
+    }
+}
+
+#[cfg(feature = "std")]
+impl VkRegistry {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("registry serializes")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Hex encode the keys of the versions map.
+#[cfg(feature = "std")]
+mod hex_vks {
+    use std::collections::BTreeMap;
+
+    use hex::FromHex;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        map: &BTreeMap<u8, [u8; 32]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        map.iter()
+            .map(|(k, v)| (k, format!("0x{}", hex::encode(v))))
+            .collect::<BTreeMap<_, _>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<u8, [u8; 32]>, D::Error> {
+        BTreeMap::<u8, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(k, v)| {
+                let v = v.strip_prefix("0x").unwrap_or(&v);
+                Ok((k, <[u8; 32]>::from_hex(v).map_err(D::Error::custom)?))
+            })
+            .collect()
+    }
+}
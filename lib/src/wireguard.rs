@@ -0,0 +1,37 @@
+//! WireGuard's ChaCha20-Poly1305 transport data encryption, for proving what a tunnel carried.
+//!
+//! WireGuard encrypts each transport data packet with the session key and a 64 bit counter,
+//! using the counter as the AEAD nonce after 32 zero bits and no associated data. Proving
+//! [`wireguard_encrypt`] for a captured packet shows which plaintext it carried.
+//!
+//! This is for auditing only and does NOT replace WireGuard's own implementation: there is no
+//! handshake, key rotation or replay protection here.
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::ChaCha20Poly1305;
+
+use crate::Key;
+
+/// Length of the Poly1305 tag appended to every packet.
+pub const TAG_LEN: usize = 16;
+
+/// The AEAD nonce of the packet with `counter`: 32 zero bits, then the counter as u64 LE.
+pub fn wireguard_nonce(counter: u64) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[4..].copy_from_slice(&counter.to_le_bytes());
+    nonce
+}
+
+/// The encrypted packet payload, the ciphertext followed by its [`TAG_LEN`] byte tag.
+pub fn wireguard_encrypt(key: &Key, counter: u64, plaintext: &[u8]) -> Vec<u8> {
+    ChaCha20Poly1305::new(&key.0.into())
+        .encrypt(&wireguard_nonce(counter).into(), plaintext)
+        .expect("plaintext too large")
+}
+
+/// Decrypt a packet payload, `None` if its tag is invalid.
+pub fn wireguard_decrypt(key: &Key, counter: u64, packet: &[u8]) -> Option<Vec<u8>> {
+    ChaCha20Poly1305::new(&key.0.into())
+        .decrypt(&wireguard_nonce(counter).into(), packet)
+        .ok()
+}
@@ -0,0 +1,74 @@
+//! AES-256-CTR against the NIST SP 800-38A known answer tests.
+
+use chacha_lib::aes_mode::{aes_ctr_encrypt, iv_from_nonce};
+
+/// NIST SP 800-38A appendix F.5.5, CTR-AES256.Encrypt.
+const KEY: &str = "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4";
+const INIT_COUNTER: &str = "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff";
+const PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172a\
+                         ae2d8a571e03ac9c9eb76fac45af8e51\
+                         30c81c46a35ce411e5fbc1191a0a52ef\
+                         f69f2445df4f9b17ad2b417be66c3710";
+const CIPHERTEXT: &str = "601ec313775789a5b7a7f504bbf3d228\
+                          f443e3ca4d62b59aca84e990cacaf5c5\
+                          2b0930daa23de94ce87017ba2d84988d\
+                          dfc9c58db67aada613c2dd08457941a6";
+
+fn decode<const N: usize>(hex: &str) -> [u8; N] {
+    hex::decode(hex).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn nist_encrypt_vector() {
+    let mut buffer = hex::decode(PLAINTEXT).unwrap();
+    aes_ctr_encrypt(&decode(KEY), &decode(INIT_COUNTER), &mut buffer);
+    assert_eq!(hex::encode(&buffer), CIPHERTEXT);
+}
+
+/// F.5.6, CTR-AES256.Decrypt, the same blocks the other way.
+#[test]
+fn nist_decrypt_vector() {
+    let mut buffer = hex::decode(CIPHERTEXT).unwrap();
+    aes_ctr_encrypt(&decode(KEY), &decode(INIT_COUNTER), &mut buffer);
+    assert_eq!(hex::encode(&buffer), PLAINTEXT);
+}
+
+#[test]
+fn partial_blocks_are_a_prefix() {
+    let plaintext = hex::decode(PLAINTEXT).unwrap();
+    for len in [0, 1, 15, 17, 63] {
+        let mut buffer = plaintext[..len].to_vec();
+        aes_ctr_encrypt(&decode(KEY), &decode(INIT_COUNTER), &mut buffer);
+        assert_eq!(
+            hex::encode(&buffer),
+            CIPHERTEXT[..2 * len],
+            "{len} bytes"
+        );
+    }
+}
+
+/// The counter carries past the 32 bits after the nonce, as a 128 bit integer, computed with
+/// OpenSSL through Python's `cryptography`.
+#[test]
+fn counter_carries_into_the_nonce() {
+    let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+    let mut iv = iv_from_nonce(&[0; 12]);
+    iv[12..].fill(0xff);
+    let mut buffer = [0u8; 48];
+    aes_ctr_encrypt(&key, &iv, &mut buffer);
+    assert_eq!(
+        hex::encode(buffer),
+        "b9c2739810cba157a9fe6a108c6e569e\
+         641d1a3a80becff6f0f38f9764fdcf96\
+         367ef8288831557408e102950a16e26a"
+    );
+}
+
+#[test]
+fn iv_is_the_nonce_and_a_zero_counter() {
+    let nonce: [u8; 12] = core::array::from_fn(|i| i as u8 + 1);
+    assert_eq!(
+        iv_from_nonce(&nonce),
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 0, 0, 0, 0]
+    );
+}
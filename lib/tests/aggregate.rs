@@ -0,0 +1,166 @@
+//! Aggregating the public values of a stream's chunk proofs into Merkle roots.
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::aggregate::{
+    aggregate, chunk_leaf, plaintext_leaves, Aggregate, AggregateError, ChunkPublicValues,
+    AGGREGATE_LEN, CHUNK_HEADER_LEN,
+};
+use chacha_lib::merkle::{merkle_prove, merkle_verify, node_hash};
+use chacha_lib::{chacha, chacha_at, compute_nullifier, Key, Nonce};
+
+const KEY: [u8; 32] = [1; 32];
+const NONCE: [u8; 12] = [2; 12];
+const CHUNK_SIZE: usize = 128;
+
+/// 4 chunks of `plaintext`, as the chunk program commits them.
+fn chunks(plaintext: &[u8]) -> Vec<ChunkPublicValues> {
+    let count = plaintext.chunks(CHUNK_SIZE).len() as u32;
+    plaintext
+        .chunks(CHUNK_SIZE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let counter = (i * CHUNK_SIZE / 64) as u32;
+            let mut ciphertext = chunk.to_vec();
+            chacha_at(&KEY, &NONCE, counter, &mut ciphertext);
+            ChunkPublicValues {
+                nullifier: compute_nullifier(&Key(KEY), &Nonce(NONCE)),
+                counter,
+                chunks: count,
+                plaintext_hash: Sha256::digest(chunk).into(),
+                ciphertext,
+            }
+        })
+        .collect()
+}
+
+/// The last chunk is shorter.
+fn plaintext() -> Vec<u8> {
+    (0..CHUNK_SIZE * 3 + 50).map(|i| i as u8).collect()
+}
+
+#[test]
+fn four_chunks_reconstruct_the_root() {
+    let plaintext = plaintext();
+    let chunks = chunks(&plaintext);
+    assert_eq!(chunks.len(), 4);
+    let aggregate = aggregate([9; 32], &chunks).unwrap();
+    assert_eq!(aggregate.chunk_vkey, [9; 32]);
+    assert_eq!(aggregate.nullifier, chunks[0].nullifier);
+    assert_eq!(aggregate.chunks, 4);
+
+    // The root from the chunk plaintexts alone
+    let leaves: Vec<_> = plaintext
+        .chunks(CHUNK_SIZE)
+        .map(|chunk| chunk_leaf(&Sha256::digest(chunk).into()))
+        .collect();
+    let root = node_hash(
+        &node_hash(&leaves[0], &leaves[1]),
+        &node_hash(&leaves[2], &leaves[3]),
+    );
+    assert_eq!(aggregate.plaintext_root, root);
+    assert_eq!(plaintext_leaves(&chunks), leaves);
+
+    // And from the ciphertext, which is that of encrypting the whole plaintext
+    let mut ciphertext = plaintext.clone();
+    chacha(&KEY, &NONCE, &mut ciphertext);
+    let leaves: Vec<_> = ciphertext
+        .chunks(CHUNK_SIZE)
+        .map(|chunk| chunk_leaf(&Sha256::digest(chunk).into()))
+        .collect();
+    let root = node_hash(
+        &node_hash(&leaves[0], &leaves[1]),
+        &node_hash(&leaves[2], &leaves[3]),
+    );
+    assert_eq!(aggregate.ciphertext_root, root);
+}
+
+#[test]
+fn chunks_open_against_the_root() {
+    let plaintext = plaintext();
+    let chunks = chunks(&plaintext);
+    let aggregate = aggregate([9; 32], &chunks).unwrap();
+    let leaves = plaintext_leaves(&chunks);
+    for (i, chunk) in plaintext.chunks(CHUNK_SIZE).enumerate() {
+        let proof = merkle_prove(&leaves, i);
+        let leaf = chunk_leaf(&Sha256::digest(chunk).into());
+        assert!(merkle_verify(&aggregate.plaintext_root, &leaf, &proof));
+        assert!(!merkle_verify(&aggregate.ciphertext_root, &leaf, &proof));
+    }
+    let proof = merkle_prove(&leaves, 1);
+    let other = chunk_leaf(&Sha256::digest(&plaintext[..CHUNK_SIZE]).into());
+    assert!(!merkle_verify(&aggregate.plaintext_root, &other, &proof));
+}
+
+#[test]
+fn broken_streams_are_rejected() {
+    let chunks = chunks(&plaintext());
+    assert_eq!(aggregate([9; 32], &[]), Err(AggregateError::Empty));
+
+    let mut other_stream = chunks.clone();
+    other_stream[2].nullifier = [0; 32];
+    assert_eq!(
+        aggregate([9; 32], &other_stream),
+        Err(AggregateError::OtherStream(2))
+    );
+
+    let missing = [&chunks[..1], &chunks[2..]].concat();
+    assert_eq!(
+        aggregate([9; 32], &missing),
+        Err(AggregateError::Discontinuous(1))
+    );
+
+    let mut reordered = chunks.clone();
+    reordered.swap(1, 2);
+    assert_eq!(
+        aggregate([9; 32], &reordered),
+        Err(AggregateError::Discontinuous(1))
+    );
+
+    // Dropping the last chunks keeps the rest continuous, but not complete
+    assert_eq!(
+        aggregate([9; 32], &chunks[..3]),
+        Err(AggregateError::Incomplete(0))
+    );
+    let mut miscounted = chunks.clone();
+    miscounted[3].chunks = 5;
+    assert_eq!(
+        aggregate([9; 32], &miscounted),
+        Err(AggregateError::Incomplete(3))
+    );
+
+    // Only the last chunk may end within a block
+    let mut short = chunks.clone();
+    short[1].ciphertext.pop();
+    short[2].counter = (short[1].counter * 64 + 127) / 64;
+    assert_eq!(
+        aggregate([9; 32], &short),
+        Err(AggregateError::Discontinuous(2))
+    );
+}
+
+#[test]
+fn public_values_roundtrip() {
+    let chunk = chunks(&plaintext()).pop().unwrap();
+    let bytes = chunk.to_bytes();
+    assert_eq!(bytes.len(), CHUNK_HEADER_LEN + 50);
+    assert_eq!(bytes[36..40], 4u32.to_le_bytes());
+    assert_eq!(ChunkPublicValues::parse(&bytes), Some(chunk));
+    assert_eq!(
+        ChunkPublicValues::parse(&bytes[..CHUNK_HEADER_LEN - 1]),
+        None
+    );
+
+    let aggregate = Aggregate {
+        chunk_vkey: [1; 32],
+        nullifier: [2; 32],
+        chunks: 4,
+        plaintext_root: [3; 32],
+        ciphertext_root: [4; 32],
+    };
+    let bytes = aggregate.to_bytes();
+    assert_eq!(bytes.len(), AGGREGATE_LEN);
+    assert_eq!(bytes[64..68], 4u32.to_le_bytes());
+    assert_eq!(Aggregate::parse(&bytes), Some(aggregate));
+    assert_eq!(Aggregate::parse(&bytes[1..]), None);
+}
@@ -0,0 +1,89 @@
+//! RFC 7693 BLAKE2b test vectors, and the personalization used by Zcash.
+
+use blake2::digest::consts::{U20, U32, U48, U64};
+use blake2::digest::{Mac, Update, VariableOutput};
+use blake2::{Blake2bMac, Blake2bVar};
+
+use chacha_lib::blake2b::{blake2b_256, personalization};
+
+/// RFC 7693 appendix E `selftest_seq`.
+fn selftest_seq(len: usize, seed: u32) -> Vec<u8> {
+    let mut a = 0xDEAD4BADu32.wrapping_mul(seed);
+    let mut b = 1u32;
+    (0..len)
+        .map(|_| {
+            let t = a.wrapping_add(b);
+            a = b;
+            b = t;
+            (t >> 24) as u8
+        })
+        .collect()
+}
+
+fn keyed(out_len: usize, key: &[u8], data: &[u8]) -> Vec<u8> {
+    fn mac<M: Mac + blake2::digest::KeyInit>(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut mac = <M as blake2::digest::KeyInit>::new_from_slice(key).unwrap();
+        mac.update(data);
+        mac.finalize().into_bytes().to_vec()
+    }
+    match out_len {
+        20 => mac::<Blake2bMac<U20>>(key, data),
+        32 => mac::<Blake2bMac<U32>>(key, data),
+        48 => mac::<Blake2bMac<U48>>(key, data),
+        64 => mac::<Blake2bMac<U64>>(key, data),
+        _ => unreachable!(),
+    }
+}
+
+fn unkeyed(out_len: usize, data: &[u8]) -> Vec<u8> {
+    if out_len == 32 {
+        return blake2b_256(&[0; 16], data).to_vec();
+    }
+    let mut hasher = Blake2bVar::new(out_len).unwrap();
+    hasher.update(data);
+    hasher.finalize_boxed().to_vec()
+}
+
+/// RFC 7693 appendix E, hashing the hashes of every input and output length, where the unkeyed
+/// 32 byte hashes are [`blake2b_256`].
+#[test]
+fn rfc7693_selftest() {
+    let mut results = Vec::new();
+    for out_len in [20, 32, 48, 64] {
+        for in_len in [0, 3, 128, 129, 255, 1024] {
+            let data = selftest_seq(in_len, in_len as u32);
+            results.extend(unkeyed(out_len, &data));
+            let key = selftest_seq(out_len, out_len as u32);
+            results.extend(keyed(out_len, &key, &data));
+        }
+    }
+    assert_eq!(
+        hex::encode(blake2b_256(&[0; 16], &results)),
+        "c23a7800d98123bd10f506c61e29da5603d763b8bbad2e737f5e765a7bccd475"
+    );
+}
+
+#[test]
+fn abc() {
+    // RFC 7693 appendix A gives BLAKE2b-512 of "abc", its 256 bit variant has another IV
+    assert_eq!(
+        hex::encode(blake2b_256(&[0; 16], b"abc")),
+        "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"
+    );
+    assert_eq!(
+        hex::encode(blake2b_256(&[0; 16], b"")),
+        "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+    );
+}
+
+#[test]
+fn personalized() {
+    let personal = personalization(b"ZcashPoW").unwrap();
+    assert_eq!(&personal[..8], b"ZcashPoW");
+    assert_eq!(personal[8..], [0; 8]);
+    assert_eq!(
+        hex::encode(blake2b_256(&personal, b"abc")),
+        "6469eaf47a1c918c8bd02cebc0e5063c66a7dbfc6d0308309f08ba90cdc1bdcb"
+    );
+    assert_eq!(personalization(&[0; 17]), None);
+}
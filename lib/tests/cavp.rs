@@ -0,0 +1,53 @@
+//! CAVP-style known answer tests of the ChaCha20 keystream, see `data/chacha20_vectors.json`.
+
+use chacha_lib::{chacha, chacha_keystream};
+use serde_json::Value;
+
+const VECTORS: &str = include_str!("data/chacha20_vectors.json");
+
+fn hex_field<const N: usize>(vector: &Value, field: &str) -> [u8; N] {
+    hex::decode(vector[field].as_str().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap_or_else(|_| panic!("`{field}` must be {N} bytes"))
+}
+
+fn vectors() -> Vec<Value> {
+    let file: Value = serde_json::from_str(VECTORS).expect("vectors file is valid JSON");
+    file["vectors"].as_array().expect("`vectors` array").clone()
+}
+
+#[test]
+fn keystream_matches_vectors() {
+    let vectors = vectors();
+    assert!(!vectors.is_empty());
+    for vector in &vectors {
+        let description = vector["description"].as_str().unwrap();
+        let key = hex_field::<32>(vector, "key");
+        let iv = hex_field::<12>(vector, "iv");
+        let counter = u32::try_from(vector["counter"].as_u64().unwrap()).unwrap();
+        let expected = hex::decode(vector["keystream"].as_str().unwrap()).unwrap();
+
+        let keystream = chacha_keystream(&key, &iv, counter, expected.len());
+        assert_eq!(
+            hex::encode(keystream),
+            hex::encode(&expected),
+            "{description}"
+        );
+    }
+}
+
+#[test]
+fn encryption_at_counter_zero_xors_keystream() {
+    for vector in vectors().iter().filter(|v| v["counter"] == 0) {
+        let key = hex_field::<32>(vector, "key");
+        let iv = hex_field::<12>(vector, "iv");
+        let expected = hex::decode(vector["keystream"].as_str().unwrap()).unwrap();
+
+        let plaintext: Vec<u8> = (0..expected.len() as u8).collect();
+        let mut buffer = plaintext.clone();
+        chacha(&key, &iv, &mut buffer);
+        let keystream: Vec<u8> = buffer.iter().zip(&plaintext).map(|(c, p)| c ^ p).collect();
+        assert_eq!(keystream, expected);
+    }
+}
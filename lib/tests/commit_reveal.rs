@@ -0,0 +1,60 @@
+//! Commitments open only with their key and nonce.
+
+use chacha_lib::commit_reveal::{binding, commit, reveal, Commitment, RevealError};
+use chacha_lib::{chacha, Key, Nonce};
+
+const BID: &[u8] = b"sealed bid: 1000 units";
+
+#[test]
+fn binding_hashes_key_nonce_and_ciphertext() {
+    // Python: hashlib.sha256(b"\x01" * 32 + b"\x02" * 12 + b"ct").hexdigest()
+    assert_eq!(
+        hex::encode(binding(&Key([1; 32]), &Nonce([2; 12]), b"ct")),
+        "a67a1ebc073c599b0f21edb332539fc8e1793d7dac3b14b814573d7cbcf58e22"
+    );
+}
+
+#[test]
+fn reveal_returns_the_committed_plaintext() {
+    let (key, nonce) = (Key([1; 32]), Nonce([2; 12]));
+    let commitment = commit(&key, &nonce, BID);
+    let mut ciphertext = BID.to_vec();
+    chacha(&key.0, &nonce.0, &mut ciphertext);
+    assert_eq!(commitment.ciphertext, ciphertext);
+    assert_eq!(reveal(&commitment, &key, &nonce).unwrap(), BID);
+}
+
+#[test]
+fn commitment_cannot_be_opened_to_another_plaintext() {
+    let (key, nonce) = (Key([1; 32]), Nonce([2; 12]));
+    let commitment = commit(&key, &nonce, BID);
+
+    // Any other key decrypts the ciphertext to another plaintext, and is rejected
+    for other in [Key([3; 32]), Key(core::array::from_fn(|i| i as u8))] {
+        assert_eq!(
+            reveal(&commitment, &other, &nonce),
+            Err(RevealError::BindingMismatch)
+        );
+    }
+    assert_eq!(
+        reveal(&commitment, &key, &Nonce([4; 12])),
+        Err(RevealError::BindingMismatch)
+    );
+
+    let mut tampered = commitment.clone();
+    tampered.ciphertext[0] ^= 1;
+    assert_eq!(
+        reveal(&tampered, &key, &nonce),
+        Err(RevealError::BindingMismatch)
+    );
+
+    // Nor can the binding be replaced by one of another opening
+    let swapped = Commitment {
+        binding: commit(&key, &nonce, b"sealed bid: 0001 units").binding,
+        ..commitment
+    };
+    assert_eq!(
+        reveal(&swapped, &key, &nonce),
+        Err(RevealError::BindingMismatch)
+    );
+}
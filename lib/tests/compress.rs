@@ -0,0 +1,119 @@
+//! Compress-then-encrypt round trips.
+
+use chacha_lib::compress::{
+    chacha_then_lz4_decompress, chacha_then_zstd_decompress, compress, decompress,
+    lz4_compress_then_chacha, zstd_compress, zstd_compress_then_chacha, CompressionType,
+};
+use chacha_lib::{chacha, Key, Nonce};
+
+const KEY: Key = Key([7; 32]);
+const NONCE: Nonce = Nonce([9; 12]);
+
+fn text(len: usize) -> Vec<u8> {
+    let mut text = Vec::with_capacity(len);
+    for i in 0.. {
+        if text.len() >= len {
+            break;
+        }
+        text.extend_from_slice(
+            format!("{i}: the quick brown fox jumps over the lazy dog\n").as_bytes(),
+        );
+    }
+    text.truncate(len);
+    text
+}
+
+/// A ChaCha20 keystream, which does not compress.
+fn random(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    chacha(&[1; 32], &[0; 12], &mut bytes);
+    bytes
+}
+
+fn inputs() -> [Vec<u8>; 4] {
+    [
+        Vec::new(),
+        b"x".to_vec(),
+        text(64 * 1024),
+        random(64 * 1024),
+    ]
+}
+
+#[test]
+fn lz4_round_trips() {
+    for plaintext in inputs() {
+        let ciphertext = lz4_compress_then_chacha(&KEY, &NONCE, &plaintext);
+        assert_eq!(
+            chacha_then_lz4_decompress(&KEY, &NONCE, &ciphertext).unwrap(),
+            plaintext
+        );
+    }
+}
+
+#[test]
+fn zstd_round_trips_at_every_level() {
+    for plaintext in inputs() {
+        for level in [1, 3, 22] {
+            let ciphertext = zstd_compress_then_chacha(&KEY, &NONCE, &plaintext, level);
+            assert_eq!(
+                chacha_then_zstd_decompress(&KEY, &NONCE, &ciphertext).unwrap(),
+                plaintext,
+                "level {level}"
+            );
+        }
+    }
+}
+
+#[test]
+fn decompress_inverts_compress() {
+    for compression in [
+        CompressionType::None,
+        CompressionType::Lz4,
+        CompressionType::Zstd,
+    ] {
+        for plaintext in inputs() {
+            let compressed = compress(compression, &plaintext);
+            assert_eq!(
+                decompress(compression, &compressed).unwrap(),
+                plaintext,
+                "{compression}"
+            );
+        }
+    }
+}
+
+#[test]
+fn text_compresses_and_random_bytes_do_not() {
+    let (text, random) = (text(64 * 1024), random(64 * 1024));
+    for compression in [CompressionType::Lz4, CompressionType::Zstd] {
+        assert!(
+            compress(compression, &text).len() < text.len() / 4,
+            "{compression}"
+        );
+        assert!(
+            compress(compression, &random).len() >= random.len(),
+            "{compression}"
+        );
+    }
+}
+
+#[test]
+fn zstd_levels_decompress_the_same() {
+    let plaintext = text(16 * 1024);
+    assert_ne!(zstd_compress(&plaintext, 1), zstd_compress(&plaintext, 22));
+    assert_eq!(
+        decompress(CompressionType::Zstd, &zstd_compress(&plaintext, 22)).unwrap(),
+        plaintext
+    );
+}
+
+#[test]
+fn malformed_data_is_an_error() {
+    let garbage = random(64);
+    assert!(decompress(CompressionType::Lz4, &garbage).is_err());
+    assert!(decompress(CompressionType::Zstd, &garbage).is_err());
+
+    // Decrypting under the wrong key leaves garbage
+    let ciphertext = lz4_compress_then_chacha(&KEY, &NONCE, &text(1024));
+    assert!(chacha_then_lz4_decompress(&Key([8; 32]), &NONCE, &ciphertext).is_err());
+}
@@ -0,0 +1,103 @@
+//! Round trips of [`ChaCha20Config`] through serde and inline strings.
+
+use chacha_lib::config::{ChaCha20Config, ChaChaRounds};
+use chacha_lib::{chacha, chacha_original, CompressionType, NonceFormat};
+
+fn custom() -> ChaCha20Config {
+    ChaCha20Config {
+        rounds: ChaChaRounds::R12,
+        initial_counter: 7,
+        nonce_format: NonceFormat::Original,
+        compression: CompressionType::Zstd,
+    }
+}
+
+#[test]
+fn default_matches_chacha() {
+    let config = ChaCha20Config::default();
+    assert_eq!(config.rounds, ChaChaRounds::R20);
+    assert_eq!(config.initial_counter, 0);
+    assert_eq!(config.nonce_format, NonceFormat::Ietf);
+    assert_eq!(config.compression, CompressionType::None);
+
+    let mut expected = [0u8; 100];
+    chacha(&[1; 32], &[2; 12], &mut expected);
+    let mut buffer = [0u8; 100];
+    config
+        .apply_keystream(&[1; 32], &[2; 12], &mut buffer)
+        .unwrap();
+    assert_eq!(buffer, expected);
+}
+
+#[test]
+fn original_format_matches_chacha_original() {
+    let config = ChaCha20Config {
+        nonce_format: NonceFormat::Original,
+        initial_counter: 3,
+        ..Default::default()
+    };
+    let mut expected = [0u8; 100];
+    chacha_original(&[1; 32], &[2; 8], 3, &mut expected);
+    let mut buffer = [0u8; 100];
+    config
+        .apply_keystream(&[1; 32], &[2; 8], &mut buffer)
+        .unwrap();
+    assert_eq!(buffer, expected);
+    assert!(config
+        .apply_keystream(&[1; 32], &[2; 12], &mut buffer)
+        .is_err());
+}
+
+#[test]
+fn serde_round_trip() {
+    for config in [ChaCha20Config::default(), custom()] {
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ChaCha20Config>(&json).unwrap(),
+            config
+        );
+    }
+    assert_eq!(
+        serde_json::to_value(custom()).unwrap(),
+        serde_json::json!({
+            "rounds": 12,
+            "initial_counter": 7,
+            "nonce_format": "original",
+            "compression": "zstd",
+        })
+    );
+    // Omitted fields are defaults
+    let config: ChaCha20Config = serde_json::from_str(r#"{"rounds": 8}"#).unwrap();
+    assert_eq!(config.rounds, ChaChaRounds::R8);
+    assert_eq!(config.initial_counter, 0);
+    assert!(serde_json::from_str::<ChaCha20Config>(r#"{"rounds": 10}"#).is_err());
+}
+
+#[test]
+fn inline_string_round_trip() {
+    for config in [ChaCha20Config::default(), custom()] {
+        let inline = config.to_string();
+        assert_eq!(ChaCha20Config::try_from(inline.as_str()).unwrap(), config);
+    }
+    assert_eq!(
+        ChaCha20Config::try_from("").unwrap(),
+        ChaCha20Config::default()
+    );
+    assert_eq!(
+        ChaCha20Config::try_from(" compression = LZ4 , counter=7").unwrap(),
+        ChaCha20Config {
+            compression: CompressionType::Lz4,
+            initial_counter: 7,
+            ..Default::default()
+        }
+    );
+    for invalid in [
+        "rounds=10",
+        "counter=-1",
+        "nonce=xchacha",
+        "speed=fast",
+        "rounds",
+    ] {
+        assert!(ChaCha20Config::try_from(invalid).is_err(), "{invalid}");
+    }
+}
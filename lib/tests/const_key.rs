@@ -0,0 +1,26 @@
+//! [`key_from_hex_const`] gives the same keys at compile time and at runtime.
+
+use chacha_lib::{key_from_hex_const, Key};
+
+const HEX: &str = "c0ffee00000102030405060708090a0b0c0d0e0f101112131415161718191aAB";
+const TEST_KEY: Key = Key(key_from_hex_const(HEX));
+
+#[test]
+fn const_and_runtime_keys_are_equal() {
+    let runtime_hex = String::from(HEX);
+    assert_eq!(Key(key_from_hex_const(&runtime_hex)), TEST_KEY);
+    assert_eq!(hex::decode(HEX).unwrap(), TEST_KEY.0);
+}
+
+#[test]
+#[should_panic(expected = "invalid hex character")]
+fn runtime_rejects_invalid_hex() {
+    let runtime_hex = HEX.replace('c', "g");
+    let _ = key_from_hex_const(&runtime_hex);
+}
+
+#[test]
+#[should_panic(expected = "64 characters")]
+fn runtime_rejects_short_hex() {
+    let _ = key_from_hex_const(&HEX[2..]);
+}
@@ -0,0 +1,26 @@
+//! Custody hashes tell operators apart.
+
+use std::collections::HashSet;
+
+use chacha_lib::custody::OPERATOR_ID_LEN;
+use chacha_lib::custody_hash;
+
+const TIMESTAMP: u64 = 1_700_000_000;
+const INPUT_HASH: [u8; 32] = [7; 32];
+
+#[test]
+fn operators_have_distinct_custody_hashes() {
+    let hashes: HashSet<_> = (0..1000u128)
+        .map(|i| custody_hash(&i.to_le_bytes(), TIMESTAMP, &INPUT_HASH))
+        .collect();
+    assert_eq!(hashes.len(), 1000);
+}
+
+#[test]
+fn custody_hash_binds_time_and_input() {
+    let operator_id = [0x24; OPERATOR_ID_LEN];
+    let hash = custody_hash(&operator_id, TIMESTAMP, &INPUT_HASH);
+    assert_eq!(hash, custody_hash(&operator_id, TIMESTAMP, &INPUT_HASH));
+    assert_ne!(hash, custody_hash(&operator_id, TIMESTAMP + 1, &INPUT_HASH));
+    assert_ne!(hash, custody_hash(&operator_id, TIMESTAMP, &[8; 32]));
+}
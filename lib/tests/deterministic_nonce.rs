@@ -0,0 +1,33 @@
+//! Nonces derived from the key and plaintext.
+
+use chacha_lib::deterministic_nonce::deterministic_nonce;
+use chacha_lib::{chacha_encrypt, Key};
+
+#[test]
+fn nonce_is_truncated_hmac_sha256() {
+    // Python: hmac.new(bytes([7] * 32), b"attack at dawn", hashlib.sha256).digest()[:12]
+    let nonce = deterministic_nonce(&Key([7; 32]), b"attack at dawn");
+    assert_eq!(hex::encode(&nonce.0), "9d355517a91afefd031d0290");
+}
+
+#[test]
+fn same_plaintext_gives_same_nonce_and_ciphertext() {
+    let key = Key([7; 32]);
+    let first = deterministic_nonce(&key, b"attack at dawn");
+    let second = deterministic_nonce(&key, b"attack at dawn");
+    assert_eq!(first, second);
+    assert_eq!(
+        chacha_encrypt(&key.0, &first.0, b"attack at dawn"),
+        chacha_encrypt(&key.0, &second.0, b"attack at dawn")
+    );
+}
+
+#[test]
+fn different_inputs_give_different_nonces() {
+    let key = Key([7; 32]);
+    let nonce = deterministic_nonce(&key, b"attack at dawn");
+    assert_ne!(nonce, deterministic_nonce(&key, b"attack at dusk"));
+    assert_ne!(nonce, deterministic_nonce(&key, b"attack at dawn!"));
+    assert_ne!(nonce, deterministic_nonce(&key, b""));
+    assert_ne!(nonce, deterministic_nonce(&Key([8; 32]), b"attack at dawn"));
+}
@@ -0,0 +1,76 @@
+//! Domain separated subkeys.
+
+use chacha_lib::domain::{domain_separate, DOMAIN_AUTH, DOMAIN_DERIVE, DOMAIN_ENCRYPT};
+use chacha_lib::{chacha, Key};
+
+/// All zeros, all ones, the RFC 8439 key and keystream blocks.
+fn keys() -> Vec<Key> {
+    let mut keys = vec![
+        Key([0; 32]),
+        Key([0xff; 32]),
+        Key(core::array::from_fn(|i| i as u8)),
+    ];
+    for seed in 0..16u8 {
+        let mut key = [0; 32];
+        chacha(&[seed; 32], &[0; 12], &mut key);
+        keys.push(Key(key));
+    }
+    keys
+}
+
+#[test]
+fn subkeys_match_python_hkdf() {
+    // Python cryptography: HKDF(SHA256(), 32, salt=None, info=<domain>).derive(bytes([7] * 32))
+    let key = Key([7; 32]);
+    assert_eq!(
+        hex::encode(domain_separate(&key, DOMAIN_ENCRYPT).0),
+        "7ceca56f939308ca93b935ccab1e713d2aed952037efd4a85a9581c5b881a3c0"
+    );
+    assert_eq!(
+        hex::encode(domain_separate(&key, DOMAIN_AUTH).0),
+        "443cb8ff609a75d95361b18787ca71255089d6b00d7140620ea30df650d1b133"
+    );
+}
+
+#[test]
+fn encrypt_and_auth_subkeys_differ() {
+    for key in keys() {
+        assert!(
+            domain_separate(&key, DOMAIN_ENCRYPT) != domain_separate(&key, DOMAIN_AUTH),
+            "key {}",
+            hex::encode(key.0)
+        );
+    }
+}
+
+#[test]
+fn every_domain_gives_its_own_subkey() {
+    for key in keys() {
+        let subkeys: Vec<Key> = [
+            DOMAIN_ENCRYPT,
+            DOMAIN_DERIVE,
+            DOMAIN_AUTH,
+            b"",
+            b"encrypt\0",
+        ]
+        .iter()
+        .map(|domain| domain_separate(&key, domain))
+        .collect();
+        for (i, subkey) in subkeys.iter().enumerate() {
+            assert!(*subkey != key, "subkey {i} is the key");
+            assert!(
+                subkeys[i + 1..].iter().all(|other| other != subkey),
+                "subkey {i} repeats"
+            );
+        }
+    }
+}
+
+#[test]
+fn subkeys_are_deterministic_and_depend_on_the_key() {
+    let keys = keys();
+    for key in &keys {
+        assert!(domain_separate(key, DOMAIN_ENCRYPT) == domain_separate(key, DOMAIN_ENCRYPT));
+    }
+    assert!(domain_separate(&keys[0], DOMAIN_ENCRYPT) != domain_separate(&keys[1], DOMAIN_ENCRYPT));
+}
@@ -0,0 +1,38 @@
+//! Empty and single byte plaintexts, the edge cases of everything the encryption program runs.
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::compress::{compress, decompress};
+use chacha_lib::selective::segments_root;
+use chacha_lib::{chacha, CompressionType};
+
+/// SHA256 of the empty string.
+const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+/// First keystream byte of an all zero key and nonce, RFC 8439 section A.1 test vector #1.
+const ZERO_KEYSTREAM_BYTE: u8 = 0x76;
+
+#[test]
+fn empty_buffer() {
+    let mut buffer = Vec::new();
+    chacha(&[0; 32], &[0; 12], &mut buffer);
+    assert!(buffer.is_empty());
+    assert_eq!(hex::encode(Sha256::digest(&buffer)), EMPTY_SHA256);
+    // No segments to commit to
+    segments_root(&buffer);
+    for compression in [CompressionType::None, CompressionType::Lz4] {
+        let compressed = compress(compression, &buffer);
+        assert_eq!(decompress(compression, &compressed).unwrap(), buffer);
+    }
+}
+
+#[test]
+fn single_byte() {
+    for byte in [0x00, 0xFF] {
+        let mut buffer = vec![byte];
+        chacha(&[0; 32], &[0; 12], &mut buffer);
+        assert_eq!(buffer, [byte ^ ZERO_KEYSTREAM_BYTE]);
+        chacha(&[0; 32], &[0; 12], &mut buffer);
+        assert_eq!(buffer, [byte]);
+    }
+}
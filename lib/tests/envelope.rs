@@ -0,0 +1,91 @@
+//! Signing and serializing proof envelopes.
+
+use chacha_lib::envelope::ed25519_dalek::SigningKey;
+use chacha_lib::envelope::{ProofEnvelope, SignedEnvelope, ENVELOPE_VERSION};
+use chacha_lib::ProofType;
+
+fn envelope() -> ProofEnvelope {
+    let mut envelope = ProofEnvelope::new(ProofType::Groth16, [1; 32], vec![2; 40], vec![3, 4, 5]);
+    envelope
+        .extensions
+        .insert("chain_id".to_string(), 11155111u64.to_be_bytes().to_vec());
+    envelope
+        .extensions
+        .insert("expiry".to_string(), vec![0xff; 8]);
+    envelope
+}
+
+#[test]
+fn json_round_trips() {
+    let envelope = envelope();
+    assert_eq!(envelope.version, ENVELOPE_VERSION);
+    let json = envelope.to_json();
+    assert!(json.contains(r#""proof_bytes":"030405""#), "{json}");
+    assert!(json.contains(r#""expiry":"ffffffffffffffff""#), "{json}");
+    assert_eq!(ProofEnvelope::from_json(&json).unwrap(), envelope);
+}
+
+#[test]
+fn extensions_are_optional() {
+    let mut envelope = envelope();
+    envelope.extensions.clear();
+    let json = envelope.to_json().replace(r#","extensions":{}"#, "");
+    assert!(!json.contains("extensions"), "{json}");
+    assert_eq!(ProofEnvelope::from_json(&json).unwrap(), envelope);
+}
+
+#[test]
+fn malformed_json_is_rejected() {
+    let json = envelope().to_json();
+    assert!(ProofEnvelope::from_json(&json.replace("030405", "0304g5")).is_err());
+    assert!(ProofEnvelope::from_json(&json.replace(&"01".repeat(32), "01")).is_err());
+    assert!(ProofEnvelope::from_json("{}").is_err());
+}
+
+#[test]
+fn signature_verifies() {
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    let signed = envelope().sign(&signing_key);
+    assert_eq!(signed.public_key, signing_key.verifying_key().to_bytes());
+    signed.verify_signature().unwrap();
+
+    // Also after a JSON round trip
+    let json = serde_json::to_string(&signed).unwrap();
+    let parsed: SignedEnvelope = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, signed);
+    parsed.verify_signature().unwrap();
+}
+
+#[test]
+fn tampering_breaks_the_signature() {
+    let signed = envelope().sign(&SigningKey::from_bytes(&[7; 32]));
+
+    let mut tampered = signed.clone();
+    tampered.envelope.proof_bytes[0] ^= 1;
+    assert!(tampered.verify_signature().is_err());
+
+    let mut tampered = signed.clone();
+    tampered.envelope.extensions.remove("expiry");
+    assert!(tampered.verify_signature().is_err());
+
+    let mut tampered = signed.clone();
+    tampered.signature[0] ^= 1;
+    assert!(tampered.verify_signature().is_err());
+
+    // A valid signature, but by another key than the one claimed
+    let mut other = signed.clone();
+    other.public_key = SigningKey::from_bytes(&[8; 32]).verifying_key().to_bytes();
+    assert!(other.verify_signature().is_err());
+}
+
+#[test]
+fn v1_proofs_upgrade() {
+    let envelope = ProofEnvelope::from_v1(ProofType::Plonk, [9; 32], &[1, 2], &[3]);
+    assert_eq!(envelope.version, ENVELOPE_VERSION);
+    assert_eq!(envelope.proof_type, ProofType::Plonk);
+    assert_eq!(envelope.public_values, [1, 2]);
+    assert_eq!(envelope.proof_bytes, [3]);
+    assert!(envelope.extensions.is_empty());
+    // Too short for the public values layout
+    assert!(envelope.public_values().is_none());
+}
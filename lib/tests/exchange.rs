@@ -0,0 +1,148 @@
+//! X25519 key exchange and public key validation.
+
+use chacha_lib::exchange::{public_key, recipient_key, sender_key, validate_x25519_public_key};
+use chacha_lib::{chacha, KeyError};
+
+/// The u-coordinates of the 8 points of small order on Curve25519 are 0, 1 and the two of order
+/// 8 below. Rejected besides them are p - 1, of order 2 on the twist, and p and p + 1, the
+/// non-canonical encodings of 0 and 1, as in libsodium.
+const LOW_ORDER_POINTS: [&str; 6] = [
+    "0100000000000000000000000000000000000000000000000000000000000000",
+    "e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800",
+    "5f9c95bca3508c24b1d0b1559c83ef5b04445cc4581c8e86d8224eddd09f1157",
+    "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+];
+
+fn decode(hex: &str) -> [u8; 32] {
+    hex::decode(hex).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn low_order_points_are_rejected() {
+    for point in LOW_ORDER_POINTS {
+        let mut pk = decode(point);
+        assert_eq!(
+            validate_x25519_public_key(&pk),
+            Err(KeyError::WeakPublicKey),
+            "{point}"
+        );
+        // X25519 ignores the most significant bit
+        pk[31] |= 0x80;
+        assert_eq!(
+            validate_x25519_public_key(&pk),
+            Err(KeyError::WeakPublicKey),
+            "{point} with bit 255 set"
+        );
+        assert!(sender_key(&[7; 32], &pk).is_none(), "{point}");
+        assert!(recipient_key(&[7; 32], &pk).is_none(), "{point}");
+    }
+}
+
+#[test]
+fn point_at_infinity_is_rejected() {
+    assert_eq!(
+        validate_x25519_public_key(&[0; 32]),
+        Err(KeyError::PointAtInfinity)
+    );
+    let mut pk = [0; 32];
+    pk[31] = 0x80;
+    assert_eq!(
+        validate_x25519_public_key(&pk),
+        Err(KeyError::PointAtInfinity)
+    );
+    assert!(sender_key(&[7; 32], &[0; 32]).is_none());
+    assert_eq!(
+        KeyError::PointAtInfinity.to_string(),
+        "X25519 public key is the point at infinity"
+    );
+}
+
+#[test]
+fn low_order_points_give_a_known_shared_secret() {
+    // Why they are rejected: every secret key multiplies them to the point at infinity
+    for point in LOW_ORDER_POINTS {
+        for secret in [[1u8; 32], [0xa5; 32]] {
+            assert_eq!(
+                x25519_dalek::x25519(secret, decode(point)),
+                [0; 32],
+                "{point}"
+            );
+        }
+    }
+}
+
+#[test]
+fn public_keys_are_accepted() {
+    let recipient_secret = [3u8; 32];
+    let recipient_public = public_key(&recipient_secret);
+    assert_eq!(validate_x25519_public_key(&recipient_public), Ok(()));
+    // The RFC 7748 section 6.1 test vector of Alice's public key
+    let alice = decode("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+    assert_eq!(validate_x25519_public_key(&alice), Ok(()));
+
+    let (ephemeral_public, key, nonce) = sender_key(&[5; 32], &recipient_public).unwrap();
+    let (recipient_key, recipient_nonce) =
+        recipient_key(&recipient_secret, &ephemeral_public).unwrap();
+    assert_eq!(key, recipient_key);
+    assert_eq!(nonce, recipient_nonce);
+}
+
+#[test]
+fn public_key_matches_rfc_7748() {
+    // Section 6.1, Alice's private and public key
+    let alice = decode("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    assert_eq!(
+        hex::encode(public_key(&alice)),
+        "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"
+    );
+}
+
+#[test]
+fn derived_key_matches_python_hkdf() {
+    // Python cryptography: HKDF(SHA256(), 44, salt=R || P, info=b"sp1-chacha/x25519") of the
+    // X25519 shared secret, with ephemeral secret [5; 32] and recipient secret [3; 32]
+    let (ephemeral_public, key, nonce) = sender_key(&[5; 32], &public_key(&[3; 32])).unwrap();
+    assert_eq!(
+        hex::encode(ephemeral_public),
+        "50a61409b1ddd0325e9b16b700e719e9772c07000b1bd7786e907c653d20495d"
+    );
+    assert_eq!(
+        hex::encode(key.0),
+        "5298af6d76e62198576e57cfc1a7f043424dcd71a8b59533e7ee7e1ae5d35cf9"
+    );
+    assert_eq!(hex::encode(nonce.0), "5322f5321c6d9f02bbc76b83");
+}
+
+#[test]
+fn sender_and_recipient_agree() {
+    for recipient in 1..=8u8 {
+        let recipient_secret = [recipient; 32];
+        for ephemeral in 10..=13u8 {
+            let (ephemeral_public, key, nonce) =
+                sender_key(&[ephemeral; 32], &public_key(&recipient_secret)).unwrap();
+            assert_eq!(ephemeral_public, public_key(&[ephemeral; 32]));
+
+            let mut message = *b"to the recipient's public key";
+            chacha(&key.0, &nonce.0, &mut message);
+            let (key, nonce) = recipient_key(&recipient_secret, &ephemeral_public).unwrap();
+            chacha(&key.0, &nonce.0, &mut message);
+            assert_eq!(&message, b"to the recipient's public key");
+        }
+    }
+}
+
+#[test]
+fn only_the_recipient_derives_the_key() {
+    let recipient_public = public_key(&[3; 32]);
+    let (ephemeral_public, key, nonce) = sender_key(&[5; 32], &recipient_public).unwrap();
+    let (other_key, other_nonce) = recipient_key(&[4; 32], &ephemeral_public).unwrap();
+    assert!(other_key != key);
+    assert!(other_nonce != nonce);
+
+    // A new ephemeral secret gives a new key and nonce for the same recipient
+    let (_, next_key, next_nonce) = sender_key(&[6; 32], &recipient_public).unwrap();
+    assert!(next_key != key);
+    assert!(next_nonce != nonce);
+}
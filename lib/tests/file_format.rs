@@ -0,0 +1,122 @@
+//! Random-access reads of the seekable file format against a full decryption.
+
+use chacha_lib::file_format::{
+    encrypt_file, seek_decrypt, FileFormatError, Header, FORMAT_VERSION, HEADER_LEN,
+};
+use chacha_lib::{chacha, chacha_at, Key, Nonce};
+
+const KEY: Key = Key([3; 32]);
+const NONCE: Nonce = Nonce([4; 12]);
+const SIZE: usize = 10_000;
+
+/// xorshift64, so failures reproduce.
+struct Offsets(u64);
+
+impl Offsets {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+fn plaintext() -> Vec<u8> {
+    (0..SIZE).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+#[test]
+fn reads_at_random_offsets_match_the_plaintext() {
+    let plaintext = plaintext();
+    let file = encrypt_file(&KEY, &NONCE, &plaintext);
+    let mut offsets = Offsets(0x9e3779b97f4a7c15);
+    for _ in 0..500 {
+        let offset = offsets.next(SIZE);
+        let len = offsets.next(300);
+        let end = (offset + len).min(SIZE);
+        assert_eq!(
+            seek_decrypt(&KEY, &file, offset as u64, len).unwrap(),
+            plaintext[offset..end],
+            "offset {offset}, len {len}"
+        );
+    }
+}
+
+#[test]
+fn reads_at_block_boundaries_and_the_end() {
+    let plaintext = plaintext();
+    let file = encrypt_file(&KEY, &NONCE, &plaintext);
+    for offset in [
+        0,
+        1,
+        63,
+        64,
+        65,
+        127,
+        128,
+        SIZE - 64,
+        SIZE - 1,
+        SIZE,
+        SIZE + 100,
+    ] {
+        for len in [0, 1, 63, 64, 65, 1000, usize::MAX] {
+            let start = offset.min(SIZE);
+            let end = start.saturating_add(len).min(SIZE);
+            assert_eq!(
+                seek_decrypt(&KEY, &file, offset as u64, len).unwrap(),
+                plaintext[start..end],
+                "offset {offset}, len {len}"
+            );
+        }
+    }
+    assert_eq!(seek_decrypt(&KEY, &file, 0, usize::MAX).unwrap(), plaintext);
+}
+
+#[test]
+fn chacha_at_continues_the_keystream() {
+    let mut keystream = vec![0; 64 * 64];
+    chacha(&KEY.0, &NONCE.0, &mut keystream);
+    let mut offsets = Offsets(42);
+    for _ in 0..100 {
+        let counter = offsets.next(64);
+        let len = offsets.next(64 * (64 - counter)) + 1;
+        let mut buffer = vec![0; len];
+        chacha_at(&KEY.0, &NONCE.0, counter as u32, &mut buffer);
+        let start = counter * 64;
+        assert_eq!(
+            buffer,
+            keystream[start..start + len],
+            "counter {counter}, len {len}"
+        );
+    }
+}
+
+#[test]
+fn header_round_trips() {
+    let plaintext = plaintext();
+    let file = encrypt_file(&KEY, &NONCE, &plaintext);
+    let header = Header::parse(&file).unwrap();
+    assert_eq!(header.version, FORMAT_VERSION);
+    assert_eq!(header.plaintext_size, SIZE as u64);
+    assert_eq!(header.nonce, NONCE);
+    assert_eq!(header.to_bytes(), file[..HEADER_LEN]);
+}
+
+#[test]
+fn malformed_files_are_rejected() {
+    let file = encrypt_file(&KEY, &NONCE, &plaintext());
+    assert_eq!(
+        seek_decrypt(&KEY, &file[..HEADER_LEN - 1], 0, 1),
+        Err(FileFormatError::TooShort)
+    );
+    assert_eq!(
+        seek_decrypt(&KEY, &file[..file.len() - 1], 0, 1),
+        Err(FileFormatError::SizeMismatch)
+    );
+    let mut future = file.clone();
+    future[0] = 2;
+    assert_eq!(
+        seek_decrypt(&KEY, &future, 0, 1),
+        Err(FileFormatError::UnsupportedVersion(2))
+    );
+}
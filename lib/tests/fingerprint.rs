@@ -0,0 +1,46 @@
+//! Key fingerprints.
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::{fingerprint_matches, Key};
+
+#[test]
+fn fingerprint_vectors() {
+    // BASE58 of the first 8 bytes of SHA256(key), computed independently
+    assert_eq!(Key([0; 32]).fingerprint(), "J8VFV81t2qU");
+    assert_eq!(Key([1; 32]).fingerprint(), "LCjD8QTpHRt");
+    let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+    assert_eq!(Key(key).fingerprint(), "HZwvnmqbcbw");
+    assert_eq!(
+        &Sha256::digest(key)[..8],
+        hex::decode("630dcd2966c43366").unwrap()
+    );
+}
+
+#[test]
+fn fingerprint_is_deterministic() {
+    let key = Key::random();
+    assert_eq!(key.fingerprint(), key.clone().fingerprint());
+    assert!(fingerprint_matches(&key, &key.fingerprint()));
+}
+
+#[test]
+fn fingerprints_are_unique() {
+    let fingerprints: std::collections::HashSet<_> =
+        (0..1000).map(|_| Key::random().fingerprint()).collect();
+    assert_eq!(fingerprints.len(), 1000);
+    assert!(!fingerprint_matches(
+        &Key([1; 32]),
+        &Key([2; 32]).fingerprint()
+    ));
+    assert!(!fingerprint_matches(&Key([1; 32]), ""));
+}
+
+#[test]
+fn fingerprint_uses_the_base58_alphabet() {
+    for _ in 0..100 {
+        let fingerprint = Key::random().fingerprint();
+        assert!(fingerprint.len() <= 11, "{fingerprint}");
+        assert!(!fingerprint.contains(['0', 'O', 'I', 'l']), "{fingerprint}");
+    }
+}
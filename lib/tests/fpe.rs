@@ -0,0 +1,95 @@
+//! Format-preserving encryption keeps every character in its alphabet.
+
+use std::collections::HashMap;
+
+use chacha_lib::fpe::{chacha_fpe_decrypt, chacha_fpe_encrypt};
+use chacha_lib::{Key, Nonce};
+
+const DIGITS: &str = "0123456789";
+const ALPHANUMERIC: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const KEY: Key = Key([1; 32]);
+const NONCE: Nonce = Nonce([2; 12]);
+
+#[test]
+fn numeric_alphabet() {
+    let card = "4111111111111111";
+    let ciphertext = chacha_fpe_encrypt(&KEY, &NONCE, card, DIGITS);
+    assert_eq!(ciphertext.len(), 16);
+    assert!(
+        ciphertext.chars().all(|c| c.is_ascii_digit()),
+        "{ciphertext}"
+    );
+    assert_ne!(ciphertext, card);
+    assert_eq!(chacha_fpe_decrypt(&KEY, &NONCE, &ciphertext, DIGITS), card);
+
+    // Separators are kept
+    let formatted = chacha_fpe_encrypt(&KEY, &NONCE, "4111-1111-1111-1111", DIGITS);
+    assert_eq!(formatted.replace('-', ""), ciphertext);
+    assert_eq!(
+        formatted
+            .match_indices('-')
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>(),
+        [4, 9, 14]
+    );
+
+    assert_ne!(
+        chacha_fpe_encrypt(&Key([3; 32]), &NONCE, card, DIGITS),
+        ciphertext
+    );
+    assert_ne!(
+        chacha_fpe_encrypt(&KEY, &Nonce([3; 12]), card, DIGITS),
+        ciphertext
+    );
+}
+
+#[test]
+fn alphanumeric_alphabet() {
+    let plaintext = "Order 42 shipped to Zurich";
+    let ciphertext = chacha_fpe_encrypt(&KEY, &NONCE, plaintext, ALPHANUMERIC);
+    assert_eq!(ciphertext.len(), plaintext.len());
+    for (p, c) in plaintext.chars().zip(ciphertext.chars()) {
+        assert_eq!(p == ' ', c == ' ', "{ciphertext}");
+        assert!(c == ' ' || c.is_ascii_alphanumeric(), "{ciphertext}");
+    }
+    assert_eq!(
+        chacha_fpe_decrypt(&KEY, &NONCE, &ciphertext, ALPHANUMERIC),
+        plaintext
+    );
+
+    // Non-ASCII alphabets work on characters, not bytes
+    let greek = "αβγδε";
+    let ciphertext = chacha_fpe_encrypt(&KEY, &NONCE, "αααα", greek);
+    assert_eq!(ciphertext.chars().count(), 4);
+    assert!(ciphertext.chars().all(|c| greek.contains(c)));
+    assert_eq!(chacha_fpe_decrypt(&KEY, &NONCE, &ciphertext, greek), "αααα");
+}
+
+#[test]
+fn offsets_are_unbiased() {
+    // 256 % 10 != 0, so plain `byte % 10` would favour 0 to 5
+    let plaintext = "0".repeat(100_000);
+    let ciphertext = chacha_fpe_encrypt(&KEY, &NONCE, &plaintext, DIGITS);
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in ciphertext.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    assert_eq!(counts.len(), 10);
+    for (digit, count) in counts {
+        // Expected 10000, the standard deviation is 95
+        assert!((9_600..=10_400).contains(&count), "{digit}: {count}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "alphabet has repeated characters")]
+fn repeated_alphabet_characters_panic() {
+    chacha_fpe_encrypt(&KEY, &NONCE, "abc", "abca");
+}
+
+#[test]
+#[should_panic(expected = "alphabet of 1 characters")]
+fn single_character_alphabet_panics() {
+    chacha_fpe_encrypt(&KEY, &NONCE, "aaa", "a");
+}
@@ -0,0 +1,132 @@
+//! HMAC-SHA256 authentication of the encryption program inputs.
+
+use chacha_lib::input_auth::{
+    hmac_key_hash, input_hmac, input_hmac_v6, verify_input_hmac, AuthenticatedInputs,
+};
+use chacha_lib::{CompressionType, Key, Nonce};
+
+const HMAC_KEY: [u8; 32] = [9; 32];
+const BUFFER: &[u8] = b"attack at dawn";
+static KEY: Key = Key([1; 32]);
+static NONCE: Nonce = Nonce([2; 12]);
+
+fn inputs() -> AuthenticatedInputs<'static> {
+    AuthenticatedInputs {
+        key: &KEY,
+        nonce: &NONCE,
+        compression: CompressionType::None,
+        commit_segments: false,
+        commit_ciphertext_hash: false,
+        timestamp: None,
+        custody: None,
+        lineage: None,
+        buffer: BUFFER,
+    }
+}
+
+#[test]
+fn tag_is_hmac_sha256_of_the_encoded_inputs() {
+    // Python: hmac.new(bytes([9] * 32), bytes([1] * 32) + bytes([2] * 12) + bytes(6)
+    // + (14).to_bytes(8, "little") + b"attack at dawn", hashlib.sha256)
+    let tag = input_hmac(&HMAC_KEY, &inputs());
+    assert_eq!(
+        hex::encode(tag),
+        "515f1563f7f87f9f6e36ff28b235d89c18c33d9650822bf6056342f560df8e11"
+    );
+    assert!(verify_input_hmac(&HMAC_KEY, &inputs(), &tag));
+}
+
+#[test]
+fn v6_tag_is_hmac_sha256_of_key_nonce_and_buffer() {
+    // Python: hmac.new(bytes([9] * 32), bytes([1] * 32) + bytes([2] * 12) + b"attack at dawn",
+    // hashlib.sha256)
+    assert_eq!(
+        hex::encode(input_hmac_v6(&HMAC_KEY, &KEY, &NONCE, BUFFER)),
+        "afa8a23a1e8b199d8fd1a6fff5625d2bd241b3dbaf9e2ad01c0abce9440df9e2"
+    );
+}
+
+#[test]
+fn changed_inputs_fail() {
+    let tag = input_hmac(&HMAC_KEY, &inputs());
+    let other_key = Key([3; 32]);
+    let other_nonce = Nonce([3; 12]);
+
+    assert!(!verify_input_hmac(&[8; 32], &inputs(), &tag));
+    let changed = [
+        AuthenticatedInputs {
+            key: &other_key,
+            ..inputs()
+        },
+        AuthenticatedInputs {
+            nonce: &other_nonce,
+            ..inputs()
+        },
+        AuthenticatedInputs {
+            compression: CompressionType::Lz4,
+            ..inputs()
+        },
+        AuthenticatedInputs {
+            commit_segments: true,
+            ..inputs()
+        },
+        AuthenticatedInputs {
+            commit_ciphertext_hash: true,
+            ..inputs()
+        },
+        AuthenticatedInputs {
+            timestamp: Some(b""),
+            ..inputs()
+        },
+        AuthenticatedInputs {
+            custody: Some(([0; 16], 0)),
+            ..inputs()
+        },
+        AuthenticatedInputs {
+            lineage: Some([0x11; 32]),
+            ..inputs()
+        },
+        AuthenticatedInputs {
+            buffer: b"attack at dusk",
+            ..inputs()
+        },
+    ];
+    for inputs in &changed {
+        assert!(!verify_input_hmac(&HMAC_KEY, inputs, &tag));
+    }
+
+    let mut tampered = tag;
+    tampered[31] ^= 1;
+    assert!(!verify_input_hmac(&HMAC_KEY, &inputs(), &tampered));
+}
+
+#[test]
+fn bytes_do_not_move_between_inputs() {
+    // A timestamp byte moved to the front of the buffer keeps the concatenation the same
+    let tag = input_hmac(
+        &HMAC_KEY,
+        &AuthenticatedInputs {
+            timestamp: Some(b"a"),
+            buffer: b"ttack at dawn",
+            ..inputs()
+        },
+    );
+    assert!(!verify_input_hmac(
+        &HMAC_KEY,
+        &AuthenticatedInputs {
+            timestamp: Some(b""),
+            buffer: BUFFER,
+            ..inputs()
+        },
+        &tag
+    ));
+}
+
+#[test]
+fn committed_key_hash_is_sha256_of_the_key() {
+    // Python: hashlib.sha256(bytes([9] * 32))
+    assert_eq!(
+        hex::encode(hmac_key_hash(&HMAC_KEY)),
+        "8c0cc17a04942cc4f8e0fe0b302606d3108860c126428ba2ceeb5f9ed41c2b05"
+    );
+}
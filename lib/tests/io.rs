@@ -0,0 +1,95 @@
+//! Streaming encryption through files against the buffered API.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+
+use chacha_lib::io::{ChaChaReader, ChaChaWriter};
+use chacha_lib::{chacha, Key, Nonce};
+
+const KEY: Key = Key([0x42; 32]);
+const NONCE: Nonce = Nonce([7; 12]);
+const SIZE: usize = 1024 * 1024;
+
+fn temp_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("chacha-io-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// A ChaCha20 keystream under an unrelated key, as random file contents.
+fn random(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    chacha(&[9; 32], &[1; 12], &mut bytes);
+    bytes
+}
+
+fn buffered(data: &[u8]) -> Vec<u8> {
+    let mut buffer = data.to_vec();
+    chacha(&KEY.0, &NONCE.0, &mut buffer);
+    buffer
+}
+
+#[test]
+fn one_megabyte_file_round_trips() {
+    let plaintext = random(SIZE);
+    let path = temp_file("round-trip");
+
+    let mut writer = ChaChaWriter::new(BufWriter::new(File::create(&path).unwrap()), &KEY, &NONCE);
+    // Uneven writes, so chunks straddle keystream blocks
+    for piece in plaintext.chunks(10_007) {
+        writer.write_all(piece).unwrap();
+    }
+    writer.flush().unwrap();
+    drop(writer.into_inner());
+
+    let ciphertext = std::fs::read(&path).unwrap();
+    assert_eq!(ciphertext.len(), SIZE);
+    assert!(
+        ciphertext == buffered(&plaintext),
+        "streaming ciphertext differs"
+    );
+
+    let mut reader = ChaChaReader::new(BufReader::new(File::open(&path).unwrap()), &KEY, &NONCE);
+    let mut decrypted = Vec::new();
+    reader.read_to_end(&mut decrypted).unwrap();
+    assert!(
+        decrypted == plaintext,
+        "decryption differs from the plaintext"
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn reader_matches_buffered_decryption() {
+    let ciphertext = buffered(&random(SIZE));
+    let mut reader = ChaChaReader::new(ciphertext.as_slice(), &KEY, &NONCE);
+    let mut streamed = Vec::new();
+    let mut buf = [0; 777];
+    loop {
+        let read = reader.read(&mut buf).unwrap();
+        if read == 0 {
+            break;
+        }
+        streamed.extend_from_slice(&buf[..read]);
+    }
+    assert!(
+        streamed == buffered(&ciphertext),
+        "streaming plaintext differs"
+    );
+}
+
+#[test]
+fn writer_matches_buffered_encryption_for_any_write_size() {
+    let plaintext = random(70_000);
+    for size in [1, 63, 64, 65, 4095, 4096, 4097, 70_000] {
+        let mut writer = ChaChaWriter::new(Vec::new(), &KEY, &NONCE);
+        for piece in plaintext.chunks(size) {
+            writer.write_all(piece).unwrap();
+        }
+        assert!(
+            writer.into_inner() == buffered(&plaintext),
+            "write size {size}"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Interoperability with libsodium's ChaCha20.
+//!
+//! The expected ciphertexts were produced with libsodium 1.0.18, encrypting `i % 256` for
+//! `i in 0..len` with the key `0x00..=0x1f` and the nonce `0x40..`. With the `libsodium-compat`
+//! feature and libsodium found by `pkg-config`, the ciphertexts are also compared live.
+//!
+//! Known differences between the APIs:
+//!
+//! - [`chacha`] is libsodium's `crypto_stream_chacha20_ietf_xor`: a 96 bit nonce and a 32 bit
+//!   block counter starting at 0. libsodium's `crypto_stream_chacha20_xor` is the original format
+//!   with a 64 bit nonce and a 64 bit counter, which is [`chacha_original`].
+//! - libsodium's `_xor_ic` variants take the initial block counter, like [`chacha_at`] and
+//!   [`chacha_original`].
+//! - libsodium produces keystream block `u32::MAX` of the IETF format, the `chacha20` crate panics
+//!   before reaching it.
+
+use chacha_lib::{chacha, chacha_original, key_from_hex_const};
+
+const KEY: [u8; 32] =
+    key_from_hex_const("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+const NONCE: [u8; 12] = [
+    0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4a, 0x4b,
+];
+const NONCE64: [u8; 8] = [0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47];
+
+/// `crypto_stream_chacha20_ietf_xor`, 32 bytes.
+const IETF_32: &str = "19519c54d4253da8a4f42cfe9abaaa0b011b3d5efc8d16da4ad1815edbc6d9dc";
+/// `crypto_stream_chacha20_ietf_xor`, 64 bytes.
+const IETF_64: &str = concat!(
+    "19519c54d4253da8a4f42cfe9abaaa0b011b3d5efc8d16da4ad1815edbc6d9dc58836eb0eecd6af89bc42e0d17efc629",
+    "6beadda74c26c3ab4fb70c6cd4c9dfd8",
+);
+/// `crypto_stream_chacha20_ietf_xor`, 128 bytes.
+const IETF_128: &str = concat!(
+    "19519c54d4253da8a4f42cfe9abaaa0b011b3d5efc8d16da4ad1815edbc6d9dc58836eb0eecd6af89bc42e0d17efc629",
+    "6beadda74c26c3ab4fb70c6cd4c9dfd8b8153ec2360fa8461086bc4bf7a22382e49d314b10a91738a6558800b67a6435",
+    "90d2fb13422e04916206452c063980f191221eba8a03fb58a3bb754074164d1e",
+);
+/// `crypto_stream_chacha20_ietf_xor`, 1024 bytes.
+const IETF_1024: &str = concat!(
+    "19519c54d4253da8a4f42cfe9abaaa0b011b3d5efc8d16da4ad1815edbc6d9dc58836eb0eecd6af89bc42e0d17efc629",
+    "6beadda74c26c3ab4fb70c6cd4c9dfd8b8153ec2360fa8461086bc4bf7a22382e49d314b10a91738a6558800b67a6435",
+    "90d2fb13422e04916206452c063980f191221eba8a03fb58a3bb754074164d1e4bb1abded79661acca87e7024fa3abe5",
+    "4dca02bd682876f925e836b690c11ceb3e992cf98821b3d0aa723a6fa714c8161248074b602a29fa51a61fc0c8e1bab4",
+    "1ef5fa005078365b8171eceb77335e7d5bc80d540879d4708a47ec52f06312a4c58c9888b88d8b1a9b072ad48d076c91",
+    "cf386db9715b530ec2cd9ea8d90b371846745c9d82d5e605f6370563b7ab9ec2a25a4861fec79c62a0e236a8e9d799fe",
+    "95e0b0e0f1ffcedabb60d9c074aa06fd24a390854ea4d38ef8090427358965e04bfbf072353db8ad8444eb60696f967c",
+    "d0c374f83b5440dbd2b77c5af8cef62112aabd5aff420b9d830a1d59ad2e129a5c1fb3e5f94cc72b9cef46d25eb7dbe5",
+    "254b89cf01e2ee9a5774f1791df2488b3e61cb621bddf38e1463b7430e0373bc3a8ca5e954e6c7fab51d91344c71a78f",
+    "a76457c677f775da9393c1c0e1486284526060e53a800b395fd0013e6ca293519961648eebd22bea7e55fc0168cce33b",
+    "849d45286d0516e9b2836cceb9ca38a3b3db4d8f2186154df5a4e24a800802d4e31b452c2f6e4423599676a971b582b3",
+    "74291bd94a7e26b19472f5de8dd2337ddb08f6af00e4ac7939ac87d933d8fef18b6e5bf6769938dcdbf86189991c1050",
+    "4d2fffbd43e4f08a74ee40259c23ad8b93f12eaa9c7c0a78aa220b47a4a9ecb049860dcbbe52aacf45e709207492f9dd",
+    "513b2e0f80e384fd5a2405e2e5cc72fc883dda327f13b193ccfbd4307c68ceaaaeba7cbe69479ae0827caf80b6279cb0",
+    "aa8a04954c8a708c7b6b4c39c87e8c5a22d0155c420a48fa42d437b3ca928c49c6922fdc647835f2c4f9b7c280bd0789",
+    "6aac8bb35669d838b94ab697d34894f4db455f2ea846bb16a8aea3901bb47097bc939f7dc284887a2f5a588fe35d78fc",
+    "e4e6f2cccfcfcfe2c7b50b7b0bb91e81a3fe54f7421f200f756599fdc8ee69e4702cbbe202840ae0ad5f6600e9456753",
+    "5b1d67ca63b6e1c3488ae47180882f5485a34c7e675dda5524c5d7f428b4409d1f1d03899bf60432f6ebe0d137ffe0c9",
+    "ad89a3ace9adde0499906da7307ca903a4210661aad252bf96c3bd1b17f61df0384be0e7b86858e5de7f982c56aaf1d0",
+    "fbe763ceaa005cdf51d0231f703c6a94d53bb6fd791a6c37c533434d78e1b3dd61de517efac9b0867381f5493b060e3d",
+    "25b06b5421a023e873427536edc8a802e38ac7e01d7412473704ba9529b2b0583e1745bc2268532a3ae79533988a74df",
+    "f30ec500e2e07a2c51ef966756470ac7",
+);
+/// `crypto_stream_chacha20_xor`, 32 bytes.
+const ORIGINAL_32: &str = "db6544e75e520e6f3457a0722e1d7ee83149d8bb24cec13dba33bc8fd2a2ed47";
+/// `crypto_stream_chacha20_xor`, 64 bytes.
+const ORIGINAL_64: &str = concat!(
+    "db6544e75e520e6f3457a0722e1d7ee83149d8bb24cec13dba33bc8fd2a2ed47dfef89dee3d446e36517df97cc4077db",
+    "520c09644e2a6ae8cd3557b6f52ec349",
+);
+/// `crypto_stream_chacha20_xor`, 128 bytes.
+const ORIGINAL_128: &str = concat!(
+    "db6544e75e520e6f3457a0722e1d7ee83149d8bb24cec13dba33bc8fd2a2ed47dfef89dee3d446e36517df97cc4077db",
+    "520c09644e2a6ae8cd3557b6f52ec3492451a7dbece1425048bbc702e9f70724a075842990f5f77338f0f55b5a568f82",
+    "a744fe6770593fbdd90b8b7383d33bb6cd3ffa7371f01f2dff5ecac803617050",
+);
+/// `crypto_stream_chacha20_xor`, 1024 bytes.
+const ORIGINAL_1024: &str = concat!(
+    "db6544e75e520e6f3457a0722e1d7ee83149d8bb24cec13dba33bc8fd2a2ed47dfef89dee3d446e36517df97cc4077db",
+    "520c09644e2a6ae8cd3557b6f52ec3492451a7dbece1425048bbc702e9f70724a075842990f5f77338f0f55b5a568f82",
+    "a744fe6770593fbdd90b8b7383d33bb6cd3ffa7371f01f2dff5ecac803617050fdb09f84aaf7a9cf68d32e5eed55dd33",
+    "1d58cceba210b54087e16274df4f3dfe33f44fe4d69b7b81761c9216e0086d48ba23cc8be51e9ac37f727e5c6bef3355",
+    "615b8d4c06eb7187c45a8771cbffe9e6fa1f98d2151576da250d88613d3ee828158fc02ff6171e287b088241e0463ba5",
+    "895f7d4e2952d93e023d35cd3bc06598dac3cbdb6f2490c67489734cf4f2e9f5fb179619c004517de2ee788ee14d5d86",
+    "3a1d3eb8f9b4c569d50f71f025e60db3194e3fe61f1ff49340b2df9de2e6a47122cd4e218ea16e5cf475054415f98882",
+    "6561447ba0c01f616d4b6b19b651ed8fec6f0f3e12a94c08dbfe2f3a69ab972a39810df141953c5c62df823a8380032c",
+    "0199781b721e941dd8401c7ef46476cbd3155f3b01c43f138e47fcf475c3570416377cc7d0906ad7e1c4a04714aa5474",
+    "ffe4028cfb3f3a570d9fecc050ad83880fb3e6c5beb084ab1a3903d9781debbc3a9a5b357e872dc132a4dbdf59f7de58",
+    "bcbbc31d41cb3a6cfefbcd509c0436fe25988ea33588ce6b116a9f7819756c9d5d62adca0cb340cf2d07fc6e174536c5",
+    "fe24c4ddc81ddcdfb8ac0c887fca79be5a05b69e4dadb8ca1284473263e118ac64ead15bb0e4652d194a29422d7d2250",
+    "df8097beace06a96fa16dc04b9169604ba2f8c445f7886f9e16b568e7f6f56c540bb147cae71ca869283a1bc520dbfa2",
+    "2bf54d27e6ec44be25ee371bad1141c90b777b599fec1d1b967c1267c64cd02cbef4975da9be34566d8619c4ca5781ff",
+    "62d082be746e428d7499eb25ee7c296f689cc593ac72777b6886a1661abed22af3c09b33098d85a002fb87d9843ddfc6",
+    "a978fefebd9b1a4b1806c9ca07154075222bf0acc33c9e2bf8463eec7f21f16825af9322a1a08739cf31c17b7ce4c285",
+    "19f09c1290c39ff1035b145d82c54921c202d3d5a1a51874ed67a91e871747f4db0544d74e0a0ceec0782328262c543c",
+    "4db1741298d3c19cb85a82744deb977ed5639461cd81948bba7e26cd7b03475eaa7aaac04bc9c69374887658dda30492",
+    "4a42db920d0bc9314c03f4e8a7d48329134783d4cfae03454e187d80bd0606ad3529c70f68dbb10aa6021cccc0f66c65",
+    "c79b5c5147d37cb6657d50eb41110764f4cd628188347a51659e170d0042cf0cda3140b814599faf30b10e1a363b204d",
+    "9ff92768fce1acd71840c0950c34a545cbf39ab74f59836e078cd3c6041db1c7d6ad7ae07c002ffe7b1f889df3f50198",
+    "80e9943e9cb5ebf9a4c9300677368d97",
+);
+
+const IETF: [(usize, &str); 4] = [
+    (32, IETF_32),
+    (64, IETF_64),
+    (128, IETF_128),
+    (1024, IETF_1024),
+];
+const ORIGINAL: [(usize, &str); 4] = [
+    (32, ORIGINAL_32),
+    (64, ORIGINAL_64),
+    (128, ORIGINAL_128),
+    (1024, ORIGINAL_1024),
+];
+
+fn plaintext(len: usize) -> Vec<u8> {
+    (0..len).map(|i| i as u8).collect()
+}
+
+#[test]
+fn chacha_matches_libsodium_ietf() {
+    for (len, expected) in IETF {
+        let mut buffer = plaintext(len);
+        chacha(&KEY, &NONCE, &mut buffer);
+        assert_eq!(hex::encode(buffer), expected, "{len} bytes");
+    }
+}
+
+#[test]
+fn chacha_original_matches_libsodium() {
+    for (len, expected) in ORIGINAL {
+        let mut buffer = plaintext(len);
+        chacha_original(&KEY, &NONCE64, 0, &mut buffer);
+        assert_eq!(hex::encode(buffer), expected, "{len} bytes");
+    }
+}
+
+/// Live comparison against the installed libsodium, see `build.rs`.
+#[cfg(libsodium)]
+mod live {
+    use super::*;
+
+    unsafe extern "C" {
+        fn sodium_init() -> i32;
+        fn crypto_stream_chacha20_ietf_xor_ic(
+            c: *mut u8,
+            m: *const u8,
+            mlen: u64,
+            n: *const u8,
+            ic: u32,
+            k: *const u8,
+        ) -> i32;
+        fn crypto_stream_chacha20_xor_ic(
+            c: *mut u8,
+            m: *const u8,
+            mlen: u64,
+            n: *const u8,
+            ic: u64,
+            k: *const u8,
+        ) -> i32;
+    }
+
+    #[test]
+    fn matches_installed_libsodium() {
+        assert!(unsafe { sodium_init() } >= 0);
+        for len in [0, 1, 63, 64, 65, 1000, 4096] {
+            for counter in [0u32, 1, 7, 0xffff_ff00] {
+                let message = plaintext(len);
+                let mut expected = vec![0u8; len];
+                // SAFETY: the buffers hold `len` bytes, the nonce 12 and the key 32.
+                let ret = unsafe {
+                    crypto_stream_chacha20_ietf_xor_ic(
+                        expected.as_mut_ptr(),
+                        message.as_ptr(),
+                        len as u64,
+                        NONCE.as_ptr(),
+                        counter,
+                        KEY.as_ptr(),
+                    )
+                };
+                assert_eq!(ret, 0);
+                let mut buffer = message.clone();
+                chacha_lib::chacha_at(&KEY, &NONCE, counter, &mut buffer);
+                assert_eq!(buffer, expected, "ietf, {len} bytes at block {counter}");
+
+                let counter64 = (u64::from(counter) << 16) | 3;
+                // SAFETY: as above, with an 8 byte nonce.
+                let ret = unsafe {
+                    crypto_stream_chacha20_xor_ic(
+                        expected.as_mut_ptr(),
+                        message.as_ptr(),
+                        len as u64,
+                        NONCE64.as_ptr(),
+                        counter64,
+                        KEY.as_ptr(),
+                    )
+                };
+                assert_eq!(ret, 0);
+                let mut buffer = message;
+                chacha_original(&KEY, &NONCE64, counter64, &mut buffer);
+                assert_eq!(
+                    buffer, expected,
+                    "original, {len} bytes at block {counter64}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Lineage commitments link each stage to the one before.
+
+use chacha_lib::lineage::GENESIS_LINEAGE_HASH;
+use chacha_lib::lineage_commitment;
+
+#[test]
+fn matches_sha256_of_both_hashes() {
+    // Python: hashlib.sha256(b"\xaa" * 32 + b"\xbb" * 32).hexdigest()
+    assert_eq!(
+        hex::encode(lineage_commitment(&[0xaa; 32], &[0xbb; 32])),
+        "e2d80f78d79027556d6619a1400605abbdca6bb6eb24e0831e33ecd5466fa5f6"
+    );
+}
+
+#[test]
+fn commitment_depends_on_the_whole_chain() {
+    let plaintext_hashes = [[1; 32], [2; 32], [3; 32]];
+    let chain = |hashes: &[[u8; 32]]| {
+        hashes.iter().fold(GENESIS_LINEAGE_HASH, |lineage, hash| {
+            lineage_commitment(&lineage, hash)
+        })
+    };
+    let last = chain(&plaintext_hashes);
+    assert_ne!(last, chain(&[[1; 32], [9; 32], [3; 32]]));
+    assert_ne!(last, chain(&[[2; 32], [1; 32], [3; 32]]));
+    assert_ne!(last, chain(&plaintext_hashes[1..]));
+}
@@ -0,0 +1,75 @@
+//! Encrypt-then-MAC with HKDF subkeys.
+//!
+//! The expected values were computed with Python's `hmac` and the `cryptography` package's
+//! ChaCha20.
+
+use chacha_lib::domain::{domain_separate, DOMAIN_ENCRYPT};
+use chacha_lib::mac::{chacha_with_hmac, open_with_hmac, DOMAIN_MAC};
+use chacha_lib::{chacha, Key, Nonce};
+
+const PLAINTEXT: &[u8] = b"Ladies and Gentlemen of the class of '99";
+
+fn key() -> Key {
+    Key(core::array::from_fn(|i| i as u8))
+}
+
+fn nonce() -> Nonce {
+    Nonce([0, 0, 0, 0, 0x4a, 0, 0, 0, 0, 0, 0, 0])
+}
+
+#[test]
+fn matches_vector() {
+    let (ciphertext, tag) = chacha_with_hmac(&key(), &nonce(), PLAINTEXT);
+    assert_eq!(
+        hex::encode(&ciphertext),
+        "b79a5ca73424ecc91659e93793864381fb8964adefa15705dd088f4f23df255787a975d0ca925d7e"
+    );
+    assert_eq!(
+        hex::encode(tag),
+        "c44fd04387807596ccd883b1ccc0f09d464a400e83417eba6cd521f755f412ba"
+    );
+    assert_eq!(
+        open_with_hmac(&key(), &nonce(), &ciphertext, &tag).unwrap(),
+        PLAINTEXT
+    );
+}
+
+#[test]
+fn subkeys_are_independent() {
+    let encrypt_key = domain_separate(&key(), DOMAIN_ENCRYPT);
+    assert_ne!(encrypt_key, key());
+    assert_ne!(encrypt_key, domain_separate(&key(), DOMAIN_MAC));
+
+    // The key itself never encrypts
+    let (ciphertext, _) = chacha_with_hmac(&key(), &nonce(), PLAINTEXT);
+    let mut direct = PLAINTEXT.to_vec();
+    chacha(&key().0, &nonce().0, &mut direct);
+    assert_ne!(ciphertext, direct);
+}
+
+#[test]
+fn tampering_invalidates_the_tag() {
+    let (ciphertext, tag) = chacha_with_hmac(&key(), &nonce(), PLAINTEXT);
+
+    for i in 0..ciphertext.len() {
+        let mut tampered = ciphertext.clone();
+        tampered[i] ^= 0x80;
+        assert!(
+            open_with_hmac(&key(), &nonce(), &tampered, &tag).is_none(),
+            "byte {i}"
+        );
+    }
+    assert!(open_with_hmac(&key(), &nonce(), &ciphertext[1..], &tag).is_none());
+
+    let mut other_nonce = nonce();
+    other_nonce.0[0] ^= 1;
+    assert!(open_with_hmac(&key(), &other_nonce, &ciphertext, &tag).is_none());
+
+    let mut bad_tag = tag;
+    bad_tag[31] ^= 1;
+    assert!(open_with_hmac(&key(), &nonce(), &ciphertext, &bad_tag).is_none());
+
+    let mut other_key = key();
+    other_key.0[0] ^= 1;
+    assert!(open_with_hmac(&other_key, &nonce(), &ciphertext, &tag).is_none());
+}
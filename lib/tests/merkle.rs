@@ -0,0 +1,91 @@
+//! Inclusion proofs of 8 leaf trees, which `SP1Chacha.verifyChunk` checks on-chain.
+
+use chacha_lib::merkle::{leaf_hash, merkle_prove, merkle_verify, MerkleProof, MerkleTree};
+use chacha_lib::selective::{SegmentPublicValues, SEGMENT_HEADER_LEN};
+
+fn leaves() -> Vec<[u8; 32]> {
+    (0..8)
+        .map(|i| leaf_hash(format!("segment {i}").as_bytes()))
+        .collect()
+}
+
+#[test]
+fn every_leaf_of_8_verifies() {
+    let leaves = leaves();
+    let root = MerkleTree::from_leaves(leaves.clone()).root();
+    // Python: leaf = sha256(b"\x00" + b"segment %d" % i), node = sha256(b"\x01" + left + right)
+    assert_eq!(
+        hex::encode(root),
+        "55a1a2cee6c5f7cebfd2afd3315ebd643abe51814dee90dafc92e84ade888136"
+    );
+
+    for (index, leaf) in leaves.iter().enumerate() {
+        let proof = merkle_prove(&leaves, index);
+        assert_eq!(proof.index, index);
+        assert_eq!(proof.path.len(), 3);
+        assert!(merkle_verify(&root, leaf, &proof), "leaf {index}");
+    }
+
+    let proof = merkle_prove(&leaves, 5);
+    assert_eq!(
+        proof.path.iter().map(hex::encode).collect::<Vec<_>>(),
+        [
+            "141db44e3d5d5a2c8bad02261c410cc5c263fb28454da025131fef20fd97711d",
+            "e2febd190f5e73d04ceed5421518a8efa7ce48b95dc0b30e909931b13bf3b89b",
+            "a4ec702e032356865a9f6114baeb74fffe4ffd313719e39e84ed2d47da54b702",
+        ]
+    );
+}
+
+#[test]
+fn proofs_are_bound_to_their_leaf_and_index() {
+    let leaves = leaves();
+    let root = MerkleTree::from_leaves(leaves.clone()).root();
+    let proof = merkle_prove(&leaves, 5);
+
+    assert!(!merkle_verify(&root, &leaves[4], &proof));
+    let moved = MerkleProof {
+        index: 4,
+        ..proof.clone()
+    };
+    assert!(!merkle_verify(&root, &leaves[5], &moved));
+    // An index past the tree would alias a leaf of it
+    let aliased = MerkleProof {
+        index: 5 + 8,
+        ..proof
+    };
+    assert!(!merkle_verify(&root, &leaves[5], &aliased));
+}
+
+#[test]
+#[should_panic(expected = "leaf 8 of 8")]
+fn proving_a_missing_leaf_panics() {
+    merkle_prove(&leaves(), 8);
+}
+
+#[test]
+fn parses_segment_public_values() {
+    let header = [[1; 32], [2; 32], [3; 32]].concat();
+    let values = SegmentPublicValues::parse(&header).unwrap();
+    assert_eq!(values.segments_root, [2; 32]);
+    assert_eq!(values.revealed, None);
+
+    let revealed = [
+        header.clone(),
+        7u32.to_le_bytes().to_vec(),
+        b"segment".to_vec(),
+    ]
+    .concat();
+    let values = SegmentPublicValues::parse(&revealed).unwrap();
+    assert_eq!(values.revealed, Some((7, &b"segment"[..])));
+
+    assert_eq!(SegmentPublicValues::parse(&header[1..]), None);
+    assert_eq!(
+        SegmentPublicValues::parse(&revealed[..SEGMENT_HEADER_LEN + 4]),
+        None
+    );
+    assert_eq!(
+        SegmentPublicValues::parse(&[revealed, vec![0; 32]].concat()),
+        None
+    );
+}
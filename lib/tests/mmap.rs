@@ -0,0 +1,82 @@
+//! Memory-mapped encryption of temp files against the buffered API.
+
+use std::path::PathBuf;
+
+use chacha_lib::mmap::{chacha_mmap_encrypt, MmapError};
+use chacha_lib::{chacha, Key, Nonce};
+
+const KEY: Key = Key([0x42; 32]);
+const NONCE: Nonce = Nonce([7; 12]);
+
+fn temp_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("chacha-mmap-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn buffered(data: &[u8]) -> Vec<u8> {
+    let mut buffer = data.to_vec();
+    chacha(&KEY.0, &NONCE.0, &mut buffer);
+    buffer
+}
+
+#[test]
+fn mapped_encryption_matches_buffered() {
+    // Not a multiple of the 4 KB chunks or of the keystream blocks
+    for len in [1, 64, 4096, 4097, 3 * 4096 + 100, 1024 * 1024 + 1] {
+        let (input, output) = (temp_file("in"), temp_file("out"));
+        let plaintext: Vec<u8> = (0..len).map(|i| (i * 7 % 256) as u8).collect();
+        std::fs::write(&input, &plaintext).unwrap();
+
+        assert_eq!(
+            chacha_mmap_encrypt(&KEY, &NONCE, &input, &output).unwrap(),
+            len as u64
+        );
+        let ciphertext = std::fs::read(&output).unwrap();
+        assert!(ciphertext == buffered(&plaintext), "len {len}");
+
+        // And back
+        assert_eq!(
+            chacha_mmap_encrypt(&KEY, &NONCE, &output, &input).unwrap(),
+            len as u64
+        );
+        assert!(std::fs::read(&input).unwrap() == plaintext, "len {len}");
+        std::fs::remove_file(&input).unwrap();
+        std::fs::remove_file(&output).unwrap();
+    }
+}
+
+#[test]
+fn longer_output_is_truncated() {
+    let (input, output) = (temp_file("short-in"), temp_file("long-out"));
+    std::fs::write(&input, b"short").unwrap();
+    std::fs::write(&output, vec![0xaa; 10_000]).unwrap();
+
+    chacha_mmap_encrypt(&KEY, &NONCE, &input, &output).unwrap();
+    assert_eq!(std::fs::read(&output).unwrap(), buffered(b"short"));
+    std::fs::remove_file(&input).unwrap();
+    std::fs::remove_file(&output).unwrap();
+}
+
+#[test]
+fn empty_file_gives_an_empty_output() {
+    let (input, output) = (temp_file("empty-in"), temp_file("empty-out"));
+    std::fs::write(&input, b"").unwrap();
+
+    assert_eq!(
+        chacha_mmap_encrypt(&KEY, &NONCE, &input, &output).unwrap(),
+        0
+    );
+    assert!(std::fs::read(&output).unwrap().is_empty());
+    std::fs::remove_file(&input).unwrap();
+    std::fs::remove_file(&output).unwrap();
+}
+
+#[test]
+fn missing_input_is_an_error() {
+    let (input, output) = (temp_file("missing-in"), temp_file("missing-out"));
+    assert!(matches!(
+        chacha_mmap_encrypt(&KEY, &NONCE, &input, &output),
+        Err(MmapError::Io(_))
+    ));
+}
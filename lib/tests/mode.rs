@@ -0,0 +1,80 @@
+//! OFB is the standard keystream, CFB feeds back the ciphertext.
+
+use chacha_lib::mode::{chacha_cfb_decrypt, chacha_cfb_encrypt, chacha_ofb_encrypt, BLOCK_LEN};
+use chacha_lib::{chacha, Key, Nonce};
+
+const KEY: Key = Key([1; 32]);
+const NONCE: Nonce = Nonce([2; 12]);
+
+fn plaintext(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7) as u8).collect()
+}
+
+#[test]
+fn ofb_matches_chacha() {
+    for len in [0, 1, BLOCK_LEN - 1, BLOCK_LEN, 3 * BLOCK_LEN + 5, 4096] {
+        let mut ofb = plaintext(len);
+        chacha_ofb_encrypt(&KEY, &NONCE, &mut ofb);
+        let mut ctr = plaintext(len);
+        chacha(&KEY.0, &NONCE.0, &mut ctr);
+        assert_eq!(ofb, ctr, "{len} bytes");
+    }
+}
+
+#[test]
+fn cfb_roundtrips_and_differs_after_the_first_block() {
+    for len in [0, 1, BLOCK_LEN, BLOCK_LEN + 1, 3 * BLOCK_LEN + 5] {
+        let mut buffer = plaintext(len);
+        chacha_cfb_encrypt(&KEY, &NONCE, &mut buffer);
+        chacha_cfb_decrypt(&KEY, &NONCE, &mut buffer);
+        assert_eq!(buffer, plaintext(len), "{len} bytes");
+    }
+
+    let mut cfb = plaintext(3 * BLOCK_LEN);
+    chacha_cfb_encrypt(&KEY, &NONCE, &mut cfb);
+    let mut ctr = plaintext(3 * BLOCK_LEN);
+    chacha(&KEY.0, &NONCE.0, &mut ctr);
+    assert_eq!(cfb[..BLOCK_LEN], ctr[..BLOCK_LEN]);
+    assert_ne!(cfb[BLOCK_LEN..2 * BLOCK_LEN], ctr[BLOCK_LEN..2 * BLOCK_LEN]);
+    assert_ne!(cfb[2 * BLOCK_LEN..], ctr[2 * BLOCK_LEN..]);
+
+    // Each block depends on the plaintext before it
+    let mut changed = plaintext(3 * BLOCK_LEN);
+    changed[0] ^= 1;
+    chacha_cfb_encrypt(&KEY, &NONCE, &mut changed);
+    assert_eq!(changed[0], cfb[0] ^ 1);
+    assert_eq!(changed[1..BLOCK_LEN], cfb[1..BLOCK_LEN]);
+    assert_ne!(
+        changed[BLOCK_LEN..2 * BLOCK_LEN],
+        cfb[BLOCK_LEN..2 * BLOCK_LEN]
+    );
+    assert_ne!(changed[2 * BLOCK_LEN..], cfb[2 * BLOCK_LEN..]);
+}
+
+#[test]
+fn corrupted_ciphertext_bits() {
+    let original = plaintext(3 * BLOCK_LEN);
+
+    // OFB: only the flipped bit
+    let mut ofb = original.clone();
+    chacha_ofb_encrypt(&KEY, &NONCE, &mut ofb);
+    ofb[10] ^= 0x80;
+    chacha_ofb_encrypt(&KEY, &NONCE, &mut ofb);
+    let flipped: Vec<_> = (0..ofb.len()).filter(|&i| ofb[i] != original[i]).collect();
+    assert_eq!(flipped, [10]);
+    assert_eq!(ofb[10], original[10] ^ 0x80);
+
+    // CFB: the flipped bit, then the whole next block, then recovers
+    let mut cfb = original.clone();
+    chacha_cfb_encrypt(&KEY, &NONCE, &mut cfb);
+    cfb[10] ^= 0x80;
+    chacha_cfb_decrypt(&KEY, &NONCE, &mut cfb);
+    assert_eq!(cfb[10], original[10] ^ 0x80);
+    assert_eq!(cfb[..10], original[..10]);
+    assert_eq!(cfb[11..BLOCK_LEN], original[11..BLOCK_LEN]);
+    let garbled = (BLOCK_LEN..2 * BLOCK_LEN)
+        .filter(|&i| cfb[i] != original[i])
+        .count();
+    assert!(garbled > BLOCK_LEN / 2, "{garbled} garbled bytes");
+    assert_eq!(cfb[2 * BLOCK_LEN..], original[2 * BLOCK_LEN..]);
+}
@@ -0,0 +1,78 @@
+//! `Noise_NN_25519_ChaChaPoly_SHA256` handshakes.
+//!
+//! The Noise specification has no test vector appendix. The inputs are those of the NN vector in
+//! the community `cacophony` test vectors, the expected outputs were computed with the `snow`
+//! 0.9.6 crate, an independent implementation.
+
+use chacha_lib::noise_compat::{noise_nn_handshake, CipherState, SymmetricState, NOISE_NN};
+
+fn unhex<const N: usize>(s: &str) -> [u8; N] {
+    hex::decode(s).unwrap().try_into().unwrap()
+}
+
+const INITIATOR_EPHEMERAL: &str =
+    "893e28b9dc6ca8d611ab664754b8ceb7bac5117349a4439a6b0569da977c464a";
+const RESPONDER_EPHEMERAL: &str =
+    "bbdb4cdbd309f1a1f2e1456967fe288cadd6f712d65dc7b7793d5e63da6b375b";
+
+#[test]
+fn nn_matches_snow() {
+    let mut handshake = noise_nn_handshake(
+        &unhex(INITIATOR_EPHEMERAL),
+        &unhex(RESPONDER_EPHEMERAL),
+        b"John Galt",
+        b"Ludwig von Mises",
+        b"Murray Rothbard",
+    );
+    assert_eq!(
+        hex::encode(&handshake.message1),
+        "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79444c756477696720766f6e204d69736573"
+    );
+    assert_eq!(
+        hex::encode(&handshake.message2),
+        "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f144808843a0ff96bdf86b579ef7dbf94e812a7470b903c20a85a87e3a1fe863264ae547"
+    );
+    assert_eq!(handshake.initiator_ephemeral, handshake.message1[..32]);
+    assert_eq!(handshake.responder_ephemeral, handshake.message2[..32]);
+    assert_eq!(
+        hex::encode(handshake.handshake_hash),
+        "9223fec1b892ec9d0dc2fb3bbeb261f170d1ea679f9c44ccf34aa131b4f5d97e"
+    );
+
+    let (to_responder, to_initiator) = &mut handshake.transport;
+    assert_eq!(
+        hex::encode(to_responder.encrypt_with_ad(&[], b"F. A. Hayek")),
+        "eb1a3e3d80c1792b1bb9cb0e1382f8d8322bfb1ca7c4c8517bb686"
+    );
+    assert_eq!(
+        hex::encode(to_initiator.encrypt_with_ad(&[], b"Carl Menger")),
+        "c781b198d2a974eb1da2c7d518c000cf6396de87ca540963c03713"
+    );
+}
+
+#[test]
+fn decrypt_rejects_tampering() {
+    let mut sender = SymmetricState::new(NOISE_NN);
+    sender.mix_key(b"input key material");
+    let mut receiver = sender.clone();
+
+    let mut ciphertext = sender.encrypt_and_hash(b"payload");
+    ciphertext[0] ^= 1;
+    let before = receiver.clone();
+    assert_eq!(receiver.decrypt_and_hash(&ciphertext), None);
+    assert_eq!(receiver, before);
+
+    ciphertext[0] ^= 1;
+    assert_eq!(
+        receiver.decrypt_and_hash(&ciphertext).as_deref(),
+        Some(&b"payload"[..])
+    );
+    assert_eq!(receiver, sender);
+}
+
+#[test]
+fn cipher_without_key_passes_through() {
+    let mut cipher = CipherState::default();
+    assert!(!cipher.has_key());
+    assert_eq!(cipher.encrypt_with_ad(b"ad", b"plaintext"), b"plaintext");
+}
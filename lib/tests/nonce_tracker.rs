@@ -0,0 +1,26 @@
+//! Uniqueness of [`tracked_nonce`] under concurrent calls.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use chacha_lib::nonce_tracker::tracked_nonce;
+
+#[test]
+fn concurrent_nonces_are_distinct() {
+    const THREADS: usize = 100;
+    let key_hash = [7u8; 32];
+    let barrier = Arc::new(Barrier::new(THREADS));
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                barrier.wait();
+                tracked_nonce(&key_hash)
+            })
+        })
+        .collect();
+    let nonces: HashSet<[u8; 12]> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(nonces.len(), THREADS);
+}
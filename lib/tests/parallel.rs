@@ -0,0 +1,37 @@
+//! The multi lane keystream matches `chacha` for every lane count and buffer length.
+
+use chacha_lib::parallel::{chacha_lanes, chacha_parallel};
+use chacha_lib::{chacha, Key, Nonce};
+
+/// RFC 8439 section A.1, test vector #1: the first keystream block for an all zero key and nonce.
+const ZERO_BLOCK: &str = "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586";
+
+#[test]
+fn rfc8439_zero_block() {
+    let mut buffer = [0u8; 64];
+    chacha_parallel(&Key([0; 32]), &Nonce([0; 12]), &mut buffer);
+    assert_eq!(hex::encode(buffer), ZERO_BLOCK);
+}
+
+#[test]
+fn matches_chacha() {
+    let (key, nonce) = (Key(core::array::from_fn(|i| i as u8)), Nonce([9; 12]));
+    for len in [0, 1, 63, 64, 65, 255, 256, 257, 511, 1000, 4096 + 3] {
+        let plaintext: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+        let mut expected = plaintext.clone();
+        chacha(&key.0, &nonce.0, &mut expected);
+
+        let mut one = plaintext.clone();
+        chacha_lanes::<1>(&key, &nonce, &mut one);
+        let mut four = plaintext.clone();
+        chacha_parallel(&key, &nonce, &mut four);
+        let mut eight = plaintext.clone();
+        chacha_lanes::<8>(&key, &nonce, &mut eight);
+        let mut three = plaintext.clone();
+        chacha_lanes::<3>(&key, &nonce, &mut three);
+        assert_eq!(one, expected, "{len}");
+        assert_eq!(four, expected, "{len}");
+        assert_eq!(eight, expected, "{len}");
+        assert_eq!(three, expected, "{len}");
+    }
+}
@@ -0,0 +1,35 @@
+//! Pedersen commitments hide the value and bind to it.
+
+use chacha_lib::pedersen::{pedersen_commit, pedersen_verify};
+
+#[test]
+fn hiding() {
+    let a = pedersen_commit(b"yes", &[1; 32]);
+    let b = pedersen_commit(b"yes", &[2; 32]);
+    assert_ne!(a, b);
+    assert!(pedersen_verify(&a, b"yes", &[1; 32]));
+    assert!(pedersen_verify(&b, b"yes", &[2; 32]));
+}
+
+#[test]
+fn binding() {
+    let commitment = pedersen_commit(b"yes", &[1; 32]);
+    assert_ne!(pedersen_commit(b"no", &[1; 32]), commitment);
+    assert!(!pedersen_verify(&commitment, b"no", &[1; 32]));
+    assert!(!pedersen_verify(&commitment, b"yes", &[2; 32]));
+    assert!(!pedersen_verify(&commitment, b"yes\0", &[1; 32]));
+}
+
+#[test]
+fn deterministic() {
+    assert_eq!(
+        pedersen_commit(b"", &[0; 32]),
+        pedersen_commit(b"", &[0; 32])
+    );
+    // Randomness is reduced modulo the group order `l`
+    let mut l = [0; 32];
+    l.copy_from_slice(
+        &hex::decode("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010").unwrap(),
+    );
+    assert_eq!(pedersen_commit(b"v", &l), pedersen_commit(b"v", &[0; 32]));
+}
@@ -0,0 +1,153 @@
+//! Policies grant access only to the attributes they name.
+
+use chacha_lib::policy::{evaluate, PolicyError};
+
+const ADMIN_BEFORE_EXPIRY: &str =
+    r#"{"and": [{"eq": ["role", "admin"]}, {"lt": ["time", 1767225600]}]}"#;
+
+#[test]
+fn passing_and_failing_policies() {
+    let attributes = r#"{"role": "admin", "time": 1700000000, "salt": "3f9c"}"#;
+    assert_eq!(evaluate(ADMIN_BEFORE_EXPIRY, attributes), Ok(true));
+    // Expired
+    assert_eq!(
+        evaluate(
+            ADMIN_BEFORE_EXPIRY,
+            r#"{"role": "admin", "time": 1767225600}"#
+        ),
+        Ok(false)
+    );
+    assert_eq!(
+        evaluate(ADMIN_BEFORE_EXPIRY, r#"{"role": "auditor", "time": 0}"#),
+        Ok(false)
+    );
+
+    let policy = r#"{"or": [
+        {"eq": ["role", "admin"]},
+        {"and": [{"eq": ["department", "finance"]}, {"not": {"eq": ["suspended", true]}}]}
+    ]}"#;
+    let finance = r#"{"role": "analyst", "department": "finance", "suspended": false}"#;
+    assert_eq!(evaluate(policy, finance), Ok(true));
+    let suspended = r#"{"role": "analyst", "department": "finance", "suspended": true}"#;
+    assert_eq!(evaluate(policy, suspended), Ok(false));
+    // `or` stops at the first operand that holds
+    assert_eq!(evaluate(policy, r#"{"role": "admin"}"#), Ok(true));
+}
+
+#[test]
+fn comparisons() {
+    let attributes = r#"{"level": 3, "name": "a\"b\\c"}"#;
+    for (operator, expected) in [
+        ("eq", [false, true, false]),
+        ("ne", [true, false, true]),
+        ("lt", [false, false, true]),
+        ("le", [false, true, true]),
+        ("gt", [true, false, false]),
+        ("ge", [true, true, false]),
+    ] {
+        for (literal, expected) in [2, 3, 4].into_iter().zip(expected) {
+            let policy = format!(r#"{{"{operator}": ["level", {literal}]}}"#);
+            assert_eq!(evaluate(&policy, attributes), Ok(expected), "{policy}");
+        }
+    }
+    assert_eq!(
+        evaluate(r#"{"eq": ["name", "a\"b\\c"]}"#, attributes),
+        Ok(true)
+    );
+    assert_eq!(evaluate(r#"{"and": []}"#, "{}"), Ok(true));
+    assert_eq!(evaluate(r#"{"or": []}"#, "{}"), Ok(false));
+}
+
+#[test]
+fn invalid_policies_are_errors() {
+    let attributes = r#"{"role": "admin", "time": 1700000000}"#;
+    for (policy, error) in [
+        (
+            r#"{"eq": ["group", "admin"]}"#,
+            PolicyError::MissingAttribute("group".into()),
+        ),
+        // A missing attribute cannot grant access through a `not`
+        (
+            r#"{"not": {"eq": ["suspended", true]}}"#,
+            PolicyError::MissingAttribute("suspended".into()),
+        ),
+        (
+            r#"{"eq": ["time", "1700000000"]}"#,
+            PolicyError::TypeMismatch("time".into()),
+        ),
+        (
+            r#"{"lt": ["role", "b"]}"#,
+            PolicyError::TypeMismatch("role".into()),
+        ),
+        (r#"{"xor": []}"#, PolicyError::InvalidExpression),
+        (
+            r#"{"eq": ["role", "admin"], "lt": ["time", 1]}"#,
+            PolicyError::InvalidExpression,
+        ),
+        (
+            r#"{"and": {"eq": ["role", "admin"]}}"#,
+            PolicyError::InvalidExpression,
+        ),
+        (r#"{"eq": ["role"]}"#, PolicyError::InvalidExpression),
+        (r#"["role", "admin"]"#, PolicyError::InvalidExpression),
+        // Only unsigned integers are compared
+        (
+            r#"{"eq": ["time", 1.5]}"#,
+            PolicyError::TypeMismatch("time".into()),
+        ),
+        (
+            r#"{"ge": ["time", -1]}"#,
+            PolicyError::TypeMismatch("time".into()),
+        ),
+        (
+            r#"{"eq": ["time", 18446744073709551616]}"#,
+            PolicyError::TypeMismatch("time".into()),
+        ),
+        (
+            r#"{"eq": ["role", "admin"]} x"#,
+            PolicyError::Syntax {
+                line: 1,
+                column: 27,
+            },
+        ),
+        (
+            r#"{"eq": ["role", "admin"]"#,
+            PolicyError::Syntax {
+                line: 1,
+                column: 24,
+            },
+        ),
+    ] {
+        assert_eq!(evaluate(policy, attributes), Err(error), "{policy}");
+    }
+
+    // Beyond the recursion limit of serde_json
+    let nested = "[".repeat(200) + &"]".repeat(200);
+    assert!(matches!(
+        evaluate(&nested, attributes),
+        Err(PolicyError::Syntax { .. })
+    ));
+}
+
+#[test]
+fn invalid_attributes_are_errors() {
+    assert_eq!(
+        evaluate(ADMIN_BEFORE_EXPIRY, r#"["admin"]"#),
+        Err(PolicyError::InvalidAttributes)
+    );
+    assert_eq!(
+        evaluate(ADMIN_BEFORE_EXPIRY, r#"{"role": 1"#),
+        Err(PolicyError::Syntax {
+            line: 1,
+            column: 10
+        })
+    );
+    // Parsers disagree on which of duplicate keys wins
+    assert_eq!(
+        evaluate(
+            ADMIN_BEFORE_EXPIRY,
+            r#"{"role": "guest", "role": "admin", "time": 0}"#
+        ),
+        Err(PolicyError::DuplicateKey("role".into()))
+    );
+}
@@ -0,0 +1,63 @@
+//! Re-keying a ciphertext and the public values of the re-keying program.
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::rekey::{rekey, Rekey, REKEY_LEN};
+use chacha_lib::{chacha, Key, Nonce};
+
+const OLD_KEY: Key = Key([1; 32]);
+const OLD_NONCE: Nonce = Nonce([2; 12]);
+const NEW_KEY: Key = Key([3; 32]);
+const NEW_NONCE: Nonce = Nonce([4; 12]);
+
+#[test]
+fn plaintext_hash_is_that_of_both_encryptions() {
+    let plaintext: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+    let mut old = plaintext.clone();
+    chacha(&OLD_KEY.0, &OLD_NONCE.0, &mut old);
+
+    let mut buffer = old.clone();
+    let rekeyed = rekey(&OLD_KEY, &OLD_NONCE, &NEW_KEY, &NEW_NONCE, &mut buffer);
+    assert_ne!(buffer, old);
+
+    // The new ciphertext is the plaintext encrypted under the new key
+    let mut new = plaintext.clone();
+    chacha(&NEW_KEY.0, &NEW_NONCE.0, &mut new);
+    assert_eq!(buffer, new);
+
+    assert_eq!(rekeyed.plaintext_hash[..], Sha256::digest(&plaintext)[..]);
+    assert_eq!(rekeyed.ciphertext_hash[..], Sha256::digest(&new)[..]);
+    assert_eq!(rekeyed.old_key_hash[..], Sha256::digest(OLD_KEY.0)[..]);
+    assert_eq!(rekeyed.new_key_hash[..], Sha256::digest(NEW_KEY.0)[..]);
+
+    // Re-keying back gives the old ciphertext and the same plaintext hash
+    let back = rekey(&NEW_KEY, &NEW_NONCE, &OLD_KEY, &OLD_NONCE, &mut buffer);
+    assert_eq!(buffer, old);
+    assert_eq!(back.plaintext_hash, rekeyed.plaintext_hash);
+    assert_eq!(back.ciphertext_hash[..], Sha256::digest(&old)[..]);
+}
+
+#[test]
+fn wrong_old_key_changes_the_plaintext_hash() {
+    let plaintext = b"stored record".to_vec();
+    let mut buffer = plaintext.clone();
+    chacha(&OLD_KEY.0, &OLD_NONCE.0, &mut buffer);
+    let rekeyed = rekey(&Key([9; 32]), &OLD_NONCE, &NEW_KEY, &NEW_NONCE, &mut buffer);
+    assert_ne!(rekeyed.plaintext_hash[..], Sha256::digest(&plaintext)[..]);
+}
+
+#[test]
+fn public_values_roundtrip() {
+    let rekeyed = Rekey {
+        old_key_hash: [1; 32],
+        new_key_hash: [2; 32],
+        plaintext_hash: [3; 32],
+        ciphertext_hash: [4; 32],
+    };
+    let bytes = rekeyed.to_bytes();
+    assert_eq!(bytes.len(), REKEY_LEN);
+    assert_eq!(bytes[96..], [4; 32]);
+    assert_eq!(Rekey::parse(&bytes), Some(rekeyed));
+    assert_eq!(Rekey::parse(&bytes[..REKEY_LEN - 1]), None);
+    assert_eq!(Rekey::parse(&[bytes.as_slice(), &[0]].concat()), None);
+}
@@ -0,0 +1,54 @@
+//! Compliance of [`chacha`] with RFC 8439, and of [`chacha_original`] with the original format.
+
+use chacha_lib::{chacha, chacha_at, chacha_original, NonceFormat};
+
+/// RFC 8439 section 2.4.2.
+const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+const SUNSCREEN_CIPHERTEXT: &str = "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d";
+
+/// RFC 8439 section A.1, test vector #1: the first keystream block for an all zero key and nonce.
+const ZERO_BLOCK: &str = "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586";
+
+fn sunscreen_key() -> [u8; 32] {
+    core::array::from_fn(|i| i as u8)
+}
+
+#[test]
+fn rfc8439_encryption() {
+    // The RFC starts at block 1, block 0 is reserved for the Poly1305 key
+    let nonce = hex::decode("000000000000004a00000000").unwrap();
+    let mut buffer = SUNSCREEN.to_vec();
+    chacha_at(&sunscreen_key(), &nonce.try_into().unwrap(), 1, &mut buffer);
+    assert_eq!(hex::encode(&buffer), SUNSCREEN_CIPHERTEXT);
+}
+
+#[test]
+fn rfc8439_zero_block() {
+    let mut buffer = [0u8; 64];
+    chacha(&[0; 32], &[0; 12], &mut buffer);
+    assert_eq!(hex::encode(buffer), ZERO_BLOCK);
+}
+
+#[test]
+fn original_format() {
+    assert_eq!(NonceFormat::default(), NonceFormat::Ietf);
+    assert_eq!(NonceFormat::Original.nonce_len(), 8);
+
+    // Both formats agree on an all zero state
+    let mut buffer = [0u8; 64];
+    chacha_original(&[0; 32], &[0; 8], 0, &mut buffer);
+    assert_eq!(hex::encode(buffer), ZERO_BLOCK);
+
+    // From libsodium 1.0.18 `crypto_stream_chacha20_xor_ic`, a counter above 2^32
+    let mut buffer = [0u8; 80];
+    chacha_original(
+        &sunscreen_key(),
+        &[0, 1, 2, 3, 4, 5, 6, 7],
+        0x1_0000_0001,
+        &mut buffer,
+    );
+    assert_eq!(
+        hex::encode(buffer),
+        "cbc048a2c82215c7942b999ba103f3831e882df26b12ff4897c0fa37670783be942f12b87c52c3fc5d03dc7a6b1860ad78024e1c7ee1b570ae413d1bb99537b814528bb4184522388f5400d2d0a4f67a"
+    );
+}
@@ -0,0 +1,35 @@
+//! Locked pages, checked against the `VmLck` line of `/proc/self/status`.
+#![cfg(all(target_os = "linux", feature = "secure-memory"))]
+
+use chacha_lib::Key;
+
+/// Locked memory of this process in kB.
+fn locked_kb() -> u64 {
+    let status = std::fs::read_to_string("/proc/self/status").unwrap();
+    let line = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmLck:"))
+        .expect("VmLck in /proc/self/status");
+    line.trim().trim_end_matches("kB").trim().parse().unwrap()
+}
+
+#[test]
+fn key_pages_are_locked_until_dropped() {
+    let before = locked_kb();
+    let first = Key([1; 32]).lock().expect("mlock, check `ulimit -l`");
+    let second = Key([2; 32]).lock().unwrap();
+    assert!(
+        locked_kb() >= before + 8,
+        "both keys lock a page of their own"
+    );
+
+    drop(first);
+    assert!(
+        locked_kb() >= before + 4,
+        "dropping one key keeps the other locked"
+    );
+    assert_eq!(second.as_bytes(), &[2; 32]);
+
+    drop(second);
+    assert_eq!(locked_kb(), before);
+}
@@ -0,0 +1,85 @@
+//! Which segments `segment_commit` opens for a set of byte ranges.
+
+use chacha_lib::selective::{segment_commit, segments_root, SEGMENT_SIZE};
+
+/// 8 segments, the last one 5 bytes.
+fn plaintext() -> Vec<u8> {
+    (0..SEGMENT_SIZE * 7 + 5).map(|i| i as u8).collect()
+}
+
+fn opened(plaintext: &[u8], ranges: &[(usize, usize)]) -> Vec<usize> {
+    let tree = segment_commit(plaintext, ranges);
+    assert_eq!(tree.root, segments_root(plaintext));
+    for proof in &tree.proofs {
+        assert!(proof.verify(&tree.root), "segment {}", proof.index);
+        let end = (proof.offset() + SEGMENT_SIZE).min(plaintext.len());
+        assert_eq!(proof.segment, plaintext[proof.offset()..end]);
+    }
+    tree.proofs.iter().map(|proof| proof.index).collect()
+}
+
+#[test]
+fn ranges_within_a_segment() {
+    let plaintext = plaintext();
+    assert_eq!(opened(&plaintext, &[(0, 1)]), [0]);
+    assert_eq!(opened(&plaintext, &[(0, SEGMENT_SIZE)]), [0]);
+    assert_eq!(opened(&plaintext, &[(SEGMENT_SIZE, SEGMENT_SIZE + 1)]), [1]);
+    assert_eq!(opened(&plaintext, &[(70, 90)]), [2]);
+}
+
+#[test]
+fn ranges_spanning_segments() {
+    let plaintext = plaintext();
+    assert_eq!(
+        opened(&plaintext, &[(SEGMENT_SIZE - 1, SEGMENT_SIZE + 1)]),
+        [0, 1]
+    );
+    assert_eq!(
+        opened(&plaintext, &[(10, 3 * SEGMENT_SIZE + 1)]),
+        [0, 1, 2, 3]
+    );
+    assert_eq!(
+        opened(&plaintext, &[(0, plaintext.len())]),
+        (0..8).collect::<Vec<_>>()
+    );
+    // Overlapping and unordered ranges open each segment once, in order
+    assert_eq!(
+        opened(&plaintext, &[(200, 210), (40, 70), (0, 33)]),
+        [0, 1, 2, 6]
+    );
+}
+
+#[test]
+fn empty_and_out_of_range_values_open_nothing() {
+    let plaintext = plaintext();
+    assert!(opened(&plaintext, &[]).is_empty());
+    assert!(opened(&plaintext, &[(5, 5), (40, 40)]).is_empty());
+    // Reversed
+    assert!(opened(&plaintext, &[(40, 10)]).is_empty());
+    assert!(opened(&plaintext, &[(plaintext.len(), plaintext.len() + 10)]).is_empty());
+    assert!(opened(&plaintext, &[(1000, usize::MAX)]).is_empty());
+    assert!(opened(&[], &[(0, 10)]).is_empty());
+}
+
+#[test]
+fn ranges_past_the_end_open_the_last_segment() {
+    let plaintext = plaintext();
+    let opened = opened(&plaintext, &[(plaintext.len() - 1, plaintext.len() + 100)]);
+    assert_eq!(opened, [7]);
+    let tree = segment_commit(&plaintext, &[(7 * SEGMENT_SIZE, usize::MAX)]);
+    assert_eq!(tree.proofs.len(), 1);
+    assert_eq!(tree.proofs[0].segment.len(), 5);
+}
+
+#[test]
+fn openings_do_not_verify_against_another_root() {
+    let plaintext = plaintext();
+    let tree = segment_commit(&plaintext, &[(0, 1)]);
+    let mut other = plaintext.clone();
+    other[100] ^= 1;
+    assert!(!tree.proofs[0].verify(&segments_root(&other)));
+
+    let mut proof = tree.proofs[0].clone();
+    proof.segment[0] ^= 1;
+    assert!(!proof.verify(&tree.root));
+}
@@ -0,0 +1,133 @@
+//! Shamir secret sharing of keys.
+
+use chacha_lib::shamir::{reconstruct_key, split_key, split_key_with, KeyShare, ShamirError};
+use chacha_lib::Key;
+
+const KEY: [u8; 32] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31,
+];
+
+/// The (3, 5) split of [`KEY`] with the coefficients `0xa5` of degree 1 and `7 * i` of degree 2 at
+/// byte `i`, computed with an independent implementation.
+const SHARES: [&str; 5] = [
+    "0301a5a3a9b3bd8389939593e9e3fdf3c9c3c5c3c9333d232913151309037d736963",
+    "0302514c6b0625d8ff92b9a45875162bcce19a87a060433e19f4dfc2a588704d2a07",
+    "0303f4eec0b69c5e7006243ebb9de7d50b2d4f557b406a0826f0d2c8b69011235d7b",
+    "0304a2d340ea7da132980776a4159968d667f3821139ae44d74bd4a52d9c4abb3382",
+    "03050771eb5ac427bd0c9aec47fd689611ab2650ca198772e84fd9af3e842bd544fe",
+];
+
+fn three_of_five() -> Vec<KeyShare> {
+    let mut degree = 0;
+    split_key_with(&Key(KEY), 5, 3, |coefficient| {
+        degree += 1;
+        for (i, byte) in coefficient.iter_mut().enumerate() {
+            *byte = if degree == 1 { 0xa5 } else { (i * 7) as u8 };
+        }
+    })
+}
+
+#[test]
+fn three_of_five_vector() {
+    let shares = three_of_five();
+    let encoded: Vec<_> = shares.iter().map(KeyShare::to_hex).collect();
+    assert_eq!(encoded, SHARES);
+    for (share, hex) in shares.iter().zip(SHARES) {
+        assert_eq!(&KeyShare::from_hex(hex).unwrap(), share);
+    }
+}
+
+#[test]
+fn any_three_of_five_reconstruct() {
+    let shares: Vec<_> = SHARES
+        .iter()
+        .map(|hex| KeyShare::from_hex(hex).unwrap())
+        .collect();
+    for a in 0..5 {
+        for b in a + 1..5 {
+            for c in b + 1..5 {
+                let subset = [shares[c].clone(), shares[a].clone(), shares[b].clone()];
+                assert_eq!(reconstruct_key(&subset), Ok(Key(KEY)), "{a} {b} {c}");
+            }
+        }
+    }
+    assert_eq!(reconstruct_key(&shares), Ok(Key(KEY)));
+}
+
+/// {57} • {83} = {c1} and {57} • {13} = {fe}, the multiplication examples of FIPS-197 section
+/// 4.2, through a (2, n) split of the zero key with the coefficient 0x57.
+#[test]
+fn field_matches_fips_197() {
+    let shares = split_key_with(&Key([0; 32]), 0x83, 2, |c| c.fill(0x57));
+    assert_eq!(shares[0x83 - 1].value, [0xc1; 32]);
+    assert_eq!(shares[0x13 - 1].value, [0xfe; 32]);
+}
+
+#[test]
+fn random_splits_reconstruct() {
+    let key = Key::random();
+    for (n, t) in [(1, 1), (2, 1), (5, 3), (5, 5), (255, 17)] {
+        let shares = split_key(&key, n, t);
+        assert_eq!(shares.len(), n as usize);
+        let start = (n - t) as usize;
+        assert_eq!(
+            reconstruct_key(&shares[start..]),
+            Ok(key.clone()),
+            "{n} {t}"
+        );
+    }
+}
+
+#[test]
+fn invalid_share_sets_are_rejected() {
+    let shares = three_of_five();
+    assert_eq!(reconstruct_key(&[]), Err(ShamirError::NoShares));
+    assert_eq!(
+        reconstruct_key(&shares[..2]),
+        Err(ShamirError::TooFewShares {
+            got: 2,
+            threshold: 3
+        })
+    );
+    assert_eq!(
+        reconstruct_key(&[shares[0].clone(), shares[1].clone(), shares[0].clone()]),
+        Err(ShamirError::DuplicateIndex(1))
+    );
+    let mut other = shares[2].clone();
+    other.threshold = 2;
+    assert_eq!(
+        reconstruct_key(&[shares[0].clone(), shares[1].clone(), other]),
+        Err(ShamirError::ThresholdMismatch)
+    );
+    let mut zero = shares[2].clone();
+    zero.index = 0;
+    assert_eq!(
+        reconstruct_key(&[shares[0].clone(), shares[1].clone(), zero]),
+        Err(ShamirError::InvalidShare)
+    );
+}
+
+#[test]
+fn share_encoding_is_checked() {
+    assert_eq!(
+        KeyShare::from_hex("0301"),
+        Err(ShamirError::InvalidEncoding)
+    );
+    assert_eq!(
+        KeyShare::from_hex(&format!("0300{}", "00".repeat(32))),
+        Err(ShamirError::InvalidShare)
+    );
+    // Share files end with a newline
+    assert!(KeyShare::from_hex(&format!("{}\n", SHARES[0])).is_ok());
+    assert_eq!(
+        format!("{:?}", KeyShare::from_hex(SHARES[0]).unwrap()),
+        "KeyShare(1/3, ..)"
+    );
+}
+
+#[test]
+#[should_panic(expected = "threshold must be between 1 and 5, got 6")]
+fn threshold_above_n_panics() {
+    split_key(&Key([1; 32]), 5, 6);
+}
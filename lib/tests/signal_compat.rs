@@ -0,0 +1,68 @@
+//! Sending chain steps of the Signal double ratchet.
+//!
+//! The Double Ratchet specification publishes no test vectors, so the expected values were
+//! computed from its `KDF_CK` and `ENCRYPT` recommendations with Python's `hmac` and the
+//! `cryptography` package's HKDF and ChaCha20-Poly1305.
+
+use chacha_lib::signal_compat::{kdf_ck, signal_decrypt, signal_encrypt};
+
+const CHAIN_KEY: &str = "a0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebf";
+
+/// `(plaintext, message key, ciphertext, next chain key)` of consecutive steps from [`CHAIN_KEY`].
+const STEPS: [(&str, &str, &str, &str); 3] = [
+    (
+        "",
+        "bef26ea3a0c75d3231e03f3a7b45065b74f7e393c9e06c94f518176514e24640",
+        "78ba709e470b2a2350af5c50da5dd9c9",
+        "1b3a7fbd4ff6ca9b98298bca6f7ea1007dc763968aa0862450438e935bc0e37a",
+    ),
+    (
+        "48656c6c6f2c20426f6221",
+        "d09b18ac2d18c0996dffe7781882c2a0a3521a06192a8d99a718b92b078bcd0b",
+        "117de8bf680a6351b055a8e76d52d04826990008bd8a4d3ddacaf6",
+        "404908d601228ef64f0583da21f1096fc0cfcf1345e383423bfe2a0b095c273c",
+    ),
+    (
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f60616263",
+        "ed0cf45148368a098adfa97ca5983e17f21bcad319c221beb93c353985d39b60",
+        "51186564a0c3bbac3cd4a0ce594b9960de5012bf33a419fe8966be709f05211f716cbf8a251fec6a42f763170b2e26e225c7705e09ab0427986aa96953c5209bda57c6a265fff0b01802252e62c9af6183e44f2960ad620dc78266190bec110826cd065d8e74389226ea83f683642b4a1f66033e",
+        "7e8de83be0b6b0d2f9c68d08a02cc2cc730d6fcc4db6ae44199d0482745d4de6",
+    ),
+];
+
+fn unhex<const N: usize>(s: &str) -> [u8; N] {
+    hex::decode(s).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn chain_matches_vectors() {
+    let mut chain_key: [u8; 32] = unhex(CHAIN_KEY);
+    for (i, (plaintext, message_key, ciphertext, next_chain_key)) in STEPS.into_iter().enumerate() {
+        assert_eq!(
+            kdf_ck(&chain_key),
+            (unhex(next_chain_key), unhex(message_key)),
+            "step {i}"
+        );
+
+        let plaintext = hex::decode(plaintext).unwrap();
+        let (encrypted, next) = signal_encrypt(&chain_key, &plaintext);
+        assert_eq!(hex::encode(&encrypted), ciphertext, "step {i}");
+        assert_eq!(next, unhex(next_chain_key));
+        assert_eq!(
+            signal_decrypt(&chain_key, &encrypted),
+            Some((plaintext, next))
+        );
+        chain_key = next;
+    }
+}
+
+#[test]
+fn decrypt_rejects_wrong_step() {
+    let chain_key: [u8; 32] = unhex(CHAIN_KEY);
+    let (ciphertext, next_chain_key) = signal_encrypt(&chain_key, b"Hello, Bob!");
+    assert_eq!(signal_decrypt(&next_chain_key, &ciphertext), None);
+
+    let mut tampered = ciphertext;
+    tampered[0] ^= 1;
+    assert_eq!(signal_decrypt(&chain_key, &tampered), None);
+}
@@ -0,0 +1,71 @@
+//! `chacha20-poly1305@openssh.com` packets.
+//!
+//! OpenSSH ships no known answer tests for this cipher, so the expected packets were computed
+//! from `PROTOCOL.chacha20poly1305` with libsodium 1.0.18 `crypto_stream_chacha20_xor_ic` and
+//! `crypto_onetimeauth_poly1305`. The 64 byte key is `00 01 .. 3f`, split into `K_2` (first half)
+//! and `K_1` (second half) as OpenSSH does.
+
+use chacha_lib::ssh_compat::{ssh_chacha_decrypt, ssh_chacha_encrypt, TAG_LEN};
+
+fn keys() -> ([u8; 32], [u8; 32]) {
+    let key: [u8; 64] = core::array::from_fn(|i| i as u8);
+    (key[32..].try_into().unwrap(), key[..32].try_into().unwrap())
+}
+
+/// `(sequence number, plaintext, packet)`
+const VECTORS: [(u32, &str, &str); 3] = [
+    // SSH_MSG_IGNORE with an empty string
+    (
+        0,
+        "0a020000000000000000000000000000",
+        "94450e4912ba4231ade6a6d113615c61af434e279cabfbfa43cb245414c67f77f1988028",
+    ),
+    // SSH_MSG_CHANNEL_DATA carrying "hello"
+    (
+        7,
+        "095e000000000000000568656c6c6f000000000000000000",
+        "a39afcb2211815434e832a5e6c68d395bbe3bc2c34b230367ee33d83761542abde2bd0fce576aa27734b3de5",
+    ),
+    (
+        u32::MAX,
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+        "b90ee4f009930e98aa01d2e59e3fa1b97e8b0ca106ab2b05db3acd6f4a3dfc5b32fa0824e07bfbaedf706b9fca6908d93a32dfb7b2e06fa15672096b2b36855059636685037ac3d9a94e1f02d13189816d5613b2",
+    ),
+];
+
+#[test]
+fn encrypt_matches_vectors() {
+    let (key1, key2) = keys();
+    for (sequence_number, plaintext, packet) in VECTORS {
+        let plaintext = hex::decode(plaintext).unwrap();
+        let encrypted = ssh_chacha_encrypt(&key1, &key2, sequence_number, &plaintext);
+        assert_eq!(encrypted.len(), 4 + plaintext.len() + TAG_LEN);
+        assert_eq!(hex::encode(&encrypted), packet, "seqnr {sequence_number}");
+        assert_eq!(
+            ssh_chacha_decrypt(&key1, &key2, sequence_number, &encrypted),
+            Some(plaintext)
+        );
+    }
+}
+
+#[test]
+fn decrypt_rejects_tampering() {
+    let (key1, key2) = keys();
+    let packet = ssh_chacha_encrypt(&key1, &key2, 3, b"payload");
+
+    assert_eq!(ssh_chacha_decrypt(&key1, &key2, 4, &packet), None);
+    assert_eq!(ssh_chacha_decrypt(&key2, &key1, 3, &packet), None);
+    assert_eq!(
+        ssh_chacha_decrypt(&key1, &key2, 3, &packet[..TAG_LEN]),
+        None
+    );
+    for i in [0, 4, packet.len() - 1] {
+        let mut tampered = packet.clone();
+        tampered[i] ^= 1;
+        assert_eq!(
+            ssh_chacha_decrypt(&key1, &key2, 3, &tampered),
+            None,
+            "byte {i}"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! Mock block headers and the time-lock public values.
+
+use chacha_lib::timelock::{BlockHeader, TimelockPublicValues, BLOCK_HEADER_LEN};
+
+const HEADER: BlockHeader = BlockHeader {
+    parent_hash: [1; 32],
+    number: 100,
+    timestamp: 1_700_000_000,
+    state_root: [2; 32],
+};
+
+#[test]
+fn header_roundtrips_and_hashes_its_encoding() {
+    let bytes = HEADER.to_bytes();
+    assert_eq!(bytes[32..40], 100u64.to_le_bytes());
+    assert_eq!(BlockHeader::from_bytes(&bytes), Some(HEADER));
+    assert_eq!(BlockHeader::from_bytes(&bytes[1..]), None);
+    assert_eq!(BlockHeader::from_bytes(&[0; BLOCK_HEADER_LEN + 1]), None);
+
+    // Python: hashlib.sha256(b"\x01" * 32 + struct.pack("<QQ", 100, 1700000000) + b"\x02" * 32)
+    assert_eq!(
+        hex::encode(HEADER.hash()),
+        "ebdd73b9420246700f5984c7b58b583d594beca9cd922e423258702927b5ffce"
+    );
+}
+
+#[test]
+fn unlocks_at_and_after_the_unlock_block() {
+    assert!(HEADER.unlocks(99));
+    assert!(HEADER.unlocks(100));
+    assert!(!HEADER.unlocks(101));
+}
+
+#[test]
+fn parses_public_values() {
+    let mut bytes = Vec::new();
+    bytes.extend(90u64.to_le_bytes());
+    bytes.extend(100u64.to_le_bytes());
+    bytes.extend([3; 32]);
+    bytes.extend([4; 32]);
+    bytes.extend([5; 32]);
+    assert_eq!(TimelockPublicValues::parse(&bytes[1..]), None);
+    bytes.extend(b"released");
+
+    let values = TimelockPublicValues::parse(&bytes).unwrap();
+    assert_eq!(values.unlock_block, 90);
+    assert_eq!(values.block_number, 100);
+    assert_eq!(values.block_hash, [3; 32]);
+    assert_eq!(values.key_hash, [4; 32]);
+    assert_eq!(values.ciphertext_hash, [5; 32]);
+    assert_eq!(values.plaintext, b"released");
+}
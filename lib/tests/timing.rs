@@ -0,0 +1,75 @@
+//! Statistical check that [`chacha`] runs in data independent time, see `src/security.md`.
+//!
+//! Encryptions of all zero and random buffers are timed in an interleaved order and compared with
+//! Welch's t-test, as in dudect. Outliers from preemption are cropped before the test.
+//!
+//! Wall-clock timings are noisy on shared machines, so the test is ignored by default and runs in
+//! its own CI job: `cargo test --release -p chacha-lib --test timing -- --ignored`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use chacha_lib::{chacha, chacha_keystream};
+
+const SAMPLES: usize = 1000;
+const BUFFER_LEN: usize = 4096;
+/// dudect treats |t| above 10 as a definite timing leak. Real leaks give values in the hundreds.
+const T_THRESHOLD: f64 = 10.0;
+/// A leak shows in every measurement, noise from other processes rarely twice in a row.
+const ATTEMPTS: usize = 3;
+
+/// Mean and variance of the fastest 90% of the samples.
+fn cropped_stats(mut samples: Vec<f64>) -> (f64, f64, f64) {
+    samples.sort_by(f64::total_cmp);
+    samples.truncate(samples.len() * 9 / 10);
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance, n)
+}
+
+fn welch_t(a: Vec<f64>, b: Vec<f64>) -> f64 {
+    let (mean_a, var_a, n_a) = cropped_stats(a);
+    let (mean_b, var_b, n_b) = cropped_stats(b);
+    (mean_a - mean_b) / (var_a / n_a + var_b / n_b).sqrt()
+}
+
+/// The t statistic of one interleaved measurement of both classes.
+fn measure() -> f64 {
+    let key = [0x42u8; 32];
+    let nonce = [0x24u8; 12];
+    let zeros = vec![0u8; BUFFER_LEN];
+    // Pseudo random contents, and a shuffled order of the two classes
+    let random = chacha_keystream(&[1; 32], &[1; 12], 0, BUFFER_LEN);
+    let mut order: Vec<usize> = (0..2 * SAMPLES).map(|i| i % 2).collect();
+    let shuffle = chacha_keystream(&[2; 32], &[2; 12], 0, 8 * order.len());
+    for i in (1..order.len()).rev() {
+        let r = u64::from_le_bytes(shuffle[8 * i..8 * i + 8].try_into().unwrap());
+        order.swap(i, (r % (i as u64 + 1)) as usize);
+    }
+
+    let mut times = [Vec::with_capacity(SAMPLES), Vec::with_capacity(SAMPLES)];
+    let mut buffer = vec![0u8; BUFFER_LEN];
+    for class in order {
+        buffer.copy_from_slice(if class == 0 { &zeros } else { &random });
+        let start = Instant::now();
+        chacha(&key, &nonce, black_box(&mut buffer));
+        times[class].push(start.elapsed().as_nanos() as f64);
+    }
+    let [zero_times, random_times] = times;
+    welch_t(zero_times, random_times)
+}
+
+#[test]
+#[ignore = "wall-clock timing, run alone with --release -- --ignored"]
+fn zeros_and_random_buffers_take_the_same_time() {
+    let mut ts = Vec::with_capacity(ATTEMPTS);
+    for _ in 0..ATTEMPTS {
+        let t = measure();
+        if t.abs() < T_THRESHOLD {
+            return;
+        }
+        ts.push(t);
+    }
+    panic!("timing depends on the data, t = {ts:.2?}");
+}
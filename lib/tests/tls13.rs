@@ -0,0 +1,71 @@
+//! TLS 1.3 `TLS_CHACHA20_POLY1305_SHA256` records.
+//!
+//! RFC 8448's example handshakes use AES-128-GCM only, so the expected records were computed with
+//! the RFC 8446 section 5.2 and 5.3 construction using Python's `cryptography` package (OpenSSL).
+
+use chacha_lib::tls13::{tls13_decrypt, tls13_encrypt, tls13_nonce, HANDSHAKE, TAG_LEN};
+
+const CLIENT_WRITE_KEY: &str = "101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f";
+const CLIENT_WRITE_IV: &str = "606162636465666768696a6b";
+
+fn unhex<const N: usize>(s: &str) -> [u8; N] {
+    hex::decode(s).unwrap().try_into().unwrap()
+}
+
+/// `(sequence number, content type, plaintext, nonce, record)`
+const VECTORS: [(u64, u8, &str, &str, &str); 3] = [
+    // Client Finished, an encrypted handshake record
+    (
+        0,
+        HANDSHAKE,
+        "14000020000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        "606162636465666768696a6b",
+        "1703030035d7a4c6e7781c1662b32cacd5d271c67c88436eeb3d33d8ec7bc7947a3a9cc09c526efe43ab592a0824616e748fed68d611862c974f",
+    ),
+    // Application data
+    (
+        1,
+        23,
+        "474554202f20485454502f312e310d0a0d0a",
+        "606162636465666768696a6a",
+        "1703030023f91a6a8e24728b8fad21f1ab1c5af8fedac17f00ca9df9d88b6472712ea237620dc736",
+    ),
+    // A close_notify alert
+    (
+        0x0102_0304_0506_0708,
+        21,
+        "0100",
+        "60616263656765636d6f6d63",
+        "170303001373e322b9691a8feadb51ac07ef1369cc822d02",
+    ),
+];
+
+#[test]
+fn records_match_vectors() {
+    let (key, iv) = (unhex(CLIENT_WRITE_KEY), unhex(CLIENT_WRITE_IV));
+    for (seq, content_type, plaintext, nonce, record) in VECTORS {
+        assert_eq!(hex::encode(tls13_nonce(&iv, seq)), nonce, "seq {seq}");
+
+        let plaintext = hex::decode(plaintext).unwrap();
+        let encrypted = tls13_encrypt(&key, &iv, seq, content_type, &plaintext);
+        assert_eq!(encrypted.len(), 5 + plaintext.len() + 1 + TAG_LEN);
+        assert_eq!(hex::encode(&encrypted), record, "seq {seq}");
+        assert_eq!(
+            tls13_decrypt(&key, &iv, seq, &encrypted),
+            Some((content_type, plaintext))
+        );
+    }
+}
+
+#[test]
+fn decrypt_rejects_tampering() {
+    let (key, iv) = (unhex(CLIENT_WRITE_KEY), unhex(CLIENT_WRITE_IV));
+    let record = tls13_encrypt(&key, &iv, 5, 23, b"application data");
+
+    assert_eq!(tls13_decrypt(&key, &iv, 6, &record), None);
+    for i in [0, 4, 5, record.len() - 1] {
+        let mut tampered = record.clone();
+        tampered[i] ^= 1;
+        assert_eq!(tls13_decrypt(&key, &iv, 5, &tampered), None, "byte {i}");
+    }
+}
@@ -0,0 +1,124 @@
+//! Upgrade public values and the checks of the upgrade program.
+
+use chacha_lib::upgrade::{check_upgrade, vkey_bytes32, Upgrade, UpgradeError, UPGRADE_LEN};
+use chacha_lib::PublicValues;
+
+/// Version 1 public values have no custody hash, version 1 and 2 no lineage commitment and
+/// version 1 to 3 no plaintext size, version 1 to 5 no ciphertext kind and version 1 to 6 no
+/// input HMAC key hash.
+fn public_values(version: u8, ciphertext: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![version];
+    bytes.extend([1; 32]); // plaintext hash
+    bytes.extend([2; 32]); // nullifier
+    bytes.extend([3; 32]); // key hash
+    bytes.extend([0; 64]); // segments root, timestamp commitment
+    if version > 1 {
+        bytes.extend([4; 32]);
+    }
+    if version > 2 {
+        bytes.extend([5; 32]);
+    }
+    if version > 3 {
+        bytes.extend(42u64.to_be_bytes());
+    }
+    if version > 5 {
+        bytes.push(0);
+    }
+    if version > 6 {
+        bytes.extend([6; 32]);
+    }
+    bytes.extend(ciphertext);
+    bytes
+}
+
+#[test]
+fn upgrade_roundtrips() {
+    let upgrade = Upgrade {
+        old_version: 1,
+        old_vkey: [0xaa; 32],
+        new_version: 2,
+        new_vkey: [0xbb; 32],
+    };
+    let bytes = upgrade.to_bytes();
+    assert_eq!(bytes.len(), UPGRADE_LEN);
+    assert_eq!(bytes[0], 1);
+    assert_eq!(bytes[33], 2);
+    assert_eq!(Upgrade::parse(&bytes), Some(upgrade));
+    assert_eq!(Upgrade::parse(&bytes[1..]), None);
+}
+
+#[test]
+fn vkey_packs_31_bit_words() {
+    let digest = [0x7fffffff, 1, 0x12345678, 3, 0x40000000, 5, 6, 0x7ffffffe];
+    assert_eq!(
+        hex::encode(vkey_bytes32(&digest)),
+        "00fffffffe0000000491a2b3c00000003800000000000001400000037ffffffe"
+    );
+    assert_eq!(vkey_bytes32(&[0; 8]), [0; 32]);
+}
+
+#[test]
+fn upgrade_needs_a_newer_version_encrypting_alike() {
+    let v1 = public_values(1, b"ciphertext");
+    let v2 = public_values(2, b"ciphertext");
+    let old = PublicValues::parse(&v1).unwrap();
+    let new = PublicValues::parse(&v2).unwrap();
+    assert_eq!(old.ciphertext, b"ciphertext");
+    assert_eq!(old.custody_hash, [0; 32]);
+    assert_eq!(new.lineage_commitment, [0; 32]);
+    assert_eq!(check_upgrade(&old, &new), Ok(()));
+
+    let v3 = public_values(3, b"ciphertext");
+    let newest = PublicValues::parse(&v3).unwrap();
+    assert_eq!(newest.custody_hash, [4; 32]);
+    assert_eq!(newest.lineage_commitment, [5; 32]);
+    assert_eq!(newest.ciphertext, b"ciphertext");
+    assert_eq!(check_upgrade(&new, &newest), Ok(()));
+    assert_eq!(newest.plaintext_size, None);
+
+    let v4 = public_values(4, b"ciphertext");
+    let latest = PublicValues::parse(&v4).unwrap();
+    assert_eq!(latest.plaintext_size, Some(42));
+    assert_eq!(latest.ciphertext, b"ciphertext");
+    assert_eq!(check_upgrade(&newest, &latest), Ok(()));
+    let mut v5 = v4.clone();
+    v5[0] = 5;
+    v5[225..233].copy_from_slice(&43u64.to_be_bytes());
+    assert_eq!(
+        check_upgrade(&latest, &PublicValues::parse(&v5).unwrap()),
+        Err(UpgradeError::Mismatch("plaintext size"))
+    );
+
+    let v6 = public_values(6, b"ciphertext");
+    let hashed = PublicValues::parse(&v6).unwrap();
+    assert_eq!(latest.ciphertext_is_hash, None);
+    assert_eq!(hashed.ciphertext_is_hash, Some(false));
+    assert_eq!(hashed.ciphertext, b"ciphertext");
+    assert_eq!(check_upgrade(&latest, &hashed), Ok(()));
+
+    let v7 = public_values(7, b"ciphertext");
+    let authenticated = PublicValues::parse(&v7).unwrap();
+    assert_eq!(hashed.input_hmac_key_hash, [0; 32]);
+    assert_eq!(authenticated.input_hmac_key_hash, [6; 32]);
+    assert_eq!(authenticated.ciphertext, b"ciphertext");
+    assert_eq!(check_upgrade(&hashed, &authenticated), Ok(()));
+    let mut v8 = v7.clone();
+    v8[0] = 8;
+    v8[233] = 1;
+    assert_eq!(
+        check_upgrade(&authenticated, &PublicValues::parse(&v8).unwrap()),
+        Err(UpgradeError::Mismatch("ciphertext kind"))
+    );
+    v8[233] = 2;
+    assert_eq!(PublicValues::parse(&v8), None);
+    assert_eq!(
+        check_upgrade(&new, &old),
+        Err(UpgradeError::NotNewer { old: 2, new: 1 })
+    );
+
+    let other = public_values(2, b"other ciphertext");
+    assert_eq!(
+        check_upgrade(&old, &PublicValues::parse(&other).unwrap()),
+        Err(UpgradeError::Mismatch("ciphertext"))
+    );
+}
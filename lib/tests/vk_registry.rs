@@ -0,0 +1,54 @@
+//! The JSON registry of program verification keys.
+
+use chacha_lib::vk_registry::VkRegistry;
+use chacha_lib::PROGRAM_VERSION;
+
+fn registry() -> VkRegistry {
+    let mut registry = VkRegistry::new();
+    for version in [0, 3, PROGRAM_VERSION] {
+        assert_eq!(registry.insert(version, [version + 0xa0; 32]), None);
+    }
+    registry
+}
+
+#[test]
+fn json_round_trips() {
+    let registry = registry();
+    let json = registry.to_json();
+    assert!(
+        json.contains(&format!("\"0x{}\"", hex::encode([0xa3; 32]))),
+        "{json}"
+    );
+    assert_eq!(VkRegistry::from_json(&json).unwrap(), registry);
+
+    // Keys without the 0x prefix are accepted
+    let json = format!("{{\"versions\": {{\"1\": \"{}\"}}}}", hex::encode([1; 32]));
+    assert_eq!(VkRegistry::from_json(&json).unwrap().get(1), Some(&[1; 32]));
+    assert!(VkRegistry::from_json("{\"versions\": {\"1\": \"0x1234\"}}").is_err());
+}
+
+#[test]
+fn vk_for_reads_the_version_byte() {
+    let registry = registry();
+    assert_eq!(registry.vk_for(&[3, 0xff, 0xff]), Some(&[0xa3; 32]));
+    assert_eq!(
+        registry.vk_for(&[PROGRAM_VERSION]),
+        Some(&[PROGRAM_VERSION + 0xa0; 32])
+    );
+    assert_eq!(registry.vk_for(&[1, 0]), None);
+    assert_eq!(registry.vk_for(&[]), None);
+}
+
+#[test]
+fn insert_replaces_and_latest_is_the_highest_version() {
+    let mut registry = registry();
+    assert_eq!(
+        registry.latest(),
+        Some((PROGRAM_VERSION, &[PROGRAM_VERSION + 0xa0; 32]))
+    );
+    assert_eq!(registry.insert(3, [0; 32]), Some([0xa3; 32]));
+    assert_eq!(registry.get(3), Some(&[0; 32]));
+    let versions: Vec<u8> = registry.iter().map(|(version, _)| version).collect();
+    assert_eq!(versions, [0, 3, PROGRAM_VERSION]);
+    assert_eq!(VkRegistry::new().latest(), None);
+}
@@ -0,0 +1,60 @@
+//! WireGuard transport data packets.
+//!
+//! WireGuard publishes no transport packet vectors of its own, so the expected payloads were
+//! computed with WireGuard's nonce construction (32 zero bits then the counter LE, no associated
+//! data) by both OpenSSL and libsodium 1.0.18 `crypto_aead_chacha20poly1305_ietf_encrypt`, which
+//! agree.
+
+use chacha_lib::wireguard::{wireguard_decrypt, wireguard_encrypt, wireguard_nonce, TAG_LEN};
+use chacha_lib::Key;
+
+fn key() -> Key {
+    Key(core::array::from_fn(|i| 0x80 + i as u8))
+}
+
+/// `(counter, plaintext, packet)`
+const VECTORS: [(u64, &str, &str); 3] = [
+    // A keepalive, only the tag
+    (0, "", "3ae5d3f2a376d317eaea5aef0215ba54"),
+    (
+        1,
+        "576972654775617264207472616e73706f72742064617461",
+        "b4780668390a46940625ae64eb1af59a763d58fe7b487a67fd029492b4212f06f1920a6bf3cea9a1",
+    ),
+    (
+        0x0807_0605_0403_0201,
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f60616263",
+        "e1b816fb13e071ff579069b735e1b12fdec2911e83cc657864fba8eba09e54a7e5c7874bceb3f1c00d5bf523886aa4702b2ee9b7ad3995efca3baa03bc407dd0105d22a5895eb59676b665daefc1ea74b4d4404127b2195e419b886378b70818a930bd8eada6054ae6aa8e81c4d0a8e1f060fe3c",
+    ),
+];
+
+#[test]
+fn nonce_construction() {
+    assert_eq!(
+        hex::encode(wireguard_nonce(0x0807_0605_0403_0201)),
+        "000000000102030405060708"
+    );
+}
+
+#[test]
+fn encrypt_matches_vectors() {
+    for (counter, plaintext, packet) in VECTORS {
+        let plaintext = hex::decode(plaintext).unwrap();
+        let encrypted = wireguard_encrypt(&key(), counter, &plaintext);
+        assert_eq!(encrypted.len(), plaintext.len() + TAG_LEN);
+        assert_eq!(hex::encode(&encrypted), packet, "counter {counter}");
+        assert_eq!(
+            wireguard_decrypt(&key(), counter, &encrypted),
+            Some(plaintext)
+        );
+    }
+}
+
+#[test]
+fn decrypt_rejects_tampering() {
+    let (counter, _, packet) = VECTORS[1];
+    let mut packet = hex::decode(packet).unwrap();
+    assert!(wireguard_decrypt(&key(), counter + 1, &packet).is_none());
+    packet[0] ^= 1;
+    assert!(wireguard_decrypt(&key(), counter, &packet).is_none());
+}
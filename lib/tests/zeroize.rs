@@ -0,0 +1,23 @@
+//! Explicit and on-drop zeroizing of keys and nonces.
+
+use chacha_lib::{Key, Nonce};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+#[test]
+fn zeroize_clears_bytes() {
+    let mut key = Key([0xa5; 32]);
+    key.zeroize();
+    assert_eq!(key.0, [0; 32]);
+
+    let mut nonce = Nonce([0x5a; 12]);
+    nonce.zeroize();
+    assert_eq!(nonce.0, [0; 12]);
+}
+
+#[test]
+fn zeroized_on_drop() {
+    assert_zeroize_on_drop::<Key>();
+    assert_zeroize_on_drop::<Nonce>();
+}
@@ -3,13 +3,60 @@ sp1_zkvm::entrypoint!(main);
 
 use sha2::{Digest, Sha256};
 
-use chacha_lib::chacha;
+use chacha_lib::compress::decompress;
+use chacha_lib::custody::OPERATOR_ID_LEN;
+use chacha_lib::input_auth::{
+    hmac_key_hash, verify_input_hmac, AuthenticatedInputs, INPUT_HMAC_FAILED,
+};
+use chacha_lib::selective::segments_root;
+use chacha_lib::{
+    chacha, compute_nullifier, custody_hash, lineage_commitment, timestamp_commitment,
+    CompressionType, Key, Nonce, PROGRAM_VERSION,
+};
 
 pub fn main() {
-    let key = sp1_zkvm::io::read_vec(); // 32 bytes
-    let nonce = sp1_zkvm::io::read_vec(); // 12 bytes
-                                          // The plaintext to be encrypted _in place_
-    let mut buffer = sp1_zkvm::io::read_vec(); // 12 bytes
+    // Key and Nonce are zeroized on drop, but the zkVM has no mlock or mprotect and its memory
+    // is part of the execution trace, so zeroizing is best effort here.
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    let compression = CompressionType::try_from(sp1_zkvm::io::read::<u8>()) // 1 byte
+        .expect("unknown compression type");
+    let commit_segments = sp1_zkvm::io::read::<bool>();
+    let commit_ciphertext_hash = sp1_zkvm::io::read::<bool>();
+    // Signed oracle timestamp, already verified by the host
+    let timestamp = sp1_zkvm::io::read::<Option<Vec<u8>>>();
+    // Operator ID and the host's unix time, never committed in the clear
+    let custody = sp1_zkvm::io::read::<Option<([u8; OPERATOR_ID_LEN], u64)>>();
+    // Lineage commitment of the previous pipeline stage's proof
+    let lineage = sp1_zkvm::io::read::<Option<[u8; 32]>>();
+    // The plaintext to be encrypted _in place_, already compressed by the host
+    let mut buffer = sp1_zkvm::io::read_vec();
+    // HMAC key and tag of the inputs above, written last by the host
+    let input_auth = sp1_zkvm::io::read::<Option<([u8; 32], [u8; 32])>>();
+
+    // Refuse inputs changed after they were tagged, before committing anything else
+    let authentic = match input_auth {
+        Some((hmac_key, tag)) => {
+            let inputs = AuthenticatedInputs {
+                key: &key,
+                nonce: &nonce,
+                compression,
+                commit_segments,
+                commit_ciphertext_hash,
+                timestamp: timestamp.as_deref(),
+                custody,
+                lineage,
+                buffer: &buffer,
+            };
+            verify_input_hmac(&hmac_key, &inputs, &tag)
+        }
+        None => true,
+    };
+    if !authentic {
+        sp1_zkvm::io::commit_slice(&INPUT_HMAC_FAILED); // 32 bytes
+        return;
+    }
 
     // Commit to buffer (plaintext) hash
     //
@@ -20,20 +67,79 @@ pub fn main() {
     //
     // BUT the cycle count is significantly higher for SHA3 (even accelerated)
     // so we choose to use SHA2, for slightly higher on chain verification gas costs.
-    let plaintext_hash = Sha256::digest(buffer.as_slice());
+    //
+    // The hash is always over the uncompressed plaintext, so the verifier can check the original
+    // content independently of the compression used.
+    let decompressed;
+    let plaintext = if compression == CompressionType::None {
+        buffer.as_slice()
+    } else {
+        decompressed = decompress(compression, &buffer).expect("malformed compressed plaintext");
+        decompressed.as_slice()
+    };
+    // Commit to the program version, so verifiers can select the matching verification key
+    sp1_zkvm::io::commit_slice(&[PROGRAM_VERSION]); // 1 byte
+
     // Hash plaintext & commit
+    let plaintext_hash: [u8; 32] = Sha256::digest(plaintext).into();
     sp1_zkvm::io::commit_slice(&plaintext_hash); // 32 bytes
+    let segments_root = if commit_segments {
+        segments_root(plaintext)
+    } else {
+        [0u8; 32]
+    };
+
+    // Commit to the nullifier, so on-chain verifiers can reject replayed proofs
+    sp1_zkvm::io::commit_slice(&compute_nullifier(&key, &nonce)); // 32 bytes
+
+    // Commit to the key hash, so a recipient can check a key received out-of-band
+    sp1_zkvm::io::commit_slice(&Sha256::digest(key.0)); // 32 bytes
+
+    // Commit to the segments root, so segments can be disclosed individually
+    sp1_zkvm::io::commit_slice(&segments_root); // 32 bytes
 
-    // FIXME // TODO:
-    // Hash key and/or nonce & commit?
+    // Commit to the timestamp, so the proof attests the plaintext existed at that time
+    let timestamp_commitment = match timestamp {
+        Some(timestamp) => timestamp_commitment(&timestamp, &plaintext_hash),
+        None => [0u8; 32],
+    };
+    sp1_zkvm::io::commit_slice(&timestamp_commitment); // 32 bytes
+
+    // Commit to the custody hash, so the operator that generated the proof is accountable for it
+    let custody_hash = match custody {
+        Some((operator_id, timestamp)) => custody_hash(&operator_id, timestamp, &plaintext_hash),
+        None => [0u8; 32],
+    };
+    sp1_zkvm::io::commit_slice(&custody_hash); // 32 bytes
+
+    // Commit to the lineage, so the proof of the next pipeline stage can link to this one
+    let lineage_commitment = match lineage {
+        Some(lineage_hash) => lineage_commitment(&lineage_hash, &plaintext_hash),
+        None => [0u8; 32],
+    };
+    sp1_zkvm::io::commit_slice(&lineage_commitment); // 32 bytes
+
+    // Commit to the plaintext size, so a verifier can bound it without the plaintext
+    sp1_zkvm::io::commit_slice(&(plaintext.len() as u64).to_be_bytes()); // 8 bytes
+
+    // Commit to what follows, so a verifier can not take the ciphertext hash for the ciphertext
+    sp1_zkvm::io::commit_slice(&[commit_ciphertext_hash as u8]); // 1 byte
+
+    // Commit to the HMAC key the inputs were authenticated with, so a verifier can tell an
+    // authenticated run from one without
+    let input_hmac_key_hash = match input_auth {
+        Some((hmac_key, _)) => hmac_key_hash(&hmac_key),
+        None => [0u8; 32],
+    };
+    sp1_zkvm::io::commit_slice(&input_hmac_key_hash); // 32 bytes
 
     // Encrypt and commit
-    // Incorrect sized buffers passed in are unacceptable, and thus panic.
-    chacha(
-        &key.try_into().expect("key=32B"),
-        &nonce.try_into().expect("nonce=12B"),
-        &mut buffer,
-    );
+    chacha(&key.0, &nonce.0, &mut buffer);
 
-    sp1_zkvm::io::commit_slice(&buffer);
+    if commit_ciphertext_hash {
+        // The ciphertext is delivered separately and checked against this hash
+        sp1_zkvm::io::commit_slice(&Sha256::digest(buffer.as_slice())); // 32 bytes
+    } else {
+        sp1_zkvm::io::commit_slice(&buffer);
+    }
 }
@@ -0,0 +1,27 @@
+//! Encryption with AES-256-CTR instead of ChaCha20, for environments restricted to FIPS-approved
+//! algorithms, see `chacha_lib::aes_mode`.
+//!
+//! Public values: `sha256(plaintext) (32) | ciphertext`
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use chacha_lib::aes_mode::{aes_ctr_encrypt, IV_LEN};
+use sha2::{Digest, Sha256};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key: [u8; 32] = sp1_zkvm::io::read_vec().try_into().expect("key=32B");
+    let iv: [u8; IV_LEN] = sp1_zkvm::io::read_vec().try_into().expect("iv=16B");
+    // The plaintext to be encrypted _in place_
+    let mut buffer = sp1_zkvm::io::read_vec();
+
+    let plaintext_hash = Sha256::digest(&buffer);
+    sp1_zkvm::io::commit_slice(&plaintext_hash);
+
+    // No precompile, AES runs in RISC-V instructions
+    println!("cycle-tracker-report-start: aes");
+    aes_ctr_encrypt(&key, &iv, &mut buffer);
+    println!("cycle-tracker-report-end: aes");
+    sp1_zkvm::io::commit_slice(&buffer);
+}
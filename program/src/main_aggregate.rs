@@ -0,0 +1,33 @@
+//! Aggregate the chunk proofs of one stream into a proof of their Merkle roots, see
+//! `chacha_lib::aggregate`.
+//!
+//! Public values: `chunk_vkey (32) | nullifier (32) | chunks (4, LE) | plaintext_root (32) |
+//! ciphertext_root (32)`
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::aggregate::{aggregate, ChunkPublicValues};
+use chacha_lib::upgrade::vkey_bytes32;
+
+pub fn main() {
+    // Verification key digest (`HashableKey::hash_u32`) of the chunk program
+    let chunk_vk = sp1_zkvm::io::read::<[u32; 8]>();
+    let count = sp1_zkvm::io::read::<u32>();
+
+    // Verify the compressed chunk proofs passed alongside the inputs, in order
+    let chunks: Vec<_> = (0..count)
+        .map(|_| {
+            let public_values = sp1_zkvm::io::read_vec();
+            let digest: [u8; 32] = Sha256::digest(&public_values).into();
+            sp1_zkvm::lib::verify::verify_sp1_proof(&chunk_vk, &digest);
+            ChunkPublicValues::parse(&public_values).expect("chunk public values too short")
+        })
+        .collect();
+
+    let aggregate =
+        aggregate(vkey_bytes32(&chunk_vk), &chunks).unwrap_or_else(|e| panic!("not a stream: {e}"));
+    sp1_zkvm::io::commit_slice(&aggregate.to_bytes()); // 132 bytes
+}
@@ -0,0 +1,29 @@
+//! Encryption committing a BLAKE2b-256 plaintext hash instead of SHA-256, for Zcash
+//! compatibility, see `chacha_lib::blake2b`.
+//!
+//! Public values: `personal (16) | blake2b_256(plaintext) (32) | ciphertext`
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use chacha_lib::blake2b::{blake2b_256, PERSONAL_LEN};
+use chacha_lib::{chacha, Key, Nonce};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    let personal: [u8; PERSONAL_LEN] = sp1_zkvm::io::read_vec().try_into().expect("personal=16B");
+    // The plaintext to be encrypted _in place_
+    let mut buffer = sp1_zkvm::io::read_vec();
+
+    // There is no BLAKE2b precompile, unlike for SHA-256
+    println!("cycle-tracker-report-start: blake2b");
+    let plaintext_hash = blake2b_256(&personal, &buffer);
+    println!("cycle-tracker-report-end: blake2b");
+    sp1_zkvm::io::commit_slice(&personal); // 16 bytes
+    sp1_zkvm::io::commit_slice(&plaintext_hash); // 32 bytes
+
+    chacha(&key.0, &nonce.0, &mut buffer);
+    sp1_zkvm::io::commit_slice(&buffer);
+}
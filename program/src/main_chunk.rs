@@ -0,0 +1,34 @@
+//! Encrypt one chunk of a larger plaintext, starting at a keystream block offset.
+//!
+//! Public values: `nullifier (32) | counter (4, LE) | chunks (4, LE) | sha256(chunk) (32) |
+//! ciphertext`
+//!
+//! The nullifier ties all chunks of a stream to the same key and nonce, the counter orders them and
+//! the chunk count of the stream shows whether any are missing.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::{chacha_at, compute_nullifier, Key, Nonce};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    // Keystream block (64 bytes) the chunk starts at
+    let counter = sp1_zkvm::io::read::<u32>();
+    // Number of chunks in the stream
+    let chunks = sp1_zkvm::io::read::<u32>();
+    // The chunk to be encrypted _in place_
+    let mut buffer = sp1_zkvm::io::read_vec();
+
+    sp1_zkvm::io::commit_slice(&compute_nullifier(&key, &nonce)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&counter.to_le_bytes()); // 4 bytes
+    sp1_zkvm::io::commit_slice(&chunks.to_le_bytes()); // 4 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(buffer.as_slice())); // 32 bytes
+
+    chacha_at(&key.0, &nonce.0, counter, &mut buffer);
+    sp1_zkvm::io::commit_slice(&buffer);
+}
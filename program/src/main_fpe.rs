@@ -0,0 +1,29 @@
+//! Format-preserving encryption of a structured value, see `chacha_lib::fpe`.
+//!
+//! Public values: `sha256(key) (32) | sha256(alphabet) (32) | sha256(plaintext) (32) | ciphertext`,
+//! with the ciphertext in UTF-8, in the format of the plaintext.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::fpe::chacha_fpe_encrypt;
+use chacha_lib::{Key, Nonce};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    let plaintext = String::from_utf8(sp1_zkvm::io::read_vec()).expect("plaintext is UTF-8");
+    let alphabet = String::from_utf8(sp1_zkvm::io::read_vec()).expect("alphabet is UTF-8");
+
+    println!("cycle-tracker-report-start: fpe");
+    let ciphertext = chacha_fpe_encrypt(&key, &nonce, &plaintext, &alphabet);
+    println!("cycle-tracker-report-end: fpe");
+
+    sp1_zkvm::io::commit_slice(&Sha256::digest(key.0)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&alphabet)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&plaintext)); // 32 bytes
+    sp1_zkvm::io::commit_slice(ciphertext.as_bytes());
+}
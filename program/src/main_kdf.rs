@@ -0,0 +1,25 @@
+//! Derive a ChaCha20 key with HKDF-SHA256, see `chacha_lib::kdf`.
+//!
+//! Public values: `sha256(ikm) (32) | sha256(key) (32) | info`
+//!
+//! The derived key itself is never committed, only its hash, which matches the key hash committed
+//! by the encryption program.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::kdf::hkdf_derive_key;
+
+pub fn main() {
+    let ikm = sp1_zkvm::io::read_vec();
+    let salt = sp1_zkvm::io::read::<Option<Vec<u8>>>();
+    let info = sp1_zkvm::io::read_vec();
+
+    let key = hkdf_derive_key(&ikm, salt.as_deref(), &info);
+
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&ikm)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(key.0)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&info);
+}
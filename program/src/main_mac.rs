@@ -0,0 +1,27 @@
+//! Encrypt-then-MAC with one key, see `chacha_lib::mac`.
+//!
+//! Public values: `sha256(plaintext) (32) | tag (32) | ciphertext`
+//!
+//! The tag authenticates the nonce and ciphertext under the MAC subkey of the key, so a holder of
+//! the key can check the ciphertext is the one proven without trusting its source.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::mac::chacha_with_hmac;
+use chacha_lib::{Key, Nonce};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    let plaintext = sp1_zkvm::io::read_vec();
+
+    let (ciphertext, tag) = chacha_with_hmac(&key, &nonce, &plaintext);
+
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&plaintext)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&tag); // 32 bytes
+    sp1_zkvm::io::commit_slice(&ciphertext);
+}
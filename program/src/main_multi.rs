@@ -0,0 +1,35 @@
+//! Encrypt one plaintext for several recipients, each with its own key and nonce.
+//!
+//! Public values: `sha256(plaintext) | (sha256(key_i) | sha256(ciphertext_i))` for each recipient
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::chacha;
+
+pub fn main() {
+    let recipients = sp1_zkvm::io::read::<u32>();
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let keys: Vec<([u8; 32], [u8; 12])> = (0..recipients)
+        .map(|_| {
+            let key = sp1_zkvm::io::read_vec().try_into().expect("key=32B");
+            // MUST BE UNIQUE per key - NO REUSE!
+            let nonce = sp1_zkvm::io::read_vec().try_into().expect("nonce=12B");
+            (key, nonce)
+        })
+        .collect();
+    let plaintext = sp1_zkvm::io::read_vec();
+
+    sp1_zkvm::io::commit_slice(&Sha256::digest(plaintext.as_slice())); // 32 bytes
+
+    // The ciphertexts are delivered to each recipient alongside the proof
+    let mut buffer = vec![0u8; plaintext.len()];
+    for (key, nonce) in &keys {
+        buffer.copy_from_slice(&plaintext);
+        chacha(key, nonce, &mut buffer);
+        sp1_zkvm::io::commit_slice(&Sha256::digest(key)); // 32 bytes
+        sp1_zkvm::io::commit_slice(&Sha256::digest(buffer.as_slice())); // 32 bytes
+    }
+}
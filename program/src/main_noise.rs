@@ -0,0 +1,32 @@
+//! Run a `Noise_NN_25519_ChaChaPoly_SHA256` handshake, see `chacha_lib::noise_compat`.
+//!
+//! Public values: `initiator e (32) | responder e (32) | handshake hash (32)`
+//!
+//! Both parties compute the same handshake hash, so either can check the proof against their own
+//! session.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use chacha_lib::noise_compat::noise_nn_handshake;
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let initiator_secret: [u8; 32] = sp1_zkvm::io::read_vec().try_into().expect("initiator=32B");
+    let responder_secret: [u8; 32] = sp1_zkvm::io::read_vec().try_into().expect("responder=32B");
+    let prologue = sp1_zkvm::io::read_vec();
+    let initiator_payload = sp1_zkvm::io::read_vec();
+    let responder_payload = sp1_zkvm::io::read_vec();
+
+    let handshake = noise_nn_handshake(
+        &initiator_secret,
+        &responder_secret,
+        &prologue,
+        &initiator_payload,
+        &responder_payload,
+    );
+
+    sp1_zkvm::io::commit_slice(&handshake.initiator_ephemeral); // 32 bytes
+    sp1_zkvm::io::commit_slice(&handshake.responder_ephemeral); // 32 bytes
+    sp1_zkvm::io::commit_slice(&handshake.handshake_hash); // 32 bytes
+}
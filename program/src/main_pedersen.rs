@@ -0,0 +1,33 @@
+//! Encrypt a plaintext committing to it with both SHA256 and a Pedersen commitment, see
+//! `chacha_lib::pedersen`.
+//!
+//! Public values: `sha256(plaintext) (32) | pedersen(plaintext, r) (32) | sha256(ciphertext) (32) |
+//! ciphertext`.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::pedersen::pedersen_commit;
+use chacha_lib::{chacha, Key, Nonce};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    let plaintext = sp1_zkvm::io::read_vec();
+    let randomness: [u8; 32] = sp1_zkvm::io::read_vec().try_into().expect("randomness=32B");
+
+    println!("cycle-tracker-report-start: pedersen");
+    let commitment = pedersen_commit(&plaintext, &randomness);
+    println!("cycle-tracker-report-end: pedersen");
+
+    let mut ciphertext = plaintext.clone();
+    chacha(&key.0, &nonce.0, &mut ciphertext);
+
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&plaintext)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&commitment.0); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&ciphertext)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&ciphertext);
+}
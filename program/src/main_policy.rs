@@ -0,0 +1,39 @@
+//! Decrypt only for a requester whose attributes satisfy a policy, see `chacha_lib::policy`.
+//!
+//! Public values: `sha256(policy) (32) | sha256(attributes) (32) | sha256(ciphertext) (32) |
+//! sha256(plaintext) (32)`
+//!
+//! No proof exists for attributes that do not satisfy the policy. The attributes stay private,
+//! but a hash of low entropy attributes can be brute forced: add a random `salt` attribute, which
+//! the policy ignores. Attributes such as the time are the requester's claim, attest them
+//! separately, e.g. with a signed timestamp.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::policy::evaluate;
+use chacha_lib::{chacha, Key, Nonce};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    let ciphertext = sp1_zkvm::io::read_vec();
+    let policy = String::from_utf8(sp1_zkvm::io::read_vec()).expect("policy is UTF-8");
+    let attributes = String::from_utf8(sp1_zkvm::io::read_vec()).expect("attributes are UTF-8");
+
+    println!("cycle-tracker-report-start: policy");
+    let satisfied = evaluate(&policy, &attributes).expect("invalid policy or attributes");
+    println!("cycle-tracker-report-end: policy");
+    assert!(satisfied, "attributes do not satisfy the policy");
+
+    let mut plaintext = ciphertext.clone();
+    chacha(&key.0, &nonce.0, &mut plaintext);
+
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&policy)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&attributes)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&ciphertext)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&plaintext)); // 32 bytes
+}
@@ -0,0 +1,36 @@
+//! Re-key a ciphertext from the registered old key to a new key, committing only hashes, see
+//! `chacha_lib::rekey`.
+//!
+//! Public values: `sha256(old_key) (32) | sha256(new_key) (32) | sha256(plaintext) (32) |
+//! sha256(ciphertext_new) (32)`
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::rekey::rekey;
+use chacha_lib::{Key, Nonce};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    // The commitment to the old key the ciphertext is registered under
+    let old_key_hash: [u8; 32] = sp1_zkvm::io::read_vec()
+        .try_into()
+        .expect("old_key_hash=32B");
+    let old_key = Key(sp1_zkvm::io::read_vec().try_into().expect("old_key=32B"));
+    let old_nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("old_nonce=12B"));
+    let new_key = Key(sp1_zkvm::io::read_vec().try_into().expect("new_key=32B"));
+    // MUST BE UNIQUE for new_key - NO REUSE!
+    let new_nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("new_nonce=12B"));
+    // Decrypted, then re-encrypted _in place_
+    let mut buffer = sp1_zkvm::io::read_vec();
+
+    assert_eq!(
+        Sha256::digest(old_key.0).as_slice(),
+        old_key_hash,
+        "the old key does not match its commitment"
+    );
+    let rekey = rekey(&old_key, &old_nonce, &new_key, &new_nonce, &mut buffer);
+    sp1_zkvm::io::commit_slice(&rekey.to_bytes()); // 128 bytes
+}
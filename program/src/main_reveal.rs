@@ -0,0 +1,29 @@
+//! Reveal a commitment without publishing its key, see `chacha_lib::commit_reveal`.
+//!
+//! Public values: `binding (32) | plaintext`
+//!
+//! A verifier holding the published binding hash checks it against the committed one. The proof
+//! only needs the binding, not a proof of the commitment: the binding alone fixes the key, nonce
+//! and ciphertext, and with them the plaintext.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use chacha_lib::commit_reveal::{binding, reveal, Commitment};
+use chacha_lib::{Key, Nonce};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    let ciphertext = sp1_zkvm::io::read_vec();
+
+    let commitment = Commitment {
+        binding: binding(&key, &nonce, &ciphertext),
+        ciphertext,
+    };
+    let plaintext = reveal(&commitment, &key, &nonce).expect("binding computed from the inputs");
+
+    sp1_zkvm::io::commit_slice(&commitment.binding); // 32 bytes
+    sp1_zkvm::io::commit_slice(&plaintext);
+}
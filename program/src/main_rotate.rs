@@ -0,0 +1,31 @@
+//! Re-encrypt a ciphertext under a new key without revealing either key.
+//!
+//! Public values: `sha256(old_key) | sha256(new_key) | sha256(plaintext) | ciphertext_new`
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::chacha;
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let old_key: [u8; 32] = sp1_zkvm::io::read_vec().try_into().expect("old_key=32B");
+    let new_key: [u8; 32] = sp1_zkvm::io::read_vec().try_into().expect("new_key=32B");
+    let nonce_old: [u8; 12] = sp1_zkvm::io::read_vec().try_into().expect("nonce_old=12B");
+    // MUST BE UNIQUE for new_key - NO REUSE!
+    let nonce_new: [u8; 12] = sp1_zkvm::io::read_vec().try_into().expect("nonce_new=12B");
+    // Decrypted, then re-encrypted _in place_
+    let mut buffer = sp1_zkvm::io::read_vec();
+
+    sp1_zkvm::io::commit_slice(&Sha256::digest(old_key)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(new_key)); // 32 bytes
+
+    chacha(&old_key, &nonce_old, &mut buffer);
+    // The hash is over the decrypted buffer as is, compressed plaintexts are not decompressed.
+    sp1_zkvm::io::commit_slice(&Sha256::digest(buffer.as_slice())); // 32 bytes
+
+    chacha(&new_key, &nonce_new, &mut buffer);
+    sp1_zkvm::io::commit_slice(&buffer);
+}
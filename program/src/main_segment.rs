@@ -0,0 +1,43 @@
+//! Encrypt, committing the Merkle root of the plaintext segments and revealing at most one of them,
+//! see `chacha_lib::selective`.
+//!
+//! Public values: `sha256(plaintext) (32) | segments_root (32) | sha256(ciphertext) (32) |
+//! [reveal_index (4, LE) | segment]`, parsed by `chacha_lib::selective::SegmentPublicValues`.
+//!
+//! The revealed segment is committed in the clear and every other segment only through the root,
+//! which matches the root the encryption program commits with `--disclose`.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::selective::{segments_root, SEGMENT_SIZE};
+use chacha_lib::{chacha, Key, Nonce};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    // The plaintext to be encrypted _in place_
+    let mut buffer = sp1_zkvm::io::read_vec();
+    let reveal_index = sp1_zkvm::io::read::<Option<u32>>();
+
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&buffer)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&segments_root(&buffer)); // 32 bytes
+    let revealed = reveal_index.map(|index| {
+        let segment = buffer
+            .chunks(SEGMENT_SIZE)
+            .nth(index as usize)
+            .expect("reveal index past the last segment");
+        (index, segment.to_vec())
+    });
+
+    chacha(&key.0, &nonce.0, &mut buffer);
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&buffer)); // 32 bytes
+
+    if let Some((index, segment)) = revealed {
+        sp1_zkvm::io::commit_slice(&index.to_le_bytes()); // 4 bytes
+        sp1_zkvm::io::commit_slice(&segment); // up to 32 bytes
+    }
+}
@@ -0,0 +1,46 @@
+//! Hash the input with and without the SP1 SHA-256 precompile, and with SHA-512, BLAKE2b-256 and
+//! BLAKE3, for cycle counts.
+//!
+//! `sha2` is patched by the workspace to call the precompile, `sha2-software` is an unpatched
+//! version running the compression function in RISC-V instructions. SHA-512, BLAKE2b and BLAKE3
+//! have no precompile. Each hash is wrapped in a cycle tracker reported in
+//! `ExecutionReport::cycle_tracker`.
+//!
+//! Public values: `sha256(input) (32)`
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use std::hint::black_box;
+
+pub fn main() {
+    let input = sp1_zkvm::io::read_vec();
+
+    println!("cycle-tracker-report-start: sha256-precompile");
+    let precompile: [u8; 32] = <sha2::Sha256 as sha2::Digest>::digest(&input).into();
+    println!("cycle-tracker-report-end: sha256-precompile");
+
+    println!("cycle-tracker-report-start: sha256-software");
+    let software: [u8; 32] =
+        <sha2_software::Sha256 as sha2_software::Digest>::digest(&input).into();
+    println!("cycle-tracker-report-end: sha256-software");
+
+    println!("cycle-tracker-report-start: sha512");
+    // Unused otherwise, so kept from being optimized out
+    black_box(<sha2::Sha512 as sha2::Digest>::digest(&input));
+    println!("cycle-tracker-report-end: sha512");
+
+    println!("cycle-tracker-report-start: blake2b");
+    black_box(chacha_lib::blake2b::blake2b_256(&[0; 16], &input));
+    println!("cycle-tracker-report-end: blake2b");
+
+    println!("cycle-tracker-report-start: blake3");
+    black_box(blake3::hash(&input));
+    println!("cycle-tracker-report-end: blake3");
+
+    assert_eq!(
+        precompile, software,
+        "precompile and software hashes differ"
+    );
+    sp1_zkvm::io::commit_slice(&precompile);
+}
@@ -0,0 +1,27 @@
+//! Encryption committing the full 64 byte SHA-512 plaintext hash instead of SHA-256, see
+//! `chacha_lib::public_values::Sha512PublicValues`.
+//!
+//! Public values: `sha512(plaintext) (64) | ciphertext`
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use chacha_lib::{chacha, Key, Nonce};
+use sha2::{Digest, Sha512};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    // The plaintext to be encrypted _in place_
+    let mut buffer = sp1_zkvm::io::read_vec();
+
+    // The workspace patch only adds a precompile for SHA-256, SHA-512 runs in RISC-V instructions
+    println!("cycle-tracker-report-start: sha512");
+    let plaintext_hash = Sha512::digest(&buffer);
+    println!("cycle-tracker-report-end: sha512");
+    sp1_zkvm::io::commit_slice(&plaintext_hash); // 64 bytes
+
+    chacha(&key.0, &nonce.0, &mut buffer);
+    sp1_zkvm::io::commit_slice(&buffer);
+}
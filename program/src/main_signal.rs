@@ -0,0 +1,28 @@
+//! Encrypt one message of a Signal sending chain, see `chacha_lib::signal_compat`.
+//!
+//! Public values: `sha256(chain_key) (32) | sha256(next_chain_key) (32) | sha256(plaintext) (32) |
+//! ciphertext`
+//!
+//! The next chain key hash of one proof is the chain key hash of the next message's proof, so
+//! consecutive proofs show the messages came from consecutive steps of the same chain.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::signal_compat::signal_encrypt;
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let chain_key: [u8; 32] = sp1_zkvm::io::read_vec().try_into().expect("chain_key=32B");
+    let plaintext = sp1_zkvm::io::read_vec();
+
+    let (ciphertext, next_chain_key) = signal_encrypt(&chain_key, &plaintext);
+
+    sp1_zkvm::io::commit_slice(&Sha256::digest(chain_key)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(next_chain_key)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(plaintext.as_slice())); // 32 bytes
+
+    sp1_zkvm::io::commit_slice(&ciphertext);
+}
@@ -0,0 +1,39 @@
+//! Encrypt an SSH packet with `chacha20-poly1305@openssh.com`, see `chacha_lib::ssh_compat`.
+//!
+//! Public values: `sha256(key1 || key2) (32) | sequence number (4, BE) | sha256(plaintext) (32) |
+//! packet`
+//!
+//! The packet is the encrypted length, the encrypted plaintext and the Poly1305 tag, as sent on
+//! the wire.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::ssh_compat::ssh_chacha_encrypt;
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key1: [u8; 32] = sp1_zkvm::io::read_vec().try_into().expect("key1=32B");
+    let key2: [u8; 32] = sp1_zkvm::io::read_vec().try_into().expect("key2=32B");
+    // Sequence number of the packet in the session
+    let sequence_number = sp1_zkvm::io::read::<u32>();
+    // Padding length, payload and padding
+    let plaintext = sp1_zkvm::io::read_vec();
+
+    let keys_hash = Sha256::new()
+        .chain_update(key1)
+        .chain_update(key2)
+        .finalize();
+    sp1_zkvm::io::commit_slice(&keys_hash); // 32 bytes
+    sp1_zkvm::io::commit_slice(&sequence_number.to_be_bytes()); // 4 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(plaintext.as_slice())); // 32 bytes
+
+    sp1_zkvm::io::commit_slice(&ssh_chacha_encrypt(
+        &key1,
+        &key2,
+        sequence_number,
+        &plaintext,
+    ));
+}
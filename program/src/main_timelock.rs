@@ -0,0 +1,40 @@
+//! Decrypt a time-locked ciphertext once its unlock block is reached, see `chacha_lib::timelock`.
+//!
+//! Public values: `unlock_block (8) | block_number (8) | block_hash (32) | sha256(key) (32) |
+//! sha256(ciphertext) (32) | plaintext`, parsed by `chacha_lib::timelock::TimelockPublicValues`.
+//!
+//! The block header is an input: a verifier MUST check `block_hash` against a trusted source of
+//! block hashes, otherwise any header unlocks the ciphertext.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::timelock::BlockHeader;
+use chacha_lib::{chacha, Key, Nonce};
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    let nonce = Nonce(sp1_zkvm::io::read_vec().try_into().expect("nonce=12B"));
+    let ciphertext = sp1_zkvm::io::read_vec();
+    let unlock_block = sp1_zkvm::io::read::<u64>();
+    let header = BlockHeader::from_bytes(&sp1_zkvm::io::read_vec()).expect("header=80B");
+
+    assert!(
+        header.unlocks(unlock_block),
+        "block {} is before the unlock block {unlock_block}",
+        header.number
+    );
+
+    let mut plaintext = ciphertext.clone();
+    chacha(&key.0, &nonce.0, &mut plaintext);
+
+    sp1_zkvm::io::commit_slice(&unlock_block.to_le_bytes()); // 8 bytes
+    sp1_zkvm::io::commit_slice(&header.number.to_le_bytes()); // 8 bytes
+    sp1_zkvm::io::commit_slice(&header.hash()); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(key.0)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(&ciphertext)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&plaintext);
+}
@@ -0,0 +1,31 @@
+//! Encrypt a TLS 1.3 record with `TLS_CHACHA20_POLY1305_SHA256`, see `chacha_lib::tls13`.
+//!
+//! Public values: `sha256(key || iv) (32) | sequence number (8, BE) | content type (1) |
+//! sha256(plaintext) (32) | record`
+//!
+//! The record includes its 5 byte header, as sent on the wire.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::tls13::tls13_encrypt;
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key: [u8; 32] = sp1_zkvm::io::read_vec().try_into().expect("key=32B");
+    let iv: [u8; 12] = sp1_zkvm::io::read_vec().try_into().expect("iv=12B");
+    // Sequence number of the record under this key
+    let seq = sp1_zkvm::io::read::<u64>();
+    let content_type = sp1_zkvm::io::read::<u8>();
+    let plaintext = sp1_zkvm::io::read_vec();
+
+    let secrets_hash = Sha256::new().chain_update(key).chain_update(iv).finalize();
+    sp1_zkvm::io::commit_slice(&secrets_hash); // 32 bytes
+    sp1_zkvm::io::commit_slice(&seq.to_be_bytes()); // 8 bytes
+    sp1_zkvm::io::commit_slice(&[content_type]); // 1 byte
+    sp1_zkvm::io::commit_slice(&Sha256::digest(plaintext.as_slice())); // 32 bytes
+
+    sp1_zkvm::io::commit_slice(&tls13_encrypt(&key, &iv, seq, content_type, &plaintext));
+}
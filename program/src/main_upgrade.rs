@@ -0,0 +1,41 @@
+//! Upgrade from one version of the encryption program to the next, see `chacha_lib::upgrade`.
+//!
+//! Public values:
+//! `old_version (1) | old_vkey (32) | new_version (1) | new_vkey (32)`
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::upgrade::{check_upgrade, vkey_bytes32, Upgrade};
+use chacha_lib::PublicValues;
+
+pub fn main() {
+    // Verification key digests (`HashableKey::hash_u32`) of the two programs
+    let old_vk = sp1_zkvm::io::read::<[u32; 8]>();
+    let new_vk = sp1_zkvm::io::read::<[u32; 8]>();
+    // Public values of both programs encrypting the same input
+    let old_public_values = sp1_zkvm::io::read_vec();
+    let new_public_values = sp1_zkvm::io::read_vec();
+
+    // Verify the compressed proofs passed alongside the inputs, in this order
+    let old_digest: [u8; 32] = Sha256::digest(&old_public_values).into();
+    sp1_zkvm::lib::verify::verify_sp1_proof(&old_vk, &old_digest);
+    let new_digest: [u8; 32] = Sha256::digest(&new_public_values).into();
+    sp1_zkvm::lib::verify::verify_sp1_proof(&new_vk, &new_digest);
+
+    let old = PublicValues::parse(&old_public_values).expect("old public values too short");
+    let new = PublicValues::parse(&new_public_values).expect("new public values too short");
+    if let Err(e) = check_upgrade(&old, &new) {
+        panic!("not an upgrade: {e}");
+    }
+
+    let upgrade = Upgrade {
+        old_version: old.version,
+        old_vkey: vkey_bytes32(&old_vk),
+        new_version: new.version,
+        new_vkey: vkey_bytes32(&new_vk),
+    };
+    sp1_zkvm::io::commit_slice(&upgrade.to_bytes()); // 66 bytes
+}
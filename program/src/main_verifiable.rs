@@ -0,0 +1,35 @@
+//! Verifiable encryption to a recipient's X25519 public key, see `chacha_lib::exchange`.
+//!
+//! Public values:
+//! `recipient_public (32) | ephemeral_public (32) | sha256(plaintext) (32) | sha256(ciphertext) (32)`
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::chacha;
+use chacha_lib::exchange::sender_key;
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let recipient_public: [u8; 32] = sp1_zkvm::io::read_vec()
+        .try_into()
+        .expect("recipient_public=32B");
+    // MUST BE USED ONCE - NO REUSE!
+    let ephemeral_secret: [u8; 32] = sp1_zkvm::io::read_vec()
+        .try_into()
+        .expect("ephemeral_secret=32B");
+    // The plaintext to be encrypted _in place_
+    let mut buffer = sp1_zkvm::io::read_vec();
+
+    let (ephemeral_public, key, nonce) =
+        sender_key(&ephemeral_secret, &recipient_public).expect("low order recipient key");
+    sp1_zkvm::io::commit_slice(&recipient_public); // 32 bytes
+    sp1_zkvm::io::commit_slice(&ephemeral_public); // 32 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(buffer.as_slice())); // 32 bytes
+
+    chacha(&key.0, &nonce.0, &mut buffer);
+    // The ciphertext is delivered alongside the proof
+    sp1_zkvm::io::commit_slice(&Sha256::digest(buffer.as_slice())); // 32 bytes
+}
@@ -0,0 +1,27 @@
+//! Encrypt a WireGuard transport data packet, see `chacha_lib::wireguard`.
+//!
+//! Public values: `sha256(key) (32) | counter (8, LE) | sha256(plaintext) (32) | packet`
+//!
+//! The packet is the ciphertext followed by the Poly1305 tag, as sent on the wire.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+
+use chacha_lib::wireguard::wireguard_encrypt;
+use chacha_lib::Key;
+
+pub fn main() {
+    // Incorrect sized buffers passed in are unacceptable, and thus panic.
+    let key = Key(sp1_zkvm::io::read_vec().try_into().expect("key=32B"));
+    // Counter of the packet in the session
+    let counter = sp1_zkvm::io::read::<u64>();
+    let plaintext = sp1_zkvm::io::read_vec();
+
+    sp1_zkvm::io::commit_slice(&Sha256::digest(key.0)); // 32 bytes
+    sp1_zkvm::io::commit_slice(&counter.to_le_bytes()); // 8 bytes
+    sp1_zkvm::io::commit_slice(&Sha256::digest(plaintext.as_slice())); // 32 bytes
+
+    sp1_zkvm::io::commit_slice(&wireguard_encrypt(&key, counter, &plaintext));
+}
@@ -0,0 +1,82 @@
+//! Cycles saved by compressing the plaintext before encrypting it, for text, which compresses
+//! well, and random bytes, which do not. Then the ratio and cycles of LZ4 against Zstandard at
+//! its default level on 100 KB of text.
+//!
+//! ```shell
+//! cargo bench -p chacha-script --bench compression
+//! ```
+
+use sp1_sdk::{CpuProver, ProverClient};
+
+use chacha_lib::{chacha, CompressionType};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+const SIZE: usize = 64 * 1024;
+const COMPARISON_SIZE: usize = 100 * 1024;
+
+fn text(len: usize) -> Vec<u8> {
+    let mut text = Vec::with_capacity(len);
+    for i in 0.. {
+        if text.len() >= len {
+            break;
+        }
+        text.extend_from_slice(
+            format!("{i}: the quick brown fox jumps over the lazy dog\n").as_bytes(),
+        );
+    }
+    text.truncate(len);
+    text
+}
+
+/// A ChaCha20 keystream, which does not compress.
+fn random(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    chacha(&[1; 32], &[0; 12], &mut bytes);
+    bytes
+}
+
+/// The cycles of encrypting `plaintext` and the size of the buffer the program encrypts.
+fn execute(client: &CpuProver, compression: CompressionType, plaintext: &[u8]) -> (u64, usize) {
+    let inputs = EncryptInputs {
+        compression,
+        ..EncryptInputs::new(core::array::from_fn(|i| i as u8 ^ 0x5c), [0; 12], plaintext)
+    };
+    let (_, report) = client
+        .execute(CHACHA_ELF, &inputs.to_stdin())
+        .run()
+        .unwrap();
+    (
+        report.total_instruction_count(),
+        inputs.compressed_plaintext().len(),
+    )
+}
+
+fn main() {
+    // Execution only, the mock prover never proves
+    let client = ProverClient::builder().mock().build();
+    println!(
+        "{:<8} {:>10} {:>12} {:>12} {:>8}",
+        "input", "bytes", "cycles", "lz4 cycles", "saved"
+    );
+    for (name, plaintext) in [("text", text(SIZE)), ("random", random(SIZE))] {
+        let (uncompressed, _) = execute(&client, CompressionType::None, &plaintext);
+        let (compressed, bytes) = execute(&client, CompressionType::Lz4, &plaintext);
+        let saved = 100.0 * (1.0 - compressed as f64 / uncompressed as f64);
+        println!("{name:<8} {bytes:>10} {uncompressed:>12} {compressed:>12} {saved:>7.1}%");
+    }
+
+    let plaintext = text(COMPARISON_SIZE);
+    let (uncompressed, _) = execute(&client, CompressionType::None, &plaintext);
+    println!(
+        "\n{:<8} {:>10} {:>8} {:>12} {:>8}",
+        "100 KB", "bytes", "ratio", "cycles", "vs none"
+    );
+    for compression in [CompressionType::Lz4, CompressionType::Zstd] {
+        let (cycles, bytes) = execute(&client, compression, &plaintext);
+        let ratio = plaintext.len() as f64 / bytes as f64;
+        let change = 100.0 * (cycles as f64 / uncompressed as f64 - 1.0);
+        // The `Display` of `CompressionType` ignores the width
+        let name = compression.to_string();
+        println!("{name:<8} {bytes:>10} {ratio:>8.2} {cycles:>12} {change:>+7.1}%");
+    }
+}
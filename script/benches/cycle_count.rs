@@ -0,0 +1,89 @@
+//! Cycle counts of executing the encryption program on inputs of 1 B to 256 KB, compared
+//! against `benches/baseline.json`.
+//!
+//! ```shell
+//! cargo bench -p chacha-script --bench cycle_count
+//! cargo bench -p chacha-script --bench cycle_count -- --save-baseline
+//! ```
+//!
+//! Fails if the counts are not linear in the input size, or if any size takes more than
+//! `REGRESSION_THRESHOLD_PERCENT` more cycles than the baseline. Without a baseline only the
+//! linearity is checked. No baseline is committed yet, CI downloads the one of the last run on
+//! `main`.
+
+use std::path::Path;
+
+use sp1_sdk::ProverClient;
+
+use chacha_script::cycles::{
+    plot, r_squared, regressions, CycleCounts, BENCH_SIZES, MIN_R_SQUARED,
+    REGRESSION_THRESHOLD_PERCENT,
+};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+fn main() {
+    let save_baseline = std::env::args().any(|arg| arg == "--save-baseline");
+    let baseline_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/baseline.json");
+
+    // Execution only, the mock prover never proves
+    let client = ProverClient::builder().mock().build();
+    let current: CycleCounts = BENCH_SIZES
+        .into_iter()
+        .map(|size| {
+            // Fixed, so runs differ only by the program
+            let plaintext: Vec<u8> = (0..size).map(|i| i as u8).collect();
+            let stdin = EncryptInputs::new(
+                core::array::from_fn(|i| i as u8 ^ 0x5c),
+                [0; 12],
+                &plaintext,
+            )
+            .to_stdin();
+            let (_, report) = client.execute(CHACHA_ELF, &stdin).run().unwrap();
+            (size, report.total_instruction_count())
+        })
+        .collect();
+    print!("{}", plot(&current, 40));
+
+    let r_squared = r_squared(&current);
+    println!("R² = {r_squared:.6}");
+    assert!(
+        r_squared > MIN_R_SQUARED,
+        "cycle counts are not linear in the input size, R² = {r_squared}"
+    );
+
+    if save_baseline {
+        let json = serde_json::to_string_pretty(&current).expect("cycle counts serialize");
+        std::fs::write(&baseline_path, json + "\n").expect("Failed to write baseline");
+        println!("Saved baseline to {}", baseline_path.display());
+        return;
+    }
+    let baseline: CycleCounts = match std::fs::read_to_string(&baseline_path) {
+        Ok(json) => serde_json::from_str(&json).expect("Malformed baseline"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!("No baseline at {}, skipping", baseline_path.display());
+            return;
+        }
+        Err(e) => panic!("Failed to read baseline: {e}"),
+    };
+    let regressions = regressions(&baseline, &current, REGRESSION_THRESHOLD_PERCENT);
+    for regression in &regressions {
+        eprintln!(
+            "{} B: {} cycles, {:+.1}% over the baseline of {}",
+            regression.size,
+            regression.current,
+            regression.percent(),
+            regression.baseline
+        );
+    }
+    if !regressions.is_empty() {
+        eprintln!(
+            "Error: {} sizes take more than {REGRESSION_THRESHOLD_PERCENT}% more cycles than \
+             the baseline",
+            regressions.len()
+        );
+        std::process::exit(1);
+    }
+    println!(
+        "No size takes more than {REGRESSION_THRESHOLD_PERCENT}% more cycles than the baseline"
+    );
+}
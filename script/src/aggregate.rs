@@ -0,0 +1,86 @@
+//! Proving the aggregation of a stream's chunk proofs, see `chacha_lib::aggregate`.
+//!
+//! The chunk proofs written by `chunk_prove` must be compressed, the aggregation program verifies
+//! them in the zkVM. Its own proof replaces all of them on-chain.
+
+use std::fmt;
+
+use sp1_sdk::{HashableKey, SP1Proof, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey};
+
+use chacha_lib::aggregate::{aggregate, Aggregate, AggregateError, ChunkPublicValues};
+use chacha_lib::upgrade::vkey_bytes32;
+use chacha_lib::ProofType;
+
+use crate::convert::proof_type;
+
+/// Why chunk proofs cannot be passed to the aggregation program.
+#[derive(Debug)]
+pub enum AggregationError {
+    /// The chunk proof at this index is not compressed.
+    NotCompressed(usize, ProofType),
+    /// The public values of the chunk at this index are not of the chunk program.
+    PublicValues(usize),
+    Aggregate(AggregateError),
+}
+
+impl fmt::Display for AggregationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregationError::NotCompressed(i, proof_type) => {
+                write!(
+                    f,
+                    "chunk {i}: expected a compressed proof, got a {proof_type} proof"
+                )
+            }
+            AggregationError::PublicValues(i) => write!(f, "chunk {i}: public values too short"),
+            AggregationError::Aggregate(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AggregationError {}
+
+impl From<AggregateError> for AggregationError {
+    fn from(e: AggregateError) -> Self {
+        AggregationError::Aggregate(e)
+    }
+}
+
+/// The chunk public values of `proofs`, in order.
+pub fn chunk_public_values(
+    proofs: &[SP1ProofWithPublicValues],
+) -> Result<Vec<ChunkPublicValues>, AggregationError> {
+    proofs
+        .iter()
+        .enumerate()
+        .map(|(i, proof)| {
+            ChunkPublicValues::parse(proof.public_values.as_slice())
+                .ok_or(AggregationError::PublicValues(i))
+        })
+        .collect()
+}
+
+/// Inputs of the aggregation program from the chunk proofs of a stream, in order, and the
+/// [`Aggregate`] it is expected to commit.
+pub fn aggregate_stdin(
+    chunk_vk: &SP1VerifyingKey,
+    proofs: &[SP1ProofWithPublicValues],
+) -> Result<(SP1Stdin, Aggregate), AggregationError> {
+    // Fail here rather than as a zkVM panic
+    let chunks = chunk_public_values(proofs)?;
+    let expected = aggregate(vkey_bytes32(&chunk_vk.hash_u32()), &chunks)?;
+
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&chunk_vk.hash_u32());
+    stdin.write(&expected.chunks);
+    for proof in proofs {
+        stdin.write_slice(proof.public_values.as_slice());
+    }
+    for (i, proof) in proofs.iter().enumerate() {
+        let SP1Proof::Compressed(reduce) = &proof.proof else {
+            return Err(AggregationError::NotCompressed(i, proof_type(&proof.proof)));
+        };
+        stdin.write_proof(*reduce.clone(), chunk_vk.vk.clone());
+    }
+    Ok((stdin, expected))
+}
@@ -0,0 +1,155 @@
+//! Operation counts of ChaCha20, and what a lookup argument based chip could save on them.
+//!
+//! Host side only, printed by `--analyze-constraints`. The zkVM executes ChaCha20 as RV32IM
+//! instructions, which have no rotate: each rotation is a shift left, a shift right and an OR, so
+//! a block costs [`riscv_instructions`] ALU instructions, before the loads and stores.
+//!
+//! ## Lookup model
+//!
+//! A dedicated chip with lookup arguments, like the SP1 SHA-256 precompile, could do better:
+//!
+//! - In ChaCha20 every rotation follows a XOR of the same word, a lookup table over byte pairs can
+//!   return the XOR and the rotation at once, so the pair costs one operation.
+//! - Rotations by 16 and 8 only move whole bytes, they are free once words are held as bytes. The
+//!   rotations by 12 and 7 split bytes and need the lookup.
+//! - Additions stay one operation each, a carry chain cannot be folded into the XOR tables.
+//!
+//! [`lookup_minimum`] counts one operation per addition, per fused XOR and rotation and per
+//! remaining XOR. It is a roadmap, not a cycle count: the real cost depends on how SP1 prices a
+//! precompile row against an instruction.
+
+/// Double rounds of ChaCha20, 20 rounds in total.
+pub const DOUBLE_ROUNDS: u64 = 10;
+/// Quarter rounds per double round, 4 column and 4 diagonal.
+pub const QUARTER_ROUNDS_PER_DOUBLE_ROUND: u64 = 8;
+/// Words in the ChaCha20 state, each added to its initial value after the rounds.
+pub const STATE_WORDS: u64 = 16;
+/// Size of a ChaCha20 keystream block.
+pub const BLOCK_LEN: u64 = 64;
+/// RV32IM instructions per 32 bit rotation: `sll`, `srl`, `or`.
+pub const RISCV_ROTATE_INSTRUCTIONS: u64 = 3;
+
+/// Counts of 32 bit operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OpCounts {
+    pub add: u64,
+    pub xor: u64,
+    pub rotate: u64,
+}
+
+impl OpCounts {
+    /// `a += b; d ^= a; d <<<= 16; c += d; b ^= c; b <<<= 12; a += b; d ^= a; d <<<= 8; c += d;
+    /// b ^= c; b <<<= 7;`, RFC 8439 section 2.1.
+    pub const QUARTER_ROUND: OpCounts = OpCounts {
+        add: 4,
+        xor: 4,
+        rotate: 4,
+    };
+
+    pub fn total(self) -> u64 {
+        self.add + self.xor + self.rotate
+    }
+
+    pub fn times(self, n: u64) -> OpCounts {
+        OpCounts {
+            add: self.add * n,
+            xor: self.xor * n,
+            rotate: self.rotate * n,
+        }
+    }
+}
+
+/// Operations of the block function, RFC 8439 section 2.3: the 20 rounds and adding the initial
+/// state.
+pub fn block_function_ops() -> OpCounts {
+    let rounds = OpCounts::QUARTER_ROUND.times(DOUBLE_ROUNDS * QUARTER_ROUNDS_PER_DOUBLE_ROUND);
+    OpCounts {
+        add: rounds.add + STATE_WORDS,
+        ..rounds
+    }
+}
+
+/// Operations to encrypt one full block, RFC 8439 section 2.4: the block function and XORing the
+/// keystream onto the plaintext a word at a time.
+pub fn encryption_block_ops() -> OpCounts {
+    let block = block_function_ops();
+    OpCounts {
+        xor: block.xor + STATE_WORDS,
+        ..block
+    }
+}
+
+/// Keystream blocks needed for `len` bytes.
+pub fn blocks(len: usize) -> u64 {
+    (len as u64).div_ceil(BLOCK_LEN)
+}
+
+/// RV32IM ALU instructions for `ops`.
+pub fn riscv_instructions(ops: OpCounts) -> u64 {
+    ops.add + ops.xor + RISCV_ROTATE_INSTRUCTIONS * ops.rotate
+}
+
+/// Operations for `ops` under the lookup model of the [module docs](self), where each rotation is
+/// fused with the XOR before it.
+pub fn lookup_minimum(ops: OpCounts) -> u64 {
+    ops.add + ops.rotate + ops.xor.saturating_sub(ops.rotate)
+}
+
+/// The analysis of encrypting a plaintext, as printed by `--analyze-constraints`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConstraintAnalysis {
+    pub plaintext_len: usize,
+    pub blocks: u64,
+    pub ops: OpCounts,
+    pub riscv_instructions: u64,
+    pub lookup_minimum: u64,
+}
+
+impl ConstraintAnalysis {
+    pub fn new(plaintext_len: usize) -> Self {
+        let blocks = blocks(plaintext_len);
+        let ops = encryption_block_ops().times(blocks);
+        Self {
+            plaintext_len,
+            blocks,
+            ops,
+            riscv_instructions: riscv_instructions(ops),
+            lookup_minimum: lookup_minimum(ops),
+        }
+    }
+}
+
+impl std::fmt::Display for ConstraintAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let per_block = encryption_block_ops();
+        writeln!(
+            f,
+            "ChaCha20 over {} bytes, {} blocks:",
+            self.plaintext_len, self.blocks
+        )?;
+        writeln!(f, "  {:<22} {:>9} {:>12}", "", "per block", "total")?;
+        for (name, per_block, total) in [
+            ("add", per_block.add, self.ops.add),
+            ("xor", per_block.xor, self.ops.xor),
+            ("rotate", per_block.rotate, self.ops.rotate),
+            (
+                "RV32IM instructions",
+                riscv_instructions(per_block),
+                self.riscv_instructions,
+            ),
+            (
+                "lookup minimum",
+                lookup_minimum(per_block),
+                self.lookup_minimum,
+            ),
+        ] {
+            writeln!(f, "  {name:<22} {per_block:>9} {total:>12}")?;
+        }
+        write!(
+            f,
+            "A lookup based ChaCha20 chip could need {:.1}x fewer operations than RV32IM, \
+             loads and stores excluded",
+            riscv_instructions(per_block) as f64 / lookup_minimum(per_block) as f64
+        )
+    }
+}
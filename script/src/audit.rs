@@ -0,0 +1,190 @@
+//! Tamper-evident audit log of proving operations.
+//!
+//! Each proof is recorded as one JSON line. Every entry includes the hash of the previous entry,
+//! so editing, inserting, removing or reordering lines breaks the chain from that point on, except
+//! for removing lines from the end: a truncated log is still a valid chain. To detect truncation,
+//! keep the hash of the last entry, the chain head, somewhere the log writer cannot change, and
+//! check the log still ends there. Verify a log with the `audit_verify` binary.
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_sdk::SP1ProofWithPublicValues;
+
+/// `previous_hash` of the first entry.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// One proving operation. Hashes are hex encoded SHA256 digests.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// API key, client address or user that requested the proof.
+    pub requester: String,
+    pub input_hash: String,
+    /// Hash of the bincode serialized proof, as written by `SP1ProofWithPublicValues::save`.
+    pub proof_hash: String,
+    pub elapsed_ms: u64,
+    pub previous_hash: String,
+    /// Hash of this entry with `hash` set to the empty string.
+    pub hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> String {
+        let unhashed = AuditEntry {
+            hash: String::new(),
+            ..self.clone()
+        };
+        let json = serde_json::to_string(&unhashed).expect("audit entry serializes");
+        hex::encode(Sha256::digest(json.as_bytes()))
+    }
+}
+
+/// Appends entries to a log file, continuing the chain of an existing log.
+pub struct AuditLog {
+    inner: Mutex<(File, String)>,
+}
+
+impl AuditLog {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let previous_hash = match File::open(path) {
+            Ok(file) => match BufReader::new(file).lines().last() {
+                Some(line) => {
+                    let entry: AuditEntry = serde_json::from_str(&line?)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                    entry.hash
+                }
+                None => GENESIS_HASH.to_string(),
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => GENESIS_HASH.to_string(),
+            Err(e) => return Err(e),
+        };
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            inner: Mutex::new((file, previous_hash)),
+        })
+    }
+
+    /// Append an entry for a proof of `input` and return it.
+    pub fn record(
+        &self,
+        requester: &str,
+        input: &[u8],
+        proof: &SP1ProofWithPublicValues,
+        elapsed: Duration,
+    ) -> io::Result<AuditEntry> {
+        let proof_bytes = bincode::serialize(proof).map_err(io::Error::other)?;
+
+        let mut inner = self.inner.lock().expect("audit log lock poisoned");
+        let (file, previous_hash) = &mut *inner;
+        let mut entry = AuditEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock before unix epoch")
+                .as_secs(),
+            requester: requester.to_string(),
+            input_hash: hex::encode(Sha256::digest(input)),
+            proof_hash: hex::encode(Sha256::digest(&proof_bytes)),
+            elapsed_ms: elapsed.as_millis() as u64,
+            previous_hash: previous_hash.clone(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+
+        let line = serde_json::to_string(&entry).expect("audit entry serializes");
+        writeln!(file, "{line}")?;
+        file.flush()?;
+        *previous_hash = entry.hash.clone();
+        Ok(entry)
+    }
+}
+
+/// Why a log failed verification. Lines are numbered from 1.
+#[derive(Debug)]
+pub enum AuditError {
+    Io(io::Error),
+    Malformed {
+        line: usize,
+    },
+    /// The entry content does not match its `hash`.
+    HashMismatch {
+        line: usize,
+    },
+    /// `previous_hash` does not match the hash of the preceding entry.
+    BrokenChain {
+        line: usize,
+    },
+    /// No entry has the expected head hash, entries were removed from the end.
+    MissingHead,
+}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditError::Io(e) => write!(f, "failed to read audit log: {e}"),
+            AuditError::Malformed { line } => write!(f, "line {line}: malformed entry"),
+            AuditError::HashMismatch { line } => {
+                write!(f, "line {line}: entry was modified (hash mismatch)")
+            }
+            AuditError::BrokenChain { line } => write!(
+                f,
+                "line {line}: chain is broken (previous_hash mismatch), entries were removed or reordered"
+            ),
+            AuditError::MissingHead => {
+                write!(f, "no entry has the expected head hash, the log was truncated")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AuditError {}
+
+/// An intact log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedChain {
+    pub entries: usize,
+    /// Hash of the last entry, [`GENESIS_HASH`] for an empty log.
+    pub head: String,
+}
+
+/// Check the hash chain of the log at `path`. With `head`, the hash of the last entry at some
+/// earlier point, also check that entry is still there, so the log was not truncated since.
+pub fn verify_chain(
+    path: impl AsRef<Path>,
+    head: Option<&str>,
+) -> Result<VerifiedChain, AuditError> {
+    let file = File::open(path).map_err(AuditError::Io)?;
+    let mut previous_hash = GENESIS_HASH.to_string();
+    let mut count = 0;
+    let mut found_head = head.is_none_or(|head| head == GENESIS_HASH);
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.map_err(AuditError::Io)?;
+        let entry: AuditEntry =
+            serde_json::from_str(&line).map_err(|_| AuditError::Malformed { line: line_number })?;
+        if entry.hash != entry.compute_hash() {
+            return Err(AuditError::HashMismatch { line: line_number });
+        }
+        if entry.previous_hash != previous_hash {
+            return Err(AuditError::BrokenChain { line: line_number });
+        }
+        found_head |= head == Some(entry.hash.as_str());
+        previous_hash = entry.hash;
+        count += 1;
+    }
+    if !found_head {
+        return Err(AuditError::MissingHead);
+    }
+    Ok(VerifiedChain {
+        entries: count,
+        head: previous_hash,
+    })
+}
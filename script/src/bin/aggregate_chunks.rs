@@ -0,0 +1,111 @@
+//! Aggregate the chunk proofs written by `chunk_prove` into one proof of their Merkle roots.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin aggregate_chunks -- --dir . --open 0,2
+//! ```
+//!
+//! `chunk_prove` must have written compressed proofs, the default. The aggregated proof commits
+//! the roots of the chunk plaintext and ciphertext hash trees, see `chacha_lib::aggregate`. Each
+//! `--open` index prints the inclusion proof of that chunk, to check against the plaintext root
+//! with `SP1Chacha.verifyChunk`.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use sp1_sdk::{HashableKey, ProverClient, SP1ProofWithPublicValues};
+
+use chacha_lib::aggregate::{plaintext_leaves, Aggregate};
+use chacha_lib::merkle::merkle_prove;
+use chacha_lib::ProofType;
+use chacha_script::aggregate::{aggregate_stdin, chunk_public_values};
+use chacha_script::{run_proof, AGGREGATE_ELF, CHUNK_ELF};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Directory holding `chunk_0.proof`, `chunk_1.proof`, ...
+    #[clap(long, default_value = ".")]
+    dir: PathBuf,
+
+    /// Chunks to print an inclusion proof of.
+    #[clap(long, value_delimiter = ',')]
+    open: Vec<usize>,
+
+    /// Proof system of the aggregated proof, groth16 or plonk to be verified on-chain.
+    #[clap(long, default_value_t = ProofType::Groth16)]
+    proof_type: ProofType,
+
+    /// Where to save the aggregated proof.
+    #[clap(long, default_value = "aggregate_proof.bin")]
+    output: PathBuf,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+
+    let mut proofs = Vec::new();
+    loop {
+        let path = args.dir.join(format!("chunk_{}.proof", proofs.len()));
+        if !path.exists() {
+            break;
+        }
+        proofs.push(SP1ProofWithPublicValues::load(&path).expect("failed to load proof"));
+    }
+    if let Some(&i) = args.open.iter().find(|&&i| i >= proofs.len()) {
+        eprintln!("Error: cannot open chunk {i} of {}", proofs.len());
+        std::process::exit(1);
+    }
+
+    let client = ProverClient::from_env();
+    let (_, chunk_vk) = client.setup(CHUNK_ELF);
+    let (aggregate_pk, aggregate_vk) = client.setup(AGGREGATE_ELF);
+
+    let (stdin, expected) = aggregate_stdin(&chunk_vk, &proofs).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    });
+    let proof = run_proof(&client, &aggregate_pk, &stdin, args.proof_type)
+        .expect("failed to prove the aggregation");
+    client
+        .verify(&proof, &aggregate_vk)
+        .expect("failed to verify aggregated proof");
+    proof
+        .save(&args.output)
+        .expect("failed to save aggregated proof");
+
+    let aggregate = Aggregate::parse(proof.public_values.as_slice()).expect("malformed aggregate");
+    assert_eq!(aggregate, expected, "the program aggregated other chunks");
+    println!(
+        "Aggregated {} chunks of stream 0x{}, saved to {}",
+        aggregate.chunks,
+        hex::encode(aggregate.nullifier),
+        args.output.display()
+    );
+    println!(
+        "Plaintext root: 0x{}",
+        hex::encode(aggregate.plaintext_root)
+    );
+    println!(
+        "Ciphertext root: 0x{}",
+        hex::encode(aggregate.ciphertext_root)
+    );
+    println!("Aggregation program key: {}", aggregate_vk.bytes32());
+
+    let leaves = plaintext_leaves(&chunk_public_values(&proofs).expect("checked above"));
+    for &i in &args.open {
+        let opening = merkle_prove(&leaves, i);
+        let path: Vec<_> = opening
+            .path
+            .iter()
+            .map(|node| format!("0x{}", hex::encode(node)))
+            .collect();
+        println!(
+            "Chunk {i}: leaf 0x{}, proof [{}]",
+            hex::encode(leaves[i]),
+            path.join(",")
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! Verify the hash chain of an audit log written with `--audit-log`.
+//!
+//! ```shell
+//! cargo run --release --bin audit_verify -- audit.log
+//! cargo run --release --bin audit_verify -- audit.log --head <hash>
+//! ```
+//!
+//! A log truncated after any entry is still a valid chain, so pass `--head` with the last hash
+//! printed by an earlier run, kept out of reach of the log writer, to detect removed entries.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use chacha_script::audit::verify_chain;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(default_value = "audit.log")]
+    path: PathBuf,
+
+    /// Hash the log must end with, or contain as an entry if more were appended since.
+    #[clap(long)]
+    head: Option<String>,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    match verify_chain(&args.path, args.head.as_deref()) {
+        Ok(chain) => println!(
+            "Audit log intact: {} entries, head {}",
+            chain.entries, chain.head
+        ),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Prove a batch of files across every GPU of the machine, with a fresh nonce each, saved next to
+//! each proof.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --features cuda --bin batch_prove -- --output-dir proofs \
+//!     data/*
+//! ```
+//!
+//! A `moongate` server is started per GPU on consecutive ports from `--base-port`, see
+//! [`chacha_script::gpu`] for why.
+
+use std::path::PathBuf;
+use std::process::Child;
+
+use clap::Parser;
+use sp1_sdk::{Prover, ProverClient};
+
+use chacha_lib::ProofType;
+use chacha_script::gpu::{
+    assign_proofs_to_gpus, detect_gpus, moongate_container_name, moongate_endpoint,
+    moongate_server, prove_file, prove_on_gpus, GpuInfo, ProveTask,
+};
+use chacha_script::pipeline::env_key;
+use chacha_script::CHACHA_ELF;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Files to encrypt and prove.
+    #[clap(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Directory to write the proofs to, as `<file name>.proof.bin` next to `<file name>.proof.json`
+    /// with the nonce needed to decrypt.
+    #[clap(long)]
+    output_dir: PathBuf,
+
+    /// Compressed by default, as the proofs are saved rather than sent on-chain.
+    #[clap(long, default_value_t = ProofType::Compressed)]
+    proof_type: ProofType,
+
+    /// Port of the `moongate` server of GPU 0, GPU `i` uses `base-port + i`.
+    #[clap(long, default_value_t = 3000)]
+    base_port: u16,
+
+    /// Use `moongate` servers that are already running on the ports instead of starting them.
+    #[clap(long)]
+    servers_running: bool,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    let (var, key) = env_key().expect("Missing ENCRYPTION_KEY env var");
+    let key: [u8; 32] = hex::decode(key)
+        .ok()
+        .and_then(|key| key.try_into().ok())
+        .unwrap_or_else(|| panic!("{var} must be 32 hex encoded bytes"));
+
+    let tasks: Vec<_> = args
+        .inputs
+        .iter()
+        .map(|input| {
+            let metadata = std::fs::metadata(input).unwrap_or_else(|e| {
+                eprintln!("Error: Failed to read {}: {e}", input.display());
+                std::process::exit(1);
+            });
+            ProveTask {
+                input: input.clone(),
+                size_bytes: metadata.len() as usize,
+            }
+        })
+        .collect();
+    let gpus = detect_gpus();
+    if gpus.is_empty() {
+        eprintln!("Error: No GPU visible to NVML, prove with the `chacha` binary instead");
+        std::process::exit(1);
+    }
+    if let Err(e) = std::fs::create_dir_all(&args.output_dir) {
+        eprintln!("Error: Failed to create {}: {e}", args.output_dir.display());
+        std::process::exit(1);
+    }
+
+    let assignments: Vec<_> = assign_proofs_to_gpus(&tasks, &gpus)
+        .into_iter()
+        .filter(|assignment| !assignment.tasks.is_empty())
+        .collect();
+    let port = |gpu: &GpuInfo| args.base_port + gpu.index as u16;
+    let mut servers: Vec<(String, Child)> = Vec::new();
+    for assignment in &assignments {
+        let gpu = &assignment.gpu;
+        println!(
+            "GPU {} ({}, {} MB free): {} files",
+            gpu.index,
+            gpu.name,
+            gpu.free_memory_mb,
+            assignment.tasks.len()
+        );
+        if !args.servers_running {
+            let server = moongate_server(gpu, port(gpu)).spawn().unwrap_or_else(|e| {
+                eprintln!(
+                    "Error: Failed to start the moongate server of GPU {}: {e}",
+                    gpu.index
+                );
+                std::process::exit(1);
+            });
+            servers.push((moongate_container_name(gpu), server));
+        }
+    }
+
+    // Waits until each server is ready
+    let provers: Vec<_> = assignments
+        .iter()
+        .map(|assignment| {
+            let client = ProverClient::builder()
+                .cuda()
+                .with_moongate_endpoint(&moongate_endpoint(port(&assignment.gpu)))
+                .build();
+            let (pk, _) = client.setup(CHACHA_ELF);
+            (assignment.gpu.index, client, pk)
+        })
+        .collect();
+
+    let results = prove_on_gpus(&tasks, &assignments, |gpu, task| {
+        let (_, client, pk) = provers
+            .iter()
+            .find(|(index, _, _)| *index == gpu.index)
+            .unwrap();
+        prove_file(
+            client,
+            pk,
+            key,
+            &task.input,
+            &args.output_dir,
+            args.proof_type,
+        )
+    });
+    drop(provers);
+    for (name, mut server) in servers {
+        let _ = std::process::Command::new("docker")
+            .args(["rm", "-f", &name])
+            .output();
+        let _ = server.wait();
+    }
+
+    let mut failed = 0;
+    for (task, result) in tasks.iter().zip(results) {
+        match result {
+            Some(Ok(path)) => println!("{}: saved to {}", task.input.display(), path.display()),
+            Some(Err(e)) => {
+                eprintln!("{}: Error: {e}", task.input.display());
+                failed += 1;
+            }
+            None => unreachable!("every task is assigned to a GPU"),
+        }
+    }
+    if failed > 0 {
+        eprintln!("Error: {failed} of {} files failed", tasks.len());
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,81 @@
+//! Prove an encryption chunk by chunk, so a verifier can check chunks as they arrive.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin chunk_prove -- --input <path> --chunk-size 65536
+//! ```
+//!
+//! Every chunk is proved independently at its keystream offset and saved to
+//! `<output-dir>/chunk_<i>.proof`. Check them with the `chunk_verify` binary.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use hex::FromHex;
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::ProofType;
+use chacha_script::{run_proof, CHUNK_ELF};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// File to encrypt. Defaults to the bundled example input.
+    #[clap(long)]
+    input: Option<PathBuf>,
+
+    /// Bytes per chunk, a multiple of the 64 byte ChaCha20 block.
+    #[clap(long, default_value = "65536")]
+    chunk_size: usize,
+
+    /// Directory to write the chunk proofs to.
+    #[clap(long, default_value = ".")]
+    output_dir: PathBuf,
+
+    /// Compressed by default, as chunks are verified off-chain as they arrive.
+    #[clap(long, default_value_t = ProofType::Compressed)]
+    proof_type: ProofType,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    if args.chunk_size == 0 || args.chunk_size % 64 != 0 {
+        eprintln!("Error: --chunk-size must be a non-zero multiple of 64");
+        std::process::exit(1);
+    }
+
+    let key = <[u8; 32]>::from_hex(
+        std::env::var("ENCRYPTION_KEY").expect("Missing ENCRYPTION_KEY env var"),
+    )
+    .expect("Key must be 32 bytes");
+    let nonce: [u8; 12] = chacha_lib::random_nonce();
+    let plaintext: Vec<u8> = match &args.input {
+        Some(path) => std::fs::read(path).expect("Failed to read input file"),
+        None => chacha_lib::INPUT_BYTES.to_vec(),
+    };
+
+    let client = ProverClient::from_env();
+    let (pk, _) = client.setup(CHUNK_ELF);
+
+    let chunks = plaintext.chunks(args.chunk_size);
+    let count = u32::try_from(chunks.len()).expect("input too large");
+    for (i, chunk) in chunks.enumerate() {
+        let counter = u32::try_from(i * args.chunk_size / 64).expect("input too large");
+        let mut stdin = SP1Stdin::new();
+        stdin.write_slice(&key);
+        stdin.write_slice(&nonce);
+        stdin.write(&counter);
+        stdin.write(&count);
+        stdin.write_slice(chunk);
+
+        let proof =
+            run_proof(&client, &pk, &stdin, args.proof_type).expect("failed to generate proof");
+
+        let path = args.output_dir.join(format!("chunk_{i}.proof"));
+        proof.save(&path).expect("failed to save proof");
+        println!("Proved chunk {}/{count} to {}", i + 1, path.display());
+    }
+    println!("Nonce: {}", hex::encode(nonce));
+}
@@ -0,0 +1,85 @@
+//! Verify the chunk proofs written by `chunk_prove` and reassemble the ciphertext.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin chunk_verify -- --dir . --output ciphertext.bin
+//! ```
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use sp1_sdk::{HashableKey, ProverClient, SP1ProofWithPublicValues};
+
+use chacha_lib::aggregate::{aggregate, ChunkPublicValues};
+use chacha_lib::upgrade::vkey_bytes32;
+use chacha_script::CHUNK_ELF;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Directory holding `chunk_0.proof`, `chunk_1.proof`, ...
+    #[clap(long, default_value = ".")]
+    dir: PathBuf,
+
+    /// Where to write the reassembled ciphertext.
+    #[clap(long, default_value = "ciphertext.bin")]
+    output: PathBuf,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+
+    let args = Args::parse();
+
+    let client = ProverClient::from_env();
+    let (_, vk) = client.setup(CHUNK_ELF);
+
+    let path = |i: usize| args.dir.join(format!("chunk_{i}.proof"));
+    if !path(0).exists() {
+        eprintln!("Error: no chunk proofs found in {}", args.dir.display());
+        std::process::exit(1);
+    }
+    let load = |i: usize| {
+        let proof = SP1ProofWithPublicValues::load(path(i)).unwrap_or_else(|e| {
+            eprintln!("Error: chunk {i} is missing or unreadable: {e}");
+            std::process::exit(1);
+        });
+        client.verify(&proof, &vk).unwrap_or_else(|e| {
+            eprintln!("Error: chunk {i} failed verification: {e}");
+            std::process::exit(1);
+        });
+        let chunk = ChunkPublicValues::parse(proof.public_values.as_slice()).unwrap_or_else(|| {
+            eprintln!("Error: chunk {i} public values are too short");
+            std::process::exit(1);
+        });
+        println!("Verified chunk {i}");
+        chunk
+    };
+
+    // The first chunk commits how many chunks the stream has
+    let first = load(0);
+    let count = first.chunks as usize;
+    let mut chunks = vec![first];
+    chunks.extend((1..count).map(load));
+    if path(count).exists() {
+        eprintln!("Error: more chunk proofs than the {count} the stream commits");
+        std::process::exit(1);
+    }
+
+    // Every chunk must belong to the same stream, start where the previous one ended and commit the
+    // same chunk count
+    if let Err(e) = aggregate(vkey_bytes32(&vk.hash_u32()), &chunks) {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+
+    let ciphertext: Vec<u8> = chunks
+        .iter()
+        .flat_map(|chunk| chunk.ciphertext.iter().copied())
+        .collect();
+    std::fs::write(&args.output, &ciphertext).expect("Failed to write ciphertext");
+    println!(
+        "Wrote {} bytes of ciphertext from {count} chunks to {}",
+        ciphertext.len(),
+        args.output.display()
+    );
+}
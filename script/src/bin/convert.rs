@@ -0,0 +1,67 @@
+//! Convert a saved proof of the encryption program to another proof type.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin convert -- --from-file proof.bin --to-type groth16 --output groth16.bin
+//! ```
+//!
+//! Core proofs convert to compressed, Groth16 and PLONK, compressed proofs to Groth16 and PLONK.
+//! Other conversions need the original inputs, see `chacha_script::convert`.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use sp1_sdk::{Prover, ProverClient, SP1ProofWithPublicValues};
+
+use chacha_lib::ProofType;
+use chacha_script::convert::{convert, proof_type};
+use chacha_script::CHACHA_ELF;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Proof saved with `SP1ProofWithPublicValues::save`.
+    #[clap(long)]
+    from_file: PathBuf,
+
+    /// core, compressed, groth16 or plonk.
+    #[clap(long)]
+    to_type: ProofType,
+
+    #[clap(long)]
+    output: PathBuf,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+    let args = Args::parse();
+
+    let proof = SP1ProofWithPublicValues::load(&args.from_file).unwrap_or_else(|e| {
+        eprintln!("Error: Failed to load {}: {e}", args.from_file.display());
+        std::process::exit(1);
+    });
+    let from = proof_type(&proof.proof);
+
+    let client = ProverClient::from_env();
+    let (pk, vk) = client.setup(CHACHA_ELF);
+    if let Err(e) = client.verify(&proof, &vk) {
+        eprintln!("Error: The proof is not a valid proof of the encryption program: {e}");
+        std::process::exit(1);
+    }
+
+    let converted = convert(client.inner(), &pk, proof, args.to_type).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    });
+    client
+        .verify(&converted, &vk)
+        .expect("failed to verify converted proof");
+    converted
+        .save(&args.output)
+        .expect("failed to save converted proof");
+    println!(
+        "Converted {from} proof to {}, saved to {}",
+        args.to_type,
+        args.output.display()
+    );
+}
@@ -0,0 +1,52 @@
+//! Export a saved Groth16 or PLONK proof as JSON for EVM tooling, e.g. `examples/hardhat`.
+//!
+//! ```shell
+//! cargo run --release --bin evm_fixture -- --from-file proof.bin --output fixture.json
+//! ```
+//!
+//! The fixture holds the `vkey`, `publicValues` and `proof` arguments of `SP1Chacha`, hex encoded.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use serde_json::json;
+use sp1_sdk::{HashableKey, Prover, ProverClient};
+
+use chacha_script::error::ScriptError;
+use chacha_script::post::proof_bytes;
+use chacha_script::storage::load_proof;
+use chacha_script::{select_elf, ProgramSelector};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Proof saved with `SP1ProofWithPublicValues::save`, e.g. by `--output`.
+    #[clap(long)]
+    from_file: PathBuf,
+
+    /// The program the proof is of.
+    #[clap(long, default_value_t = ProgramSelector::Encrypt)]
+    program: ProgramSelector,
+
+    #[clap(long)]
+    output: PathBuf,
+}
+
+fn main() -> Result<(), ScriptError> {
+    let args = Args::parse();
+    let proof = load_proof(&args.from_file)?;
+    let proof_bytes = proof_bytes(&proof)?;
+
+    let (_, vk) = ProverClient::builder()
+        .cpu()
+        .build()
+        .setup(select_elf(args.program));
+    let fixture = json!({
+        "vkey": vk.bytes32(),
+        "publicValues": format!("0x{}", hex::encode(proof.public_values.as_slice())),
+        "proof": format!("0x{}", hex::encode(proof_bytes)),
+    });
+    std::fs::write(&args.output, format!("{fixture:#}\n"))?;
+    println!("Wrote {}", args.output.display());
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! Create a development CA with a server and a client certificate for the mutual TLS of the
+//! `server` binary.
+//!
+//! ```shell
+//! cargo run --release --bin gen_certs -- --out-dir certs
+//! ```
+//!
+//! Writes `ca.pem`, `server.pem`, `server.key`, `client.pem` and `client.key`, see
+//! `chacha_script::tls`.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use chacha_script::tls::generate_certs;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(long, default_value = "certs")]
+    out_dir: PathBuf,
+
+    /// DNS names and IP addresses the server certificate is valid for.
+    #[clap(long, num_args = 1.., default_values = ["localhost", "127.0.0.1"])]
+    server_name: Vec<String>,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let certs = generate_certs(args.server_name).unwrap_or_else(|e| {
+        eprintln!("Error: Failed to generate the certificates: {e}");
+        std::process::exit(1);
+    });
+    certs.write(&args.out_dir).unwrap_or_else(|e| {
+        eprintln!("Error: Failed to write {}: {e}", args.out_dir.display());
+        std::process::exit(1);
+    });
+    println!("Wrote the certificates to {}", args.out_dir.display());
+}
@@ -0,0 +1,47 @@
+//! Generate an encryption key, or split a fresh key into share files for distributed custody.
+//!
+//! ```shell
+//! cargo run --release --bin keygen
+//! cargo run --release --bin keygen -- --key-split 5/3 --output-dir shares
+//! ```
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use chacha_lib::Key;
+use chacha_script::key_shares::{write_shares, KeySplit};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Split the key into `<n>` share files, any `<t>` of which reconstruct it, instead of
+    /// printing it. The key itself is never written.
+    #[clap(long, value_name = "N/T")]
+    key_split: Option<KeySplit>,
+
+    /// Directory of the share files.
+    #[clap(long, default_value = ".", requires = "key_split")]
+    output_dir: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+    let key = Key::random();
+
+    let Some(split) = args.key_split else {
+        println!("{}", hex::encode(key.0));
+        return;
+    };
+    let paths = write_shares(&key, split, &args.output_dir).unwrap_or_else(|e| {
+        eprintln!("Error: Failed to write the key shares: {e}");
+        std::process::exit(1);
+    });
+    for path in &paths {
+        println!("Wrote {}", path.display());
+    }
+    println!(
+        "Any {} of the {} shares reconstruct the key, with --key-shares",
+        split.threshold, split.shares
+    );
+}
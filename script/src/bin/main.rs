@@ -9,20 +9,70 @@
 //! ```shell
 //! RUST_LOG=info cargo run --release -- --prove
 //! ```
+//! or, to execute small inputs and prove large ones
+//! ```shell
+//! RUST_LOG=info cargo run --release -- --auto --input <path>
+//! ```
+//! or, to restart a proof saved with `--prove --checkpoint-dir <dir>` that was interrupted
+//! ```shell
+//! RUST_LOG=info cargo run --release -- --resume <dir>
+//! ```
 
-use clap::Parser;
-use hex::FromHex;
-use sha2::{Digest, Sha256};
-use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use chacha_lib::chacha;
+use alloy::primitives::utils::format_units;
+use alloy::primitives::Address;
+use anyhow::{anyhow, Context};
+use clap::{ArgGroup, Parser};
+use hex::FromHex;
+use serde_json::json;
+use sha2::{Digest, Sha256, Sha512};
+use sp1_sdk::{
+    EnvProver, HashableKey, ProverClient, SP1ProofWithPublicValues, SP1ProvingKey, SP1Stdin,
+};
 
-/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
-pub const CHACHA_ELF: &[u8] = include_elf!("chacha-program");
+use chacha_lib::aes_mode::{aes_ctr_encrypt, iv_from_nonce};
+use chacha_lib::blake2b::{blake2b_256, personalization, PERSONAL_LEN};
+use chacha_lib::compress::{decompress, ZSTD_DEFAULT_LEVEL};
+use chacha_lib::custody::OPERATOR_ID_LEN;
+use chacha_lib::deterministic_nonce::deterministic_nonce;
+use chacha_lib::domain::domain_separate;
+use chacha_lib::input_auth::{hmac_key_hash, INPUT_HMAC_FAILED};
+use chacha_lib::public_values::{Sha512PublicValues, SHA512_HASH_LEN};
+use chacha_lib::selective::segment_commit;
+use chacha_lib::{
+    chacha, compute_nullifier, custody_hash, lineage_commitment, timestamp_commitment,
+    CompressionType, Key, Nonce, ProofType, PROGRAM_VERSION,
+};
+use chacha_script::analysis::ConstraintAnalysis;
+use chacha_script::audit::AuditLog;
+use chacha_script::checkpoint::{Checkpoint, CheckpointMeta};
+use chacha_script::dry_run::{check_inputs, format_errors, DryRunInputs, DryRunSummary};
+use chacha_script::error::ScriptError;
+use chacha_script::estimate::Mode;
+use chacha_script::flamegraph::export_flamegraph;
+use chacha_script::key_shares::read_key_shares;
+use chacha_script::metrics::push::push_metrics;
+use chacha_script::metrics::ProveMetrics;
+use chacha_script::pipeline::{self, env_key, PipelineArgs};
+use chacha_script::post::{post_tx, verify_calldata, ExpectedHashes};
+use chacha_script::storage::ipfs::{ipfs_upload, DEFAULT_ENDPOINT};
+use chacha_script::storage::s3::upload_proof;
+use chacha_script::storage::ProofFile;
+use chacha_script::summary::ProofSummary;
+use chacha_script::timestamp::fetch_timestamp;
+use chacha_script::{
+    cost, estimate, parse_public_values, run_proof, validate_prove_inputs, EncryptInputs,
+    ProgramSelector, AES_ELF, BLAKE2B_ELF, CHACHA_ELF, SHA512_ELF,
+};
 
 /// The arguments for the command.
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
+// Flags only the ChaCha20 encryption program supports, and the modes that run another program
+#[clap(group(ArgGroup::new("chacha_only").multiple(true)))]
+#[clap(group(ArgGroup::new("program_mode")))]
 struct Args {
     #[clap(long)]
     execute: bool,
@@ -30,11 +80,257 @@ struct Args {
     #[clap(long)]
     prove: bool,
 
+    /// Write the opcode counts of the execution as folded stacks to this file, to render with
+    /// `inferno-flamegraph < <path> > flamegraph.svg`.
+    #[clap(long, group = "chacha_only")]
+    flamegraph: Option<PathBuf>,
+
+    /// Execute inputs smaller than `--auto-prove-threshold` and prove larger ones.
+    #[clap(long)]
+    auto: bool,
+
+    /// Input size in bytes from which `--auto` proves instead of executing.
+    #[clap(long, default_value_t = estimate::AUTO_PROVE_THRESHOLD_BYTES)]
+    auto_prove_threshold: usize,
+
     #[clap(long, default_value = "20")]
     n: u32,
+
+    #[clap(flatten)]
+    pipeline: PipelineArgs,
+
+    /// Exit early if the GPU does not have enough free memory for this input.
+    #[clap(long)]
+    check_gpu_memory: bool,
+
+    /// Compress the plaintext before encrypting: none, lz4 or zstd.
+    #[clap(long, group = "chacha_only", default_value_t = CompressionType::None)]
+    compress: CompressionType,
+
+    /// Zstandard level for `--compress zstd`, from 1 (fastest) to 22 (smallest).
+    #[clap(long, default_value_t = ZSTD_DEFAULT_LEVEL, value_parser = clap::value_parser!(i32).range(1..=22))]
+    zstd_level: i32,
+
+    /// The program to run, this binary runs `encrypt` and names the binary of any other.
+    #[clap(long, default_value_t = ProgramSelector::Encrypt)]
+    program: ProgramSelector,
+
+    /// Check every input and execute the program for its cycle count, then exit without proving.
+    #[clap(long, group = "chacha_only")]
+    dry_run: bool,
+
+    /// Commit a BLAKE2b-256 instead of a SHA-256 plaintext hash, for Zcash compatibility. Same as
+    /// `--program blake2b`.
+    #[clap(long, group = "program_mode", conflicts_with = "chacha_only")]
+    blake2b: bool,
+
+    /// Commit the 64 byte SHA-512 instead of the SHA-256 plaintext hash, for a 256 bit collision
+    /// resistance at about twice the hashing cycles. Same as `--program sha512`.
+    #[clap(long, group = "program_mode", conflicts_with = "chacha_only")]
+    sha512: bool,
+
+    /// Encrypt with AES-256-CTR instead of ChaCha20, for environments restricted to FIPS-approved
+    /// algorithms. Same as `--program aes`, see `chacha_lib::aes_mode`.
+    #[clap(long, group = "program_mode", conflicts_with = "chacha_only")]
+    fips_mode: bool,
+
+    /// BLAKE2b personalization of up to 16 bytes, e.g. `ZcashPoW`, zero padded.
+    #[clap(long, value_parser = parse_personal, default_value = "")]
+    blake2b_personal: [u8; PERSONAL_LEN],
+
+    /// Print the estimated proving cost in USD and exit without proving.
+    #[clap(long)]
+    cost_estimate: bool,
+
+    /// Print the ChaCha20 operation counts of the input against a lookup argument based chip, see
+    /// `chacha_script::analysis`, and exit without proving.
+    #[clap(long)]
+    analyze_constraints: bool,
+
+    /// Instance price per hour in USD for `--cost-estimate`, overriding `--instance`.
+    #[clap(long)]
+    price_per_hour: Option<f64>,
+
+    /// Cloud GPU instance for `--cost-estimate`: l4, a10g, a100 or h100.
+    #[clap(long, default_value = "l4")]
+    instance: String,
+
+    /// Encrypt with a fresh random key instead of `ENCRYPTION_KEY`. The key is never saved.
+    #[clap(long)]
+    ephemeral_key: bool,
+
+    /// Reconstruct the key from these share files of `keygen --key-split` instead of reading
+    /// `ENCRYPTION_KEY`, comma separated. At least the threshold of them are needed.
+    #[clap(long, value_delimiter = ',', conflicts_with = "ephemeral_key")]
+    key_shares: Vec<PathBuf>,
+
+    /// Encrypt with the HKDF subkey of the key for this domain, e.g. `encrypt`.
+    #[clap(long)]
+    domain: Option<String>,
+
+    /// Commit to the plaintext segments and open those overlapping this byte range, e.g. `64..96`.
+    /// Can be repeated.
+    #[clap(long, group = "chacha_only", value_parser = parse_range)]
+    disclose: Vec<(usize, usize)>,
+
+    /// Where to write the segment openings of `--disclose`.
+    #[clap(long, default_value = "disclosure.json")]
+    disclosure_output: PathBuf,
+
+    /// Commit only SHA256(ciphertext) instead of the ciphertext, for smaller public values.
+    #[clap(long, group = "chacha_only")]
+    commit_ciphertext_hash: bool,
+
+    /// Append the generated proof to this tamper-evident log.
+    #[clap(long, group = "chacha_only")]
+    audit_log: Option<PathBuf>,
+
+    /// Commit to a timestamp signed over the plaintext hash by the time oracle at this URL.
+    #[clap(long, group = "chacha_only", requires = "timestamp_key")]
+    timestamp_url: Option<String>,
+
+    /// Hex encoded ed25519 public key of the `--timestamp-url` oracle.
+    #[clap(long)]
+    timestamp_key: Option<String>,
+
+    /// Hex encoded 16 byte ID of the operator generating the proof. The proof commits to it and the
+    /// current time in its custody hash without revealing either.
+    #[clap(long, group = "chacha_only", value_parser = parse_operator_id)]
+    operator_id: Option<[u8; OPERATOR_ID_LEN]>,
+
+    /// Hex encoded lineage commitment of the previous pipeline stage's proof, or 32 zero bytes for
+    /// the first stage. The proof commits `SHA256(lineage_hash || plaintext_hash)`, the lineage
+    /// hash of the next stage.
+    #[clap(long, group = "chacha_only", value_parser = parse_lineage_hash)]
+    lineage_hash: Option<[u8; 32]>,
+
+    /// Hex encoded 32 byte HMAC key shared with whoever checks the inputs, separate from the
+    /// encryption key. The program checks an HMAC-SHA256 of all its other inputs under it, and
+    /// commits only 32 zero bytes if it does not match, see `chacha_lib::input_auth`.
+    #[clap(long, group = "chacha_only", value_parser = parse_hmac_key)]
+    hmac_key: Option<[u8; 32]>,
+
+    /// Save the inputs and proving key here before proving, to `--resume` if interrupted.
+    /// The saved inputs include the encryption key.
+    #[clap(long, group = "chacha_only")]
+    checkpoint_dir: Option<PathBuf>,
+
+    /// Restart the interrupted proof saved in this `--checkpoint-dir`, skipping the setup.
+    #[clap(long, group = "chacha_only", conflicts_with = "checkpoint_dir")]
+    resume: Option<PathBuf>,
+
+    /// Submit the proof to the `SP1Chacha` contract after proving.
+    #[clap(
+        long,
+        group = "chacha_only",
+        requires_all = ["rpc_url", "contract", "private_key"],
+        conflicts_with = "commit_ciphertext_hash"
+    )]
+    post_tx: bool,
+
+    /// JSON-RPC endpoint of the chain for `--post-tx`.
+    #[clap(long, env = "RPC_URL")]
+    rpc_url: Option<String>,
+
+    /// Address of the `SP1Chacha` contract for `--post-tx`.
+    #[clap(long)]
+    contract: Option<Address>,
+
+    /// Hex encoded key of the account paying for `--post-tx`.
+    #[clap(long, env = "PRIVATE_KEY", hide_env_values = true)]
+    private_key: Option<String>,
+
+    /// Confirmations to wait for after `--post-tx`.
+    #[clap(long, default_value_t = 1)]
+    wait_for_confirmation: u64,
+
+    /// Fail unless the proof commits this SHA256 of the plaintext, the hash an on-chain verifier
+    /// will check it against.
+    #[clap(long, group = "chacha_only", value_parser = parse_hash)]
+    expected_plaintext_hash: Option<[u8; 32]>,
+
+    /// Fail unless the proof commits a ciphertext with this SHA256, or this ciphertext hash with
+    /// `--commit-ciphertext-hash`.
+    #[clap(long, group = "chacha_only", value_parser = parse_hash)]
+    expected_ciphertext_hash: Option<[u8; 32]>,
+
+    /// Write the hex encoded `SP1Chacha.verifyEncryption` calldata of the proof to this file, for
+    /// `cast send <SP1Chacha> $(cat <path>)`.
+    #[clap(long, group = "chacha_only", conflicts_with = "commit_ciphertext_hash")]
+    calldata_output: Option<PathBuf>,
+
+    /// Upload the proof to this S3 bucket after proving, with credentials from the standard AWS
+    /// environment variables.
+    #[clap(long, group = "chacha_only", requires = "s3_key")]
+    s3_bucket: Option<String>,
+
+    /// Object key of the proof in `--s3-bucket`, e.g. `proofs/<nonce>.bin`.
+    #[clap(long, requires = "s3_bucket")]
+    s3_key: Option<String>,
+
+    /// Add the proof to IPFS after proving, and print its CID in the summary.
+    #[clap(long, group = "chacha_only")]
+    ipfs: bool,
+
+    /// HTTP RPC API of the IPFS node for `--ipfs`.
+    #[clap(long, default_value = DEFAULT_ENDPOINT)]
+    ipfs_endpoint: String,
+
+    /// Pin the proof on the `--ipfs` node, so its garbage collection keeps it.
+    #[clap(long, requires = "ipfs")]
+    pin: bool,
+
+    /// Push the cycle count, proving time, input size, proof type and outcome to this Prometheus
+    /// Pushgateway after proving, e.g. `http://localhost:9091`. Costs an extra execution.
+    #[clap(long, group = "chacha_only")]
+    metrics_push_gateway: Option<String>,
+
+    /// Job name of the pushed metrics.
+    #[clap(long, default_value = "sp1-chacha")]
+    metrics_job: String,
 }
 
-fn main() {
+/// Parse a `start..end` byte range.
+fn parse_range(s: &str) -> Result<(usize, usize), String> {
+    let (start, end) = s
+        .split_once("..")
+        .ok_or_else(|| format!("expected `start..end`, got `{s}`"))?;
+    let start = start.parse().map_err(|e| format!("invalid start: {e}"))?;
+    let end = end.parse().map_err(|e| format!("invalid end: {e}"))?;
+    if start >= end {
+        return Err(format!("empty range `{s}`"));
+    }
+    Ok((start, end))
+}
+
+/// Parse a hex encoded operator ID.
+fn parse_operator_id(s: &str) -> Result<[u8; OPERATOR_ID_LEN], String> {
+    <[u8; OPERATOR_ID_LEN]>::from_hex(s)
+        .map_err(|_| format!("expected {OPERATOR_ID_LEN} hex encoded bytes, got `{s}`"))
+}
+
+/// Parse a hex encoded lineage hash.
+fn parse_lineage_hash(s: &str) -> Result<[u8; 32], String> {
+    <[u8; 32]>::from_hex(s).map_err(|_| format!("expected 32 hex encoded bytes, got `{s}`"))
+}
+
+/// Parse a hex encoded HMAC key.
+fn parse_hmac_key(s: &str) -> Result<[u8; 32], String> {
+    <[u8; 32]>::from_hex(s).map_err(|_| format!("expected 32 hex encoded bytes, got `{s}`"))
+}
+
+/// Parse a hex encoded SHA256 hash, with or without `0x`.
+fn parse_hash(s: &str) -> Result<[u8; 32], String> {
+    <[u8; 32]>::from_hex(s.strip_prefix("0x").unwrap_or(s))
+        .map_err(|_| format!("expected 32 hex encoded bytes, got `{s}`"))
+}
+
+/// Parse a BLAKE2b personalization.
+fn parse_personal(s: &str) -> Result<[u8; PERSONAL_LEN], String> {
+    personalization(s.as_bytes()).ok_or_else(|| format!("longer than {PERSONAL_LEN} bytes: `{s}`"))
+}
+
+fn main() -> Result<(), ScriptError> {
     // Setup the logger.
     sp1_sdk::utils::setup_logger();
     dotenv::dotenv().ok();
@@ -42,54 +338,425 @@ fn main() {
     // Parse the command line arguments.
     let args = Args::parse();
 
-    if args.execute == args.prove {
-        eprintln!("Error: You must specify either --execute or --prove");
-        std::process::exit(1);
+    let blake2b = args.blake2b || args.program == ProgramSelector::Blake2b;
+    let sha512 = args.sha512 || args.program == ProgramSelector::Sha512;
+    let fips_mode = args.fips_mode || args.program == ProgramSelector::Aes;
+    if !matches!(
+        args.program,
+        ProgramSelector::Encrypt
+            | ProgramSelector::Blake2b
+            | ProgramSelector::Sha512
+            | ProgramSelector::Aes
+    ) {
+        return Err(match args.program.binary() {
+            Some(binary) => anyhow!(
+                "The {} program is run by its own binary, use cargo run --release --bin {binary}",
+                args.program
+            ),
+            None => anyhow!("The {} program has no script binary", args.program),
+        }
+        .into());
+    }
+
+    if args.analyze_constraints {
+        println!("{}", ConstraintAnalysis::new(input_size(&args)?));
+        return Ok(());
+    }
+
+    if args.cost_estimate {
+        let input_size = input_size(&args)?;
+        let price = match args.price_per_hour {
+            Some(price) => price,
+            None => cost::instance_price_per_hour(&args.instance).with_context(|| {
+                format!("Unknown instance `{}`, use --price-per-hour", args.instance)
+            })?,
+        };
+        println!(
+            "Estimated {} proof of {input_size} bytes: ~{} cycles, ~{:.1} min, ~${:.2} at ${price:.2}/h",
+            args.pipeline.proof_type,
+            estimate::estimate_cycles(input_size),
+            cost::estimate_proving_minutes(input_size, args.pipeline.proof_type),
+            cost::estimate_proving_cost_usd(input_size, args.pipeline.proof_type, price),
+        );
+        return Ok(());
+    }
+
+    if args.dry_run {
+        return dry_run(&args);
+    }
+
+    if [args.execute, args.prove, args.auto, args.resume.is_some()]
+        .iter()
+        .filter(|&&mode| mode)
+        .count()
+        != 1
+    {
+        return Err(
+            anyhow!("You must specify one of --execute, --prove, --auto or --resume").into(),
+        );
+    }
+
+    if (args.post_tx || args.calldata_output.is_some())
+        && !matches!(
+            args.pipeline.proof_type,
+            ProofType::Groth16 | ProofType::Plonk
+        )
+    {
+        return Err(
+            anyhow!("--post-tx and --calldata-output need --proof-type groth16 or plonk").into(),
+        );
+    }
+
+    if let Some(dir) = &args.resume {
+        let (checkpoint, stdin, pk, meta) = Checkpoint::load(dir)
+            .with_context(|| format!("Failed to load the checkpoint in {}", dir.display()))?;
+        // The audit log hashes the plaintext, which the checkpoint only holds compressed
+        let input_plaintext = match &args.audit_log {
+            Some(_) => {
+                let input_plaintext = read_input(&args)?;
+                if Sha256::digest(&input_plaintext).as_slice() != meta.plaintext_hash {
+                    return Err(anyhow!(
+                        "--input does not match the checkpoint in {}, pass the --input of the \
+                         interrupted session",
+                        dir.display()
+                    )
+                    .into());
+                }
+                Some(input_plaintext)
+            }
+            None => None,
+        };
+        println!("Resuming the proof saved in {}", dir.display());
+        let client = ProverClient::from_env();
+        return prove(
+            &client,
+            &args,
+            &stdin,
+            &pk,
+            &meta,
+            input_plaintext.as_deref(),
+            Some(checkpoint),
+        );
+    }
+    let interrupted = args
+        .checkpoint_dir
+        .as_ref()
+        .filter(|dir| Checkpoint::is_interrupted(dir));
+    if let Some(dir) = interrupted {
+        return Err(anyhow!(
+            "{0} holds the checkpoint of an interrupted proving session. Rerun with --resume {0} \
+             to continue it, or delete it to start over.",
+            dir.display()
+        )
+        .into());
     }
 
-    let mut stdin = SP1Stdin::new();
     // Setup the inputs:
     // - key = 32 bytes
     // - nonce = 12 bytes (MUST BE UNIQUE - NO REUSE!)
+    // - compression = 1 byte
     // - input_plaintext = bytes to encrypt
+    let key = if args.ephemeral_key {
+        eprintln!(
+            "WARNING: Using an ephemeral key. It is NOT logged or saved, \
+             losing it means losing the ability to decrypt the ciphertext."
+        );
+        Key::random().0.to_vec()
+    } else if !args.key_shares.is_empty() {
+        shared_key(&args.key_shares)?.0.to_vec()
+    } else {
+        let (var, key) = env_key().context("Missing ENCRYPTION_KEY env var")?;
+        if var == pipeline::KEY_VAR {
+            eprintln!("{}", pipeline::KEY_WARNING);
+        }
+        hex::decode(key).with_context(|| format!("{var} must be hex"))?
+    };
 
-    let key = <[u8; 32]>::from_hex(
-        std::env::var("ENCRYPTION_KEY").expect("Missing ENCRYPTION_KEY env var"),
-    )
-    .expect("Key must be 32 bytes");
-    stdin.write_slice(&key);
-
-    let nonce: [u8; 12] = chacha_lib::random_nonce();
-    stdin.write_slice(&nonce);
+    let nonce: [u8; 12] = nonce(&args)?.unwrap_or_else(chacha_lib::random_nonce);
 
     // TODO: replace example bytes with service interface
-    let input_plaintext: &[u8] = chacha_lib::INPUT_BYTES;
-    stdin.write_slice(input_plaintext);
+    let input_plaintext = read_input(&args)?;
+    if input_plaintext.is_empty() {
+        // Valid, but a proof of nothing is more likely a wrong --input than intended
+        eprintln!("WARNING: The input is empty, the proof will attest an empty plaintext.");
+    }
+
+    let mode = if args.auto {
+        let mode = estimate::auto_mode(input_plaintext.len(), args.auto_prove_threshold);
+        let (action, reason) = match mode {
+            Mode::Execute => ("executing", "below"),
+            Mode::Prove => ("proving", "at or above"),
+        };
+        println!(
+            "Auto mode: {action}, the {} byte input is {reason} the {} byte threshold",
+            input_plaintext.len(),
+            args.auto_prove_threshold
+        );
+        mode
+    } else if args.execute {
+        Mode::Execute
+    } else {
+        Mode::Prove
+    };
+
+    // Catch bad inputs here rather than as a zkVM panic after the prover setup
+    validate_prove_inputs(&key, &nonce, &input_plaintext).map_err(anyhow::Error::new)?;
+    let key: [u8; 32] = key
+        .try_into()
+        .map_err(|key: Vec<u8>| anyhow!("Key must be 32 bytes, got {}", key.len()))?;
+    let key = match &args.domain {
+        Some(domain) => domain_separate(&Key(key), domain.as_bytes()).0,
+        None => key,
+    };
+    // From the key ChaCha20 uses, so keys of different `--domain`s get different nonces
+    let nonce = if args.pipeline.deterministic_nonce {
+        eprintln!(
+            "WARNING: Deriving the nonce from the plaintext. Equal plaintexts encrypt to equal \
+             ciphertexts under this key, revealing that they are equal."
+        );
+        deterministic_nonce(&Key(key), &input_plaintext).0
+    } else {
+        nonce
+    };
+
+    if blake2b {
+        return encrypt_blake2b(&args, mode, key, nonce, &input_plaintext);
+    }
+    if sha512 {
+        return encrypt_sha512(&args, mode, key, nonce, &input_plaintext);
+    }
+    if fips_mode {
+        return encrypt_aes(&args, mode, key, nonce, &input_plaintext);
+    }
+
+    let plaintext_hash: [u8; 32] = Sha256::digest(&input_plaintext).into();
+    let timestamp = match (&args.timestamp_url, &args.timestamp_key) {
+        (Some(url), Some(timestamp_key)) => {
+            let oracle_key = <[u8; 32]>::from_hex(timestamp_key)
+                .context("Timestamp key must be 32 hex encoded bytes")?;
+            let timestamp = fetch_timestamp(url, &oracle_key, &plaintext_hash)
+                .with_context(|| format!("Failed to fetch a timestamp from {url}"))?;
+            println!(
+                "Oracle timestamp {}, timestamp bytes: {}",
+                timestamp.timestamp,
+                hex::encode(timestamp.to_bytes())
+            );
+            Some(timestamp.to_bytes().to_vec())
+        }
+        _ => None,
+    };
+    let custody = match args.operator_id {
+        Some(operator_id) => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .context("System time is before the unix epoch")?
+                .as_secs();
+            println!(
+                "Custody: operator {} at {now}, a verifier needs both to check the custody hash",
+                hex::encode(operator_id)
+            );
+            Some((operator_id, now))
+        }
+        None => None,
+    };
+
+    let inputs = EncryptInputs {
+        compression: args.compress,
+        compression_level: Some(args.zstd_level),
+        commit_segments: !args.disclose.is_empty(),
+        commit_ciphertext_hash: args.commit_ciphertext_hash,
+        timestamp: timestamp.clone(),
+        custody,
+        lineage: args.lineage_hash,
+        input_hmac_key: args.hmac_key,
+        ..EncryptInputs::new(key, nonce, &input_plaintext)
+    };
+    let stdin = inputs.to_stdin();
+
+    let segments = segment_commit(&input_plaintext, &args.disclose);
+    if !args.disclose.is_empty() {
+        let disclosure = json!({
+            "segments_root": hex::encode(segments.root),
+            "segments": segments.proofs.iter().map(|proof| json!({
+                "index": proof.index,
+                "offset": proof.offset(),
+                "segment": hex::encode(&proof.segment),
+                "path": proof.path.iter().map(hex::encode).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>(),
+        });
+        std::fs::write(&args.disclosure_output, disclosure.to_string()).with_context(|| {
+            format!(
+                "Failed to write the disclosure to {}",
+                args.disclosure_output.display()
+            )
+        })?;
+        println!(
+            "Wrote {} segment openings to {}",
+            segments.proofs.len(),
+            args.disclosure_output.display()
+        );
+    }
+
+    if args.check_gpu_memory {
+        let available_mb =
+            estimate::check_available_gpu_memory(&estimate::NvmlGpuMemory, input_plaintext.len())
+                .map_err(anyhow::Error::new)?;
+        println!(
+            "GPU memory check passed: ~{} MB needed, {} MB free.",
+            estimate::estimate_gpu_memory_mb(input_plaintext.len()),
+            available_mb
+        );
+    }
 
     let client = ProverClient::from_env();
-    if args.execute {
+    if mode == Mode::Execute {
         // Execute the program
-        let (output, report) = client.execute(CHACHA_ELF, &stdin).run().unwrap();
+        let (output, report) = client
+            .execute(CHACHA_ELF, &stdin)
+            .run()
+            .context("Program execution failed")?;
         println!("Program executed successfully.");
 
         // Read the output.
+        // - program version = 1 byte
         // - sha2 hash = 32 bytes
-        // - ciphertext = encrypted bytes
+        // - nullifier = 32 bytes
+        // - key hash = 32 bytes
+        // - segments root = 32 bytes
+        // - timestamp commitment = 32 bytes
+        // - custody hash = 32 bytes
+        // - lineage commitment = 32 bytes
+        // - plaintext size = 8 bytes
+        // - ciphertext is hash = 1 byte
+        // - input hmac key hash = 32 bytes
+        // - ciphertext = encrypted bytes, or its sha2 hash = 32 bytes
         let output = output.to_vec();
-        let (output_hash_plaintext, output_ciphertext) = output.split_at(32);
+        if output == INPUT_HMAC_FAILED {
+            return Err(
+                anyhow!("The program rejected the inputs, their HMAC does not match").into(),
+            );
+        }
+        let public_values = parse_public_values(&output)?;
+        assert_eq!(
+            public_values.version, PROGRAM_VERSION,
+            "unexpected program version"
+        );
+        expected_hashes(&args)
+            .check(&output, args.commit_ciphertext_hash)
+            .map_err(anyhow::Error::new)?;
 
         // Check against the input
-        let input_plaintext_digest = Sha256::digest(input_plaintext);
+        let input_plaintext_digest = Sha256::digest(&input_plaintext);
         println!(
             "Input -> plaintext hash: 0x{}",
             chacha_lib::bytes_to_hex(&input_plaintext_digest)
         );
         println!(
             "zkVM -> plaintext hash: 0x{}",
-            chacha_lib::bytes_to_hex(output_hash_plaintext)
+            chacha_lib::bytes_to_hex(&public_values.plaintext_hash)
+        );
+
+        assert_eq!(
+            public_values.nullifier,
+            compute_nullifier(&Key(key), &Nonce(nonce)),
+            "zkVM nullifier does not match key and nonce"
+        );
+        println!(
+            "zkVM -> nullifier: 0x{}",
+            chacha_lib::bytes_to_hex(&public_values.nullifier)
         );
 
-        let ciphertext_digest = Sha256::digest(output_ciphertext);
+        assert_eq!(
+            public_values.key_hash.as_slice(),
+            Sha256::digest(key).as_slice(),
+            "zkVM key hash does not match key"
+        );
+        println!(
+            "zkVM -> key hash: 0x{} (fingerprint {})",
+            chacha_lib::bytes_to_hex(&public_values.key_hash),
+            Key(key).fingerprint()
+        );
+
+        let expected_root = if args.disclose.is_empty() {
+            [0u8; 32]
+        } else {
+            segments.root
+        };
+        assert_eq!(
+            public_values.segments_root, expected_root,
+            "zkVM segments root does not match plaintext"
+        );
+
+        let expected_timestamp = match &timestamp {
+            Some(timestamp) => timestamp_commitment(timestamp, &plaintext_hash),
+            None => [0u8; 32],
+        };
+        assert_eq!(
+            public_values.timestamp_commitment, expected_timestamp,
+            "zkVM timestamp commitment does not match timestamp"
+        );
+
+        let expected_custody = match custody {
+            Some((operator_id, timestamp)) => {
+                custody_hash(&operator_id, timestamp, &plaintext_hash)
+            }
+            None => [0u8; 32],
+        };
+        assert_eq!(
+            public_values.custody_hash, expected_custody,
+            "zkVM custody hash does not match operator ID and time"
+        );
+
+        let expected_lineage = match &args.lineage_hash {
+            Some(lineage_hash) => lineage_commitment(lineage_hash, &plaintext_hash),
+            None => [0u8; 32],
+        };
+        assert_eq!(
+            public_values.lineage_commitment, expected_lineage,
+            "zkVM lineage commitment does not match lineage hash"
+        );
+        if args.lineage_hash.is_some() {
+            println!(
+                "zkVM -> lineage commitment: 0x{}, the --lineage-hash of the next stage",
+                chacha_lib::bytes_to_hex(&public_values.lineage_commitment)
+            );
+        }
+
+        assert_eq!(
+            public_values.plaintext_size,
+            Some(input_plaintext.len() as u64),
+            "zkVM plaintext size does not match input"
+        );
+        println!("zkVM -> plaintext size: {} bytes", input_plaintext.len());
+
+        assert_eq!(
+            public_values.ciphertext_is_hash,
+            Some(args.commit_ciphertext_hash),
+            "zkVM committed the wrong kind of ciphertext"
+        );
+
+        assert_eq!(
+            public_values.input_hmac_key_hash,
+            args.hmac_key
+                .map_or([0u8; 32], |hmac_key| hmac_key_hash(&hmac_key)),
+            "zkVM input HMAC key hash does not match --hmac-key"
+        );
+
+        let output_ciphertext = if args.commit_ciphertext_hash {
+            // Reproduce the ciphertext, as a verifier receiving it separately would check it
+            let mut ciphertext = inputs.compressed_plaintext();
+            chacha(&key, &nonce, &mut ciphertext);
+            assert_eq!(
+                public_values.ciphertext,
+                Sha256::digest(&ciphertext).as_slice(),
+                "zkVM ciphertext hash does not match ciphertext"
+            );
+            ciphertext
+        } else {
+            public_values.ciphertext.to_vec()
+        };
+
+        let ciphertext_digest = Sha256::digest(&output_ciphertext);
         println!(
             "zkVM -> ciphertext hash: 0x{}",
             chacha_lib::bytes_to_hex(&ciphertext_digest)
@@ -99,30 +766,517 @@ fn main() {
         // (plaintext XOR keystream XOR keystream = plaintext; QED)
         let mut output_plaintext = output_ciphertext.to_owned();
         chacha(&key, &nonce, &mut output_plaintext);
+        let output_plaintext = decompress(args.compress, &output_plaintext)
+            .map_err(|e| anyhow!("Failed to decompress zkVM plaintext: {e}"))?;
 
         assert_eq!(output_plaintext, input_plaintext);
         println!("Decryption of zkVM ciphertext matches input!");
 
         // Record the number of cycles executed.
         println!("Number of cycles: {}", report.total_instruction_count());
+        if let Some(path) = &args.flamegraph {
+            export_flamegraph(&report, path).with_context(|| {
+                format!("Failed to write flame graph stacks to {}", path.display())
+            })?;
+            println!(
+                "Wrote flame graph stacks to {0}, render them with `inferno-flamegraph < {0} > \
+                 flamegraph.svg`",
+                path.display()
+            );
+        }
+
+        let mut summary = ProofSummary::from_public_values(&output)?;
+        summary.ciphertext_hash = format!("0x{}", hex::encode(ciphertext_digest));
+        summary.nonce = Some(format!("0x{}", hex::encode(nonce)));
+        summary.chain_id = args.pipeline.chain_id;
+        println!("{summary}");
     } else {
         // Setup the program for proving.
-        let (pk, vk) = client.setup(CHACHA_ELF);
-
-        // Generate the proof
-        //
-        // NOTE:
-        // Using the [groth16 proof type](https://docs.succinct.xyz/docs/sp1/generating-proofs/proof-types#groth16-recommended) to trade increased proving costs & time for minimal EVM gas costs.
-        let proof = client
-            .prove(&pk, &stdin)
-            .groth16()
+        let (pk, _) = client.setup(CHACHA_ELF);
+
+        let meta = CheckpointMeta {
+            nonce,
+            plaintext_hash,
+            commit_ciphertext_hash: args.commit_ciphertext_hash,
+            plaintext_size: input_plaintext.len(),
+        };
+        let checkpoint = match &args.checkpoint_dir {
+            Some(dir) => {
+                let checkpoint = Checkpoint::save(dir, &stdin, &pk, &meta).with_context(|| {
+                    format!("Failed to save the checkpoint to {}", dir.display())
+                })?;
+                println!(
+                    "Saved checkpoint to {0}, rerun with --resume {0} if proving is interrupted",
+                    dir.display()
+                );
+                Some(checkpoint)
+            }
+            None => None,
+        };
+
+        prove(
+            &client,
+            &args,
+            &stdin,
+            &pk,
+            &meta,
+            Some(&input_plaintext),
+            checkpoint,
+        )?;
+    }
+    Ok(())
+}
+
+/// Execute or prove the BLAKE2b program, see `program/src/main_blake2b.rs`.
+fn encrypt_blake2b(
+    args: &Args,
+    mode: Mode,
+    key: [u8; 32],
+    nonce: [u8; 12],
+    plaintext: &[u8],
+) -> Result<(), ScriptError> {
+    let personal = args.blake2b_personal;
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&key);
+    stdin.write_slice(&nonce);
+    stdin.write_slice(&personal);
+    stdin.write_slice(plaintext);
+    let plaintext_hash = blake2b_256(&personal, plaintext);
+    println!(
+        "Input -> BLAKE2b-256 plaintext hash: 0x{}",
+        hex::encode(plaintext_hash)
+    );
+
+    let client = ProverClient::from_env();
+    if mode == Mode::Execute {
+        let (output, report) = client
+            .execute(BLAKE2B_ELF, &stdin)
+            .run()
+            .context("Program execution failed")?;
+        println!("Program executed successfully.");
+
+        // - personalization = 16 bytes
+        // - blake2b hash = 32 bytes
+        // - ciphertext = encrypted bytes
+        let output = output.as_slice();
+        assert_eq!(
+            output[..16],
+            personal,
+            "zkVM personalization does not match"
+        );
+        assert_eq!(
+            output[16..48],
+            plaintext_hash,
+            "zkVM plaintext hash does not match input"
+        );
+        let mut ciphertext = plaintext.to_vec();
+        chacha(&key, &nonce, &mut ciphertext);
+        assert_eq!(output[48..], ciphertext, "zkVM ciphertext does not match");
+        println!("Values are correct!");
+
+        println!(
+            "Number of cycles: {}, {} of them hashing",
+            report.total_instruction_count(),
+            report.cycle_tracker["blake2b"]
+        );
+        return Ok(());
+    }
+    prove_and_verify(
+        &client,
+        BLAKE2B_ELF,
+        &stdin,
+        args.pipeline.proof_type,
+        nonce,
+    )
+}
+
+/// Execute or prove the SHA-512 program, see `program/src/main_sha512.rs`.
+fn encrypt_sha512(
+    args: &Args,
+    mode: Mode,
+    key: [u8; 32],
+    nonce: [u8; 12],
+    plaintext: &[u8],
+) -> Result<(), ScriptError> {
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&key);
+    stdin.write_slice(&nonce);
+    stdin.write_slice(plaintext);
+    let plaintext_hash: [u8; SHA512_HASH_LEN] = Sha512::digest(plaintext).into();
+    println!(
+        "Input -> SHA-512 plaintext hash: 0x{}",
+        hex::encode(plaintext_hash)
+    );
+
+    let client = ProverClient::from_env();
+    if mode == Mode::Execute {
+        let (output, report) = client
+            .execute(SHA512_ELF, &stdin)
+            .run()
+            .context("Program execution failed")?;
+        println!("Program executed successfully.");
+
+        let output = output.as_slice();
+        let output =
+            Sha512PublicValues::parse(output).ok_or(ScriptError::InvalidPublicValuesLength {
+                got: output.len(),
+                expected: SHA512_HASH_LEN,
+            })?;
+        assert_eq!(
+            output.plaintext_hash, plaintext_hash,
+            "zkVM plaintext hash does not match input"
+        );
+        let mut ciphertext = plaintext.to_vec();
+        chacha(&key, &nonce, &mut ciphertext);
+        assert_eq!(
+            output.ciphertext, ciphertext,
+            "zkVM ciphertext does not match"
+        );
+        println!("Values are correct!");
+
+        println!(
+            "Number of cycles: {}, {} of them hashing",
+            report.total_instruction_count(),
+            report.cycle_tracker["sha512"]
+        );
+        return Ok(());
+    }
+    prove_and_verify(&client, SHA512_ELF, &stdin, args.pipeline.proof_type, nonce)
+}
+
+/// Execute or prove the AES-256-CTR program, see `program/src/main_aes.rs`. The nonce is the start
+/// of the initial counter block, see [`iv_from_nonce`].
+fn encrypt_aes(
+    args: &Args,
+    mode: Mode,
+    key: [u8; 32],
+    nonce: [u8; 12],
+    plaintext: &[u8],
+) -> Result<(), ScriptError> {
+    let iv = iv_from_nonce(&nonce);
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&key);
+    stdin.write_slice(&iv);
+    stdin.write_slice(plaintext);
+    let plaintext_hash: [u8; 32] = Sha256::digest(plaintext).into();
+    println!("FIPS mode: AES-256-CTR with IV 0x{}", hex::encode(iv));
+
+    let client = ProverClient::from_env();
+    if mode == Mode::Execute {
+        let (output, report) = client
+            .execute(AES_ELF, &stdin)
             .run()
-            .expect("failed to generate proof");
+            .context("Program execution failed")?;
+        println!("Program executed successfully.");
 
-        println!("Successfully generated proof!");
+        // - sha256 hash = 32 bytes
+        // - ciphertext = encrypted bytes
+        let output = output.as_slice();
+        assert_eq!(
+            output[..32],
+            plaintext_hash,
+            "zkVM plaintext hash does not match input"
+        );
+        let mut ciphertext = plaintext.to_vec();
+        aes_ctr_encrypt(&key, &iv, &mut ciphertext);
+        assert_eq!(output[32..], ciphertext, "zkVM ciphertext does not match");
+        println!("Values are correct!");
 
-        // Verify the proof.
-        client.verify(&proof, &vk).expect("failed to verify proof");
-        println!("Successfully verified proof!");
+        println!(
+            "Number of cycles: {}, {} of them encrypting",
+            report.total_instruction_count(),
+            report.cycle_tracker["aes"]
+        );
+        return Ok(());
     }
+    prove_and_verify(&client, AES_ELF, &stdin, args.pipeline.proof_type, nonce)
+}
+
+/// Prove `elf` as `proof_type` and verify the proof.
+fn prove_and_verify(
+    client: &EnvProver,
+    elf: &[u8],
+    stdin: &SP1Stdin,
+    proof_type: ProofType,
+    nonce: [u8; 12],
+) -> Result<(), ScriptError> {
+    let (pk, vk) = client.setup(elf);
+    let proof = run_proof(client, &pk, stdin, proof_type).context("Failed to generate proof")?;
+    println!("Successfully generated proof!");
+    client
+        .verify(&proof, &vk)
+        .map_err(|e| ScriptError::VerificationFailed(e.to_string()))?;
+    println!("Successfully verified proof!");
+    println!("Nonce: 0x{}", hex::encode(nonce));
+    println!("Program hash: {}", vk.bytes32());
+    Ok(())
+}
+
+/// The key reconstructed from `--key-shares`.
+fn shared_key(paths: &[PathBuf]) -> Result<Key, ScriptError> {
+    let key = read_key_shares(paths).map_err(anyhow::Error::msg)?;
+    println!("Reconstructed the key from {} shares", paths.len());
+    Ok(key)
+}
+
+/// Report every invalid input, or what would be proved.
+fn dry_run(args: &Args) -> Result<(), ScriptError> {
+    let encryption_key = if args.key_shares.is_empty() {
+        env_key().map(|(_, key)| key)
+    } else {
+        Some(hex::encode(shared_key(&args.key_shares)?.0))
+    };
+    let inputs = DryRunInputs {
+        encryption_key: encryption_key.as_deref(),
+        ephemeral_key: args.ephemeral_key,
+        input: args.pipeline.input.as_deref(),
+        timestamp_key: args.timestamp_key.as_deref(),
+        disclose: &args.disclose,
+    };
+    let valid = match check_inputs(&inputs) {
+        Ok(valid) => valid,
+        Err(errors) => {
+            eprintln!("{}", format_errors(&errors));
+            return Err(anyhow!("{} invalid inputs", errors.len()).into());
+        }
+    };
+
+    // The oracle is not queried, which leaves out the few cycles of its timestamp
+    let stdin = EncryptInputs {
+        compression: args.compress,
+        compression_level: Some(args.zstd_level),
+        commit_segments: !args.disclose.is_empty(),
+        commit_ciphertext_hash: args.commit_ciphertext_hash,
+        lineage: args.lineage_hash,
+        input_hmac_key: args.hmac_key,
+        ..EncryptInputs::new(
+            valid.key.unwrap_or_else(|| Key::random().0),
+            nonce(args)?.unwrap_or_else(chacha_lib::random_nonce),
+            &valid.plaintext,
+        )
+    }
+    .to_stdin();
+    // Only executes, the mock prover never proves
+    let client = ProverClient::builder().mock().build();
+    let (_, report) = client
+        .execute(CHACHA_ELF, &stdin)
+        .run()
+        .context("Program execution failed")?;
+
+    let summary = DryRunSummary::new(
+        valid.plaintext.len(),
+        args.compress,
+        args.pipeline.proof_type,
+        report.total_instruction_count(),
+    );
+    println!("{summary}");
+    Ok(())
+}
+
+/// The `--nonce`, or the fixed nonce of `--nonce-from-env`. `None` for a random nonce.
+fn nonce(args: &Args) -> Result<Option<[u8; 12]>, ScriptError> {
+    if !args.pipeline.nonce_from_env {
+        return Ok(args.pipeline.nonce);
+    }
+    eprintln!("{}", pipeline::FIXED_NONCE_WARNING);
+    let nonce = pipeline::fixed_nonce().map_err(anyhow::Error::msg)?;
+    Ok(Some(nonce))
+}
+
+/// Size of the input, without reading it.
+fn input_size(args: &Args) -> Result<usize, ScriptError> {
+    Ok(match &args.pipeline.input {
+        Some(path) => std::fs::metadata(path)
+            .with_context(|| format!("Failed to read input file {}", path.display()))?
+            .len() as usize,
+        None => chacha_lib::INPUT_BYTES.len(),
+    })
+}
+
+fn read_input(args: &Args) -> Result<Vec<u8>, ScriptError> {
+    Ok(match &args.pipeline.input {
+        Some(path) => std::fs::read(path)
+            .with_context(|| format!("Failed to read input file {}", path.display()))?,
+        None => chacha_lib::INPUT_BYTES.to_vec(),
+    })
+}
+
+/// Generate and verify the proof, removing `checkpoint` once it is verified.
+fn prove(
+    client: &EnvProver,
+    args: &Args,
+    stdin: &SP1Stdin,
+    pk: &SP1ProvingKey,
+    meta: &CheckpointMeta,
+    input_plaintext: Option<&[u8]>,
+    checkpoint: Option<Checkpoint>,
+) -> Result<(), ScriptError> {
+    // Generate the proof
+    //
+    // NOTE:
+    // Defaults to the [groth16 proof type](https://docs.succinct.xyz/docs/sp1/generating-proofs/proof-types#groth16-recommended) to trade increased proving costs & time for minimal EVM gas costs.
+    let metrics = |cycles, prove_duration, success| ProveMetrics {
+        cycles,
+        prove_duration,
+        input_size: meta.plaintext_size,
+        proof_type: args.pipeline.proof_type,
+        success,
+    };
+    // Proving reports no cycle count, execute for the metrics
+    let cycles = match &args.metrics_push_gateway {
+        Some(gateway) => match client.execute(CHACHA_ELF, stdin).run() {
+            Ok((_, report)) => Some(report.total_instruction_count()),
+            Err(e) => {
+                // Proving would fail the same way, report the failure without proving
+                push(
+                    gateway,
+                    &args.metrics_job,
+                    &metrics(0, Duration::ZERO, false),
+                )?;
+                return Err(e.context("Program execution failed").into());
+            }
+        },
+        None => None,
+    };
+    let start = Instant::now();
+    let proof = run_proof(client, pk, stdin, args.pipeline.proof_type);
+    if let (Some(gateway), Some(cycles)) = (&args.metrics_push_gateway, cycles) {
+        let metrics = metrics(cycles, start.elapsed(), proof.is_ok());
+        push(gateway, &args.metrics_job, &metrics)?;
+    }
+    let proof = proof.map_err(|e| {
+        if let Some(checkpoint) = &checkpoint {
+            eprintln!(
+                "The inputs and proving key are saved, rerun with --resume {} to skip the setup",
+                checkpoint.dir().display()
+            );
+        }
+        e.context("Failed to generate proof")
+    })?;
+
+    println!("Successfully generated proof!");
+
+    if let (Some(path), Some(input_plaintext)) = (&args.audit_log, input_plaintext) {
+        let requester = std::env::var("USER").unwrap_or_else(|_| "unknown".to_string());
+        AuditLog::open(path)
+            .and_then(|log| log.record(&requester, input_plaintext, &proof, start.elapsed()))?;
+    }
+
+    // Verify the proof.
+    client
+        .verify(&proof, &pk.vk)
+        .map_err(|e| ScriptError::VerificationFailed(e.to_string()))?;
+    println!("Successfully verified proof!");
+
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.complete()?;
+    }
+
+    expected_hashes(args)
+        .check(proof.public_values.as_slice(), meta.commit_ciphertext_hash)
+        .map_err(anyhow::Error::new)?;
+
+    let mut summary = ProofSummary::from(&proof);
+    summary.nonce = Some(format!("0x{}", hex::encode(meta.nonce)));
+    summary.program_hash = Some(pk.vk.bytes32());
+    summary.chain_id = args.pipeline.chain_id;
+
+    if let Some(path) = &args.pipeline.output {
+        ProofFile::from_proof(&proof).save(path)?;
+        println!("Saved proof to {}", path.display());
+    }
+
+    if let Some(path) = &args.calldata_output {
+        let calldata = format!("0x{}", hex::encode(verify_calldata(&proof, None)?));
+        std::fs::write(path, calldata)?;
+        println!("Wrote the verifyEncryption calldata to {}", path.display());
+    }
+
+    summary.ipfs_cid = store(args, &proof)?;
+    println!("{summary}");
+
+    if args.post_tx {
+        post(args, verify_calldata(&proof, None)?)?;
+    }
+    Ok(())
+}
+
+/// The `--expected-plaintext-hash` and `--expected-ciphertext-hash`.
+fn expected_hashes(args: &Args) -> ExpectedHashes {
+    ExpectedHashes {
+        plaintext_hash: args.expected_plaintext_hash,
+        ciphertext_hash: args.expected_ciphertext_hash,
+    }
+}
+
+/// Push `metrics` to the `--metrics-push-gateway`. A gateway that is down fails the push, not the
+/// proof.
+fn push(gateway: &str, job_name: &str, metrics: &ProveMetrics) -> Result<(), ScriptError> {
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start the tokio runtime")?;
+    match runtime.block_on(push_metrics(gateway, job_name, metrics)) {
+        Ok(()) => println!("Pushed metrics of job {job_name} to {gateway}"),
+        Err(e) => eprintln!("Warning: {e}"),
+    }
+    Ok(())
+}
+
+/// Upload `proof` to `--s3-bucket` and `--ipfs`, returning its IPFS CID.
+fn store(args: &Args, proof: &SP1ProofWithPublicValues) -> Result<Option<String>, ScriptError> {
+    if args.s3_bucket.is_none() && !args.ipfs {
+        return Ok(None);
+    }
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start the tokio runtime")?;
+    let file = ProofFile::from_proof(proof);
+    if let (Some(bucket), Some(key)) = (&args.s3_bucket, &args.s3_key) {
+        runtime
+            .block_on(upload_proof(&file, bucket, key))
+            .map_err(anyhow::Error::new)?;
+        println!("Uploaded proof to s3://{bucket}/{key}");
+    }
+    if !args.ipfs {
+        return Ok(None);
+    }
+    let cid = runtime
+        .block_on(ipfs_upload(&args.ipfs_endpoint, &file, args.pin))
+        .map_err(anyhow::Error::new)?;
+    Ok(Some(cid))
+}
+
+/// Submit the `calldata` verifying a proof to the `--contract` and wait for its confirmations.
+fn post(args: &Args, calldata: Vec<u8>) -> Result<(), ScriptError> {
+    let (Some(rpc_url), Some(contract), Some(private_key)) =
+        (&args.rpc_url, args.contract, &args.private_key)
+    else {
+        return Err(anyhow!("--post-tx needs --rpc-url, --contract and --private-key").into());
+    };
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start the tokio runtime")?;
+    let posted = runtime
+        .block_on(post_tx(
+            rpc_url,
+            contract,
+            private_key,
+            calldata,
+            args.pipeline.chain_id,
+            args.wait_for_confirmation,
+            |tx_hash| {
+                println!(
+                    "Sent transaction {tx_hash}, waiting for {} confirmations",
+                    args.wait_for_confirmation
+                )
+            },
+        ))
+        .map_err(anyhow::Error::new)?;
+    println!(
+        "Transaction {} confirmed in block {}",
+        posted.tx_hash,
+        posted
+            .block_number
+            .map_or("unknown".to_string(), |n| n.to_string())
+    );
+    println!(
+        "Gas used: {}, effective gas price: {} gwei",
+        posted.gas_used,
+        format_units(posted.effective_gas_price, "gwei").context("gwei is not a valid unit")?
+    );
+    Ok(())
 }
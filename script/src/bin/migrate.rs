@@ -0,0 +1,72 @@
+//! Migrate a saved proof to the current proof file format.
+//!
+//! ```shell
+//! cargo run --release --bin migrate -- --input proof.bin --output proof.json
+//! ```
+//!
+//! The version of the input is detected, see `chacha_script::migrate`.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use hex::FromHex;
+
+use chacha_lib::envelope::ENVELOPE_VERSION;
+use chacha_script::migrate::migrate;
+use chacha_script::{compute_vk_hash, select_elf, ProgramSelector};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(long)]
+    input: PathBuf,
+
+    #[clap(long)]
+    output: PathBuf,
+
+    /// Fail unless the input is of this version, detected by default.
+    #[clap(long)]
+    from_version: Option<u8>,
+
+    #[clap(long, default_value_t = ENVELOPE_VERSION)]
+    to_version: u8,
+
+    /// The program v1 proofs are of, recorded in the envelope.
+    #[clap(long, default_value_t = ProgramSelector::Encrypt)]
+    program: ProgramSelector,
+
+    /// Hex encoded verification key hash to record instead of the key of `--program`.
+    #[clap(long, conflicts_with = "program")]
+    program_hash: Option<String>,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let old = std::fs::read(&args.input).unwrap_or_else(|e| {
+        eprintln!("Error: Failed to read {}: {e}", args.input.display());
+        std::process::exit(1);
+    });
+    let program_hash = match &args.program_hash {
+        Some(hash) => <[u8; 32]>::from_hex(hash.trim_start_matches("0x")).unwrap_or_else(|_| {
+            eprintln!("Error: --program-hash must be 32 hex encoded bytes");
+            std::process::exit(1);
+        }),
+        None => compute_vk_hash(select_elf(args.program)),
+    };
+    let migrated =
+        migrate(&old, args.from_version, args.to_version, program_hash).unwrap_or_else(|e| {
+            eprintln!("Error: {}: {e}", args.input.display());
+            std::process::exit(1);
+        });
+    std::fs::write(&args.output, migrated).unwrap_or_else(|e| {
+        eprintln!("Error: Failed to write {}: {e}", args.output.display());
+        std::process::exit(1);
+    });
+    println!(
+        "Migrated {} to v{}: {}",
+        args.input.display(),
+        args.to_version,
+        args.output.display()
+    );
+}
@@ -0,0 +1,78 @@
+//! A time oracle for testing `--timestamp-url`, signing the local clock.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin mock_time_oracle -- --port 3100
+//! ```
+//!
+//! `GET /?nonce=<hex>` returns a [`SignedTimestamp`]. The public key to pin is printed on start.
+//! NOT for production: the signing key is random unless `--secret` is given.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use clap::Parser;
+use hex::FromHex;
+
+use chacha_lib::envelope::ed25519_dalek::{Signer, SigningKey};
+use chacha_lib::Key;
+use chacha_script::timestamp::{signed_message, SignedTimestamp};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(long, default_value = "3100")]
+    port: u16,
+
+    /// Hex encoded 32 byte ed25519 secret key, for a stable public key across restarts.
+    #[clap(long)]
+    secret: Option<String>,
+}
+
+#[tokio::main]
+async fn main() {
+    sp1_sdk::utils::setup_logger();
+
+    let args = Args::parse();
+    let secret = match &args.secret {
+        Some(secret) => <[u8; 32]>::from_hex(secret).expect("Secret must be 32 bytes"),
+        None => Key::random().0,
+    };
+    let signing_key = Arc::new(SigningKey::from_bytes(&secret));
+    println!(
+        "Oracle public key: {}",
+        hex::encode(signing_key.verifying_key().to_bytes())
+    );
+
+    let app = Router::new()
+        .route("/", get(sign_timestamp))
+        .with_state(signing_key);
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", args.port))
+        .await
+        .expect("failed to bind port");
+    tracing::info!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app).await.expect("server error");
+}
+
+async fn sign_timestamp(
+    State(signing_key): State<Arc<SigningKey>>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Json<SignedTimestamp>, StatusCode> {
+    let nonce = query
+        .get("nonce")
+        .and_then(|nonce| <[u8; 32]>::from_hex(nonce).ok())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before unix epoch")
+        .as_secs();
+    let signature = signing_key.sign(&signed_message(&nonce, timestamp));
+    Ok(Json(SignedTimestamp {
+        timestamp,
+        signature: signature.to_bytes(),
+    }))
+}
@@ -0,0 +1,119 @@
+//! Prove the encryption of one plaintext for several recipient keys.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin multi_encrypt -- --execute --recipients <hex>,<hex>
+//! ```
+//!
+//! Each ciphertext is written to `<output-dir>/ciphertext_<i>.bin` and its nonce is printed.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use hex::FromHex;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::{chacha, ProofType};
+use chacha_script::{run_proof, MULTI_ELF};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(long)]
+    execute: bool,
+
+    #[clap(long)]
+    prove: bool,
+
+    /// Comma separated hex encoded 32 byte keys, one per recipient.
+    #[clap(long, value_delimiter = ',', required = true)]
+    recipients: Vec<String>,
+
+    /// File to encrypt. Defaults to the bundled example input.
+    #[clap(long)]
+    input: Option<PathBuf>,
+
+    /// Directory to write the ciphertexts to.
+    #[clap(long, default_value = ".")]
+    output_dir: PathBuf,
+
+    /// Proof system used by `--prove`.
+    #[clap(long, default_value_t = ProofType::Groth16)]
+    proof_type: ProofType,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    if args.execute == args.prove {
+        eprintln!("Error: You must specify either --execute or --prove");
+        std::process::exit(1);
+    }
+
+    let keys: Vec<([u8; 32], [u8; 12])> = args
+        .recipients
+        .iter()
+        .map(|key| {
+            let key = <[u8; 32]>::from_hex(key).expect("Recipient keys must be 32 bytes");
+            (key, chacha_lib::random_nonce())
+        })
+        .collect();
+    let plaintext: Vec<u8> = match &args.input {
+        Some(path) => std::fs::read(path).expect("Failed to read input file"),
+        None => chacha_lib::INPUT_BYTES.to_vec(),
+    };
+
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&(keys.len() as u32));
+    for (key, nonce) in &keys {
+        stdin.write_slice(key);
+        stdin.write_slice(nonce);
+    }
+    stdin.write_slice(&plaintext);
+
+    let client = ProverClient::from_env();
+    let public_values = if args.execute {
+        let (output, report) = client.execute(MULTI_ELF, &stdin).run().unwrap();
+        println!("Program executed successfully.");
+        println!("Number of cycles: {}", report.total_instruction_count());
+        output.to_vec()
+    } else {
+        let (pk, vk) = client.setup(MULTI_ELF);
+        let proof =
+            run_proof(&client, &pk, &stdin, args.proof_type).expect("failed to generate proof");
+        println!("Successfully generated proof!");
+        client.verify(&proof, &vk).expect("failed to verify proof");
+        println!("Successfully verified proof!");
+        proof.public_values.to_vec()
+    };
+
+    // Read the output.
+    // - sha256(plaintext) = 32 bytes
+    // - per recipient: sha256(key) = 32 bytes, sha256(ciphertext) = 32 bytes
+    let (plaintext_hash, pairs) = public_values.split_at(32);
+    assert_eq!(plaintext_hash, Sha256::digest(&plaintext).as_slice());
+    assert_eq!(pairs.len(), 64 * keys.len());
+
+    // The ciphertexts are not committed, so reproduce them and check them against their hashes
+    for (i, ((key, nonce), pair)) in keys.iter().zip(pairs.chunks(64)).enumerate() {
+        let mut ciphertext = plaintext.clone();
+        chacha(key, nonce, &mut ciphertext);
+        assert_eq!(&pair[..32], Sha256::digest(key).as_slice());
+        assert_eq!(&pair[32..], Sha256::digest(&ciphertext).as_slice());
+
+        let mut decrypted = ciphertext.clone();
+        chacha(key, nonce, &mut decrypted);
+        assert_eq!(decrypted, plaintext);
+
+        let path = args.output_dir.join(format!("ciphertext_{i}.bin"));
+        std::fs::write(&path, &ciphertext).expect("Failed to write ciphertext");
+        println!(
+            "Recipient {i}: key hash 0x{}, nonce {}, ciphertext {}",
+            hex::encode(&pair[..32]),
+            hex::encode(nonce),
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Prove an encryption on the Succinct prover network instead of local hardware.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin network_prove -- --network-key <private_key>
+//! ```
+//!
+//! The proof request is retried on submission failures, then polled until fulfilled. The random
+//! nonce needed to decrypt the ciphertext is printed and saved next to `--output`, as a
+//! `chacha_script::watch::ProofSidecar` with the extension `.json`.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use sp1_sdk::{Prover, ProverClient};
+
+use chacha_lib::ProofType;
+use chacha_script::network::{request_with_retry, wait_for_proof};
+use chacha_script::watch::ProofSidecar;
+use chacha_script::{validate_prove_inputs, EncryptInputs, CHACHA_ELF};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Whitelisted private key used to authenticate with the prover network.
+    #[clap(long, env = "NETWORK_PRIVATE_KEY")]
+    network_key: String,
+
+    /// File to encrypt. Defaults to the bundled example input.
+    #[clap(long)]
+    input: Option<PathBuf>,
+
+    #[clap(long, default_value_t = ProofType::Groth16)]
+    proof_type: ProofType,
+
+    /// Where to save the downloaded proof. The nonce is saved to the same path with the extension
+    /// `.json`.
+    #[clap(long, default_value = "proof.bin")]
+    output: PathBuf,
+
+    /// Seconds between proof status checks.
+    #[clap(long, default_value = "10")]
+    poll_interval: u64,
+
+    /// Attempts to submit the proof request before giving up.
+    #[clap(long, default_value = "3")]
+    retries: u32,
+}
+
+#[tokio::main]
+async fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+
+    let key = hex::decode(std::env::var("ENCRYPTION_KEY").expect("Missing ENCRYPTION_KEY env var"))
+        .expect("ENCRYPTION_KEY must be hex");
+    let nonce: [u8; 12] = chacha_lib::random_nonce();
+    let input_plaintext: Vec<u8> = match &args.input {
+        Some(path) => std::fs::read(path).expect("Failed to read input file"),
+        None => chacha_lib::INPUT_BYTES.to_vec(),
+    };
+    if let Err(e) = validate_prove_inputs(&key, &nonce, &input_plaintext) {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+    let key: [u8; 32] = key.try_into().expect("validated key length");
+    let sidecar_path = args.output.with_extension("json");
+    if sidecar_path == args.output {
+        eprintln!("Error: --output must not end in .json, the nonce is saved there");
+        std::process::exit(1);
+    }
+    // Saved before proving, a proof without its nonce could not be decrypted
+    ProofSidecar { nonce }
+        .save(&sidecar_path)
+        .unwrap_or_else(|e| {
+            eprintln!("Error: Failed to save {}: {e}", sidecar_path.display());
+            std::process::exit(1);
+        });
+    println!(
+        "Nonce: 0x{}, saved to {}",
+        hex::encode(nonce),
+        sidecar_path.display()
+    );
+
+    let stdin = EncryptInputs::new(key, nonce, &input_plaintext).to_stdin();
+
+    let client = ProverClient::builder()
+        .network()
+        .private_key(&args.network_key)
+        .build();
+    let (pk, vk) = client.setup(CHACHA_ELF);
+
+    // The network bills in prover gas units, so execute locally first to log the expected cost.
+    let (_, report) = client
+        .execute(CHACHA_ELF, &stdin)
+        .calculate_gas(true)
+        .run()
+        .expect("failed to execute program");
+    println!(
+        "Estimated cost: {} prover gas units ({} cycles)",
+        report.gas.unwrap_or_default(),
+        report.total_instruction_count()
+    );
+
+    let start = Instant::now();
+    let request_id = request_with_retry(
+        &client,
+        &pk,
+        &stdin,
+        args.proof_type,
+        args.retries,
+        Duration::from_secs(2),
+    )
+    .await
+    .unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    });
+    println!("Proof request ID: {request_id}");
+
+    let proof = wait_for_proof(&client, request_id, Duration::from_secs(args.poll_interval))
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        });
+
+    println!("Proof fulfilled after {:?}", start.elapsed());
+    proof.save(&args.output).expect("failed to save proof");
+    println!("Saved proof to {}", args.output.display());
+
+    client.verify(&proof, &vk).expect("failed to verify proof");
+    println!("Successfully verified proof!");
+}
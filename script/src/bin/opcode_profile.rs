@@ -0,0 +1,89 @@
+//! Execute the encryption program on a fixed 1 KB input and export its per-opcode instruction
+//! counts, optionally comparing them against a baseline export.
+//!
+//! ```shell
+//! cargo run --release --bin opcode_profile -- --output opcodes.json --baseline main.json --summary opcodes.md
+//! ```
+//!
+//! `--summary` is only written if an opcode changed by more than `--threshold` percent, CI posts
+//! it as a pull request comment.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use sp1_sdk::ProverClient;
+
+use chacha_script::profile::{
+    compare, markdown_summary, opcode_histogram, OpcodeHistogram, CHANGE_THRESHOLD_PERCENT,
+};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(long, default_value = "opcodes.json")]
+    output: PathBuf,
+
+    /// Histogram of an earlier run, skipped if the file does not exist.
+    #[clap(long)]
+    baseline: Option<PathBuf>,
+
+    /// Where to write the markdown table of changed opcodes.
+    #[clap(long)]
+    summary: Option<PathBuf>,
+
+    /// Report opcodes whose count changed by more than this percentage.
+    #[clap(long, default_value_t = CHANGE_THRESHOLD_PERCENT)]
+    threshold: f64,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    let args = Args::parse();
+
+    // Fixed, so runs differ only by the program
+    let plaintext: Vec<u8> = (0..1024).map(|i| i as u8).collect();
+    let stdin = EncryptInputs::new(
+        core::array::from_fn(|i| i as u8 ^ 0x5c),
+        [0; 12],
+        &plaintext,
+    )
+    .to_stdin();
+
+    let client = ProverClient::builder().cpu().build();
+    let (_, report) = client.execute(CHACHA_ELF, &stdin).run().unwrap();
+    let current = opcode_histogram(&report);
+    std::fs::write(
+        &args.output,
+        serde_json::to_string(&current).expect("histogram serializes"),
+    )
+    .expect("Failed to write histogram");
+    println!(
+        "{} instructions, histogram written to {}",
+        report.total_instruction_count(),
+        args.output.display()
+    );
+
+    let Some(baseline_path) = &args.baseline else {
+        return;
+    };
+    let baseline: OpcodeHistogram = match std::fs::read_to_string(baseline_path) {
+        Ok(json) => serde_json::from_str(&json).expect("Malformed baseline histogram"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!("No baseline at {}, skipping", baseline_path.display());
+            return;
+        }
+        Err(e) => panic!("Failed to read baseline histogram: {e}"),
+    };
+
+    let changes = compare(&baseline, &current, args.threshold);
+    if changes.is_empty() {
+        println!("No opcode changed by more than {}%", args.threshold);
+        return;
+    }
+    let summary = markdown_summary(&changes, &baseline, &current, args.threshold);
+    println!("{summary}");
+    if let Some(path) = &args.summary {
+        std::fs::write(path, summary).expect("Failed to write summary");
+    }
+}
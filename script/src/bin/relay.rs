@@ -0,0 +1,95 @@
+//! Submit a saved Groth16 or PLONK proof to the `SP1Chacha` contracts of several chains.
+//!
+//! ```shell
+//! PRIVATE_KEY=... cargo run --release --bin relay -- --from-file proof.bin --config relay.json
+//! ```
+//!
+//! See [`chacha_script::relay::RelayConfig`] for the config.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use chacha_script::post::verify_calldata;
+use chacha_script::relay::{relay, RelayConfig};
+use chacha_script::storage::load_proof;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Proof saved with `SP1ProofWithPublicValues::save`, e.g. by `--output`.
+    #[clap(long)]
+    from_file: PathBuf,
+
+    /// JSON file listing the RPC URL and contract address of each chain.
+    #[clap(long)]
+    config: PathBuf,
+
+    /// Hex encoded key of the account paying on every chain.
+    #[clap(long, env = "PRIVATE_KEY", hide_env_values = true)]
+    private_key: String,
+
+    /// Confirmations to wait for on each chain.
+    #[clap(long, default_value_t = 1)]
+    wait_for_confirmation: u64,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let config: RelayConfig = std::fs::read_to_string(&args.config)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("Error: Failed to read {}: {e}", args.config.display());
+            std::process::exit(1);
+        });
+    let calldata = load_proof(&args.from_file)
+        .and_then(|proof| verify_calldata(&proof, None))
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        });
+
+    println!(
+        "Relaying to {} chains, waiting for {} confirmations",
+        config.chains.len(),
+        args.wait_for_confirmation
+    );
+    let runtime = tokio::runtime::Runtime::new().expect("failed to start tokio runtime");
+    let relayed = runtime.block_on(relay(
+        &config.chains,
+        &args.private_key,
+        &calldata,
+        args.wait_for_confirmation,
+    ));
+
+    let mut failed = 0;
+    for (chain, relayed) in config.chains.iter().zip(&relayed) {
+        if let (Some(expected), Some(actual)) = (chain.chain_id, relayed.other_chain_id) {
+            eprintln!(
+                "Warning: {}: expected chain {expected}, the RPC endpoint serves chain {actual}",
+                relayed.name
+            );
+        }
+        match &relayed.result {
+            Ok(posted) => println!(
+                "{}: transaction {} confirmed in block {}, gas used {}",
+                relayed.name,
+                posted.tx_hash,
+                posted
+                    .block_number
+                    .map_or("unknown".to_string(), |n| n.to_string()),
+                posted.gas_used
+            ),
+            Err(e) => {
+                eprintln!("{}: Error: {e}", relayed.name);
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        eprintln!("Error: {failed} of {} chains failed", relayed.len());
+        std::process::exit(1);
+    }
+}
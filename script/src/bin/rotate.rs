@@ -0,0 +1,171 @@
+//! Prove the re-encryption of a ciphertext from `OLD_ENCRYPTION_KEY` to `ENCRYPTION_KEY`.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin rotate -- --execute --ciphertext <path> --nonce <hex>
+//! ```
+//!
+//! The new ciphertext is written to `--output` and the new nonce is printed. With
+//! `--commit-ciphertext-hash` the re-keying program proves it instead, committing only the hash of
+//! the new ciphertext, see `chacha_lib::rekey`.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use hex::FromHex;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::rekey::Rekey;
+use chacha_lib::{chacha, Key, Nonce, ProofType};
+use chacha_script::{run_proof, REKEY_ELF, ROTATE_ELF};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(long)]
+    execute: bool,
+
+    #[clap(long)]
+    prove: bool,
+
+    /// Ciphertext encrypted under the old key.
+    #[clap(long)]
+    ciphertext: PathBuf,
+
+    /// Hex encoded nonce the ciphertext was encrypted with.
+    #[clap(long)]
+    nonce: String,
+
+    /// Where to write the ciphertext under the new key.
+    #[clap(long, default_value = "ciphertext_rotated.bin")]
+    output: PathBuf,
+
+    /// Proof system used by `--prove`.
+    #[clap(long, default_value_t = ProofType::Groth16)]
+    proof_type: ProofType,
+
+    /// Commit SHA256(ciphertext_new) instead of the new ciphertext, for re-keying stored
+    /// ciphertexts.
+    #[clap(long)]
+    commit_ciphertext_hash: bool,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    if args.execute == args.prove {
+        eprintln!("Error: You must specify either --execute or --prove");
+        std::process::exit(1);
+    }
+
+    let old_key = <[u8; 32]>::from_hex(
+        std::env::var("OLD_ENCRYPTION_KEY").expect("Missing OLD_ENCRYPTION_KEY env var"),
+    )
+    .expect("Old key must be 32 bytes");
+    let new_key = <[u8; 32]>::from_hex(
+        std::env::var("ENCRYPTION_KEY").expect("Missing ENCRYPTION_KEY env var"),
+    )
+    .expect("Key must be 32 bytes");
+    let nonce_old = <[u8; 12]>::from_hex(&args.nonce).expect("Nonce must be 12 bytes");
+    let nonce_new: [u8; 12] = chacha_lib::random_nonce();
+    let ciphertext_old = std::fs::read(&args.ciphertext).expect("Failed to read ciphertext file");
+
+    let mut stdin = SP1Stdin::new();
+    let elf = if args.commit_ciphertext_hash {
+        stdin.write_slice(&Sha256::digest(old_key));
+        stdin.write_slice(&old_key);
+        stdin.write_slice(&nonce_old);
+        stdin.write_slice(&new_key);
+        stdin.write_slice(&nonce_new);
+        stdin.write_slice(&ciphertext_old);
+        REKEY_ELF
+    } else {
+        stdin.write_slice(&old_key);
+        stdin.write_slice(&new_key);
+        stdin.write_slice(&nonce_old);
+        stdin.write_slice(&nonce_new);
+        stdin.write_slice(&ciphertext_old);
+        ROTATE_ELF
+    };
+
+    let client = ProverClient::from_env();
+    let public_values = if args.execute {
+        let (output, report) = client.execute(elf, &stdin).run().unwrap();
+        println!("Program executed successfully.");
+        println!("Number of cycles: {}", report.total_instruction_count());
+        output.to_vec()
+    } else {
+        let (pk, vk) = client.setup(elf);
+        let proof =
+            run_proof(&client, &pk, &stdin, args.proof_type).expect("failed to generate proof");
+        println!("Successfully generated proof!");
+        client.verify(&proof, &vk).expect("failed to verify proof");
+        println!("Successfully verified proof!");
+        proof.public_values.to_vec()
+    };
+
+    let (old_key_hash, new_key_hash, plaintext_hash, ciphertext_new) =
+        if args.commit_ciphertext_hash {
+            // The public values only hash the new ciphertext, re-key on the host to get it
+            let rekey = Rekey::parse(&public_values).expect("malformed public values");
+            let mut ciphertext_new = ciphertext_old;
+            let expected = chacha_lib::rekey::rekey(
+                &Key(old_key),
+                &Nonce(nonce_old),
+                &Key(new_key),
+                &Nonce(nonce_new),
+                &mut ciphertext_new,
+            );
+            assert_eq!(rekey, expected, "zkVM re-keyed another ciphertext");
+            println!(
+                "zkVM -> ciphertext hash: 0x{}",
+                chacha_lib::bytes_to_hex(&rekey.ciphertext_hash)
+            );
+            (
+                rekey.old_key_hash,
+                rekey.new_key_hash,
+                rekey.plaintext_hash,
+                ciphertext_new,
+            )
+        } else {
+            // Read the output.
+            // - sha256(old_key) = 32 bytes
+            // - sha256(new_key) = 32 bytes
+            // - sha256(plaintext) = 32 bytes
+            // - ciphertext_new = re-encrypted bytes
+            let (old_key_hash, rest) = public_values.split_at(32);
+            let (new_key_hash, rest) = rest.split_at(32);
+            let (plaintext_hash, ciphertext_new) = rest.split_at(32);
+            (
+                old_key_hash.try_into().unwrap(),
+                new_key_hash.try_into().unwrap(),
+                plaintext_hash.try_into().unwrap(),
+                ciphertext_new.to_vec(),
+            )
+        };
+
+    assert_eq!(old_key_hash, Sha256::digest(old_key).as_slice());
+    assert_eq!(new_key_hash, Sha256::digest(new_key).as_slice());
+    println!(
+        "zkVM -> key hashes: 0x{} -> 0x{}",
+        chacha_lib::bytes_to_hex(&old_key_hash),
+        chacha_lib::bytes_to_hex(&new_key_hash)
+    );
+
+    let mut plaintext = ciphertext_new.to_owned();
+    chacha(&new_key, &nonce_new, &mut plaintext);
+    assert_eq!(plaintext_hash, Sha256::digest(&plaintext).as_slice());
+    println!(
+        "zkVM -> plaintext hash: 0x{}",
+        chacha_lib::bytes_to_hex(&plaintext_hash)
+    );
+
+    std::fs::write(&args.output, ciphertext_new).expect("Failed to write ciphertext");
+    println!(
+        "Wrote rotated ciphertext to {} with nonce {}",
+        args.output.display(),
+        hex::encode(nonce_new)
+    );
+}
@@ -0,0 +1,239 @@
+//! A REST server that queues encryption proving jobs, see [`chacha_script::server`] for the
+//! routes.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin server -- --port 3000
+//! ```
+//!
+//! With `--rate-limit-per-ip` or `--rate-limit-per-key`, requests over the limit are rejected. The
+//! per-key limit applies to the keys listed in `--api-keys`, all other requests are limited per IP.
+//!
+//! With `--audit-log`, every completed proof is appended to a hash chained audit log.
+//!
+//! With `--tls-ca-cert`, `--tls-server-cert` and `--tls-server-key`, the server speaks HTTPS and
+//! only accepts clients presenting a certificate signed by the CA, see [`chacha_script::tls`].
+//! Create development certificates with `scripts/gen_certs.sh`.
+
+use std::net::SocketAddr;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum_server::tls_rustls::RustlsConfig;
+use clap::Parser;
+use hex::FromHex;
+use serde_json::json;
+use sp1_sdk::{EnvProver, ProverClient, SP1ProvingKey};
+
+use chacha_script::audit::AuditLog;
+use chacha_script::queue::JobQueue;
+use chacha_script::rate_limit::{read_api_keys, RateLimits};
+use chacha_script::server::{router, AppState};
+use chacha_script::tls;
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+/// How long the worker sleeps when the queue is empty.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(long, default_value = "3000")]
+    port: u16,
+
+    /// SQLite database holding the job queue.
+    #[clap(long, default_value = "jobs.sqlite")]
+    queue_db: PathBuf,
+
+    /// Maximum requests per minute from a single client IP.
+    #[clap(long)]
+    rate_limit_per_ip: Option<NonZeroU32>,
+
+    /// Maximum requests per minute for a single `x-api-key` of `--api-keys`.
+    #[clap(long, requires = "api_keys")]
+    rate_limit_per_key: Option<NonZeroU32>,
+
+    /// File of the API keys limited per key, one per line. Requests with other keys are
+    /// limited per IP.
+    #[clap(long)]
+    api_keys: Option<PathBuf>,
+
+    /// Requests allowed in a burst before the per-minute rate applies. Defaults to the rate.
+    #[clap(long)]
+    rate_limit_burst: Option<NonZeroU32>,
+
+    /// Append every completed proof to this tamper-evident log.
+    #[clap(long)]
+    audit_log: Option<PathBuf>,
+
+    /// CA certificate, PEM, that client certificates must be signed by.
+    #[clap(long, requires_all = ["tls_server_cert", "tls_server_key"])]
+    tls_ca_cert: Option<PathBuf>,
+
+    /// Certificate chain, PEM, the server presents to clients.
+    #[clap(long, requires_all = ["tls_ca_cert", "tls_server_key"])]
+    tls_server_cert: Option<PathBuf>,
+
+    /// Private key, PEM, of `--tls-server-cert`.
+    #[clap(long, requires_all = ["tls_ca_cert", "tls_server_cert"])]
+    tls_server_key: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+
+    let key = <[u8; 32]>::from_hex(
+        std::env::var("ENCRYPTION_KEY").expect("Missing ENCRYPTION_KEY env var"),
+    )
+    .expect("Key must be 32 bytes");
+
+    let state = Arc::new(AppState::new(
+        JobQueue::open(&args.queue_db).expect("failed to open job queue"),
+        RateLimits::new(
+            args.rate_limit_per_ip,
+            args.rate_limit_per_key,
+            args.rate_limit_burst,
+            args.api_keys
+                .as_ref()
+                .map(|path| read_api_keys(path).expect("failed to read API keys"))
+                .unwrap_or_default(),
+        ),
+        args.audit_log
+            .as_ref()
+            .map(|path| AuditLog::open(path).expect("failed to open audit log")),
+    ));
+
+    // The proving key setup is slow, so probes and job submissions are served while it runs.
+    tokio::spawn({
+        let state = state.clone();
+        async move {
+            let client = Arc::new(ProverClient::from_env());
+            let setup_client = client.clone();
+            let (pk, _) = tokio::task::spawn_blocking(move || setup_client.setup(CHACHA_ELF))
+                .await
+                .expect("proving key setup panicked");
+            state.prover_ready.store(true, Ordering::Release);
+            tracing::info!("prover ready");
+            worker(state, client, Arc::new(pk), key).await;
+        }
+    });
+
+    let app = router(state).into_make_service_with_connect_info::<SocketAddr>();
+
+    if let (Some(ca_cert), Some(server_cert), Some(server_key)) = (
+        &args.tls_ca_cert,
+        &args.tls_server_cert,
+        &args.tls_server_key,
+    ) {
+        let config = tls::server_config(ca_cert, server_cert, server_key)
+            .expect("failed to load TLS certificates");
+        tracing::info!("listening on port {} with mutual TLS", args.port);
+        axum_server::bind_rustls(
+            SocketAddr::from(([0, 0, 0, 0], args.port)),
+            RustlsConfig::from_config(Arc::new(config)),
+        )
+        .serve(app)
+        .await
+        .expect("server error");
+    } else {
+        let listener = tokio::net::TcpListener::bind(("0.0.0.0", args.port))
+            .await
+            .expect("failed to bind port");
+        tracing::warn!(
+            "listening on port {} without TLS, anyone who can reach it can submit jobs",
+            args.port
+        );
+        axum::serve(listener, app).await.expect("server error");
+    }
+}
+
+/// Pull jobs off the queue one at a time and prove them.
+async fn worker(
+    state: Arc<AppState>,
+    client: Arc<EnvProver>,
+    pk: Arc<SP1ProvingKey>,
+    key: [u8; 32],
+) {
+    loop {
+        let job = match state.queue.next_pending() {
+            Ok(job) => job,
+            Err(e) => {
+                tracing::error!("failed to read job queue: {e}");
+                None
+            }
+        };
+        let Some((job_id, plaintext, requester)) = job else {
+            let idle = Instant::now();
+            tokio::time::sleep(POLL_INTERVAL).await;
+            state
+                .worker_idle_secs
+                .fetch_add(idle.elapsed().as_secs(), Ordering::Relaxed);
+            continue;
+        };
+
+        tracing::info!("proving job {job_id}");
+        let (job_state, client, pk) = (state.clone(), client.clone(), pk.clone());
+        let outcome = tokio::task::spawn_blocking(move || {
+            prove_job(
+                &client,
+                &pk,
+                &key,
+                &plaintext,
+                job_state
+                    .audit_log
+                    .as_ref()
+                    .map(|log| (log, requester.as_str())),
+            )
+        })
+        .await
+        .unwrap_or_else(|e| Err(format!("prover task panicked: {e}")));
+
+        let recorded = match outcome {
+            Ok(result) => state.queue.complete(job_id, &result),
+            Err(error) => state.queue.fail(job_id, &error),
+        };
+        if let Err(e) = recorded {
+            tracing::error!("failed to record result of job {job_id}: {e}");
+        }
+    }
+}
+
+/// Encrypt and prove a single plaintext, returning the nonce, public values and proof as JSON.
+///
+/// The proof is recorded in `audit` on behalf of the given requester, if set.
+fn prove_job(
+    client: &EnvProver,
+    pk: &SP1ProvingKey,
+    key: &[u8; 32],
+    plaintext: &[u8],
+    audit: Option<(&AuditLog, &str)>,
+) -> Result<String, String> {
+    let start = Instant::now();
+    let nonce: [u8; 12] = chacha_lib::random_nonce();
+
+    let stdin = EncryptInputs::new(*key, nonce, plaintext).to_stdin();
+
+    let proof = client
+        .prove(pk, &stdin)
+        .groth16()
+        .run()
+        .map_err(|e| e.to_string())?;
+
+    if let Some((log, requester)) = audit {
+        log.record(requester, plaintext, &proof, start.elapsed())
+            .map_err(|e| format!("failed to write audit log: {e}"))?;
+    }
+
+    Ok(json!({
+        "nonce": hex::encode(nonce),
+        "public_values": hex::encode(proof.public_values.as_slice()),
+        "proof": hex::encode(proof.bytes()),
+    })
+    .to_string())
+}
@@ -0,0 +1,114 @@
+//! Prove the upgrade from a previous release of the encryption program to the current one, and
+//! print the `SP1Chacha.upgrade` transaction registering the new verification key.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin upgrade -- --old-elf <path> --old-version 1
+//! ```
+//!
+//! `--old-elf` is the `chacha-program` ELF built from the release being replaced.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use sp1_sdk::{HashableKey, ProverClient};
+
+use chacha_lib::upgrade::Upgrade;
+use chacha_lib::{random_nonce, Key, ProofType, PROGRAM_VERSION};
+use chacha_script::post::upgrade_calldata;
+use chacha_script::upgrade::{test_inputs, upgrade_stdin};
+use chacha_script::{CHACHA_ELF, UPGRADE_ELF};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// ELF of the program being replaced.
+    #[clap(long)]
+    old_elf: PathBuf,
+
+    /// `PROGRAM_VERSION` of `--old-elf`.
+    #[clap(long, default_value_t = PROGRAM_VERSION - 1)]
+    old_version: u8,
+
+    /// Proof system of the upgrade proof, groth16 or plonk to be verified on-chain.
+    #[clap(long, default_value_t = ProofType::Groth16)]
+    proof_type: ProofType,
+
+    /// Where to save the upgrade proof.
+    #[clap(long, default_value = "upgrade_proof.bin")]
+    output: PathBuf,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    if !matches!(args.proof_type, ProofType::Groth16 | ProofType::Plonk) {
+        eprintln!("Error: SP1Chacha only verifies groth16 and plonk proofs");
+        std::process::exit(1);
+    }
+    let old_elf = std::fs::read(&args.old_elf).unwrap_or_else(|e| {
+        eprintln!("Error: Failed to read {}: {e}", args.old_elf.display());
+        std::process::exit(1);
+    });
+
+    let client = ProverClient::from_env();
+    let (old_pk, old_vk) = client.setup(&old_elf);
+    let (new_pk, new_vk) = client.setup(CHACHA_ELF);
+    let (upgrade_pk, upgrade_vk) = client.setup(UPGRADE_ELF);
+
+    // Both programs encrypt the same input, under a key used for nothing else
+    let inputs = test_inputs(Key::random().0, random_nonce());
+    let old = client
+        .prove(&old_pk, &inputs.to_stdin_for_version(args.old_version))
+        .compressed()
+        .run()
+        .expect("failed to prove the old program");
+    let new = client
+        .prove(&new_pk, &inputs.to_stdin())
+        .compressed()
+        .run()
+        .expect("failed to prove the new program");
+    println!("Proved both programs.");
+
+    let stdin = upgrade_stdin(&old_vk, &old, &new_vk, &new).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    });
+    let builder = client.prove(&upgrade_pk, &stdin);
+    let builder = match args.proof_type {
+        ProofType::Plonk => builder.plonk(),
+        _ => builder.groth16(),
+    };
+    let proof = builder.run().expect("failed to prove the upgrade");
+    client
+        .verify(&proof, &upgrade_vk)
+        .expect("failed to verify upgrade proof");
+    proof
+        .save(&args.output)
+        .expect("failed to save upgrade proof");
+
+    let upgrade = Upgrade::parse(proof.public_values.as_slice()).expect("malformed upgrade");
+    println!(
+        "Upgrade from version {} (0x{}) to version {} (0x{}), saved to {}",
+        upgrade.old_version,
+        hex::encode(upgrade.old_vkey),
+        upgrade.new_version,
+        hex::encode(upgrade.new_vkey),
+        args.output.display()
+    );
+    println!(
+        "Upgrade program key, registered once with SP1Chacha.setUpgradeVKey: {}",
+        upgrade_vk.bytes32()
+    );
+    match upgrade_calldata(&proof) {
+        Ok(calldata) => println!(
+            "On-chain: cast send <SP1Chacha> 0x{}",
+            hex::encode(calldata)
+        ),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Prove an encryption to a recipient's X25519 public key.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin verifiable -- --execute --recipient <hex>
+//! ```
+//!
+//! The ciphertext is written to `--output`, the recipient derives its key from the ephemeral
+//! public key in the public values. With `RECIPIENT_SECRET` set, the decryption is checked too.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use hex::FromHex;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::exchange::{public_key, recipient_key, sender_key, validate_x25519_public_key};
+use chacha_lib::{chacha, Key, ProofType};
+use chacha_script::{run_proof, VERIFIABLE_ELF};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(long)]
+    execute: bool,
+
+    #[clap(long)]
+    prove: bool,
+
+    /// Hex encoded X25519 public key of the recipient.
+    #[clap(long)]
+    recipient: String,
+
+    /// File to encrypt. Defaults to the bundled example input.
+    #[clap(long)]
+    input: Option<PathBuf>,
+
+    /// Where to write the ciphertext.
+    #[clap(long, default_value = "ciphertext.bin")]
+    output: PathBuf,
+
+    /// Proof system used by `--prove`.
+    #[clap(long, default_value_t = ProofType::Groth16)]
+    proof_type: ProofType,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    if args.execute == args.prove {
+        eprintln!("Error: You must specify either --execute or --prove");
+        std::process::exit(1);
+    }
+
+    let recipient_public =
+        <[u8; 32]>::from_hex(&args.recipient).expect("Recipient key must be 32 bytes");
+    // The program panics on it too, but only after the proving key setup
+    if let Err(e) = validate_x25519_public_key(&recipient_public) {
+        eprintln!("Error: --recipient: {e}");
+        std::process::exit(1);
+    }
+    // A fresh random scalar, only ever used for this encryption
+    let ephemeral_secret = Key::random().0;
+    let plaintext: Vec<u8> = match &args.input {
+        Some(path) => std::fs::read(path).expect("Failed to read input file"),
+        None => chacha_lib::INPUT_BYTES.to_vec(),
+    };
+
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&recipient_public);
+    stdin.write_slice(&ephemeral_secret);
+    stdin.write_slice(&plaintext);
+
+    let client = ProverClient::from_env();
+    let public_values = if args.execute {
+        let (output, report) = client.execute(VERIFIABLE_ELF, &stdin).run().unwrap();
+        println!("Program executed successfully.");
+        println!("Number of cycles: {}", report.total_instruction_count());
+        output.to_vec()
+    } else {
+        let (pk, vk) = client.setup(VERIFIABLE_ELF);
+        let proof =
+            run_proof(&client, &pk, &stdin, args.proof_type).expect("failed to generate proof");
+        println!("Successfully generated proof!");
+        client.verify(&proof, &vk).expect("failed to verify proof");
+        println!("Successfully verified proof!");
+        proof.public_values.to_vec()
+    };
+
+    // Read the output.
+    // - recipient public key = 32 bytes
+    // - ephemeral public key = 32 bytes
+    // - sha256(plaintext) = 32 bytes
+    // - sha256(ciphertext) = 32 bytes
+    let (committed_recipient, rest) = public_values.split_at(32);
+    let (ephemeral_public, rest) = rest.split_at(32);
+    let (plaintext_hash, ciphertext_hash) = rest.split_at(32);
+    assert_eq!(committed_recipient, recipient_public);
+    assert_eq!(ephemeral_public, public_key(&ephemeral_secret));
+    assert_eq!(plaintext_hash, Sha256::digest(&plaintext).as_slice());
+
+    // The ciphertext is not committed, so reproduce it and check it against its hash
+    let (_, key, nonce) =
+        sender_key(&ephemeral_secret, &recipient_public).expect("low order recipient key");
+    let mut ciphertext = plaintext.clone();
+    chacha(&key.0, &nonce.0, &mut ciphertext);
+    assert_eq!(ciphertext_hash, Sha256::digest(&ciphertext).as_slice());
+
+    if let Ok(secret) = std::env::var("RECIPIENT_SECRET") {
+        let secret = <[u8; 32]>::from_hex(secret).expect("Recipient secret must be 32 bytes");
+        let ephemeral_public: [u8; 32] = ephemeral_public.try_into().unwrap();
+        let (key, nonce) =
+            recipient_key(&secret, &ephemeral_public).expect("low order ephemeral key");
+        let mut decrypted = ciphertext.clone();
+        chacha(&key.0, &nonce.0, &mut decrypted);
+        assert_eq!(decrypted, plaintext);
+        println!("Recipient decryption matches input!");
+    }
+
+    std::fs::write(&args.output, &ciphertext).expect("Failed to write ciphertext");
+    println!(
+        "Wrote ciphertext to {}, ephemeral public key 0x{}",
+        args.output.display(),
+        hex::encode(ephemeral_public)
+    );
+}
@@ -0,0 +1,76 @@
+//! Record the verification key of the current program in a JSON registry.
+//!
+//! ```shell
+//! cargo run --release --bin vk_registry -- --registry vk_registry.json
+//! ```
+//!
+//! The key is registered under [`PROGRAM_VERSION`]. A version that already has a different key is
+//! an error, since proofs of the released program would no longer verify, unless `--force` is
+//! given. The printed `setVKey` call registers the key with `SP1Chacha`.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use sp1_sdk::{HashableKey, Prover, ProverClient};
+
+use chacha_lib::vk_registry::VkRegistry;
+use chacha_lib::PROGRAM_VERSION;
+use chacha_script::CHACHA_ELF;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The registry file, created if missing.
+    #[clap(long, default_value = "vk_registry.json")]
+    registry: PathBuf,
+
+    /// Replace the key of a version that is already registered.
+    #[clap(long)]
+    force: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let mut registry = match std::fs::read_to_string(&args.registry) {
+        Ok(json) => VkRegistry::from_json(&json).expect("Malformed registry file"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => VkRegistry::new(),
+        Err(e) => panic!("Failed to read registry file: {e}"),
+    };
+
+    let prover = ProverClient::builder().cpu().build();
+    let (_, vk) = prover.setup(CHACHA_ELF);
+    let vk_hash = vk.bytes32_raw();
+
+    match registry.get(PROGRAM_VERSION) {
+        Some(existing) if *existing == vk_hash => {
+            println!(
+                "Version {PROGRAM_VERSION} is already registered with {}",
+                vk.bytes32()
+            );
+            return;
+        }
+        Some(existing) if !args.force => {
+            eprintln!(
+                "Error: Version {PROGRAM_VERSION} is registered with 0x{}, but the program now has {}. \
+                 Bump PROGRAM_VERSION, or pass --force to replace it.",
+                hex::encode(existing),
+                vk.bytes32()
+            );
+            std::process::exit(1);
+        }
+        _ => {}
+    }
+
+    registry.insert(PROGRAM_VERSION, vk_hash);
+    std::fs::write(&args.registry, registry.to_json()).expect("Failed to write registry file");
+    println!(
+        "Registered version {PROGRAM_VERSION} with {} in {}",
+        vk.bytes32(),
+        args.registry.display()
+    );
+    println!(
+        "On-chain: SP1Chacha.setVKey({PROGRAM_VERSION}, {})",
+        vk.bytes32()
+    );
+}
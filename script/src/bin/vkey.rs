@@ -1,10 +1,49 @@
-use sp1_sdk::{include_elf, HashableKey, Prover, ProverClient};
+//! Print the verification key hash of a program, or write a Solidity library with all of them.
+//!
+//! ```shell
+//! cargo run --release --bin vkey -- --program reencrypt
+//! cargo run --release --bin vkey -- --solidity ../contracts/src/ProgramVKeys.sol
+//! cargo run --release --bin vkey -- --solidity ../contracts/src/ProgramVKeys.sol --max-plaintext-size 1048576
+//! ```
 
-/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
-pub const CHACHA_ELF: &[u8] = include_elf!("chacha-program");
+use std::path::PathBuf;
+
+use chacha_script::{compute_vk_hash, select_elf, solidity_vkeys, ProgramSelector};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(long, default_value_t = ProgramSelector::Encrypt)]
+    program: ProgramSelector,
+
+    /// Write the keys of every program to this Solidity file instead.
+    #[clap(long, conflicts_with = "program")]
+    solidity: Option<PathBuf>,
+
+    /// Add a `checkPlaintextSize` rejecting committed plaintext sizes above this many bytes to
+    /// the Solidity file.
+    #[clap(long, requires = "solidity")]
+    max_plaintext_size: Option<u64>,
+}
 
 fn main() {
-    let prover = ProverClient::builder().cpu().build();
-    let (_, vk) = prover.setup(CHACHA_ELF);
-    println!("{}", vk.bytes32());
+    let args = Args::parse();
+    let vkey = |program| format!("0x{}", hex::encode(compute_vk_hash(select_elf(program))));
+
+    let Some(path) = &args.solidity else {
+        println!("{}", vkey(args.program));
+        return;
+    };
+
+    let vkeys: Vec<_> = ProgramSelector::ALL
+        .into_iter()
+        .map(|program| (program, vkey(program)))
+        .collect();
+    for (program, vkey) in &vkeys {
+        println!("{program}: {vkey}");
+    }
+    std::fs::write(path, solidity_vkeys(&vkeys, args.max_plaintext_size))
+        .expect("Failed to write Solidity file");
+    println!("Wrote {}", path.display());
 }
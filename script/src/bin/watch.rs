@@ -0,0 +1,94 @@
+//! Prove every file that arrives in a directory, with a fresh nonce each, saved next to its proof.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin watch -- --watch-dir incoming --output-dir proofs \
+//!     --processed-dir done --error-dir failed
+//! ```
+//!
+//! See [`chacha_script::watch`] for what happens to each file.
+
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+
+use clap::Parser;
+use sp1_sdk::ProverClient;
+
+use chacha_lib::ProofType;
+use chacha_script::pipeline::env_key;
+use chacha_script::watch::{watch, WatchDirs};
+use chacha_script::{run_proof, EncryptInputs, CHACHA_ELF};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Directory to watch for new files.
+    #[clap(long)]
+    watch_dir: PathBuf,
+
+    /// Directory to write the proofs to, as `<file name>.proof.bin`.
+    #[clap(long)]
+    output_dir: PathBuf,
+
+    /// Move proved files here instead of leaving them in place.
+    #[clap(long)]
+    processed_dir: Option<PathBuf>,
+
+    /// Delete proved files instead of leaving them in place.
+    #[clap(long, conflicts_with = "processed_dir")]
+    delete_processed: bool,
+
+    /// Move files that failed to prove here instead of leaving them in place.
+    #[clap(long)]
+    error_dir: Option<PathBuf>,
+
+    /// Compressed by default, as the proofs are saved rather than sent on-chain.
+    #[clap(long, default_value_t = ProofType::Compressed)]
+    proof_type: ProofType,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    let (var, key) = env_key().expect("Missing ENCRYPTION_KEY env var");
+    let key: [u8; 32] = hex::decode(key)
+        .ok()
+        .and_then(|key| key.try_into().ok())
+        .unwrap_or_else(|| panic!("{var} must be 32 hex encoded bytes"));
+
+    let client = ProverClient::from_env();
+    let (pk, _) = client.setup(CHACHA_ELF);
+    let dirs = WatchDirs {
+        input: args.watch_dir,
+        output: args.output_dir,
+        processed: args.processed_dir,
+        error: args.error_dir,
+        delete_processed: args.delete_processed,
+    };
+    println!("Watching {} for new files", dirs.input.display());
+
+    let prove = |plaintext: &[u8], nonce| {
+        let stdin = EncryptInputs::new(key, nonce, plaintext).to_stdin();
+        run_proof(&client, &pk, &stdin, args.proof_type).map_err(|e| e.to_string())
+    };
+    let on_processed = |processed: chacha_script::watch::Processed| match processed.result {
+        Ok(proof) => println!(
+            "{}: proved in {:.1?}, saved to {}",
+            processed.input.display(),
+            processed.elapsed,
+            proof.display()
+        ),
+        Err(e) => eprintln!(
+            "{}: Error after {:.1?}: {e}",
+            processed.input.display(),
+            processed.elapsed
+        ),
+    };
+    // Runs until the process is stopped
+    let stop = AtomicBool::new(false);
+    if let Err(e) = watch(&dirs, prove, on_processed, &stop) {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,106 @@
+//! Prove the WireGuard transport data packet carrying a plaintext.
+//!
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin wireguard -- --execute --counter 42 --input <path>
+//! ```
+//!
+//! `ENCRYPTION_KEY` is the session's sending key. The packet payload (ciphertext and tag) is
+//! written to `--output`, it matches the payload captured from the tunnel for that counter.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use hex::FromHex;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::wireguard::wireguard_decrypt;
+use chacha_lib::{Key, ProofType};
+use chacha_script::{run_proof, WIREGUARD_ELF};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(long)]
+    execute: bool,
+
+    #[clap(long)]
+    prove: bool,
+
+    /// Counter of the packet in the session.
+    #[clap(long)]
+    counter: u64,
+
+    /// File holding the packet plaintext. Defaults to the bundled example input.
+    #[clap(long)]
+    input: Option<PathBuf>,
+
+    /// Where to write the packet payload.
+    #[clap(long, default_value = "packet.bin")]
+    output: PathBuf,
+
+    /// Proof system used by `--prove`.
+    #[clap(long, default_value_t = ProofType::Groth16)]
+    proof_type: ProofType,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    if args.execute == args.prove {
+        eprintln!("Error: You must specify either --execute or --prove");
+        std::process::exit(1);
+    }
+
+    let key = <[u8; 32]>::from_hex(
+        std::env::var("ENCRYPTION_KEY").expect("Missing ENCRYPTION_KEY env var"),
+    )
+    .expect("Key must be 32 bytes");
+    let plaintext: Vec<u8> = match &args.input {
+        Some(path) => std::fs::read(path).expect("Failed to read input file"),
+        None => chacha_lib::INPUT_BYTES.to_vec(),
+    };
+
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&key);
+    stdin.write(&args.counter);
+    stdin.write_slice(&plaintext);
+
+    let client = ProverClient::from_env();
+    let public_values = if args.execute {
+        let (output, report) = client.execute(WIREGUARD_ELF, &stdin).run().unwrap();
+        println!("Program executed successfully.");
+        println!("Number of cycles: {}", report.total_instruction_count());
+        output.to_vec()
+    } else {
+        let (pk, vk) = client.setup(WIREGUARD_ELF);
+        let proof =
+            run_proof(&client, &pk, &stdin, args.proof_type).expect("failed to generate proof");
+        println!("Successfully generated proof!");
+        client.verify(&proof, &vk).expect("failed to verify proof");
+        println!("Successfully verified proof!");
+        proof.public_values.to_vec()
+    };
+
+    // Read the output.
+    // - sha256(key) = 32 bytes
+    // - counter = 8 bytes
+    // - sha256(plaintext) = 32 bytes
+    // - packet = ciphertext and 16 byte tag
+    let (key_hash, rest) = public_values.split_at(32);
+    let (counter, rest) = rest.split_at(8);
+    let (plaintext_hash, packet) = rest.split_at(32);
+    assert_eq!(key_hash, Sha256::digest(key).as_slice());
+    assert_eq!(counter, args.counter.to_le_bytes());
+    assert_eq!(plaintext_hash, Sha256::digest(&plaintext).as_slice());
+
+    let decrypted =
+        wireguard_decrypt(&Key(key), args.counter, packet).expect("zkVM packet tag is invalid");
+    assert_eq!(decrypted, plaintext);
+    println!("Decryption of zkVM packet matches input!");
+
+    std::fs::write(&args.output, packet).expect("Failed to write packet");
+    println!("Wrote packet {} to {}", args.counter, args.output.display());
+}
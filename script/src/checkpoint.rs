@@ -0,0 +1,152 @@
+//! Checkpoints to restart an interrupted `--prove` session with `--resume`.
+//!
+//! SP1 4 has no API to checkpoint or resume a proof in progress, so a preempted GPU proof always
+//! starts over. A checkpoint saves what the prover needs to start again: the [`SP1Stdin`] and the
+//! [`SP1ProvingKey`], which skips the setup and re-reading the inputs on restart.
+//!
+//! The checkpoint is written before proving starts and removed once the proof is verified, so a
+//! checkpoint left on disk means the session was interrupted. `meta.json` is written last, a
+//! directory without it was interrupted while saving and is not [`Checkpoint::is_interrupted`].
+//!
+//! The stdin holds the encryption key. The files are created readable by their owner only, keep
+//! the directory on an encrypted disk and delete it if the proof is abandoned.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{SP1ProvingKey, SP1Stdin};
+
+const STDIN_FILE: &str = "stdin.bin";
+const PROVING_KEY_FILE: &str = "pk.bin";
+const META_FILE: &str = "meta.json";
+
+/// What the summary and audit log of a resumed proof need besides the stdin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointMeta {
+    #[serde(with = "hex::serde")]
+    pub nonce: [u8; 12],
+    /// SHA256 of the uncompressed plaintext, to check `--input` on resume.
+    #[serde(with = "hex::serde")]
+    pub plaintext_hash: [u8; 32],
+    pub commit_ciphertext_hash: bool,
+    /// Length of the uncompressed plaintext, for the metrics. Zero in checkpoints saved without it.
+    #[serde(default)]
+    pub plaintext_size: usize,
+}
+
+/// Why a checkpoint could not be saved or loaded.
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    /// The directory has no complete checkpoint.
+    NotFound(PathBuf),
+    Malformed(&'static str),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(e) => write!(f, "failed to access checkpoint: {e}"),
+            CheckpointError::NotFound(dir) => write!(
+                f,
+                "no checkpoint in {}, prove with --checkpoint-dir to create one",
+                dir.display()
+            ),
+            CheckpointError::Malformed(file) => write!(f, "malformed checkpoint file {file}"),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl From<io::Error> for CheckpointError {
+    fn from(e: io::Error) -> Self {
+        CheckpointError::Io(e)
+    }
+}
+
+/// A saved proving session in a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    dir: PathBuf,
+}
+
+impl Checkpoint {
+    /// Whether `dir` holds the checkpoint of a session that did not complete.
+    pub fn is_interrupted(dir: impl AsRef<Path>) -> bool {
+        dir.as_ref().join(META_FILE).is_file()
+    }
+
+    /// Save a session to `dir`, creating it if needed.
+    pub fn save(
+        dir: impl AsRef<Path>,
+        stdin: &SP1Stdin,
+        pk: &SP1ProvingKey,
+        meta: &CheckpointMeta,
+    ) -> Result<Self, CheckpointError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let _ = fs::remove_file(dir.join(META_FILE));
+
+        let stdin = bincode::serialize(stdin).map_err(io::Error::other)?;
+        write_private(&dir.join(STDIN_FILE), &stdin)?;
+        let pk = bincode::serialize(pk).map_err(io::Error::other)?;
+        write_private(&dir.join(PROVING_KEY_FILE), &pk)?;
+        let meta = serde_json::to_string(meta).expect("checkpoint meta serializes");
+        write_private(&dir.join(META_FILE), meta.as_bytes())?;
+        Ok(Self { dir })
+    }
+
+    /// Load the session saved in `dir`.
+    pub fn load(
+        dir: impl AsRef<Path>,
+    ) -> Result<(Self, SP1Stdin, SP1ProvingKey, CheckpointMeta), CheckpointError> {
+        let dir = dir.as_ref().to_path_buf();
+        if !Self::is_interrupted(&dir) {
+            return Err(CheckpointError::NotFound(dir));
+        }
+        let meta = serde_json::from_slice(&fs::read(dir.join(META_FILE))?)
+            .map_err(|_| CheckpointError::Malformed(META_FILE))?;
+        let stdin = bincode::deserialize(&fs::read(dir.join(STDIN_FILE))?)
+            .map_err(|_| CheckpointError::Malformed(STDIN_FILE))?;
+        let pk = bincode::deserialize(&fs::read(dir.join(PROVING_KEY_FILE))?)
+            .map_err(|_| CheckpointError::Malformed(PROVING_KEY_FILE))?;
+        Ok((Self { dir }, stdin, pk, meta))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Remove the checkpoint after the proof completed. Only the checkpoint files are removed, and
+    /// the directory too if nothing else is left in it.
+    pub fn complete(self) -> io::Result<()> {
+        // The metadata first, so an interruption here leaves no resumable checkpoint
+        for file in [META_FILE, STDIN_FILE, PROVING_KEY_FILE] {
+            match fs::remove_file(self.dir.join(file)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        if fs::read_dir(&self.dir)?.next().is_none() {
+            fs::remove_dir(&self.dir)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write `path` through a temporary file, so an interruption never leaves it half written.
+fn write_private(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(&tmp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    fs::rename(tmp, path)
+}
@@ -0,0 +1,152 @@
+//! Converting a proof to another proof type by continuing SP1's recursion pipeline.
+//!
+//! SP1 proves in stages, each stage proving the one before:
+//!
+//! ```text
+//! core -> compressed -> shrink -> wrap -> groth16 | plonk
+//! ```
+//!
+//! A proof converts to any later stage without the inputs it was proven from. The earlier stages
+//! are not kept in a proof, so converting back, or between Groth16 and PLONK, is
+//! [`ConvertError::NeedsInputs`] and the inputs have to be proven again with `--proof-type`.
+//!
+//! Mock proofs hold nothing but their public values and convert to a mock proof of any type.
+
+use std::fmt;
+
+use sp1_prover::{SP1CoreProofData, SP1ProofWithMetadata, SP1RecursionProverError};
+use sp1_sdk::install::try_install_circuit_artifacts;
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues, SP1Prover, SP1ProvingKey, SP1Stdin};
+use sp1_stark::SP1ProverOpts;
+
+use chacha_lib::ProofType;
+
+use crate::proof_mode;
+
+/// Why a proof could not be converted.
+#[derive(Debug)]
+pub enum ConvertError {
+    /// `to` comes before `from` in the pipeline, or both finish it.
+    NeedsInputs {
+        from: ProofType,
+        to: ProofType,
+    },
+    Prover(SP1RecursionProverError),
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::NeedsInputs { from, to } => write!(
+                f,
+                "a {from} proof cannot be converted to {to} without the original inputs, \
+                 prove them again with --proof-type {to}"
+            ),
+            ConvertError::Prover(e) => write!(f, "failed to convert proof: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+impl From<SP1RecursionProverError> for ConvertError {
+    fn from(e: SP1RecursionProverError) -> Self {
+        ConvertError::Prover(e)
+    }
+}
+
+pub fn proof_type(proof: &SP1Proof) -> ProofType {
+    match proof {
+        SP1Proof::Core(_) => ProofType::Core,
+        SP1Proof::Compressed(_) => ProofType::Compressed,
+        SP1Proof::Plonk(_) => ProofType::Plonk,
+        SP1Proof::Groth16(_) => ProofType::Groth16,
+    }
+}
+
+fn stage(proof_type: ProofType) -> u8 {
+    match proof_type {
+        ProofType::Core => 0,
+        ProofType::Compressed => 1,
+        ProofType::Groth16 | ProofType::Plonk => 2,
+    }
+}
+
+/// Whether the proof was made by a mock prover, which leaves every proof field empty.
+pub fn is_mock(proof: &SP1Proof) -> bool {
+    match proof {
+        SP1Proof::Core(shards) => shards.is_empty(),
+        SP1Proof::Compressed(proof) => proof.proof.opened_values.chips.is_empty(),
+        SP1Proof::Groth16(proof) => proof.encoded_proof.is_empty(),
+        SP1Proof::Plonk(proof) => proof.encoded_proof.is_empty(),
+    }
+}
+
+/// Convert `proof` of the program of `pk` to `to`, returning it unchanged if it already is.
+///
+/// The proof is not verified, verify it against `pk.vk` first.
+pub fn convert(
+    prover: &SP1Prover,
+    pk: &SP1ProvingKey,
+    proof: SP1ProofWithPublicValues,
+    to: ProofType,
+) -> Result<SP1ProofWithPublicValues, ConvertError> {
+    let from = proof_type(&proof.proof);
+    if from == to {
+        return Ok(proof);
+    }
+    if stage(to) <= stage(from) {
+        return Err(ConvertError::NeedsInputs { from, to });
+    }
+
+    let SP1ProofWithPublicValues {
+        proof,
+        public_values,
+        sp1_version,
+    } = proof;
+    if is_mock(&proof) {
+        return Ok(SP1ProofWithPublicValues::create_mock_proof(
+            pk,
+            public_values,
+            proof_mode(to),
+            &sp1_version,
+        ));
+    }
+
+    let opts = SP1ProverOpts::default();
+    let compressed = match proof {
+        SP1Proof::Core(shards) => {
+            // Compressing only reads the shard proofs and public values
+            let core = SP1ProofWithMetadata {
+                proof: SP1CoreProofData(shards),
+                stdin: SP1Stdin::default(),
+                public_values: public_values.clone(),
+                cycles: 0,
+            };
+            // The encryption program verifies no other proofs, so there are none to defer
+            prover.compress(&pk.vk, core, vec![], opts)?
+        }
+        SP1Proof::Compressed(proof) => *proof,
+        SP1Proof::Groth16(_) | SP1Proof::Plonk(_) => unreachable!("checked by stage"),
+    };
+
+    let proof = match to {
+        ProofType::Compressed => SP1Proof::Compressed(Box::new(compressed)),
+        ProofType::Groth16 | ProofType::Plonk => {
+            let outer = prover.wrap_bn254(prover.shrink(compressed, opts)?, opts)?;
+            if to == ProofType::Groth16 {
+                let artifacts = try_install_circuit_artifacts("groth16");
+                SP1Proof::Groth16(prover.wrap_groth16_bn254(outer, &artifacts))
+            } else {
+                let artifacts = try_install_circuit_artifacts("plonk");
+                SP1Proof::Plonk(prover.wrap_plonk_bn254(outer, &artifacts))
+            }
+        }
+        ProofType::Core => unreachable!("checked by stage"),
+    };
+    Ok(SP1ProofWithPublicValues {
+        proof,
+        public_values,
+        sp1_version,
+    })
+}
@@ -0,0 +1,46 @@
+//! Proving cost estimates in USD, from the cycle count model and an instance hourly price.
+
+use chacha_lib::ProofType;
+
+use crate::estimate::estimate_cycles;
+
+/// Proving throughput measured on a single GPU.
+pub const CYCLES_PER_MINUTE: u64 = 60_000_000;
+
+/// Approximate on-demand hourly prices of common single GPU cloud instances.
+///
+/// Prices vary by provider and region: pass `--price-per-hour` to override.
+pub const INSTANCE_PRICES: &[(&str, f64)] =
+    &[("l4", 0.85), ("a10g", 1.01), ("a100", 3.67), ("h100", 9.80)];
+
+/// Hourly price of a known instance name, see [`INSTANCE_PRICES`].
+pub fn instance_price_per_hour(instance: &str) -> Option<f64> {
+    INSTANCE_PRICES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(instance))
+        .map(|(_, price)| *price)
+}
+
+/// Fixed time to wrap the core proof into the requested proof type.
+fn wrap_minutes(proof_type: ProofType) -> f64 {
+    match proof_type {
+        ProofType::Core => 0.0,
+        ProofType::Compressed => 1.0,
+        ProofType::Groth16 => 3.0,
+        ProofType::Plonk => 5.0,
+    }
+}
+
+/// Estimated proving time in minutes.
+pub fn estimate_proving_minutes(input_size_bytes: usize, proof_type: ProofType) -> f64 {
+    estimate_cycles(input_size_bytes) as f64 / CYCLES_PER_MINUTE as f64 + wrap_minutes(proof_type)
+}
+
+/// Estimated cost in USD to prove `input_size_bytes` on an instance billed `instance_price_per_hour`.
+pub fn estimate_proving_cost_usd(
+    input_size_bytes: usize,
+    proof_type: ProofType,
+    instance_price_per_hour: f64,
+) -> f64 {
+    estimate_proving_minutes(input_size_bytes, proof_type) / 60.0 * instance_price_per_hour
+}
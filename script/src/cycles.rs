@@ -0,0 +1,88 @@
+//! Cycle counts of the encryption program per input size, tracked in CI by
+//! `benches/cycle_count.rs` so a pull request that makes encrypting more expensive fails.
+//!
+//! Executions are deterministic, so unlike timings the counts are compared exactly against the
+//! baseline, with [`REGRESSION_THRESHOLD_PERCENT`] of headroom for intended changes.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Plaintext sizes the benchmark executes, in bytes.
+pub const BENCH_SIZES: [usize; 7] = [1, 64, 1024, 4096, 16384, 65536, 262144];
+
+/// Largest increase over the baseline, in percent, before the benchmark fails.
+pub const REGRESSION_THRESHOLD_PERCENT: f64 = 5.0;
+
+/// Smallest coefficient of determination of cycles against input size, as hashing and encrypting
+/// are both linear, see [`crate::estimate`].
+pub const MIN_R_SQUARED: f64 = 0.99;
+
+/// Cycle count per plaintext size.
+pub type CycleCounts = BTreeMap<usize, u64>;
+
+/// R² of the least squares line through `counts`, 1 for a perfectly linear cost.
+pub fn r_squared(counts: &CycleCounts) -> f64 {
+    let n = counts.len() as f64;
+    let points: Vec<(f64, f64)> = counts
+        .iter()
+        .map(|(&size, &cycles)| (size as f64, cycles as f64))
+        .collect();
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for (x, y) in &points {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+        syy += (y - mean_y) * (y - mean_y);
+    }
+    if syy == 0.0 {
+        return 1.0;
+    }
+    sxy * sxy / (sxx * syy)
+}
+
+/// A size whose cycle count grew from the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CycleRegression {
+    pub size: usize,
+    pub baseline: u64,
+    pub current: u64,
+}
+
+impl CycleRegression {
+    pub fn percent(&self) -> f64 {
+        (self.current as f64 - self.baseline as f64) / self.baseline as f64 * 100.0
+    }
+}
+
+/// Sizes of both `baseline` and `current` whose count grew by more than `threshold_percent`.
+pub fn regressions(
+    baseline: &CycleCounts,
+    current: &CycleCounts,
+    threshold_percent: f64,
+) -> Vec<CycleRegression> {
+    current
+        .iter()
+        .filter_map(|(&size, &current)| {
+            let baseline = *baseline.get(&size)?;
+            Some(CycleRegression {
+                size,
+                baseline,
+                current,
+            })
+        })
+        .filter(|regression| regression.percent() > threshold_percent)
+        .collect()
+}
+
+/// A bar per size, scaled to the largest count, for plotting cycles against input size in a
+/// terminal.
+pub fn plot(counts: &CycleCounts, width: usize) -> String {
+    let max = counts.values().copied().max().unwrap_or(0).max(1);
+    let mut plot = String::new();
+    for (size, cycles) in counts {
+        let bar = (*cycles as f64 / max as f64 * width as f64).ceil() as usize;
+        writeln!(plot, "{size:>8} B {cycles:>12} {}", "#".repeat(bar)).unwrap();
+    }
+    plot
+}
@@ -0,0 +1,200 @@
+//! `--dry-run` checks of every input of the `chacha` binary, reporting all problems at once
+//! instead of failing on the first one after a long setup.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use hex::FromHex;
+
+use chacha_lib::{CompressionType, ProofType};
+
+use crate::estimate::estimate_gpu_memory_mb;
+use crate::{validate_prove_inputs, ValidationError, MAX_PLAINTEXT_BYTES};
+
+/// Printed when every input is valid.
+pub const OK_MESSAGE: &str = "[OK] All inputs valid. Ready to prove.";
+
+/// The inputs as given on the command line and in the environment.
+#[derive(Debug, Clone, Default)]
+pub struct DryRunInputs<'a> {
+    /// `ENCRYPTION_KEY`, ignored with `ephemeral_key`.
+    pub encryption_key: Option<&'a str>,
+    pub ephemeral_key: bool,
+    /// `None` for the bundled example input.
+    pub input: Option<&'a Path>,
+    pub timestamp_key: Option<&'a str>,
+    pub disclose: &'a [(usize, usize)],
+}
+
+/// Inputs that passed every check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidInputs {
+    /// `None` with `--ephemeral-key`.
+    pub key: Option<[u8; 32]>,
+    pub plaintext: Vec<u8>,
+}
+
+/// One invalid input.
+#[derive(Debug)]
+pub enum InputError {
+    MissingKey,
+    KeyNotHex,
+    InputNotFound(PathBuf),
+    InputUnreadable(PathBuf, io::Error),
+    TimestampKey,
+    DiscloseOutOfRange {
+        start: usize,
+        end: usize,
+        len: usize,
+    },
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::MissingKey => {
+                write!(
+                    f,
+                    "ENCRYPTION_KEY is not set, set it or use --ephemeral-key"
+                )
+            }
+            InputError::KeyNotHex => write!(f, "ENCRYPTION_KEY must be hex"),
+            InputError::InputNotFound(path) => {
+                write!(f, "Input file {} does not exist", path.display())
+            }
+            InputError::InputUnreadable(path, e) => {
+                write!(f, "Failed to read input file {}: {e}", path.display())
+            }
+            InputError::TimestampKey => {
+                write!(f, "--timestamp-key must be 32 hex encoded bytes")
+            }
+            InputError::DiscloseOutOfRange { start, end, len } => write!(
+                f,
+                "--disclose {start}..{end} is outside the {len} byte input"
+            ),
+            InputError::Invalid(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Check every input, returning all the invalid ones.
+pub fn check_inputs(inputs: &DryRunInputs) -> Result<ValidInputs, Vec<InputError>> {
+    let mut errors = Vec::new();
+
+    let key = if inputs.ephemeral_key {
+        None
+    } else {
+        match inputs.encryption_key.map(hex::decode) {
+            None => {
+                errors.push(InputError::MissingKey);
+                None
+            }
+            Some(Err(_)) => {
+                errors.push(InputError::KeyNotHex);
+                None
+            }
+            Some(Ok(key)) => Some(key),
+        }
+    };
+
+    let plaintext = match inputs.input {
+        None => Some(chacha_lib::INPUT_BYTES.to_vec()),
+        Some(path) if !path.exists() => {
+            errors.push(InputError::InputNotFound(path.to_path_buf()));
+            None
+        }
+        Some(path) => std::fs::read(path)
+            .map_err(|e| errors.push(InputError::InputUnreadable(path.to_path_buf(), e)))
+            .ok(),
+    };
+
+    let timestamp_key = inputs.timestamp_key.map(<[u8; 32]>::from_hex);
+    if let Some(Err(_)) = timestamp_key {
+        errors.push(InputError::TimestampKey);
+    }
+
+    if let Some(plaintext) = &plaintext {
+        for &(start, end) in inputs.disclose {
+            if end > plaintext.len() {
+                errors.push(InputError::DiscloseOutOfRange {
+                    start,
+                    end,
+                    len: plaintext.len(),
+                });
+            }
+        }
+
+        // The nonce is always a fresh 12 bytes, only the key and plaintext can be invalid
+        let validated = match &key {
+            Some(key) => validate_prove_inputs(key, &[0; 12], plaintext),
+            None if plaintext.len() > MAX_PLAINTEXT_BYTES => {
+                Err(ValidationError::PlaintextTooLarge(plaintext.len()))
+            }
+            None => Ok(()),
+        };
+        if let Err(e) = validated {
+            errors.push(InputError::Invalid(e));
+        }
+    }
+
+    match (errors.is_empty(), plaintext) {
+        (true, Some(plaintext)) => Ok(ValidInputs {
+            key: key.map(|key| key.try_into().expect("validated key length")),
+            plaintext,
+        }),
+        _ => Err(errors),
+    }
+}
+
+/// What a valid run would prove.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRunSummary {
+    pub input_bytes: usize,
+    pub compression: CompressionType,
+    pub proof_type: ProofType,
+    /// Cycle count of executing the program on the inputs.
+    pub cycles: u64,
+    pub gpu_memory_mb: u64,
+}
+
+impl DryRunSummary {
+    pub fn new(
+        input_bytes: usize,
+        compression: CompressionType,
+        proof_type: ProofType,
+        cycles: u64,
+    ) -> Self {
+        Self {
+            input_bytes,
+            compression,
+            proof_type,
+            cycles,
+            gpu_memory_mb: estimate_gpu_memory_mb(input_bytes),
+        }
+    }
+}
+
+impl fmt::Display for DryRunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Dry run, nothing was proved:")?;
+        writeln!(f, "  Input:       {} bytes", self.input_bytes)?;
+        writeln!(f, "  Compression: {}", self.compression)?;
+        writeln!(f, "  Proof type:  {}", self.proof_type)?;
+        writeln!(f, "  Cycles:      {}", self.cycles)?;
+        writeln!(f, "  GPU memory:  ~{} MB (estimated)", self.gpu_memory_mb)?;
+        write!(f, "{OK_MESSAGE}")
+    }
+}
+
+/// One `[ERROR]` line per invalid input.
+pub fn format_errors(errors: &[InputError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("[ERROR] {e}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
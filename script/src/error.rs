@@ -0,0 +1,37 @@
+//! Errors of the script binaries: calldata, fixtures, saved proofs and the `chacha` binary.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use chacha_lib::ProofType;
+
+/// Why a proof could not be made, turned into calldata or written out for the contracts.
+#[derive(thiserror::Error)]
+pub enum ScriptError {
+    /// The encoded Groth16 or PLONK proof is not hex.
+    #[error("failed to ABI encode the proof: {0}")]
+    AbiEncode(#[from] hex::FromHexError),
+    /// The public values are shorter than the program commits.
+    #[error("the public values are {got} bytes, expected at least {expected}")]
+    InvalidPublicValuesLength { got: usize, expected: usize },
+    #[error("no proof at {}", .0.display())]
+    ProofFileNotFound(PathBuf),
+    /// The proof does not verify under the program's key.
+    #[error("failed to verify proof: {0}")]
+    VerificationFailed(String),
+    /// Only Groth16 and PLONK proofs are verified on-chain.
+    #[error("a {0} proof cannot be verified on-chain, convert it to groth16")]
+    InvalidProofType(ProofType),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Any other failure, with the [`anyhow::Context`] of what was being done.
+    #[error("{0:#}")]
+    Other(#[from] anyhow::Error),
+}
+
+// A `main` returning the error prints it with `Debug`, so print the message rather than the variant
+impl fmt::Debug for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
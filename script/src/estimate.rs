@@ -0,0 +1,153 @@
+//! Resource estimates used to fail fast before starting an expensive proof.
+//!
+//! ## GPU memory model
+//!
+//! Peak VRAM is modelled as a fixed cost for the proving keys and recursion circuits plus a
+//! linear term in the plaintext size:
+//!
+//! ```text
+//! vram_mb = GPU_BASE_MB + GPU_MB_PER_KB * input_size_kb
+//! ```
+//!
+//! The constants are fitted to the initial benchmarks, where proving the ~1.5 MB example input
+//! peaked at ~15 GB. A GPU with at least [`RECOMMENDED_GPU_MEMORY_MB`] is recommended.
+//!
+//! ## Cycle count model
+//!
+//! Hashing and encrypting are both linear in the plaintext, on top of a fixed cost for reading
+//! the inputs. Run the script with `--execute` for the exact count of a given input.
+
+use std::fmt;
+
+use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::Nvml;
+
+/// Fixed VRAM cost independent of the input size.
+pub const GPU_BASE_MB: u64 = 12 * 1024;
+/// Additional VRAM per KB of plaintext.
+pub const GPU_MB_PER_KB: u64 = 2;
+/// Minimum VRAM we recommend for GPU proving.
+pub const RECOMMENDED_GPU_MEMORY_MB: u64 = 20 * 1024;
+
+/// Fixed zkVM cycles independent of the input size.
+pub const CYCLES_BASE: u64 = 20_000;
+/// zkVM cycles per plaintext byte (SHA256 + ChaCha20).
+pub const CYCLES_PER_BYTE: u64 = 50;
+
+/// Estimated zkVM cycle count to hash and encrypt `input_size_bytes` bytes.
+pub fn estimate_cycles(input_size_bytes: usize) -> u64 {
+    CYCLES_BASE + CYCLES_PER_BYTE * input_size_bytes as u64
+}
+
+/// Default `--auto-prove-threshold`.
+pub const AUTO_PROVE_THRESHOLD_BYTES: usize = 10 * 1024;
+
+/// Whether to execute the program or generate a proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Execute,
+    Prove,
+}
+
+/// The mode `--auto` picks: inputs below `threshold_bytes` are only executed, larger ones proved.
+pub fn auto_mode(input_size_bytes: usize, threshold_bytes: usize) -> Mode {
+    if input_size_bytes < threshold_bytes {
+        Mode::Execute
+    } else {
+        Mode::Prove
+    }
+}
+
+/// Estimated peak GPU memory (in MB) to prove the encryption of `input_size_bytes` bytes.
+pub fn estimate_gpu_memory_mb(input_size_bytes: usize) -> u64 {
+    GPU_BASE_MB + GPU_MB_PER_KB * (input_size_bytes as u64).div_ceil(1024)
+}
+
+/// Free memory (in MB) of the first GPU visible to NVML.
+pub fn available_gpu_memory_mb() -> Result<u64, NvmlError> {
+    let nvml = Nvml::init()?;
+    let device = nvml.device_by_index(0)?;
+    Ok(device.memory_info()?.free / (1024 * 1024))
+}
+
+/// Where [`check_available_gpu_memory`] reads the free GPU memory from, [`NvmlGpuMemory`] outside
+/// of tests.
+pub trait GpuMemoryQuery {
+    /// Free memory in MB.
+    fn free_memory_mb(&self) -> Result<u64, NvmlError>;
+}
+
+/// The first GPU visible to NVML, see [`available_gpu_memory_mb`].
+pub struct NvmlGpuMemory;
+
+impl GpuMemoryQuery for NvmlGpuMemory {
+    fn free_memory_mb(&self) -> Result<u64, NvmlError> {
+        available_gpu_memory_mb()
+    }
+}
+
+/// The GPU does not have enough free memory for the estimated proving workload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsufficientGpuMemory {
+    pub required_mb: u64,
+    pub available_mb: u64,
+}
+
+impl fmt::Display for InsufficientGpuMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Insufficient GPU memory: proving needs ~{} MB but only {} MB is free. \
+             Use a GPU with at least {} GB of VRAM.",
+            self.required_mb,
+            self.available_mb,
+            RECOMMENDED_GPU_MEMORY_MB / 1024
+        )
+    }
+}
+
+impl std::error::Error for InsufficientGpuMemory {}
+
+/// Compare the estimate for `input_size_bytes` against `available_mb` of free VRAM.
+pub fn check_gpu_memory(
+    input_size_bytes: usize,
+    available_mb: u64,
+) -> Result<(), InsufficientGpuMemory> {
+    let required_mb = estimate_gpu_memory_mb(input_size_bytes);
+    if available_mb < required_mb {
+        return Err(InsufficientGpuMemory {
+            required_mb,
+            available_mb,
+        });
+    }
+    Ok(())
+}
+
+/// Why `--check-gpu-memory` failed.
+#[derive(Debug)]
+pub enum GpuMemoryError {
+    Query(NvmlError),
+    Insufficient(InsufficientGpuMemory),
+}
+
+impl fmt::Display for GpuMemoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuMemoryError::Query(e) => write!(f, "Failed to query GPU memory via NVML: {e}"),
+            GpuMemoryError::Insufficient(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GpuMemoryError {}
+
+/// Query the free GPU memory and compare the estimate for `input_size_bytes` against it,
+/// returning the free memory in MB.
+pub fn check_available_gpu_memory(
+    query: &impl GpuMemoryQuery,
+    input_size_bytes: usize,
+) -> Result<u64, GpuMemoryError> {
+    let available_mb = query.free_memory_mb().map_err(GpuMemoryError::Query)?;
+    check_gpu_memory(input_size_bytes, available_mb).map_err(GpuMemoryError::Insufficient)?;
+    Ok(available_mb)
+}
@@ -0,0 +1,61 @@
+//! Execution reports as folded stacks, the input format of `inferno-flamegraph` and Brendan
+//! Gregg's `flamegraph.pl`.
+//!
+//! Each line is `category;opcode count`, so the flame graph groups the opcodes of the
+//! [`crate::profile`] histogram by kind, e.g. `alu;xor 5120`. The syscalls an `ecall` made are a
+//! third frame, `system;ecall;sha_extend 40`, and the widths still add up to the instruction count.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+use sp1_sdk::ExecutionReport;
+
+use crate::profile::opcode_histogram;
+
+/// The frame grouping an RV32IM mnemonic.
+pub fn opcode_category(opcode: &str) -> &'static str {
+    match opcode {
+        "add" | "sub" | "xor" | "or" | "and" | "sll" | "srl" | "sra" | "slt" | "sltu" => "alu",
+        "mul" | "mulh" | "mulhu" | "mulhsu" | "div" | "divu" | "rem" | "remu" => "muldiv",
+        "lb" | "lh" | "lw" | "lbu" | "lhu" => "load",
+        "sb" | "sh" | "sw" => "store",
+        "beq" | "bne" | "blt" | "bge" | "bltu" | "bgeu" => "branch",
+        "jal" | "jalr" | "auipc" => "jump",
+        "ecall" | "ebreak" | "unimp" => "system",
+        _ => "other",
+    }
+}
+
+/// The folded stacks of `report`, one line per opcode and syscall that ran, sorted.
+pub fn flamegraph_stacks(report: &ExecutionReport) -> String {
+    let mut stacks = BTreeMap::new();
+    for (opcode, count) in opcode_histogram(report) {
+        if opcode == "ecall" {
+            let mut rest = count;
+            for (syscall, &calls) in report.syscall_counts.iter().filter(|&(_, &n)| n > 0) {
+                let name = syscall.to_string().to_ascii_lowercase();
+                stacks.insert(format!("system;ecall;{name}"), calls);
+                rest = rest.saturating_sub(calls);
+            }
+            if rest > 0 {
+                stacks.insert("system;ecall".to_string(), rest);
+            }
+        } else {
+            stacks.insert(format!("{};{opcode}", opcode_category(&opcode)), count);
+        }
+    }
+
+    let mut folded = String::new();
+    for (stack, count) in stacks {
+        writeln!(folded, "{stack} {count}").unwrap();
+    }
+    folded
+}
+
+/// Write the [`flamegraph_stacks`] of `report` to `output_path`, to render with
+/// `inferno-flamegraph < output.stacks > flamegraph.svg`.
+pub fn export_flamegraph(report: &ExecutionReport, output_path: &Path) -> io::Result<()> {
+    std::fs::write(output_path, flamegraph_stacks(report))
+}
@@ -0,0 +1,206 @@
+//! Splitting a batch of proofs across the GPUs of a multi-GPU instance.
+//!
+//! Each GPU gets its own OS thread running the proving loop over the tasks assigned to it by
+//! [`assign_proofs_to_gpus`], see [`prove_on_gpus`].
+//!
+//! ## CUDA context isolation
+//!
+//! A CUDA context belongs to a process, and the devices it can see are fixed by
+//! `CUDA_VISIBLE_DEVICES` when CUDA is initialized. The environment is shared by every thread of
+//! a process, so the threads cannot each be given a GPU by setting it. SP1 4 does not prove in the
+//! calling process anyway: the CUDA prover sends the work to a `moongate` server container, and
+//! the one it manages itself always sees every GPU, listens on port 3000 and is named `sp1-gpu`,
+//! so only one fits on a host.
+//!
+//! So each GPU gets its own `moongate` server, started by [`moongate_server`] with the
+//! [`cuda_env`] of that GPU and its own port, and each thread talks to the server of its GPU.
+//! `CUDA_DEVICE_ORDER=PCI_BUS_ID` is set as well, since CUDA numbers the devices fastest first
+//! by default while NVML, which [`detect_gpus`] uses, numbers them by PCI bus.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chacha_lib::ProofType;
+use nvml_wrapper::Nvml;
+use sp1_prover::components::SP1ProverComponents;
+use sp1_sdk::{Prover, SP1ProvingKey};
+
+use crate::estimate::{estimate_cycles, estimate_gpu_memory_mb};
+use crate::watch::save_proof;
+use crate::{run_proof, EncryptInputs};
+
+/// The `moongate` image of the SP1 version in use, overridden by `SP1_GPU_IMAGE` like SP1 does.
+pub const MOONGATE_IMAGE: &str = "public.ecr.aws/succinct-labs/moongate:v4.1.0";
+
+/// A GPU visible to NVML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuInfo {
+    /// The NVML device index.
+    pub index: u32,
+    pub name: String,
+    pub total_memory_mb: u64,
+    pub free_memory_mb: u64,
+}
+
+/// One input file of the batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProveTask {
+    pub input: PathBuf,
+    /// The plaintext size, which the memory and cycle estimates are based on.
+    pub size_bytes: usize,
+}
+
+/// The tasks one GPU proves, in batch order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuAssignment {
+    pub gpu: GpuInfo,
+    /// Indices into the batch.
+    pub tasks: Vec<usize>,
+}
+
+/// The GPUs visible to NVML, none if NVML cannot be initialized, as on machines without an
+/// NVIDIA driver.
+pub fn detect_gpus() -> Vec<GpuInfo> {
+    let Ok(nvml) = Nvml::init() else {
+        return Vec::new();
+    };
+    let count = nvml.device_count().unwrap_or(0);
+    (0..count)
+        .filter_map(|index| {
+            let device = nvml.device_by_index(index).ok()?;
+            let memory = device.memory_info().ok()?;
+            Some(GpuInfo {
+                index,
+                name: device.name().unwrap_or_default(),
+                total_memory_mb: memory.total / (1024 * 1024),
+                free_memory_mb: memory.free / (1024 * 1024),
+            })
+        })
+        .collect()
+}
+
+/// Assign each task to a GPU, one [`GpuAssignment`] per GPU.
+///
+/// Tasks are placed largest first on the GPU with the fewest estimated cycles so far among those
+/// with enough free memory for them, see [`estimate_gpu_memory_mb`]. A task too large for every
+/// GPU goes to the one with the most free memory, where it fails as it would on a single GPU.
+pub fn assign_proofs_to_gpus(proofs: &[ProveTask], gpus: &[GpuInfo]) -> Vec<GpuAssignment> {
+    let mut assignments: Vec<_> = gpus
+        .iter()
+        .map(|gpu| GpuAssignment {
+            gpu: gpu.clone(),
+            tasks: Vec::new(),
+        })
+        .collect();
+    if gpus.is_empty() {
+        return assignments;
+    }
+
+    let mut order: Vec<_> = (0..proofs.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(proofs[i].size_bytes));
+    let mut load = vec![0u64; gpus.len()];
+    for i in order {
+        let required_mb = estimate_gpu_memory_mb(proofs[i].size_bytes);
+        let gpu = (0..gpus.len())
+            .filter(|&g| gpus[g].free_memory_mb >= required_mb)
+            .min_by_key(|&g| load[g])
+            .unwrap_or_else(|| {
+                (0..gpus.len())
+                    .max_by_key(|&g| (gpus[g].free_memory_mb, std::cmp::Reverse(g)))
+                    .unwrap()
+            });
+        load[gpu] += estimate_cycles(proofs[i].size_bytes);
+        assignments[gpu].tasks.push(i);
+    }
+    for assignment in &mut assignments {
+        assignment.tasks.sort_unstable();
+    }
+    assignments
+}
+
+/// Prove the tasks with one thread per GPU, each calling `prove` on its tasks in turn. The
+/// results are in batch order, `None` for tasks without a GPU.
+pub fn prove_on_gpus<T: Send>(
+    proofs: &[ProveTask],
+    assignments: &[GpuAssignment],
+    prove: impl Fn(&GpuInfo, &ProveTask) -> Result<T, String> + Sync,
+) -> Vec<Option<Result<T, String>>> {
+    let mut results: Vec<_> = proofs.iter().map(|_| None).collect();
+    std::thread::scope(|s| {
+        let workers: Vec<_> = assignments
+            .iter()
+            .filter(|assignment| !assignment.tasks.is_empty())
+            .map(|assignment| {
+                let prove = &prove;
+                std::thread::Builder::new()
+                    .name(format!("gpu-{}", assignment.gpu.index))
+                    .spawn_scoped(s, move || {
+                        assignment
+                            .tasks
+                            .iter()
+                            .map(|&i| (i, prove(&assignment.gpu, &proofs[i])))
+                            .collect::<Vec<_>>()
+                    })
+                    .expect("failed to spawn a GPU thread")
+            })
+            .collect();
+        for worker in workers {
+            let worker_results = worker.join().expect("a GPU thread panicked");
+            for (i, result) in worker_results {
+                results[i] = Some(result);
+            }
+        }
+    });
+    results
+}
+
+/// The environment limiting CUDA to `gpu`.
+pub fn cuda_env(gpu: &GpuInfo) -> [(&'static str, String); 2] {
+    [
+        ("CUDA_DEVICE_ORDER", "PCI_BUS_ID".to_string()),
+        ("CUDA_VISIBLE_DEVICES", gpu.index.to_string()),
+    ]
+}
+
+/// The name of the `moongate` container of `gpu`.
+pub fn moongate_container_name(gpu: &GpuInfo) -> String {
+    format!("sp1-gpu-{}", gpu.index)
+}
+
+/// The endpoint of a `moongate` server on `port`, for `with_moongate_endpoint`.
+pub fn moongate_endpoint(port: u16) -> String {
+    format!("http://localhost:{port}/twirp/")
+}
+
+/// `docker run` of a `moongate` server seeing only `gpu`, listening on `port`.
+pub fn moongate_server(gpu: &GpuInfo, port: u16) -> Command {
+    let image = std::env::var("SP1_GPU_IMAGE").unwrap_or_else(|_| MOONGATE_IMAGE.to_string());
+    let mut command = Command::new("docker");
+    command.args(["run", "--rm", "--gpus", "all"]);
+    for (var, value) in cuda_env(gpu) {
+        command.args(["-e", &format!("{var}={value}")]);
+    }
+    command
+        .args(["-p", &format!("{port}:3000")])
+        .args(["--name", &moongate_container_name(gpu)])
+        .arg(image);
+    command
+}
+
+/// Encrypt `input` under a fresh nonce and save its proof to `output_dir` with [`save_proof`], so
+/// the nonce is kept next to the proof.
+pub fn prove_file<C: SP1ProverComponents>(
+    client: &impl Prover<C>,
+    pk: &SP1ProvingKey,
+    key: [u8; 32],
+    input: &Path,
+    output_dir: &Path,
+    proof_type: ProofType,
+) -> Result<PathBuf, String> {
+    let plaintext =
+        std::fs::read(input).map_err(|e| format!("Failed to read {}: {e}", input.display()))?;
+    let nonce = chacha_lib::random_nonce();
+    let stdin = EncryptInputs::new(key, nonce, &plaintext).to_stdin();
+    let proof = run_proof(client, pk, &stdin, proof_type).map_err(|e| e.to_string())?;
+    save_proof(output_dir, input, nonce, &proof)
+}
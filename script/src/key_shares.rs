@@ -0,0 +1,65 @@
+//! Share files of a key split by `keygen --key-split`, reconstructed by `chacha --key-shares`.
+//!
+//! A share file holds one hex encoded [`KeyShare`]. Each one should go to a different custodian:
+//! any threshold of them reconstruct the key, fewer reveal nothing about it.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use chacha_lib::shamir::{reconstruct_key, split_key, KeyShare};
+use chacha_lib::Key;
+
+/// `<n>/<t>`, `n` shares of which any `t` reconstruct the key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeySplit {
+    pub shares: u8,
+    pub threshold: u8,
+}
+
+impl FromStr for KeySplit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (shares, threshold) = s
+            .split_once('/')
+            .and_then(|(n, t)| Some((n.parse::<u8>().ok()?, t.parse::<u8>().ok()?)))
+            .ok_or_else(|| format!("expected <n>/<t> with n at most 255, got `{s}`"))?;
+        if threshold == 0 || threshold > shares {
+            return Err(format!(
+                "the threshold must be between 1 and {shares}, got {threshold}"
+            ));
+        }
+        Ok(KeySplit { shares, threshold })
+    }
+}
+
+/// The name of the file of share `index`.
+pub fn share_file_name(index: u8) -> String {
+    format!("key-share-{index}.hex")
+}
+
+/// Split `key` and write a share file per share to `dir`, returning their paths.
+pub fn write_shares(key: &Key, split: KeySplit, dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    split_key(key, split.shares, split.threshold)
+        .iter()
+        .map(|share| {
+            let path = dir.join(share_file_name(share.index));
+            std::fs::write(&path, format!("{}\n", share.to_hex()))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Reconstruct the key from share files, at least the threshold of them.
+pub fn read_key_shares(paths: &[PathBuf]) -> Result<Key, String> {
+    let shares = paths
+        .iter()
+        .map(|path| {
+            let hex = std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+            KeyShare::from_hex(&hex).map_err(|e| format!("{}: {e}", path.display()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    reconstruct_key(&shares).map_err(|e| e.to_string())
+}
@@ -0,0 +1,503 @@
+//! Host-side building blocks shared by the script binaries.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+use chacha_lib::compress::{compress, zstd_compress};
+use chacha_lib::custody::OPERATOR_ID_LEN;
+use chacha_lib::input_auth::{input_hmac, input_hmac_v6, AuthenticatedInputs};
+use chacha_lib::public_values::HEADER_LEN;
+use chacha_lib::{CompressionType, Key, Nonce, ProofType, PublicValues, PROGRAM_VERSION};
+use sha2::{Digest, Sha256};
+use sp1_prover::components::SP1ProverComponents;
+use sp1_sdk::{
+    include_elf, HashableKey, Prover, ProverClient, SP1ProofMode, SP1ProofWithPublicValues,
+    SP1ProvingKey, SP1Stdin,
+};
+
+use crate::error::ScriptError;
+
+pub mod aggregate;
+pub mod analysis;
+pub mod audit;
+pub mod checkpoint;
+pub mod convert;
+pub mod cost;
+pub mod cycles;
+pub mod dry_run;
+pub mod error;
+pub mod estimate;
+pub mod flamegraph;
+pub mod gpu;
+pub mod key_shares;
+pub mod metrics;
+pub mod migrate;
+pub mod network;
+pub mod pipeline;
+pub mod post;
+pub mod profile;
+pub mod queue;
+pub mod rate_limit;
+pub mod relay;
+pub mod server;
+pub mod storage;
+pub mod summary;
+pub mod timestamp;
+pub mod tls;
+pub mod upgrade;
+pub mod watch;
+
+/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
+pub const CHACHA_ELF: &[u8] = include_elf!("chacha-program");
+/// Re-encrypts a ciphertext under a new key, see `program/src/main_rotate.rs`.
+pub const ROTATE_ELF: &[u8] = include_elf!("rotate-program");
+/// Encrypts to an X25519 public key, see `program/src/main_verifiable.rs`.
+pub const VERIFIABLE_ELF: &[u8] = include_elf!("verifiable-program");
+/// Encrypts one plaintext for several keys, see `program/src/main_multi.rs`.
+pub const MULTI_ELF: &[u8] = include_elf!("multi-program");
+/// Encrypts one chunk at a keystream offset, see `program/src/main_chunk.rs`.
+pub const CHUNK_ELF: &[u8] = include_elf!("chunk-program");
+/// Encrypts a WireGuard transport data packet, see `program/src/main_wireguard.rs`.
+pub const WIREGUARD_ELF: &[u8] = include_elf!("wireguard-program");
+/// Encrypts one message of a Signal sending chain, see `program/src/main_signal.rs`.
+pub const SIGNAL_ELF: &[u8] = include_elf!("signal-program");
+/// Encrypts an SSH packet, see `program/src/main_ssh.rs`.
+pub const SSH_ELF: &[u8] = include_elf!("ssh-program");
+/// Runs a Noise NN handshake, see `program/src/main_noise.rs`.
+pub const NOISE_ELF: &[u8] = include_elf!("noise-program");
+/// Encrypts a TLS 1.3 record, see `program/src/main_tls13.rs`.
+pub const TLS13_ELF: &[u8] = include_elf!("tls13-program");
+/// Derives a key with HKDF, see `program/src/main_kdf.rs`.
+pub const KDF_ELF: &[u8] = include_elf!("kdf-program");
+/// Hashes with and without the SHA-256 precompile, see `program/src/main_sha256_bench.rs`.
+pub const SHA256_BENCH_ELF: &[u8] = include_elf!("sha256-bench-program");
+/// Encrypts committing a BLAKE2b-256 plaintext hash, see `program/src/main_blake2b.rs`.
+pub const BLAKE2B_ELF: &[u8] = include_elf!("blake2b-program");
+/// Encrypts committing a SHA-512 plaintext hash, see `program/src/main_sha512.rs`.
+pub const SHA512_ELF: &[u8] = include_elf!("sha512-program");
+/// Encrypts and MACs with subkeys of one key, see `program/src/main_mac.rs`.
+pub const MAC_ELF: &[u8] = include_elf!("mac-program");
+/// Reveals a commitment without its key, see `program/src/main_reveal.rs`.
+pub const REVEAL_ELF: &[u8] = include_elf!("reveal-program");
+/// Decrypts for requesters satisfying a policy, see `program/src/main_policy.rs`.
+pub const POLICY_ELF: &[u8] = include_elf!("policy-program");
+/// Decrypts a time-locked ciphertext after its unlock block, see `program/src/main_timelock.rs`.
+pub const TIMELOCK_ELF: &[u8] = include_elf!("timelock-program");
+/// Encrypts revealing one plaintext segment, see `program/src/main_segment.rs`.
+pub const SEGMENT_ELF: &[u8] = include_elf!("segment-program");
+/// Format-preserving encryption of a structured value, see `program/src/main_fpe.rs`.
+pub const FPE_ELF: &[u8] = include_elf!("fpe-program");
+/// Encrypts with a Pedersen commitment to the plaintext, see `program/src/main_pedersen.rs`.
+pub const PEDERSEN_ELF: &[u8] = include_elf!("pedersen-program");
+/// Aggregates the chunk proofs of a stream, see `program/src/main_aggregate.rs`.
+pub const AGGREGATE_ELF: &[u8] = include_elf!("aggregate-program");
+/// Re-encrypts committing only hashes, see `program/src/main_rekey.rs`.
+pub const REKEY_ELF: &[u8] = include_elf!("rekey-program");
+/// Links two versions of the encryption program, see `program/src/main_upgrade.rs`.
+pub const UPGRADE_ELF: &[u8] = include_elf!("upgrade-program");
+/// Encrypts with AES-256-CTR, see `program/src/main_aes.rs`.
+pub const AES_ELF: &[u8] = include_elf!("aes-program");
+
+/// The zkVM programs, each with its own ELF and verification key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ProgramSelector {
+    #[default]
+    Encrypt,
+    Reencrypt,
+    Verifiable,
+    Multi,
+    Chunk,
+    Wireguard,
+    Signal,
+    Ssh,
+    Noise,
+    Tls13,
+    Kdf,
+    Upgrade,
+    Blake2b,
+    Sha512,
+    Mac,
+    Reveal,
+    Policy,
+    Timelock,
+    Segment,
+    Fpe,
+    Pedersen,
+    Aggregate,
+    Rekey,
+    Aes,
+}
+
+impl ProgramSelector {
+    pub const ALL: [ProgramSelector; 24] = [
+        ProgramSelector::Encrypt,
+        ProgramSelector::Reencrypt,
+        ProgramSelector::Verifiable,
+        ProgramSelector::Multi,
+        ProgramSelector::Chunk,
+        ProgramSelector::Wireguard,
+        ProgramSelector::Signal,
+        ProgramSelector::Ssh,
+        ProgramSelector::Noise,
+        ProgramSelector::Tls13,
+        ProgramSelector::Kdf,
+        ProgramSelector::Upgrade,
+        ProgramSelector::Blake2b,
+        ProgramSelector::Sha512,
+        ProgramSelector::Mac,
+        ProgramSelector::Reveal,
+        ProgramSelector::Policy,
+        ProgramSelector::Timelock,
+        ProgramSelector::Segment,
+        ProgramSelector::Fpe,
+        ProgramSelector::Pedersen,
+        ProgramSelector::Aggregate,
+        ProgramSelector::Rekey,
+        ProgramSelector::Aes,
+    ];
+
+    /// The script binary proving the program, `None` if it is only exercised by tests.
+    pub fn binary(self) -> Option<&'static str> {
+        match self {
+            ProgramSelector::Encrypt => Some("chacha"),
+            ProgramSelector::Reencrypt => Some("rotate"),
+            ProgramSelector::Verifiable => Some("verifiable"),
+            ProgramSelector::Multi => Some("multi_encrypt"),
+            ProgramSelector::Chunk => Some("chunk_prove"),
+            ProgramSelector::Wireguard => Some("wireguard"),
+            ProgramSelector::Upgrade => Some("upgrade"),
+            ProgramSelector::Aggregate => Some("aggregate_chunks"),
+            // With --commit-ciphertext-hash
+            ProgramSelector::Rekey => Some("rotate"),
+            // With --blake2b, --sha512 and --fips-mode
+            ProgramSelector::Blake2b | ProgramSelector::Sha512 | ProgramSelector::Aes => {
+                Some("chacha")
+            }
+            ProgramSelector::Signal
+            | ProgramSelector::Ssh
+            | ProgramSelector::Noise
+            | ProgramSelector::Tls13
+            | ProgramSelector::Kdf
+            | ProgramSelector::Mac
+            | ProgramSelector::Reveal
+            | ProgramSelector::Policy
+            | ProgramSelector::Timelock
+            | ProgramSelector::Segment
+            | ProgramSelector::Fpe
+            | ProgramSelector::Pedersen => None,
+        }
+    }
+}
+
+impl fmt::Display for ProgramSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProgramSelector::Encrypt => "encrypt",
+            ProgramSelector::Reencrypt => "reencrypt",
+            ProgramSelector::Verifiable => "verifiable",
+            ProgramSelector::Multi => "multi",
+            ProgramSelector::Chunk => "chunk",
+            ProgramSelector::Wireguard => "wireguard",
+            ProgramSelector::Signal => "signal",
+            ProgramSelector::Ssh => "ssh",
+            ProgramSelector::Noise => "noise",
+            ProgramSelector::Tls13 => "tls13",
+            ProgramSelector::Kdf => "kdf",
+            ProgramSelector::Upgrade => "upgrade",
+            ProgramSelector::Blake2b => "blake2b",
+            ProgramSelector::Sha512 => "sha512",
+            ProgramSelector::Mac => "mac",
+            ProgramSelector::Reveal => "reveal",
+            ProgramSelector::Policy => "policy",
+            ProgramSelector::Timelock => "timelock",
+            ProgramSelector::Segment => "segment",
+            ProgramSelector::Fpe => "fpe",
+            ProgramSelector::Pedersen => "pedersen",
+            ProgramSelector::Aggregate => "aggregate",
+            ProgramSelector::Rekey => "rekey",
+            ProgramSelector::Aes => "aes",
+        })
+    }
+}
+
+impl std::str::FromStr for ProgramSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_ascii_lowercase();
+        ProgramSelector::ALL
+            .into_iter()
+            .find(|program| program.to_string() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = ProgramSelector::ALL.iter().map(|p| p.to_string()).collect();
+                format!("unknown program `{s}`, expected {}", names.join("|"))
+            })
+    }
+}
+
+/// The ELF of `program`.
+pub fn select_elf(program: ProgramSelector) -> &'static [u8] {
+    match program {
+        ProgramSelector::Encrypt => CHACHA_ELF,
+        ProgramSelector::Reencrypt => ROTATE_ELF,
+        ProgramSelector::Verifiable => VERIFIABLE_ELF,
+        ProgramSelector::Multi => MULTI_ELF,
+        ProgramSelector::Chunk => CHUNK_ELF,
+        ProgramSelector::Wireguard => WIREGUARD_ELF,
+        ProgramSelector::Signal => SIGNAL_ELF,
+        ProgramSelector::Ssh => SSH_ELF,
+        ProgramSelector::Noise => NOISE_ELF,
+        ProgramSelector::Tls13 => TLS13_ELF,
+        ProgramSelector::Kdf => KDF_ELF,
+        ProgramSelector::Upgrade => UPGRADE_ELF,
+        ProgramSelector::Blake2b => BLAKE2B_ELF,
+        ProgramSelector::Sha512 => SHA512_ELF,
+        ProgramSelector::Mac => MAC_ELF,
+        ProgramSelector::Reveal => REVEAL_ELF,
+        ProgramSelector::Policy => POLICY_ELF,
+        ProgramSelector::Timelock => TIMELOCK_ELF,
+        ProgramSelector::Segment => SEGMENT_ELF,
+        ProgramSelector::Fpe => FPE_ELF,
+        ProgramSelector::Pedersen => PEDERSEN_ELF,
+        ProgramSelector::Aggregate => AGGREGATE_ELF,
+        ProgramSelector::Rekey => REKEY_ELF,
+        ProgramSelector::Aes => AES_ELF,
+    }
+}
+
+/// The `bytes32` verification key hash of `elf`, as the contracts store it in `programVKey`.
+///
+/// Setting up a program takes seconds, so the keys are cached per ELF for the life of the process.
+pub fn compute_vk_hash(elf: &[u8]) -> [u8; 32] {
+    static VK_HASHES: OnceLock<Mutex<HashMap<[u8; 32], [u8; 32]>>> = OnceLock::new();
+
+    let elf_hash: [u8; 32] = Sha256::digest(elf).into();
+    let cache = VK_HASHES.get_or_init(Default::default);
+    if let Some(vk_hash) = cache.lock().unwrap().get(&elf_hash) {
+        return *vk_hash;
+    }
+    let (_, vk) = ProverClient::builder().cpu().build().setup(elf);
+    let vk_hash = vk.bytes32_raw();
+    cache.lock().unwrap().insert(elf_hash, vk_hash);
+    vk_hash
+}
+
+/// A Solidity library with the verification key hash of each program, as `(program, vk.bytes32())`.
+/// With `max_plaintext_size` it also gets a `checkPlaintextSize` for the committed plaintext size,
+/// see `SP1Chacha.plaintextSizeOf`.
+pub fn solidity_vkeys(
+    vkeys: &[(ProgramSelector, String)],
+    max_plaintext_size: Option<u64>,
+) -> String {
+    let mut solidity = String::from(
+        "// SPDX-License-Identifier: MIT\n\
+         pragma solidity ^0.8.20;\n\
+         \n\
+         /// @title Program verification keys\n\
+         /// @notice Generated by `cargo run --bin vkey -- --solidity <path>`, do not edit.\n\
+         library ProgramVKeys {\n",
+    );
+    for (program, vkey) in vkeys {
+        let name = program.to_string().to_ascii_uppercase();
+        solidity.push_str(&format!("    bytes32 internal constant {name} = {vkey};\n"));
+    }
+    if let Some(max) = max_plaintext_size {
+        solidity.push_str(&format!(
+            "\n    uint64 internal constant MAX_PLAINTEXT_SIZE = {max};\n\n"
+        ));
+        solidity
+            .push_str("    function checkPlaintextSize(uint64 committedSize) internal pure {\n");
+        solidity.push_str(
+            "        require(committedSize <= MAX_PLAINTEXT_SIZE, \"Plaintext too large\");\n",
+        );
+        solidity.push_str("    }\n");
+    }
+    solidity.push_str("}\n");
+    solidity
+}
+
+/// The SDK proof mode of `proof_type`.
+pub fn proof_mode(proof_type: ProofType) -> SP1ProofMode {
+    match proof_type {
+        ProofType::Core => SP1ProofMode::Core,
+        ProofType::Compressed => SP1ProofMode::Compressed,
+        ProofType::Groth16 => SP1ProofMode::Groth16,
+        ProofType::Plonk => SP1ProofMode::Plonk,
+    }
+}
+
+/// Prove a `proof_type` proof of `stdin` with any of the SDK provers.
+pub fn run_proof<C: SP1ProverComponents>(
+    client: &impl Prover<C>,
+    pk: &SP1ProvingKey,
+    stdin: &SP1Stdin,
+    proof_type: ProofType,
+) -> anyhow::Result<SP1ProofWithPublicValues> {
+    client.prove(pk, stdin, proof_mode(proof_type))
+}
+
+/// Largest plaintext accepted for proving, ~800M cycles at [`estimate::CYCLES_PER_BYTE`].
+pub const MAX_PLAINTEXT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Inputs that would make the zkVM program panic, or are unsafe to encrypt with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    KeyLength(usize),
+    NonceLength(usize),
+    PlaintextTooLarge(usize),
+    /// A key of one repeated byte, such as all zeros.
+    WeakKey(&'static str),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::KeyLength(len) => write!(f, "Key must be exactly 32 bytes, got {len}"),
+            ValidationError::NonceLength(len) => {
+                write!(f, "Nonce must be exactly 12 bytes, got {len}")
+            }
+            ValidationError::PlaintextTooLarge(len) => write!(
+                f,
+                "Plaintext must be at most {MAX_PLAINTEXT_BYTES} bytes, got {len}"
+            ),
+            ValidationError::WeakKey(reason) => write!(f, "Weak key: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Check the program inputs before spending time on the prover setup.
+pub fn validate_prove_inputs(
+    key: &[u8],
+    nonce: &[u8],
+    plaintext: &[u8],
+) -> Result<(), ValidationError> {
+    if key.len() != 32 {
+        return Err(ValidationError::KeyLength(key.len()));
+    }
+    if nonce.len() != 12 {
+        return Err(ValidationError::NonceLength(nonce.len()));
+    }
+    if plaintext.len() > MAX_PLAINTEXT_BYTES {
+        return Err(ValidationError::PlaintextTooLarge(plaintext.len()));
+    }
+    if key.iter().all(|&b| b == key[0]) {
+        return Err(ValidationError::WeakKey("all bytes are identical"));
+    }
+    Ok(())
+}
+
+/// Parse the public values of the encryption program.
+pub fn parse_public_values(bytes: &[u8]) -> Result<PublicValues<'_>, ScriptError> {
+    PublicValues::parse(bytes).ok_or(ScriptError::InvalidPublicValuesLength {
+        got: bytes.len(),
+        expected: HEADER_LEN,
+    })
+}
+
+/// Inputs of the encryption program.
+pub struct EncryptInputs<'a> {
+    pub key: [u8; 32],
+    /// MUST BE UNIQUE - NO REUSE!
+    pub nonce: [u8; 12],
+    pub compression: CompressionType,
+    /// Zstandard level (1-22), [`chacha_lib::compress::ZSTD_DEFAULT_LEVEL`] if unset.
+    /// Ignored by the other compression types.
+    pub compression_level: Option<i32>,
+    /// Commit to the Merkle root of the plaintext segments, for selective disclosure.
+    pub commit_segments: bool,
+    /// Commit `SHA256(ciphertext)` instead of the ciphertext, to keep the public values small.
+    pub commit_ciphertext_hash: bool,
+    /// Signed oracle timestamp bytes, see [`timestamp::SignedTimestamp::to_bytes`].
+    pub timestamp: Option<Vec<u8>>,
+    /// Operator ID and unix time committed to by the custody hash, see [`chacha_lib::custody`].
+    pub custody: Option<([u8; OPERATOR_ID_LEN], u64)>,
+    /// Lineage commitment of the previous pipeline stage, see [`chacha_lib::lineage`].
+    pub lineage: Option<[u8; 32]>,
+    /// Pre-shared HMAC key to authenticate the inputs with, separate from `key`, see
+    /// [`chacha_lib::input_auth`].
+    pub input_hmac_key: Option<[u8; 32]>,
+    /// The uncompressed bytes to encrypt.
+    pub plaintext: &'a [u8],
+}
+
+impl<'a> EncryptInputs<'a> {
+    /// Encrypt `plaintext` with every option off. Set the others with struct update syntax:
+    /// `EncryptInputs { commit_segments: true, ..EncryptInputs::new(key, nonce, plaintext) }`.
+    pub fn new(key: [u8; 32], nonce: [u8; 12], plaintext: &'a [u8]) -> Self {
+        Self {
+            key,
+            nonce,
+            compression: CompressionType::None,
+            compression_level: None,
+            commit_segments: false,
+            commit_ciphertext_hash: false,
+            timestamp: None,
+            custody: None,
+            lineage: None,
+            input_hmac_key: None,
+            plaintext,
+        }
+    }
+
+    /// Write the inputs in the order `program/src/main.rs` reads them.
+    pub fn to_stdin(&self) -> SP1Stdin {
+        self.to_stdin_for_version(PROGRAM_VERSION)
+    }
+
+    /// Write the inputs in the order the program of `version` reads them. Version 1 programs read
+    /// no custody, version 1 and 2 programs no lineage and version 1 to 4 programs no input HMAC,
+    /// which are left out. Version 5 and 6 programs get the tag of
+    /// [`chacha_lib::input_auth::input_hmac_v6`].
+    pub fn to_stdin_for_version(&self, version: u8) -> SP1Stdin {
+        let mut stdin = SP1Stdin::new();
+        stdin.write_slice(&self.key);
+        stdin.write_slice(&self.nonce);
+        stdin.write(&(self.compression as u8));
+        stdin.write(&self.commit_segments);
+        stdin.write(&self.commit_ciphertext_hash);
+        stdin.write(&self.timestamp);
+        if version > 1 {
+            stdin.write(&self.custody);
+        }
+        if version > 2 {
+            stdin.write(&self.lineage);
+        }
+        let buffer = self.compressed_plaintext();
+        stdin.write_slice(&buffer);
+        if version > 4 {
+            let (key, nonce) = (Key(self.key), Nonce(self.nonce));
+            let input_auth = self.input_hmac_key.map(|hmac_key| {
+                let tag = if version > 6 {
+                    let inputs = AuthenticatedInputs {
+                        key: &key,
+                        nonce: &nonce,
+                        compression: self.compression,
+                        commit_segments: self.commit_segments,
+                        commit_ciphertext_hash: self.commit_ciphertext_hash,
+                        timestamp: self.timestamp.as_deref(),
+                        custody: self.custody,
+                        lineage: self.lineage,
+                        buffer: &buffer,
+                    };
+                    input_hmac(&hmac_key, &inputs)
+                } else {
+                    input_hmac_v6(&hmac_key, &key, &nonce, &buffer)
+                };
+                (hmac_key, tag)
+            });
+            stdin.write(&input_auth);
+        }
+        stdin
+    }
+
+    /// The buffer the program encrypts.
+    pub fn compressed_plaintext(&self) -> Vec<u8> {
+        match (self.compression, self.compression_level) {
+            (CompressionType::Zstd, Some(level)) => zstd_compress(self.plaintext, level),
+            (compression, _) => compress(compression, self.plaintext),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Metrics of a proving run, in the Prometheus text format.
+//!
+//! A batch proving job exits before a Prometheus server could scrape it, so it pushes its metrics
+//! instead, see [`push`].
+
+pub mod push;
+
+use std::time::Duration;
+
+use prometheus::{Encoder, Gauge, IntGauge, Opts, Registry, TextEncoder};
+
+use chacha_lib::ProofType;
+
+/// Prefix of every metric name.
+pub const NAMESPACE: &str = "sp1_chacha";
+
+/// The outcome of proving one input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProveMetrics {
+    /// RISC-V cycles of executing the program on the input.
+    pub cycles: u64,
+    pub prove_duration: Duration,
+    /// Bytes of the uncompressed plaintext.
+    pub input_size: usize,
+    pub proof_type: ProofType,
+    /// Whether a proof was generated.
+    pub success: bool,
+}
+
+impl ProveMetrics {
+    /// The metrics in the text exposition format, each labelled with the proof type.
+    pub fn encode(&self) -> Result<String, prometheus::Error> {
+        let registry = Registry::new();
+        let opts = |name, help| {
+            Opts::new(name, help)
+                .namespace(NAMESPACE)
+                .const_label("proof_type", self.proof_type.to_string())
+        };
+        let int_gauge = |name, help, value: u64| -> Result<(), prometheus::Error> {
+            let gauge = IntGauge::with_opts(opts(name, help))?;
+            gauge.set(i64::try_from(value).unwrap_or(i64::MAX));
+            registry.register(Box::new(gauge))
+        };
+        int_gauge("cycles", "RISC-V cycles of the program", self.cycles)?;
+        int_gauge(
+            "input_bytes",
+            "Bytes of plaintext input",
+            self.input_size as u64,
+        )?;
+        int_gauge(
+            "prove_success",
+            "1 if a proof was generated, 0 if executing or proving failed",
+            self.success.into(),
+        )?;
+        let duration = Gauge::with_opts(opts("prove_duration_seconds", "Time spent proving"))?;
+        duration.set(self.prove_duration.as_secs_f64());
+        registry.register(Box::new(duration))?;
+
+        let mut text = Vec::new();
+        TextEncoder::new().encode(&registry.gather(), &mut text)?;
+        Ok(String::from_utf8(text).expect("the text format is UTF-8"))
+    }
+}
@@ -0,0 +1,68 @@
+//! Pushing the [`ProveMetrics`] of a batch job to a Prometheus Pushgateway.
+//!
+//! A push replaces the metrics of the job's group, `PUT <gateway>/metrics/job/<job_name>`, so the
+//! gateway always holds the latest run of each job.
+
+use std::fmt;
+
+use reqwest::Url;
+
+use super::ProveMetrics;
+
+/// Content type of the text exposition format.
+const TEXT_FORMAT: &str = "text/plain; version=0.0.4";
+
+#[derive(Debug)]
+pub enum MetricsError {
+    /// The gateway is not a base URL.
+    Url(String),
+    Prometheus(prometheus::Error),
+    Http(reqwest::Error),
+}
+
+impl fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricsError::Url(url) => write!(f, "invalid push gateway URL `{url}`"),
+            MetricsError::Prometheus(e) => write!(f, "failed to encode metrics: {e}"),
+            MetricsError::Http(e) => write!(f, "failed to push metrics: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MetricsError {}
+
+impl From<prometheus::Error> for MetricsError {
+    fn from(e: prometheus::Error) -> Self {
+        MetricsError::Prometheus(e)
+    }
+}
+
+impl From<reqwest::Error> for MetricsError {
+    fn from(e: reqwest::Error) -> Self {
+        MetricsError::Http(e)
+    }
+}
+
+/// Push `metrics` under `job_name` to the Pushgateway at `gateway`, e.g. `http://localhost:9091`.
+pub async fn push_metrics(
+    gateway: &str,
+    job_name: &str,
+    metrics: &ProveMetrics,
+) -> Result<(), MetricsError> {
+    let invalid = || MetricsError::Url(gateway.to_string());
+    let mut url = Url::parse(gateway).map_err(|_| invalid())?;
+    url.path_segments_mut()
+        .map_err(|_| invalid())?
+        .pop_if_empty()
+        .extend(["metrics", "job", job_name]);
+
+    reqwest::Client::new()
+        .put(url)
+        .header(reqwest::header::CONTENT_TYPE, TEXT_FORMAT)
+        .body(metrics.encode()?)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
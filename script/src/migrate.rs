@@ -0,0 +1,117 @@
+//! Migrating saved proofs to the current proof file format.
+//!
+//! | Version | Format |
+//! |---------|--------|
+//! | 1 | The raw proof, as written by `SP1ProofWithPublicValues::save`, see [`ProofFile`] |
+//! | 2 | A JSON [`ProofEnvelope`] with extensions, see [`chacha_lib::envelope`] |
+//!
+//! Raw proofs have no magic bytes, but their bincode encoding starts with the little endian
+//! `SP1Proof` variant, a zero byte or three, while an envelope starts with `{`. The version of an
+//! envelope is its `version` field.
+
+use std::fmt;
+
+use chacha_lib::envelope::{ProofEnvelope, ENVELOPE_VERSION};
+
+use crate::convert::proof_type;
+use crate::storage::ProofFile;
+
+/// The `extensions` key of the SP1 version a migrated v1 proof was generated with.
+pub const SP1_VERSION_EXTENSION: &str = "sp1_version";
+
+/// Why a proof file could not be migrated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrateError {
+    /// Neither a raw proof nor an envelope.
+    UnknownFormat,
+    /// The file is of another version than `--from-version`.
+    VersionMismatch {
+        expected: u8,
+        found: u8,
+    },
+    /// No migration from `from` to `to`.
+    UnsupportedMigration {
+        from: u8,
+        to: u8,
+    },
+    InvalidProof(String),
+    InvalidEnvelope(String),
+}
+
+impl fmt::Display for MigrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrateError::UnknownFormat => {
+                f.write_str("not a proof file, neither a raw proof nor a JSON envelope")
+            }
+            MigrateError::VersionMismatch { expected, found } => {
+                write!(f, "expected a v{expected} proof file, found v{found}")
+            }
+            MigrateError::UnsupportedMigration { from, to } => write!(
+                f,
+                "cannot migrate v{from} to v{to}, the current version is v{ENVELOPE_VERSION}"
+            ),
+            MigrateError::InvalidProof(e) => write!(f, "invalid v1 proof: {e}"),
+            MigrateError::InvalidEnvelope(e) => write!(f, "invalid proof envelope: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrateError {}
+
+/// The format version of a proof file.
+pub fn detect_version(bytes: &[u8]) -> Result<u8, MigrateError> {
+    match bytes.first() {
+        Some(b'{') => {
+            let json = std::str::from_utf8(bytes)
+                .map_err(|e| MigrateError::InvalidEnvelope(e.to_string()))?;
+            let envelope = ProofEnvelope::from_json(json)
+                .map_err(|e| MigrateError::InvalidEnvelope(e.to_string()))?;
+            Ok(envelope.version)
+        }
+        Some(0..=3) => Ok(1),
+        _ => Err(MigrateError::UnknownFormat),
+    }
+}
+
+/// Wrap a raw v1 proof in an envelope. v1 proofs do not record their program, so its verification
+/// key hash is given, while the proof type is read from the proof. The proof bytes are the bincode
+/// encoded `SP1Proof`, without the public values, which the envelope holds separately, and the SP1
+/// version goes into the [`SP1_VERSION_EXTENSION`] extension.
+pub fn migrate_v1_to_v2(old: &[u8], program_hash: [u8; 32]) -> Result<ProofEnvelope, MigrateError> {
+    let proof = ProofFile {
+        bytes: old.to_vec(),
+    }
+    .to_proof()
+    .map_err(|e| MigrateError::InvalidProof(e.to_string()))?;
+    let proof_bytes = bincode::serialize(&proof.proof).expect("proofs serialize");
+    let mut envelope = ProofEnvelope::from_v1(
+        proof_type(&proof.proof),
+        program_hash,
+        proof.public_values.as_slice(),
+        &proof_bytes,
+    );
+    envelope.extensions.insert(
+        SP1_VERSION_EXTENSION.to_string(),
+        proof.sp1_version.into_bytes(),
+    );
+    Ok(envelope)
+}
+
+/// Migrate a proof file to version `to`, checking it is of version `from` if given. `program_hash`
+/// is needed for v1 files, see [`migrate_v1_to_v2`].
+pub fn migrate(
+    old: &[u8],
+    from: Option<u8>,
+    to: u8,
+    program_hash: [u8; 32],
+) -> Result<Vec<u8>, MigrateError> {
+    let found = detect_version(old)?;
+    if let Some(expected) = from.filter(|&expected| expected != found) {
+        return Err(MigrateError::VersionMismatch { expected, found });
+    }
+    match (found, to) {
+        (1, 2) => Ok(migrate_v1_to_v2(old, program_hash)?.to_json().into_bytes()),
+        (from, to) => Err(MigrateError::UnsupportedMigration { from, to }),
+    }
+}
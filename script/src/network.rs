@@ -0,0 +1,152 @@
+//! Proving on the Succinct prover network: submitting a proof request, retried on failures, then
+//! polling it until it is fulfilled.
+//!
+//! Both go through [`ProofNetwork`], implemented for [`NetworkProver`] and by the mock network of
+//! `tests/network.rs`.
+
+use std::fmt;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use sp1_sdk::network::{Error as NetworkError, B256};
+use sp1_sdk::{NetworkProver, SP1ProofWithPublicValues, SP1ProvingKey, SP1Stdin};
+
+use chacha_lib::ProofType;
+
+use crate::proof_mode;
+
+/// Where a proof request is at.
+#[derive(Debug)]
+pub enum RequestStatus {
+    Fulfilled(Box<SP1ProofWithPublicValues>),
+    /// Not fulfilled yet, with the network's name of its status.
+    Pending(String),
+    /// The network gave up on the request, it will never be fulfilled.
+    Failed(String),
+}
+
+/// A prover network, as far as submitting and polling proof requests.
+pub trait ProofNetwork {
+    /// Submit a request for a `proof_type` proof of `stdin`, returning its ID.
+    fn request(
+        &self,
+        pk: &SP1ProvingKey,
+        stdin: &SP1Stdin,
+        proof_type: ProofType,
+    ) -> impl Future<Output = anyhow::Result<B256>>;
+
+    /// The status of request `request_id`. An error is of fetching the status, the request may
+    /// still be fulfilled.
+    fn status(&self, request_id: B256) -> impl Future<Output = anyhow::Result<RequestStatus>>;
+}
+
+impl ProofNetwork for NetworkProver {
+    async fn request(
+        &self,
+        pk: &SP1ProvingKey,
+        stdin: &SP1Stdin,
+        proof_type: ProofType,
+    ) -> anyhow::Result<B256> {
+        self.prove(pk, stdin)
+            .mode(proof_mode(proof_type))
+            .request_async()
+            .await
+    }
+
+    async fn status(&self, request_id: B256) -> anyhow::Result<RequestStatus> {
+        match self.process_proof_status(request_id, None).await {
+            Ok((Some(proof), _)) => Ok(RequestStatus::Fulfilled(Box::new(proof))),
+            Ok((None, status)) => Ok(RequestStatus::Pending(status.as_str_name().to_string())),
+            Err(e)
+                if matches!(
+                    e.downcast_ref::<NetworkError>(),
+                    Some(
+                        NetworkError::RequestUnfulfillable { .. }
+                            | NetworkError::RequestUnexecutable { .. }
+                            | NetworkError::RequestTimedOut { .. }
+                    )
+                ) =>
+            {
+                Ok(RequestStatus::Failed(e.to_string()))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Why no proof came back from the network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkProveError {
+    /// Every attempt to submit the request failed, with the error of the last one.
+    Request { attempts: u32, error: String },
+    /// The network gave up on the request.
+    Failed(String),
+}
+
+impl fmt::Display for NetworkProveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkProveError::Request { attempts, error } => {
+                write!(
+                    f,
+                    "failed to request proof after {attempts} attempts: {error}"
+                )
+            }
+            NetworkProveError::Failed(e) => write!(f, "proof request failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for NetworkProveError {}
+
+/// Submit a proof request, making up to `retries` attempts. The wait between attempts starts at
+/// `retry_delay` and doubles after each.
+pub async fn request_with_retry(
+    network: &impl ProofNetwork,
+    pk: &SP1ProvingKey,
+    stdin: &SP1Stdin,
+    proof_type: ProofType,
+    retries: u32,
+    retry_delay: Duration,
+) -> Result<B256, NetworkProveError> {
+    let mut attempt = 1;
+    let mut delay = retry_delay;
+    loop {
+        match network.request(pk, stdin, proof_type).await {
+            Ok(request_id) => return Ok(request_id),
+            Err(e) if attempt < retries => {
+                tracing::warn!("proof request attempt {attempt}/{retries} failed: {e}");
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(NetworkProveError::Request {
+                    attempts: attempt,
+                    error: e.to_string(),
+                })
+            }
+        }
+    }
+}
+
+/// Poll request `request_id` every `poll_interval` until it is fulfilled or fails. Status polling
+/// is idempotent, so failures to fetch the status are only warned about.
+pub async fn wait_for_proof(
+    network: &impl ProofNetwork,
+    request_id: B256,
+    poll_interval: Duration,
+) -> Result<SP1ProofWithPublicValues, NetworkProveError> {
+    let start = Instant::now();
+    loop {
+        tokio::time::sleep(poll_interval).await;
+        match network.status(request_id).await {
+            Ok(RequestStatus::Fulfilled(proof)) => return Ok(*proof),
+            Ok(RequestStatus::Pending(status)) => {
+                tracing::info!("request {request_id}: {status} after {:?}", start.elapsed())
+            }
+            Ok(RequestStatus::Failed(e)) => return Err(NetworkProveError::Failed(e)),
+            Err(e) => tracing::warn!("failed to fetch proof status: {e}"),
+        }
+    }
+}
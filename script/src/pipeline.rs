@@ -0,0 +1,86 @@
+//! Proving parameters of the `chacha` binary that can be set through `CHACHA_PROVE_*` environment
+//! variables, for pipelines where passing flags is awkward.
+//!
+//! A flag takes precedence over its environment variable, which takes precedence over the `.env`
+//! file, since `dotenv` never overrides a variable that is already set, which takes precedence
+//! over the default.
+
+use std::path::PathBuf;
+
+use hex::FromHex;
+
+use chacha_lib::ProofType;
+
+/// Hex encoded encryption key, read instead of `ENCRYPTION_KEY` when set. There is no flag for the
+/// key, as the arguments of a process are visible to every user of the machine.
+pub const KEY_VAR: &str = "CHACHA_PROVE_KEY";
+pub const NONCE_VAR: &str = "CHACHA_PROVE_NONCE";
+pub const INPUT_FILE_VAR: &str = "CHACHA_PROVE_INPUT_FILE";
+pub const TYPE_VAR: &str = "CHACHA_PROVE_TYPE";
+pub const CHAIN_ID_VAR: &str = "CHACHA_PROVE_CHAIN_ID";
+pub const OUTPUT_FILE_VAR: &str = "CHACHA_PROVE_OUTPUT_FILE";
+
+/// Hex encoded nonce read with `--nonce-from-env`, for tests and reproducible runs.
+pub const FIXED_NONCE_VAR: &str = "CHACHA_NONCE_HEX";
+
+/// Printed whenever the key is read from [`KEY_VAR`].
+pub const KEY_WARNING: &str = "WARNING: Reading the encryption key from CHACHA_PROVE_KEY. \
+     Environment variables can leak through process listings, crash reports and CI logs, \
+     store it as a masked secret and never echo it.";
+
+/// Printed whenever the nonce is read from [`FIXED_NONCE_VAR`].
+pub const FIXED_NONCE_WARNING: &str =
+    "[SECURITY WARNING] Using fixed nonce from environment variable. DO NOT use in production.";
+
+#[derive(clap::Args, Debug, Clone, PartialEq, Eq)]
+pub struct PipelineArgs {
+    /// File to encrypt. Defaults to the bundled example input.
+    #[clap(long, env = INPUT_FILE_VAR)]
+    pub input: Option<PathBuf>,
+
+    /// Hex encoded 12 byte nonce, random by default. NEVER reuse a nonce with the same key.
+    #[clap(long, env = NONCE_VAR, value_parser = parse_nonce)]
+    pub nonce: Option<[u8; 12]>,
+
+    /// Read the nonce from `CHACHA_NONCE_HEX`. Never read without this flag, as a stale variable
+    /// reuses the nonce of every run under the same key.
+    #[clap(long, conflicts_with = "nonce")]
+    pub nonce_from_env: bool,
+
+    /// Derive the nonce from the key and plaintext, so the same plaintext always encrypts to the
+    /// same ciphertext. This reveals which ciphertexts share a plaintext, see
+    /// `chacha_lib::deterministic_nonce`.
+    #[clap(long, conflicts_with_all = ["nonce", "nonce_from_env"])]
+    pub deterministic_nonce: bool,
+
+    /// Proof system used by `--prove`.
+    #[clap(long, env = TYPE_VAR, default_value_t = ProofType::Groth16)]
+    pub proof_type: ProofType,
+
+    /// Chain the proof is for, shown in the summary. `--post-tx` refuses to post to another chain.
+    #[clap(long, env = CHAIN_ID_VAR)]
+    pub chain_id: Option<u64>,
+
+    /// Save the proof here once it is verified, as written by `SP1ProofWithPublicValues::save`.
+    #[clap(long, env = OUTPUT_FILE_VAR)]
+    pub output: Option<PathBuf>,
+}
+
+fn parse_nonce(s: &str) -> Result<[u8; 12], String> {
+    <[u8; 12]>::from_hex(s).map_err(|_| format!("expected 12 hex encoded bytes, got `{s}`"))
+}
+
+/// The nonce of [`FIXED_NONCE_VAR`], for `--nonce-from-env`.
+pub fn fixed_nonce() -> Result<[u8; 12], String> {
+    let nonce = std::env::var(FIXED_NONCE_VAR)
+        .map_err(|_| format!("--nonce-from-env needs {FIXED_NONCE_VAR} to be set"))?;
+    parse_nonce(&nonce).map_err(|e| format!("{FIXED_NONCE_VAR}: {e}"))
+}
+
+/// The hex encoded key of [`KEY_VAR`], falling back to `ENCRYPTION_KEY`, with the name of the
+/// variable it was read from.
+pub fn env_key() -> Option<(&'static str, String)> {
+    [KEY_VAR, "ENCRYPTION_KEY"]
+        .into_iter()
+        .find_map(|var| std::env::var(var).ok().map(|key| (var, key)))
+}
@@ -0,0 +1,273 @@
+//! Submitting a proof to the `SP1Chacha` contract, see `contracts/src/SP1Chacha.sol`.
+
+use std::fmt;
+
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::primitives::{Address, Bytes, TxHash};
+use alloy::providers::{PendingTransactionError, Provider, ProviderBuilder};
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues};
+
+use chacha_lib::PublicValues;
+
+use crate::convert::proof_type;
+use crate::error::ScriptError;
+
+sol! {
+    /// The verifying and upgrading functions of `SP1Chacha`.
+    interface ISP1Chacha {
+        function verifyEncryption(bytes calldata publicValues, bytes calldata proofBytes)
+            external
+            returns (bytes32 plaintextHash, bytes memory ciphertext);
+
+        function verifyEncryptionWithCiphertext(
+            bytes calldata publicValues,
+            bytes calldata proofBytes,
+            bytes calldata ciphertext
+        ) external returns (bytes32 plaintextHash);
+
+        function upgrade(bytes calldata publicValues, bytes calldata proofBytes) external;
+    }
+}
+
+/// The bytes of `proof` passed as `proofBytes`, empty for a mock proof.
+pub fn proof_bytes(proof: &SP1ProofWithPublicValues) -> Result<Vec<u8>, ScriptError> {
+    // `SP1ProofWithPublicValues::bytes` panics on other proof types and on proofs that are not hex
+    let encoded_proof = match &proof.proof {
+        SP1Proof::Groth16(p) => &p.encoded_proof,
+        SP1Proof::Plonk(p) => &p.encoded_proof,
+        other => return Err(ScriptError::InvalidProofType(proof_type(other))),
+    };
+    hex::decode(encoded_proof)?;
+    Ok(proof.bytes())
+}
+
+/// The ABI encoded call verifying `proof`, passing `ciphertext` for a proof made with
+/// `--commit-ciphertext-hash`.
+pub fn verify_calldata(
+    proof: &SP1ProofWithPublicValues,
+    ciphertext: Option<&[u8]>,
+) -> Result<Vec<u8>, ScriptError> {
+    let public_values = Bytes::copy_from_slice(proof.public_values.as_slice());
+    let proof_bytes = Bytes::from(proof_bytes(proof)?);
+    Ok(match ciphertext {
+        None => ISP1Chacha::verifyEncryptionCall {
+            publicValues: public_values,
+            proofBytes: proof_bytes,
+        }
+        .abi_encode(),
+        Some(ciphertext) => ISP1Chacha::verifyEncryptionWithCiphertextCall {
+            publicValues: public_values,
+            proofBytes: proof_bytes,
+            ciphertext: Bytes::copy_from_slice(ciphertext),
+        }
+        .abi_encode(),
+    })
+}
+
+/// The ABI encoded call registering the new key of an upgrade `proof`, see [`crate::upgrade`].
+pub fn upgrade_calldata(proof: &SP1ProofWithPublicValues) -> Result<Vec<u8>, ScriptError> {
+    Ok(ISP1Chacha::upgradeCall {
+        publicValues: Bytes::copy_from_slice(proof.public_values.as_slice()),
+        proofBytes: Bytes::from(proof_bytes(proof)?),
+    }
+    .abi_encode())
+}
+
+/// Hashes a proof must commit before it is submitted, the values the on-chain verifier is
+/// expected to check it against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExpectedHashes {
+    pub plaintext_hash: Option<[u8; 32]>,
+    /// SHA256 of the ciphertext, whether the proof commits the ciphertext or only its hash.
+    pub ciphertext_hash: Option<[u8; 32]>,
+}
+
+/// Why a proof does not commit the [`ExpectedHashes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectationError {
+    /// The public values are not of the encryption program.
+    PublicValues,
+    Mismatch {
+        field: &'static str,
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+}
+
+impl fmt::Display for ExpectationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpectationError::PublicValues => write!(f, "public values too short"),
+            ExpectationError::Mismatch {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected {field} 0x{}, the proof commits 0x{}",
+                hex::encode(expected),
+                hex::encode(actual)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExpectationError {}
+
+impl ExpectedHashes {
+    /// Check the public values of an encryption proof, made with `commit_ciphertext_hash` if it
+    /// commits only the ciphertext hash.
+    pub fn check(
+        &self,
+        public_values: &[u8],
+        commit_ciphertext_hash: bool,
+    ) -> Result<(), ExpectationError> {
+        let public_values =
+            PublicValues::parse(public_values).ok_or(ExpectationError::PublicValues)?;
+        let ciphertext_hash = if commit_ciphertext_hash {
+            public_values
+                .ciphertext
+                .try_into()
+                .map_err(|_| ExpectationError::PublicValues)?
+        } else {
+            Sha256::digest(public_values.ciphertext).into()
+        };
+        for (field, expected, actual) in [
+            (
+                "plaintext hash",
+                self.plaintext_hash,
+                public_values.plaintext_hash,
+            ),
+            ("ciphertext hash", self.ciphertext_hash, ciphertext_hash),
+        ] {
+            match expected {
+                Some(expected) if expected != actual => {
+                    return Err(ExpectationError::Mismatch {
+                        field,
+                        expected,
+                        actual,
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A mined transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostedTx {
+    pub tx_hash: TxHash,
+    pub block_number: Option<u64>,
+    pub gas_used: u64,
+    /// In wei.
+    pub effective_gas_price: u128,
+}
+
+/// Why a transaction could not be posted.
+#[derive(Debug)]
+pub enum PostError {
+    PrivateKey,
+    RpcUrl(String),
+    Rpc(String),
+    /// The RPC endpoint serves another chain than `--chain-id`.
+    ChainId {
+        expected: u64,
+        actual: u64,
+    },
+    /// The transaction was mined but reverted, e.g. because the nullifier was already used.
+    Reverted(TxHash),
+}
+
+impl fmt::Display for PostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PostError::PrivateKey => write!(f, "private key must be 32 hex encoded bytes"),
+            PostError::RpcUrl(e) => write!(f, "invalid RPC URL: {e}"),
+            PostError::Rpc(e) => write!(f, "RPC request failed: {e}"),
+            PostError::ChainId { expected, actual } => {
+                write!(
+                    f,
+                    "expected chain {expected}, the RPC endpoint serves chain {actual}"
+                )
+            }
+            PostError::Reverted(tx_hash) => write!(f, "transaction {tx_hash} reverted"),
+        }
+    }
+}
+
+impl std::error::Error for PostError {}
+
+impl From<PendingTransactionError> for PostError {
+    fn from(e: PendingTransactionError) -> Self {
+        PostError::Rpc(e.to_string())
+    }
+}
+
+/// The chain the endpoint at `rpc_url` serves.
+pub async fn chain_id(rpc_url: &str) -> Result<u64, PostError> {
+    let rpc_url = reqwest::Url::parse(rpc_url).map_err(|e| PostError::RpcUrl(e.to_string()))?;
+    ProviderBuilder::new()
+        .on_http(rpc_url)
+        .get_chain_id()
+        .await
+        .map_err(|e| PostError::Rpc(e.to_string()))
+}
+
+/// Send `calldata` to `contract` signed with `private_key`, calling `on_sent` with the hash once
+/// sent, and wait for `confirmations` confirmations. With `chain_id`, nothing is sent unless the
+/// endpoint serves that chain.
+pub async fn post_tx(
+    rpc_url: &str,
+    contract: Address,
+    private_key: &str,
+    calldata: Vec<u8>,
+    chain_id: Option<u64>,
+    confirmations: u64,
+    on_sent: impl FnOnce(TxHash),
+) -> Result<PostedTx, PostError> {
+    let signer: PrivateKeySigner = private_key.parse().map_err(|_| PostError::PrivateKey)?;
+    let rpc_url = reqwest::Url::parse(rpc_url).map_err(|e| PostError::RpcUrl(e.to_string()))?;
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer))
+        .on_http(rpc_url);
+
+    if let Some(expected) = chain_id {
+        let actual = provider
+            .get_chain_id()
+            .await
+            .map_err(|e| PostError::Rpc(e.to_string()))?;
+        if actual != expected {
+            return Err(PostError::ChainId { expected, actual });
+        }
+    }
+
+    let tx = TransactionRequest::default()
+        .with_to(contract)
+        .with_input(calldata);
+    let pending = provider
+        .send_transaction(tx)
+        .await
+        .map_err(|e| PostError::Rpc(e.to_string()))?;
+    on_sent(*pending.tx_hash());
+
+    let receipt = pending
+        .with_required_confirmations(confirmations)
+        .get_receipt()
+        .await?;
+    if !receipt.status() {
+        return Err(PostError::Reverted(receipt.transaction_hash));
+    }
+    Ok(PostedTx {
+        tx_hash: receipt.transaction_hash,
+        block_number: receipt.block_number,
+        gas_used: receipt.gas_used,
+        effective_gas_price: receipt.effective_gas_price,
+    })
+}
@@ -0,0 +1,88 @@
+//! Per-opcode instruction counts of an execution, tracked in CI so pull requests show which
+//! opcodes they make more or less frequent.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use sp1_sdk::ExecutionReport;
+
+/// Default `--threshold` of the `opcode_profile` binary, in percent.
+pub const CHANGE_THRESHOLD_PERCENT: f64 = 10.0;
+
+/// Instruction count per opcode mnemonic, leaving out opcodes that never ran.
+pub type OpcodeHistogram = BTreeMap<String, u64>;
+
+pub fn opcode_histogram(report: &ExecutionReport) -> OpcodeHistogram {
+    report
+        .opcode_counts
+        .iter()
+        .filter(|&(_, &count)| count > 0)
+        .map(|(opcode, &count)| (opcode.to_string(), count))
+        .collect()
+}
+
+/// An opcode whose count changed from the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpcodeChange {
+    pub opcode: String,
+    pub baseline: u64,
+    pub current: u64,
+}
+
+impl OpcodeChange {
+    /// Relative change, infinite for an opcode missing from the baseline.
+    pub fn percent(&self) -> f64 {
+        (self.current as f64 - self.baseline as f64) / self.baseline as f64 * 100.0
+    }
+}
+
+/// Opcodes whose count changed by more than `threshold_percent`, largest change first.
+pub fn compare(
+    baseline: &OpcodeHistogram,
+    current: &OpcodeHistogram,
+    threshold_percent: f64,
+) -> Vec<OpcodeChange> {
+    let opcodes: BTreeSet<_> = baseline.keys().chain(current.keys()).collect();
+    let mut changes: Vec<_> = opcodes
+        .into_iter()
+        .map(|opcode| OpcodeChange {
+            opcode: opcode.clone(),
+            baseline: baseline.get(opcode).copied().unwrap_or(0),
+            current: current.get(opcode).copied().unwrap_or(0),
+        })
+        .filter(|change| change.percent().abs() > threshold_percent)
+        .collect();
+    changes.sort_by(|a, b| b.percent().abs().total_cmp(&a.percent().abs()));
+    changes
+}
+
+/// A markdown table of `changes` for a pull request comment.
+pub fn markdown_summary(
+    changes: &[OpcodeChange],
+    baseline: &OpcodeHistogram,
+    current: &OpcodeHistogram,
+    threshold_percent: f64,
+) -> String {
+    let baseline_total: u64 = baseline.values().sum();
+    let current_total: u64 = current.values().sum();
+    let mut summary = format!(
+        "### Opcode profile\n\n\
+         Executing a 1 KB input took {current_total} instructions, {baseline_total} on main. \
+         Opcodes that changed by more than {threshold_percent}%:\n\n\
+         | Opcode | main | this PR | change |\n\
+         |--------|-----:|--------:|-------:|\n"
+    );
+    for change in changes {
+        let percent = match change.percent() {
+            percent if percent.is_finite() => format!("{percent:+.1}%"),
+            _ => "new".to_string(),
+        };
+        writeln!(
+            summary,
+            "| {} | {} | {} | {percent} |",
+            change.opcode, change.baseline, change.current
+        )
+        .unwrap();
+    }
+    summary
+}
@@ -0,0 +1,235 @@
+//! A persistent proving job queue backed by SQLite.
+//!
+//! Proving saturates the GPU, so jobs are run one at a time by a single worker.
+//! Requests are recorded as [`JobState::Pending`] and picked up in insertion order.
+//! Jobs still [`JobState::Running`] when the queue is opened were interrupted by a crash or
+//! restart, and are queued again. Opening a queue of an earlier [`SCHEMA_VERSION`] migrates it.
+
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Version of the `jobs` table, kept in `PRAGMA user_version`. Version 1 queues predate it and
+/// have 0.
+///
+/// 1. The first queue.
+/// 2. Adds `requester`, `'unknown'` for the jobs of a migrated queue.
+pub const SCHEMA_VERSION: i64 = 2;
+
+/// Lifecycle of a proving job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum JobState {
+    Pending,
+    Running,
+    Complete,
+    Failed,
+}
+
+impl JobState {
+    fn as_str(self) -> &'static str {
+        match self {
+            JobState::Pending => "Pending",
+            JobState::Running => "Running",
+            JobState::Complete => "Complete",
+            JobState::Failed => "Failed",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "Pending" => Some(JobState::Pending),
+            "Running" => Some(JobState::Running),
+            "Complete" => Some(JobState::Complete),
+            "Failed" => Some(JobState::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// Snapshot of a job, as reported by `GET /status/:job_id`.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatus {
+    pub job_id: Uuid,
+    pub state: JobState,
+    /// Seconds spent waiting (if pending) or running (if started).
+    pub elapsed_secs: u64,
+    /// Output of a completed job, or the error message of a failed one.
+    pub result: Option<String>,
+}
+
+pub struct JobQueue {
+    conn: Mutex<Connection>,
+}
+
+impl JobQueue {
+    /// Open (or create) a queue database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// A queue that lives only as long as the process.
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(mut conn: Connection) -> rusqlite::Result<Self> {
+        migrate(&mut conn)?;
+        // The only worker is the one of this process, so running jobs were interrupted
+        conn.execute(
+            "UPDATE jobs SET state = ?1, started_at = NULL WHERE state = ?2",
+            params![JobState::Pending.as_str(), JobState::Running.as_str()],
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Enqueue a plaintext to be encrypted and proved on behalf of `requester`.
+    pub fn push(&self, input: &[u8], requester: &str) -> rusqlite::Result<Uuid> {
+        let id = Uuid::new_v4();
+        let conn = self.conn.lock().expect("queue lock poisoned");
+        conn.execute(
+            "INSERT INTO jobs (id, seq, state, input, requester, created_at)
+             VALUES (?1, (SELECT IFNULL(MAX(seq), 0) + 1 FROM jobs), ?2, ?3, ?4, ?5)",
+            params![
+                id.to_string(),
+                JobState::Pending.as_str(),
+                input,
+                requester,
+                now()
+            ],
+        )?;
+        Ok(id)
+    }
+
+    /// Take the oldest pending job, marking it as running. Returns its id, input and requester.
+    pub fn next_pending(&self) -> rusqlite::Result<Option<(Uuid, Vec<u8>, String)>> {
+        let conn = self.conn.lock().expect("queue lock poisoned");
+        let job: Option<(String, Vec<u8>, String)> = conn
+            .query_row(
+                "SELECT id, input, requester FROM jobs WHERE state = ?1 ORDER BY seq LIMIT 1",
+                params![JobState::Pending.as_str()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+        let Some((id, input, requester)) = job else {
+            return Ok(None);
+        };
+        conn.execute(
+            "UPDATE jobs SET state = ?1, started_at = ?2 WHERE id = ?3",
+            params![JobState::Running.as_str(), now(), id],
+        )?;
+        Ok(Some((
+            id.parse().expect("stored job id is a uuid"),
+            input,
+            requester,
+        )))
+    }
+
+    pub fn complete(&self, id: Uuid, result: &str) -> rusqlite::Result<()> {
+        self.finish(id, JobState::Complete, result)
+    }
+
+    pub fn fail(&self, id: Uuid, error: &str) -> rusqlite::Result<()> {
+        self.finish(id, JobState::Failed, error)
+    }
+
+    fn finish(&self, id: Uuid, state: JobState, result: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().expect("queue lock poisoned");
+        conn.execute(
+            "UPDATE jobs SET state = ?1, finished_at = ?2, result = ?3 WHERE id = ?4",
+            params![state.as_str(), now(), result, id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    pub fn status(&self, id: Uuid) -> rusqlite::Result<Option<JobStatus>> {
+        let conn = self.conn.lock().expect("queue lock poisoned");
+        conn.query_row(
+            "SELECT state, created_at, started_at, finished_at, result FROM jobs WHERE id = ?1",
+            params![id.to_string()],
+            |row| {
+                let state: String = row.get(0)?;
+                let created_at: u64 = row.get(1)?;
+                let started_at: Option<u64> = row.get(2)?;
+                let finished_at: Option<u64> = row.get(3)?;
+                // Zero rather than an underflow if the clock stepped back
+                let elapsed_secs = match (started_at, finished_at) {
+                    (Some(start), Some(end)) => end.saturating_sub(start),
+                    (Some(start), None) => now().saturating_sub(start),
+                    _ => now().saturating_sub(created_at),
+                };
+                Ok(JobStatus {
+                    job_id: id,
+                    state: JobState::parse(&state).expect("stored job state is valid"),
+                    elapsed_secs,
+                    result: row.get(4)?,
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// Number of jobs waiting to be picked up by the worker.
+    pub fn depth(&self) -> rusqlite::Result<u32> {
+        let conn = self.conn.lock().expect("queue lock poisoned");
+        conn.query_row(
+            "SELECT COUNT(*) FROM jobs WHERE state = ?1",
+            params![JobState::Pending.as_str()],
+            |row| row.get(0),
+        )
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock before unix epoch")
+        .as_secs()
+}
+
+/// Create the `jobs` table, or bring one of an earlier [`SCHEMA_VERSION`] up to date.
+fn migrate(conn: &mut Connection) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    let version: i64 = tx.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    let exists: bool = tx.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'jobs'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        tx.execute_batch(
+            "CREATE TABLE jobs (
+                id          TEXT PRIMARY KEY,
+                seq         INTEGER NOT NULL,
+                state       TEXT NOT NULL,
+                input       BLOB NOT NULL,
+                requester   TEXT NOT NULL,
+                created_at  INTEGER NOT NULL,
+                started_at  INTEGER,
+                finished_at INTEGER,
+                result      TEXT
+            );",
+        )?;
+    } else if version < 2 {
+        // Queues created before the version was recorded may already have the column
+        let has_requester: bool = tx.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('jobs') WHERE name = 'requester'",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_requester {
+            tx.execute_batch(
+                "ALTER TABLE jobs ADD COLUMN requester TEXT NOT NULL DEFAULT 'unknown';",
+            )?;
+        }
+    }
+    if version < SCHEMA_VERSION {
+        tx.execute_batch(&format!("PRAGMA user_version = {SCHEMA_VERSION};"))?;
+    }
+    tx.commit()
+}
@@ -0,0 +1,72 @@
+//! Token bucket rate limiting for the proving server.
+//!
+//! Every proof occupies the GPU for minutes, so requests are limited per API key (the `x-api-key`
+//! header) for the keys of a configured allow-list, and per client IP for all other requests.
+//! Unknown keys fall under the per-IP limit, so clients cannot get a fresh budget by sending new
+//! header values, and only as many per-key buckets as allowed keys are kept. Either limit can be
+//! disabled by leaving it unset.
+
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::time::Duration;
+
+use governor::clock::{Clock, DefaultClock};
+use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+
+/// Header identifying the client for the per-key limit.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+pub struct RateLimits {
+    clock: DefaultClock,
+    per_ip: Option<DefaultKeyedRateLimiter<IpAddr>>,
+    per_key: Option<DefaultKeyedRateLimiter<String>>,
+    api_keys: HashSet<String>,
+}
+
+impl RateLimits {
+    /// Limits in requests per minute, `per_key` for each of `api_keys`. `burst` defaults to the
+    /// per-minute rate of each limit.
+    pub fn new(
+        per_ip: Option<NonZeroU32>,
+        per_key: Option<NonZeroU32>,
+        burst: Option<NonZeroU32>,
+        api_keys: HashSet<String>,
+    ) -> Self {
+        let quota = |per_minute: NonZeroU32| {
+            Quota::per_minute(per_minute).allow_burst(burst.unwrap_or(per_minute))
+        };
+        Self {
+            clock: DefaultClock::default(),
+            per_ip: per_ip.map(|n| RateLimiter::keyed(quota(n))),
+            per_key: per_key.map(|n| RateLimiter::keyed(quota(n))),
+            api_keys,
+        }
+    }
+
+    /// Take a token for this request, or return how long to wait until one is available.
+    pub fn check(&self, ip: IpAddr, api_key: Option<&str>) -> Result<(), Duration> {
+        match api_key.filter(|api_key| self.api_keys.contains(*api_key)) {
+            Some(api_key) => match &self.per_key {
+                Some(limiter) => limiter.check_key(&api_key.to_owned()),
+                None => Ok(()),
+            },
+            None => match &self.per_ip {
+                Some(limiter) => limiter.check_key(&ip),
+                None => Ok(()),
+            },
+        }
+        .map_err(|e| e.wait_time_from(self.clock.now()))
+    }
+}
+
+/// Read the allowed API keys, one per line. Blank lines are skipped.
+pub fn read_api_keys(path: &Path) -> std::io::Result<HashSet<String>> {
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_owned)
+        .collect())
+}
@@ -0,0 +1,109 @@
+//! Submitting one proof to the `SP1Chacha` contracts of several chains.
+//!
+//! A proof commits to no chain, so it verifies on every chain where `SP1Chacha` is deployed with
+//! the same SP1 verifier and program key. Each contract keeps its own nullifiers, so every chain
+//! accepts the proof once.
+
+use alloy::primitives::Address;
+use serde::Deserialize;
+
+use crate::post::{chain_id, post_tx, PostError, PostedTx};
+
+/// The chains to relay to, read from a JSON file:
+///
+/// ```json
+/// {
+///   "chains": [
+///     { "name": "optimism", "rpc_url": "https://...", "contract": "0x...", "chain_id": 10 }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RelayConfig {
+    pub chains: Vec<RelayChain>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RelayChain {
+    /// Name the chain is reported under.
+    pub name: String,
+    pub rpc_url: String,
+    /// Address of its `SP1Chacha` contract.
+    pub contract: Address,
+    /// Chain the RPC endpoint should serve. An endpoint of another chain is warned about, not
+    /// skipped.
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+}
+
+/// The outcome of relaying to one chain.
+#[derive(Debug)]
+pub struct Relayed {
+    pub name: String,
+    /// The chain the endpoint serves, if not the configured `chain_id`.
+    pub other_chain_id: Option<u64>,
+    pub result: Result<PostedTx, PostError>,
+}
+
+/// Send `calldata` to the contract of every chain at once, signed with `private_key`, and wait
+/// for `confirmations` confirmations on each. The outcomes are in the order of `chains`.
+pub async fn relay(
+    chains: &[RelayChain],
+    private_key: &str,
+    calldata: &[u8],
+    confirmations: u64,
+) -> Vec<Relayed> {
+    let tasks: Vec<_> = chains
+        .iter()
+        .cloned()
+        .map(|chain| {
+            let private_key = private_key.to_string();
+            let calldata = calldata.to_vec();
+            tokio::spawn(
+                async move { relay_to(chain, &private_key, calldata, confirmations).await },
+            )
+        })
+        .collect();
+    let mut relayed = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        relayed.push(task.await.expect("relay task panicked"));
+    }
+    relayed
+}
+
+async fn relay_to(
+    chain: RelayChain,
+    private_key: &str,
+    calldata: Vec<u8>,
+    confirmations: u64,
+) -> Relayed {
+    let mut other_chain_id = None;
+    if let Some(expected) = chain.chain_id {
+        match chain_id(&chain.rpc_url).await {
+            Ok(actual) if actual != expected => other_chain_id = Some(actual),
+            Ok(_) => {}
+            Err(e) => {
+                return Relayed {
+                    name: chain.name,
+                    other_chain_id,
+                    result: Err(e),
+                }
+            }
+        }
+    }
+    let result = post_tx(
+        &chain.rpc_url,
+        chain.contract,
+        private_key,
+        calldata,
+        None,
+        confirmations,
+        |_| {},
+    )
+    .await;
+    Relayed {
+        name: chain.name,
+        other_chain_id,
+        result,
+    }
+}
@@ -0,0 +1,155 @@
+//! Routes of the proving server, see the `server` binary, which runs the worker proving the queued
+//! jobs.
+//!
+//! - `POST /prove` with the plaintext as the request body returns `{"job_id": "<uuid>"}`
+//! - `GET /status/{job_id}` returns the job state, elapsed time and result
+//! - `GET /metrics` returns the queue depth and worker idle time
+//! - `GET /health` and `GET /ready` serve liveness and readiness probes
+//!
+//! Requests over the [`RateLimits`] are rejected with `429 Too Many Requests` and a `Retry-After`
+//! header. Probes are never rate limited.
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{ConnectInfo, Path, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::audit::AuditLog;
+use crate::queue::{JobQueue, JobStatus};
+use crate::rate_limit::{RateLimits, API_KEY_HEADER};
+
+pub struct AppState {
+    pub queue: JobQueue,
+    pub rate_limits: RateLimits,
+    pub audit_log: Option<AuditLog>,
+    /// Set once the ELF is loaded and the proving key setup has completed.
+    pub prover_ready: AtomicBool,
+    /// Total seconds the worker has spent waiting for jobs.
+    pub worker_idle_secs: AtomicU64,
+}
+
+impl AppState {
+    pub fn new(queue: JobQueue, rate_limits: RateLimits, audit_log: Option<AuditLog>) -> Self {
+        Self {
+            queue,
+            rate_limits,
+            audit_log,
+            prover_ready: AtomicBool::new(false),
+            worker_idle_secs: AtomicU64::new(0),
+        }
+    }
+}
+
+/// The routes, to be served with `into_make_service_with_connect_info::<SocketAddr>`, as the rate
+/// limits need the client IP.
+pub fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/prove", post(prove))
+        .route("/status/{job_id}", get(status))
+        .route("/metrics", get(metrics))
+        // Only applies to the routes above, so probes are never throttled.
+        .layer(middleware::from_fn_with_state(state.clone(), rate_limit))
+        .route("/health", get(health))
+        .route("/ready", get(ready))
+        .with_state(state)
+}
+
+async fn rate_limit(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let api_key = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok());
+    if let Err(wait) = state.rate_limits.check(addr.ip(), api_key) {
+        // Retry-After is in whole seconds, round up so clients don't retry too early.
+        let retry_after = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
+async fn prove(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<Value>, StatusCode> {
+    let requester = requester(addr, &headers);
+    let job_id = state
+        .queue
+        .push(&body, &requester)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(json!({ "job_id": job_id })))
+}
+
+/// Identify the client for the audit log, without storing its API key.
+fn requester(addr: SocketAddr, headers: &HeaderMap) -> String {
+    match headers
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(api_key) => format!("key:{}", &hex::encode(Sha256::digest(api_key))[..16]),
+        None => format!("ip:{}", addr.ip()),
+    }
+}
+
+async fn status(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<JobStatus>, StatusCode> {
+    match state.queue.status(job_id) {
+        Ok(Some(status)) => Ok(Json(status)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+async fn metrics(State(state): State<Arc<AppState>>) -> Result<Json<Value>, StatusCode> {
+    let queue_depth = state
+        .queue
+        .depth()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(json!({
+        "queue_depth": queue_depth,
+        "worker_idle_secs": state.worker_idle_secs.load(Ordering::Relaxed),
+    })))
+}
+
+async fn health(State(state): State<Arc<AppState>>) -> Result<Json<Value>, StatusCode> {
+    let queue_depth = state
+        .queue
+        .depth()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(json!({
+        "status": "ok",
+        "prover_ready": state.prover_ready.load(Ordering::Acquire),
+        "queue_depth": queue_depth,
+        "version": env!("CARGO_PKG_VERSION"),
+    })))
+}
+
+async fn ready(State(state): State<Arc<AppState>>) -> StatusCode {
+    if state.prover_ready.load(Ordering::Acquire) {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
@@ -0,0 +1,142 @@
+//! Uploading proofs to IPFS and downloading them by CID, through the HTTP RPC API of a Kubo node.
+//!
+//! Proofs are added as CIDv1 with raw leaves, so a proof that fits in one [`CHUNK_SIZE`] block,
+//! such as a Groth16 or PLONK proof, has the raw CID of [`raw_cid`]. Its CID is then the SHA256 of
+//! its bytes and is checked on upload and download without trusting the node. Larger proofs are
+//! split into a UnixFS DAG whose CID is not recomputed here.
+
+use std::fmt;
+
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use super::ProofFile;
+
+/// `--ipfs-endpoint` default, the RPC API of a local Kubo node.
+pub const DEFAULT_ENDPOINT: &str = "http://localhost:5001";
+
+/// Kubo's default chunk size, the largest file stored as a single raw block.
+pub const CHUNK_SIZE: usize = 256 * 1024;
+
+/// Prefix of a base32 CIDv1 with the raw codec and a SHA2-256 multihash.
+const RAW_CID_PREFIX: &str = "bafkrei";
+
+#[derive(Debug)]
+pub enum IpfsError {
+    Http(reqwest::Error),
+    /// The content does not hash to its CID.
+    CidMismatch {
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for IpfsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpfsError::Http(e) => write!(f, "IPFS request failed: {e}"),
+            IpfsError::CidMismatch { expected, actual } => {
+                write!(f, "expected CID {expected}, the content hashes to {actual}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IpfsError {}
+
+impl From<reqwest::Error> for IpfsError {
+    fn from(e: reqwest::Error) -> Self {
+        IpfsError::Http(e)
+    }
+}
+
+/// The base32 CIDv1 of `bytes` stored as a single raw block.
+pub fn raw_cid(bytes: &[u8]) -> String {
+    // CIDv1, raw codec, SHA2-256 multihash of 32 bytes
+    let mut cid = vec![0x01, 0x55, 0x12, 0x20];
+    cid.extend(Sha256::digest(bytes));
+    format!("b{}", base32(&cid))
+}
+
+/// RFC 4648 base32, lower case and unpadded as in multibase.
+fn base32(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u16, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | u16::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(ALPHABET[usize::from((buffer >> bits) & 0x1f)] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)] as char);
+    }
+    encoded
+}
+
+fn check_cid(cid: &str, bytes: &[u8]) -> Result<(), IpfsError> {
+    if !cid.starts_with(RAW_CID_PREFIX) {
+        return Ok(());
+    }
+    let actual = raw_cid(bytes);
+    if actual != cid {
+        return Err(IpfsError::CidMismatch {
+            expected: cid.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct AddResponse {
+    #[serde(rename = "Hash")]
+    hash: String,
+}
+
+/// Add `proof` to the node at `endpoint`, pinning it with `pin`, and return its CID.
+pub async fn ipfs_upload(
+    endpoint: &str,
+    proof: &ProofFile,
+    pin: bool,
+) -> Result<String, IpfsError> {
+    let form = Form::new().part(
+        "file",
+        Part::bytes(proof.bytes.clone()).file_name("proof.bin"),
+    );
+    let added: AddResponse = reqwest::Client::new()
+        .post(format!("{endpoint}/api/v0/add"))
+        .query(&[
+            ("cid-version", "1"),
+            ("raw-leaves", "true"),
+            ("pin", if pin { "true" } else { "false" }),
+        ])
+        .multipart(form)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    check_cid(&added.hash, &proof.bytes)?;
+    Ok(added.hash)
+}
+
+/// Fetch the proof with `cid` from the node at `endpoint`.
+pub async fn ipfs_download(endpoint: &str, cid: &str) -> Result<ProofFile, IpfsError> {
+    let bytes = reqwest::Client::new()
+        .post(format!("{endpoint}/api/v0/cat"))
+        .query(&[("arg", cid)])
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    check_cid(cid, &bytes)?;
+    Ok(ProofFile {
+        bytes: bytes.to_vec(),
+    })
+}
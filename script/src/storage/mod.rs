@@ -0,0 +1,49 @@
+//! Storing proofs where a verifier running on another machine can fetch them.
+
+pub mod ipfs;
+pub mod s3;
+
+use std::io;
+use std::path::Path;
+
+use sp1_sdk::SP1ProofWithPublicValues;
+
+use crate::error::ScriptError;
+
+/// The bytes of a proof as written by `SP1ProofWithPublicValues::save`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofFile {
+    pub bytes: Vec<u8>,
+}
+
+impl ProofFile {
+    pub fn from_proof(proof: &SP1ProofWithPublicValues) -> Self {
+        Self {
+            bytes: bincode::serialize(proof).expect("proofs serialize"),
+        }
+    }
+
+    pub fn to_proof(&self) -> Result<SP1ProofWithPublicValues, bincode::Error> {
+        bincode::deserialize(&self.bytes)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self {
+            bytes: std::fs::read(path)?,
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, &self.bytes)
+    }
+}
+
+/// Load a proof saved with `SP1ProofWithPublicValues::save`.
+pub fn load_proof(path: &Path) -> Result<SP1ProofWithPublicValues, ScriptError> {
+    let file = ProofFile::load(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => ScriptError::ProofFileNotFound(path.to_path_buf()),
+        _ => e.into(),
+    })?;
+    file.to_proof()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e).into())
+}
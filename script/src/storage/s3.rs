@@ -0,0 +1,85 @@
+//! Uploading proofs to S3 and downloading them, e.g. from a GPU prover on EC2 to a verifier
+//! elsewhere.
+//!
+//! Credentials and the region come from the standard AWS environment variables
+//! (`AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`, `AWS_REGION`) or the rest of the default
+//! credential chain, such as an EC2 instance profile. `AWS_ENDPOINT_URL` points the client at an S3
+//! compatible endpoint like localstack, which is addressed with path style URLs.
+//!
+//! Transient errors, i.e. throttling, timeouts and 5xx responses, are retried with exponential
+//! backoff up to [`MAX_ATTEMPTS`] times.
+
+use std::fmt;
+
+use aws_config::retry::RetryConfig;
+use aws_sdk_s3::error::DisplayErrorContext;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+
+use super::ProofFile;
+
+/// Attempts of each request, including the first.
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// Why a proof could not be stored or fetched.
+#[derive(Debug)]
+pub enum S3Error {
+    Upload(String),
+    Download(String),
+}
+
+impl fmt::Display for S3Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            S3Error::Upload(e) => write!(f, "failed to upload proof to S3: {e}"),
+            S3Error::Download(e) => write!(f, "failed to download proof from S3: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for S3Error {}
+
+/// A client configured from the environment.
+pub async fn s3_client() -> Client {
+    let config = aws_config::from_env()
+        .retry_config(RetryConfig::standard().with_max_attempts(MAX_ATTEMPTS))
+        .load()
+        .await;
+    let config = aws_sdk_s3::config::Builder::from(&config)
+        // Custom endpoints rarely resolve `<bucket>.<host>`
+        .force_path_style(std::env::var_os("AWS_ENDPOINT_URL").is_some())
+        .build();
+    Client::from_conf(config)
+}
+
+pub async fn upload_proof(proof: &ProofFile, bucket: &str, key: &str) -> Result<(), S3Error> {
+    s3_client()
+        .await
+        .put_object()
+        .bucket(bucket)
+        .key(key)
+        .body(ByteStream::from(proof.bytes.clone()))
+        .send()
+        .await
+        .map_err(|e| S3Error::Upload(DisplayErrorContext(e).to_string()))?;
+    Ok(())
+}
+
+pub async fn download_proof(bucket: &str, key: &str) -> Result<ProofFile, S3Error> {
+    let object = s3_client()
+        .await
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| S3Error::Download(DisplayErrorContext(e).to_string()))?;
+    let bytes = object
+        .body
+        .collect()
+        .await
+        .map_err(|e| S3Error::Download(e.to_string()))?;
+    Ok(ProofFile {
+        bytes: bytes.into_bytes().to_vec(),
+    })
+}
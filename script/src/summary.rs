@@ -0,0 +1,121 @@
+//! Human readable overview of a proof, printed at the end of the `chacha` binary.
+
+use std::fmt;
+
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::Table;
+use sha2::{Digest, Sha256};
+use sp1_sdk::SP1ProofWithPublicValues;
+
+use chacha_lib::ProofType;
+
+use crate::convert::proof_type;
+use crate::error::ScriptError;
+use crate::parse_public_values;
+
+/// The fields of a proof worth looking at, hex encoded with a `0x` prefix.
+///
+/// Fields that are not part of the public values are `None` unless set by the caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofSummary {
+    pub version: u8,
+    /// `None` for an execution without a proof.
+    pub proof_type: Option<ProofType>,
+    /// SHA256 of the uncompressed plaintext.
+    pub plaintext_hash: String,
+    /// Committed length of the uncompressed plaintext, `None` for version 1 to 3 programs.
+    pub plaintext_size: Option<u64>,
+    /// SHA256 of the ciphertext, the committed bytes themselves if the program committed only the
+    /// hash. Version 1 to 5 programs do not say which they committed, so for those it is the hash
+    /// of the committed bytes.
+    pub ciphertext_hash: String,
+    pub nonce: Option<String>,
+    pub nullifier: String,
+    /// Verification key hash of the program, as printed by the `vkey` binary.
+    pub program_hash: Option<String>,
+    pub chain_id: Option<u64>,
+    /// Seconds since the unix epoch.
+    pub expiry: Option<u64>,
+    /// Size of the proof as written by `SP1ProofWithPublicValues::save`.
+    pub proof_size_bytes: Option<usize>,
+    /// CID of the proof uploaded with `--ipfs`.
+    pub ipfs_cid: Option<String>,
+}
+
+impl ProofSummary {
+    /// Summarize the public values of an execution.
+    pub fn from_public_values(public_values: &[u8]) -> Result<Self, ScriptError> {
+        let public_values = parse_public_values(public_values)?;
+        Ok(Self {
+            version: public_values.version,
+            proof_type: None,
+            plaintext_hash: to_hex(&public_values.plaintext_hash),
+            plaintext_size: public_values.plaintext_size,
+            ciphertext_hash: if public_values.ciphertext_is_hash == Some(true) {
+                to_hex(public_values.ciphertext)
+            } else {
+                to_hex(&Sha256::digest(public_values.ciphertext))
+            },
+            nonce: None,
+            nullifier: to_hex(&public_values.nullifier),
+            program_hash: None,
+            chain_id: None,
+            expiry: None,
+            proof_size_bytes: None,
+            ipfs_cid: None,
+        })
+    }
+}
+
+/// # Panics
+///
+/// If the proof is not of the encryption program, i.e. its public values are too short.
+impl From<&SP1ProofWithPublicValues> for ProofSummary {
+    fn from(proof: &SP1ProofWithPublicValues) -> Self {
+        let mut summary = Self::from_public_values(proof.public_values.as_slice())
+            .expect("public values too short");
+        summary.proof_type = Some(proof_type(&proof.proof));
+        summary.proof_size_bytes =
+            Some(bincode::serialized_size(proof).expect("proof serializes") as usize);
+        summary
+    }
+}
+
+impl fmt::Display for ProofSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn or_dash(value: Option<impl ToString>) -> String {
+            value.map_or_else(|| "-".to_string(), |value| value.to_string())
+        }
+
+        let mut table = Table::new();
+        table
+            .load_style(UTF8_FULL_CONDENSED)
+            .set_header(["Field", "Value"]);
+        table.add_row(["Program version".to_string(), self.version.to_string()]);
+        table.add_row(["Proof type".to_string(), or_dash(self.proof_type)]);
+        table.add_row(["Plaintext hash", &self.plaintext_hash]);
+        table.add_row([
+            "Plaintext size".to_string(),
+            or_dash(self.plaintext_size.map(|size| format!("{size} bytes"))),
+        ]);
+        table.add_row(["Ciphertext hash", &self.ciphertext_hash]);
+        table.add_row(["Nonce".to_string(), or_dash(self.nonce.as_ref())]);
+        table.add_row(["Nullifier", &self.nullifier]);
+        table.add_row([
+            "Program hash".to_string(),
+            or_dash(self.program_hash.as_ref()),
+        ]);
+        table.add_row(["Chain ID".to_string(), or_dash(self.chain_id)]);
+        table.add_row(["Expiry".to_string(), or_dash(self.expiry)]);
+        table.add_row([
+            "Proof size".to_string(),
+            or_dash(self.proof_size_bytes.map(|size| format!("{size} bytes"))),
+        ]);
+        table.add_row(["IPFS CID".to_string(), or_dash(self.ipfs_cid.as_ref())]);
+        write!(f, "{table}")
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
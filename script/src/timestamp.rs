@@ -0,0 +1,97 @@
+//! Signed timestamps from a time oracle, attesting when an encryption was proven.
+//!
+//! The protocol follows Roughtime: the client sends a nonce, and the oracle signs the nonce
+//! together with its current time. The nonce is the plaintext hash, so the signature could not
+//! have been made before the plaintext existed. Requests are `GET <url>?nonce=<hex>` and responses
+//! are JSON `{"timestamp": <unix seconds>, "signature": "<hex>"}`, where the ed25519 signature is
+//! over [`signed_message`]. See the `mock_time_oracle` binary.
+//!
+//! # Threat model
+//!
+//! - The oracle is trusted to report the correct time. Its public key is pinned by the client, a
+//!   key fetched from the oracle itself proves nothing.
+//! - The signature is verified only by the host, before proving. The program and the on-chain
+//!   verifier see [`chacha_lib::timestamp_commitment`], so anyone relying on the time must obtain
+//!   the timestamp bytes and check the signature themselves.
+//! - The timestamp is a lower bound: the proof can be generated any time after it. It only
+//!   attests that the plaintext existed at that time, not when it was encrypted or proven.
+//! - A compromised oracle key can sign any time. A single oracle is a single point of trust,
+//!   Roughtime clients mitigate this by querying several servers.
+
+use std::fmt;
+
+use chacha_lib::envelope::ed25519_dalek::{Signature, SignatureError, VerifyingKey};
+use chacha_lib::timestamp::TIMESTAMP_LEN;
+use serde::{Deserialize, Serialize};
+
+/// A timestamp signed by a time oracle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTimestamp {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    #[serde(with = "hex")]
+    pub signature: [u8; 64],
+}
+
+/// The bytes the oracle signs: `nonce || timestamp` (u64 LE).
+pub fn signed_message(nonce: &[u8; 32], timestamp: u64) -> [u8; 40] {
+    let mut message = [0u8; 40];
+    message[..32].copy_from_slice(nonce);
+    message[32..].copy_from_slice(&timestamp.to_le_bytes());
+    message
+}
+
+impl SignedTimestamp {
+    /// The timestamp bytes passed to the program: `timestamp` (u64 LE) | `signature`.
+    pub fn to_bytes(&self) -> [u8; TIMESTAMP_LEN] {
+        let mut bytes = [0u8; TIMESTAMP_LEN];
+        bytes[..8].copy_from_slice(&self.timestamp.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.signature);
+        bytes
+    }
+
+    /// Check the oracle signature over `nonce`.
+    pub fn verify(&self, oracle_key: &[u8; 32], nonce: &[u8; 32]) -> Result<(), SignatureError> {
+        VerifyingKey::from_bytes(oracle_key)?.verify_strict(
+            &signed_message(nonce, self.timestamp),
+            &Signature::from_bytes(&self.signature),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum TimestampError {
+    Http(reqwest::Error),
+    /// The oracle response is not signed by the pinned key.
+    Signature(SignatureError),
+}
+
+impl fmt::Display for TimestampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimestampError::Http(e) => write!(f, "failed to fetch timestamp: {e}"),
+            TimestampError::Signature(e) => write!(f, "invalid timestamp signature: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TimestampError {}
+
+/// Fetch a timestamp over `nonce` from the oracle at `url` and verify it against `oracle_key`.
+pub fn fetch_timestamp(
+    url: &str,
+    oracle_key: &[u8; 32],
+    nonce: &[u8; 32],
+) -> Result<SignedTimestamp, TimestampError> {
+    let timestamp: SignedTimestamp = reqwest::blocking::Client::new()
+        .get(url)
+        .query(&[("nonce", hex::encode(nonce))])
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.json())
+        .map_err(TimestampError::Http)?;
+    timestamp
+        .verify(oracle_key, nonce)
+        .map_err(TimestampError::Signature)?;
+    Ok(timestamp)
+}
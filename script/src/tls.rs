@@ -0,0 +1,146 @@
+//! Mutual TLS of the proving server.
+//!
+//! The server presents its certificate and only accepts clients presenting a certificate signed
+//! by the given CA, so reaching the port is not enough to submit jobs. [`generate_certs`] creates
+//! a throwaway CA with a server and a client certificate for development, see the `gen_certs`
+//! binary.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rcgen::{
+    BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, KeyPair,
+    KeyUsagePurpose,
+};
+use rustls::pki_types::pem::{self, PemObject};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{VerifierBuilderError, WebPkiClientVerifier};
+use rustls::{RootCertStore, ServerConfig};
+
+#[derive(Debug)]
+pub enum TlsError {
+    /// A PEM file is unreadable or malformed.
+    Pem(PathBuf, pem::Error),
+    /// The CA certificate file holds no usable certificate.
+    Verifier(VerifierBuilderError),
+    /// A certificate or key is invalid, or the server certificate does not match its key.
+    Rustls(rustls::Error),
+}
+
+impl fmt::Display for TlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TlsError::Pem(path, e) => write!(f, "failed to read {}: {e}", path.display()),
+            TlsError::Verifier(e) => write!(f, "invalid client CA: {e}"),
+            TlsError::Rustls(e) => write!(f, "invalid certificate: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TlsError {}
+
+impl From<VerifierBuilderError> for TlsError {
+    fn from(e: VerifierBuilderError) -> Self {
+        TlsError::Verifier(e)
+    }
+}
+
+impl From<rustls::Error> for TlsError {
+    fn from(e: rustls::Error) -> Self {
+        TlsError::Rustls(e)
+    }
+}
+
+fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect())
+        .map_err(|e| TlsError::Pem(path.to_path_buf(), e))
+}
+
+/// Server config presenting `server_cert` and requiring a client certificate signed by `ca_cert`.
+pub fn server_config(
+    ca_cert: &Path,
+    server_cert: &Path,
+    server_key: &Path,
+) -> Result<ServerConfig, TlsError> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+    let mut roots = RootCertStore::empty();
+    for cert in read_certs(ca_cert)? {
+        roots.add(cert)?;
+    }
+    let verifier =
+        WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider.clone()).build()?;
+
+    let key = PrivateKeyDer::from_pem_file(server_key)
+        .map_err(|e| TlsError::Pem(server_key.to_path_buf(), e))?;
+    let mut config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(read_certs(server_cert)?, key)?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// PEM encoded certificates and keys of [`generate_certs`].
+pub struct DevCerts {
+    pub ca_cert: String,
+    pub server_cert: String,
+    pub server_key: String,
+    pub client_cert: String,
+    pub client_key: String,
+}
+
+impl DevCerts {
+    /// Write the certificates and keys to `ca.pem`, `server.pem`, `server.key`, `client.pem`
+    /// and `client.key` in `dir`, creating it if needed.
+    pub fn write(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("ca.pem"), &self.ca_cert)?;
+        fs::write(dir.join("server.pem"), &self.server_cert)?;
+        fs::write(dir.join("server.key"), &self.server_key)?;
+        fs::write(dir.join("client.pem"), &self.client_cert)?;
+        fs::write(dir.join("client.key"), &self.client_key)
+    }
+}
+
+/// Create a self-signed CA and sign a server certificate for `server_names`, DNS names or IP
+/// addresses, and a client certificate with it. Not for production use: the CA key is dropped,
+/// so no further certificates can be issued.
+pub fn generate_certs(server_names: Vec<String>) -> Result<DevCerts, rcgen::Error> {
+    let ca_key = KeyPair::generate()?;
+    let mut ca_params = CertificateParams::new(Vec::new())?;
+    ca_params
+        .distinguished_name
+        .push(DnType::CommonName, "chacha development CA");
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+    let ca = ca_params.self_signed(&ca_key)?;
+
+    let server_key = KeyPair::generate()?;
+    let mut server_params = CertificateParams::new(server_names)?;
+    server_params
+        .distinguished_name
+        .push(DnType::CommonName, "chacha server");
+    server_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+    let server = server_params.signed_by(&server_key, &ca, &ca_key)?;
+
+    let client_key = KeyPair::generate()?;
+    let mut client_params = CertificateParams::new(Vec::new())?;
+    client_params
+        .distinguished_name
+        .push(DnType::CommonName, "chacha client");
+    client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+    let client = client_params.signed_by(&client_key, &ca, &ca_key)?;
+
+    Ok(DevCerts {
+        ca_cert: ca.pem(),
+        server_cert: server.pem(),
+        server_key: server_key.serialize_pem(),
+        client_cert: client.pem(),
+        client_key: client_key.serialize_pem(),
+    })
+}
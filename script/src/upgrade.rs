@@ -0,0 +1,79 @@
+//! Proving an upgrade of the encryption program, see `chacha_lib::upgrade`.
+//!
+//! The old and the new program each prove a compressed proof of encrypting [`test_inputs`], which
+//! the upgrade program verifies. Its proof is submitted to `SP1Chacha.upgrade`, see
+//! [`crate::post::upgrade_calldata`].
+
+use std::fmt;
+
+use sp1_sdk::{HashableKey, SP1Proof, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey};
+
+use chacha_lib::upgrade::{check_upgrade, UpgradeError};
+use chacha_lib::{ProofType, PublicValues};
+
+use crate::convert::proof_type;
+use crate::EncryptInputs;
+
+/// The input both programs encrypt. Use a fresh key and nonce for every upgrade, the upgrade proof
+/// does not reveal them.
+pub fn test_inputs(key: [u8; 32], nonce: [u8; 12]) -> EncryptInputs<'static> {
+    EncryptInputs::new(key, nonce, chacha_lib::INPUT_BYTES)
+}
+
+/// Why two proofs cannot be passed to the upgrade program.
+#[derive(Debug)]
+pub enum MigrateError {
+    /// The upgrade program verifies only compressed proofs.
+    NotCompressed(ProofType),
+    /// The public values are not of the encryption program.
+    PublicValues,
+    Upgrade(UpgradeError),
+}
+
+impl fmt::Display for MigrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrateError::NotCompressed(proof_type) => {
+                write!(f, "expected a compressed proof, got a {proof_type} proof")
+            }
+            MigrateError::PublicValues => write!(f, "public values too short"),
+            MigrateError::Upgrade(e) => write!(f, "not an upgrade: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrateError {}
+
+impl From<UpgradeError> for MigrateError {
+    fn from(e: UpgradeError) -> Self {
+        MigrateError::Upgrade(e)
+    }
+}
+
+/// Inputs of the upgrade program from proofs of the old and the new program.
+pub fn upgrade_stdin(
+    old_vk: &SP1VerifyingKey,
+    old: &SP1ProofWithPublicValues,
+    new_vk: &SP1VerifyingKey,
+    new: &SP1ProofWithPublicValues,
+) -> Result<SP1Stdin, MigrateError> {
+    let parse = |bytes| PublicValues::parse(bytes).ok_or(MigrateError::PublicValues);
+    // Fail here rather than as a zkVM panic
+    check_upgrade(
+        &parse(old.public_values.as_slice())?,
+        &parse(new.public_values.as_slice())?,
+    )?;
+
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&old_vk.hash_u32());
+    stdin.write(&new_vk.hash_u32());
+    stdin.write_slice(old.public_values.as_slice());
+    stdin.write_slice(new.public_values.as_slice());
+    for (vk, proof) in [(old_vk, old), (new_vk, new)] {
+        let SP1Proof::Compressed(reduce) = &proof.proof else {
+            return Err(MigrateError::NotCompressed(proof_type(&proof.proof)));
+        };
+        stdin.write_proof(*reduce.clone(), vk.vk.clone());
+    }
+    Ok(stdin)
+}
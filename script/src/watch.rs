@@ -0,0 +1,212 @@
+//! Proving every file that arrives in a directory, for inputs that arrive continuously such as
+//! sensor data or log streams.
+//!
+//! A file created in or moved into the directory is proved once its size has stopped changing for
+//! [`SETTLE_INTERVAL`], as a create event fires before the writer is done. Each file is encrypted
+//! under a new random nonce. Its proof is saved to the output directory as
+//! `<file name>.proof.bin`, next to a [`ProofSidecar`] `<file name>.proof.json` with the nonce
+//! needed to decrypt. The file is then moved to the processed directory, or to the error directory
+//! if proving failed. Without those directories it is left in place, unless
+//! [`WatchDirs::delete_processed`] is set, and is proved again when watching restarts. Hidden
+//! files, such as the temporary files of `rsync`, are skipped.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use notify::event::ModifyKind;
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1ProofWithPublicValues;
+
+use crate::storage::ProofFile;
+
+/// How long a file's size has to stay the same before it is proved.
+pub const SETTLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How often [`watch`] checks whether it should stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchDirs {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub processed: Option<PathBuf>,
+    pub error: Option<PathBuf>,
+    /// Delete proved files when there is no processed directory, instead of keeping them.
+    pub delete_processed: bool,
+}
+
+/// What is needed besides the key to decrypt the ciphertext of a proof, saved next to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofSidecar {
+    #[serde(with = "hex::serde")]
+    pub nonce: [u8; 12],
+}
+
+impl ProofSidecar {
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string(self).expect("sidecar serializes");
+        std::fs::write(path, json)
+    }
+}
+
+/// The outcome of one input file.
+#[derive(Debug)]
+pub struct Processed {
+    pub input: PathBuf,
+    /// Time spent proving.
+    pub elapsed: Duration,
+    /// The saved proof.
+    pub result: Result<PathBuf, String>,
+}
+
+/// The file the proof of `input` is saved to.
+pub fn proof_path(output: &Path, input: &Path) -> PathBuf {
+    let mut name = input.file_name().unwrap_or_default().to_os_string();
+    name.push(".proof.bin");
+    output.join(name)
+}
+
+/// The file the [`ProofSidecar`] of `input` is saved to.
+pub fn sidecar_path(output: &Path, input: &Path) -> PathBuf {
+    let mut name = input.file_name().unwrap_or_default().to_os_string();
+    name.push(".proof.json");
+    output.join(name)
+}
+
+/// Save the [`ProofSidecar`] of `input` and then its proof to `output`, returning the proof path.
+/// The sidecar comes first, a proof without it could not be decrypted.
+pub fn save_proof(
+    output: &Path,
+    input: &Path,
+    nonce: [u8; 12],
+    proof: &SP1ProofWithPublicValues,
+) -> Result<PathBuf, String> {
+    let sidecar_path = sidecar_path(output, input);
+    ProofSidecar { nonce }
+        .save(&sidecar_path)
+        .map_err(|e| format!("Failed to save {}: {e}", sidecar_path.display()))?;
+    let proof_path = proof_path(output, input);
+    ProofFile::from_proof(proof)
+        .save(&proof_path)
+        .map_err(|e| format!("Failed to save {}: {e}", proof_path.display()))?;
+    Ok(proof_path)
+}
+
+/// Prove the files already in the input directory, then every file created in it, until `stop`
+/// is set. `prove` is given the contents of each file and the nonce to encrypt it under.
+pub fn watch(
+    dirs: &WatchDirs,
+    mut prove: impl FnMut(&[u8], [u8; 12]) -> Result<SP1ProofWithPublicValues, String>,
+    mut on_processed: impl FnMut(Processed),
+    stop: &AtomicBool,
+) -> Result<(), String> {
+    for dir in [
+        Some(&dirs.output),
+        dirs.processed.as_ref(),
+        dirs.error.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if dir == &dirs.input {
+            return Err(format!(
+                "{} is the watched directory, its files would be proved again",
+                dir.display()
+            ));
+        }
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    }
+
+    // Watch before listing, so no file created in between is missed
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+    watcher
+        .watch(&dirs.input, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {e}", dirs.input.display()))?;
+
+    let mut existing: Vec<_> = std::fs::read_dir(&dirs.input)
+        .map_err(|e| format!("Failed to read {}: {e}", dirs.input.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    existing.sort();
+    for path in existing {
+        process(dirs, &path, &mut prove, &mut on_processed);
+    }
+
+    while !stop.load(Ordering::Relaxed) {
+        let event = match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(event)) => event,
+            Ok(Err(e)) => return Err(format!("Failed to watch {}: {e}", dirs.input.display())),
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+        // Files moved into the directory are renames, not creations
+        if let EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_)) = event.kind {
+            for path in event.paths {
+                process(dirs, &path, &mut prove, &mut on_processed);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn process(
+    dirs: &WatchDirs,
+    path: &Path,
+    prove: &mut impl FnMut(&[u8], [u8; 12]) -> Result<SP1ProofWithPublicValues, String>,
+    on_processed: &mut impl FnMut(Processed),
+) {
+    let hidden = path
+        .file_name()
+        .is_none_or(|name| name.to_string_lossy().starts_with('.'));
+    if hidden || !wait_until_written(path) {
+        return;
+    }
+
+    let start = Instant::now();
+    let nonce = chacha_lib::random_nonce();
+    let result = std::fs::read(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))
+        .and_then(|plaintext| prove(&plaintext, nonce))
+        .and_then(|proof| save_proof(&dirs.output, path, nonce, &proof));
+    let elapsed = start.elapsed();
+
+    let done = match (&result, &dirs.processed, &dirs.error) {
+        (Ok(_), Some(dir), _) | (Err(_), _, Some(dir)) => {
+            std::fs::rename(path, dir.join(path.file_name().unwrap()))
+        }
+        (Ok(_), None, _) if dirs.delete_processed => std::fs::remove_file(path),
+        (Ok(_), None, _) | (Err(_), _, None) => Ok(()),
+    };
+    let result = match (result, done) {
+        (Ok(_), Err(e)) => Err(format!("Failed to move {} away: {e}", path.display())),
+        (result, _) => result,
+    };
+    on_processed(Processed {
+        input: path.to_path_buf(),
+        elapsed,
+        result,
+    });
+}
+
+/// Wait until the size of the file at `path` stops changing, `false` if it is not a file (any
+/// more).
+fn wait_until_written(path: &Path) -> bool {
+    let mut len = None;
+    loop {
+        match std::fs::metadata(path) {
+            Ok(metadata) if metadata.is_file() => {
+                if len == Some(metadata.len()) {
+                    return true;
+                }
+                len = Some(metadata.len());
+            }
+            _ => return false,
+        }
+        std::thread::sleep(SETTLE_INTERVAL);
+    }
+}
@@ -0,0 +1,31 @@
+//! The AES-256-CTR program of `--fips-mode`.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::aes_mode::{aes_ctr_encrypt, iv_from_nonce};
+use chacha_script::AES_ELF;
+
+#[test]
+fn encrypts_with_aes_ctr() {
+    let key = [1u8; 32];
+    let iv = iv_from_nonce(&[2u8; 12]);
+    let plaintext = b"plaintext encrypted with an approved algorithm";
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&key);
+    stdin.write_slice(&iv);
+    stdin.write_slice(plaintext);
+
+    let client = ProverClient::builder().mock().build();
+    let (output, report) = client.execute(AES_ELF, &stdin).run().unwrap();
+    let output = output.as_slice();
+    assert_eq!(output[..32], Sha256::digest(plaintext)[..]);
+    let mut ciphertext = plaintext.to_vec();
+    aes_ctr_encrypt(&key, &iv, &mut ciphertext);
+    assert_eq!(output[32..], ciphertext);
+    println!(
+        "AES-256-CTR of {} bytes: {} cycles",
+        plaintext.len(),
+        report.cycle_tracker["aes"]
+    );
+}
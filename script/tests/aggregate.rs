@@ -0,0 +1,90 @@
+//! Aggregating 4 mock chunk proofs with the aggregation program.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{HashableKey, Prover, ProverClient, SP1Stdin};
+
+use chacha_lib::aggregate::{chunk_leaf, Aggregate, AggregateError};
+use chacha_lib::merkle::MerkleTree;
+use chacha_lib::upgrade::vkey_bytes32;
+use chacha_script::aggregate::{aggregate_stdin, AggregationError};
+use chacha_script::{AGGREGATE_ELF, CHUNK_ELF};
+
+const CHUNK_SIZE: usize = 128;
+
+fn chunk_stdin(i: usize, chunks: u32, chunk: &[u8]) -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&[1; 32]);
+    stdin.write_slice(&[2; 12]);
+    stdin.write(&((i * CHUNK_SIZE / 64) as u32));
+    stdin.write(&chunks);
+    stdin.write_slice(chunk);
+    stdin
+}
+
+#[test]
+fn aggregates_four_chunks() {
+    let plaintext: Vec<u8> = (0..CHUNK_SIZE * 4).map(|i| i as u8).collect();
+    let client = ProverClient::builder().mock().build();
+    let (chunk_pk, chunk_vk) = client.setup(CHUNK_ELF);
+    let proofs: Vec<_> = plaintext
+        .chunks(CHUNK_SIZE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            client
+                .prove(&chunk_pk, &chunk_stdin(i, 4, chunk))
+                .compressed()
+                .run()
+                .unwrap()
+        })
+        .collect();
+
+    let (stdin, expected) = aggregate_stdin(&chunk_vk, &proofs).unwrap();
+    // Mock proofs do not verify, only the aggregation program's own checks run
+    let (output, _) = client
+        .execute(AGGREGATE_ELF, &stdin)
+        .deferred_proof_verification(false)
+        .run()
+        .unwrap();
+    let aggregate = Aggregate::parse(output.as_slice()).unwrap();
+    assert_eq!(aggregate, expected);
+    assert_eq!(aggregate.chunks, 4);
+    assert_eq!(aggregate.chunk_vkey, vkey_bytes32(&chunk_vk.hash_u32()));
+    assert_eq!(aggregate.chunk_vkey, chunk_vk.bytes32_raw());
+
+    let leaves = plaintext
+        .chunks(CHUNK_SIZE)
+        .map(|chunk| chunk_leaf(&Sha256::digest(chunk).into()))
+        .collect();
+    assert_eq!(
+        aggregate.plaintext_root,
+        MerkleTree::from_leaves(leaves).root()
+    );
+}
+
+#[test]
+fn incomplete_streams_are_rejected() {
+    let plaintext = [7u8; CHUNK_SIZE * 2];
+    let client = ProverClient::builder().mock().build();
+    let (chunk_pk, chunk_vk) = client.setup(CHUNK_ELF);
+    let second = client
+        .prove(&chunk_pk, &chunk_stdin(1, 2, &plaintext[CHUNK_SIZE..]))
+        .compressed()
+        .run()
+        .unwrap();
+    assert!(matches!(
+        aggregate_stdin(&chunk_vk, std::slice::from_ref(&second)),
+        Err(AggregationError::Aggregate(AggregateError::Discontinuous(
+            0
+        )))
+    ));
+
+    let first = client
+        .prove(&chunk_pk, &chunk_stdin(0, 2, &plaintext[..CHUNK_SIZE]))
+        .core()
+        .run()
+        .unwrap();
+    assert!(matches!(
+        aggregate_stdin(&chunk_vk, &[first, second]),
+        Err(AggregationError::NotCompressed(0, _))
+    ));
+}
@@ -0,0 +1,53 @@
+//! ChaCha20 operation counts, against RFC 8439.
+
+use chacha_script::analysis::{
+    block_function_ops, blocks, encryption_block_ops, lookup_minimum, riscv_instructions,
+    ConstraintAnalysis, OpCounts,
+};
+
+#[test]
+fn block_function_matches_rfc8439() {
+    // 20 rounds of 4 quarter rounds, each of 4 additions, XORs and rotations, then 16 additions
+    assert_eq!(
+        block_function_ops(),
+        OpCounts {
+            add: 20 * 4 * 4 + 16,
+            xor: 20 * 4 * 4,
+            rotate: 20 * 4 * 4,
+        }
+    );
+    // And a XOR per keystream word to encrypt
+    assert_eq!(
+        encryption_block_ops(),
+        OpCounts {
+            add: 336,
+            xor: 336,
+            rotate: 320,
+        }
+    );
+    assert_eq!(encryption_block_ops().total(), 992);
+}
+
+#[test]
+fn instruction_and_lookup_counts() {
+    let ops = encryption_block_ops();
+    // Rotations are three RV32IM instructions
+    assert_eq!(riscv_instructions(ops), 336 + 336 + 3 * 320);
+    // Rotations are fused with the XOR before them
+    assert_eq!(lookup_minimum(ops), 336 + 320 + 16);
+}
+
+#[test]
+fn counts_scale_with_blocks() {
+    assert_eq!(blocks(0), 0);
+    assert_eq!(blocks(1), 1);
+    assert_eq!(blocks(64), 1);
+    assert_eq!(blocks(65), 2);
+
+    let analysis = ConstraintAnalysis::new(1000);
+    assert_eq!(analysis.blocks, 16);
+    assert_eq!(analysis.ops, encryption_block_ops().times(16));
+    assert_eq!(analysis.riscv_instructions, 16 * 1632);
+    assert_eq!(analysis.lookup_minimum, 16 * 672);
+    assert!(analysis.to_string().contains("2.4x fewer operations"));
+}
@@ -0,0 +1,138 @@
+//! Tampering with the hash chained audit log.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues, SP1PublicValues};
+
+use chacha_script::audit::{verify_chain, AuditEntry, AuditError, AuditLog};
+
+fn temp_log(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("chacha-audit-{name}-{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// A log of 5 entries, each for a different input and proof.
+fn write_log(path: &Path) -> Vec<AuditEntry> {
+    let log = AuditLog::open(path).unwrap();
+    (0..5u8)
+        .map(|i| {
+            let proof = SP1ProofWithPublicValues {
+                proof: SP1Proof::Core(Vec::new()),
+                public_values: SP1PublicValues::from(&[i]),
+                sp1_version: String::new(),
+            };
+            log.record(
+                &format!("ip:10.0.0.{i}"),
+                &[i; 16],
+                &proof,
+                Duration::from_millis(100),
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+fn lines(path: &Path) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+fn write_lines(path: &Path, lines: &[String]) {
+    std::fs::write(path, lines.join("\n") + "\n").unwrap();
+}
+
+/// Run the `audit_verify` binary, returning whether it succeeded and its output.
+fn audit_verify(path: &Path, head: Option<&str>) -> (bool, String) {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_audit_verify"));
+    command.arg(path);
+    if let Some(head) = head {
+        command.args(["--head", head]);
+    }
+    let output = command.output().unwrap();
+    let text =
+        String::from_utf8(output.stdout).unwrap() + &String::from_utf8(output.stderr).unwrap();
+    (output.status.success(), text)
+}
+
+#[test]
+fn intact_log_verifies() {
+    let path = temp_log("intact");
+    let entries = write_log(&path);
+
+    let chain = verify_chain(&path, Some(&entries[4].hash)).unwrap();
+    assert_eq!(chain.entries, 5);
+    assert_eq!(chain.head, entries[4].hash);
+    // A head from before more entries were appended
+    verify_chain(&path, Some(&entries[2].hash)).unwrap();
+
+    let (ok, output) = audit_verify(&path, None);
+    assert!(ok, "{output}");
+    assert!(output.contains("5 entries"), "{output}");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn tampered_entry_is_detected() {
+    let path = temp_log("tampered");
+    write_log(&path);
+
+    let mut lines = lines(&path);
+    lines[2] = lines[2].replace("ip:10.0.0.2", "ip:10.0.0.9");
+    write_lines(&path, &lines);
+
+    assert!(matches!(
+        verify_chain(&path, None),
+        Err(AuditError::HashMismatch { line: 3 })
+    ));
+    let (ok, output) = audit_verify(&path, None);
+    assert!(!ok);
+    assert!(output.contains("line 3"), "{output}");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn rehashed_entry_breaks_the_chain() {
+    let path = temp_log("rehashed");
+    write_log(&path);
+
+    // Recomputing the hash of the edited entry leaves the next one pointing at the old hash
+    let mut lines = lines(&path);
+    let mut entry: AuditEntry = serde_json::from_str(&lines[2]).unwrap();
+    entry.requester = "ip:10.0.0.9".to_string();
+    entry.hash = String::new();
+    entry.hash = hex::encode(Sha256::digest(serde_json::to_string(&entry).unwrap()));
+    lines[2] = serde_json::to_string(&entry).unwrap();
+    write_lines(&path, &lines);
+
+    assert!(matches!(
+        verify_chain(&path, None),
+        Err(AuditError::BrokenChain { line: 4 })
+    ));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn truncation_is_only_detected_against_the_head() {
+    let path = temp_log("truncated");
+    let entries = write_log(&path);
+
+    let lines = lines(&path);
+    write_lines(&path, &lines[..3]);
+
+    assert_eq!(verify_chain(&path, None).unwrap().entries, 3);
+    assert!(matches!(
+        verify_chain(&path, Some(&entries[4].hash)),
+        Err(AuditError::MissingHead)
+    ));
+    let (ok, output) = audit_verify(&path, Some(&entries[4].hash));
+    assert!(!ok);
+    assert!(output.contains("truncated"), "{output}");
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,25 @@
+//! Mode selection of `--auto` at the default threshold.
+
+use chacha_script::estimate::{auto_mode, Mode, AUTO_PROVE_THRESHOLD_BYTES};
+
+#[test]
+fn selects_mode_by_size() {
+    for (size, mode) in [
+        (1024, Mode::Execute),
+        (10 * 1024, Mode::Prove),
+        (100 * 1024, Mode::Prove),
+    ] {
+        assert_eq!(
+            auto_mode(size, AUTO_PROVE_THRESHOLD_BYTES),
+            mode,
+            "{size} bytes"
+        );
+    }
+}
+
+#[test]
+fn threshold_is_configurable() {
+    assert_eq!(auto_mode(10 * 1024, 100 * 1024), Mode::Execute);
+    assert_eq!(auto_mode(1024, 1024), Mode::Prove);
+    assert_eq!(auto_mode(0, 0), Mode::Prove);
+}
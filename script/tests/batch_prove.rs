@@ -0,0 +1,34 @@
+//! Proving a file of a batch, with the mock prover in place of a GPU.
+
+use sp1_sdk::{Prover, ProverClient};
+
+use chacha_lib::{chacha, ProofType, PublicValues};
+use chacha_script::gpu::prove_file;
+use chacha_script::storage::load_proof;
+use chacha_script::watch::{sidecar_path, ProofSidecar};
+use chacha_script::CHACHA_ELF;
+
+const KEY: [u8; 32] = [7; 32];
+
+#[test]
+fn sidecar_nonce_decrypts_the_committed_ciphertext() {
+    let dir = std::env::temp_dir().join(format!("chacha-batch-prove-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("data.bin");
+    std::fs::write(&input, b"batch of sensor readings").unwrap();
+
+    let client = ProverClient::builder().mock().build();
+    let (pk, _) = client.setup(CHACHA_ELF);
+    let path = prove_file(&client, &pk, KEY, &input, &dir, ProofType::Compressed).unwrap();
+
+    let sidecar: ProofSidecar =
+        serde_json::from_slice(&std::fs::read(sidecar_path(&dir, &input)).unwrap()).unwrap();
+    let proof = load_proof(&path).unwrap();
+    let public_values = PublicValues::parse(proof.public_values.as_slice()).unwrap();
+    let mut plaintext = public_values.ciphertext.to_vec();
+    chacha(&KEY, &sidecar.nonce, &mut plaintext);
+    assert_eq!(plaintext, b"batch of sensor readings");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,52 @@
+//! The BLAKE2b program, and the cycles of BLAKE2b-256 next to SHA-256 and BLAKE3, see
+//! `program/src/main_sha256_bench.rs`.
+
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::blake2b::{blake2b_256, personalization};
+use chacha_lib::chacha;
+use chacha_script::{BLAKE2B_ELF, SHA256_BENCH_ELF};
+
+#[test]
+fn commits_personalized_blake2b() {
+    let key = [1u8; 32];
+    let nonce = [2u8; 12];
+    let personal = personalization(b"ZcashPoW").unwrap();
+    let plaintext = b"zcash compatible plaintext";
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&key);
+    stdin.write_slice(&nonce);
+    stdin.write_slice(&personal);
+    stdin.write_slice(plaintext);
+
+    let client = ProverClient::builder().mock().build();
+    let (output, _) = client.execute(BLAKE2B_ELF, &stdin).run().unwrap();
+    let output = output.as_slice();
+    assert_eq!(output[..16], personal);
+    assert_eq!(output[16..48], blake2b_256(&personal, plaintext));
+    let mut ciphertext = plaintext.to_vec();
+    chacha(&key, &nonce, &mut ciphertext);
+    assert_eq!(output[48..], ciphertext);
+}
+
+#[test]
+fn hash_cycles() {
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&[0x61u8; 1024]);
+
+    let client = ProverClient::builder().mock().build();
+    let (_, report) = client.execute(SHA256_BENCH_ELF, &stdin).run().unwrap();
+
+    let sha256 = report.cycle_tracker["sha256-precompile"];
+    let blake2b = report.cycle_tracker["blake2b"];
+    let blake3 = report.cycle_tracker["blake3"];
+    println!(
+        "Hashing 1 KiB: SHA-256 {sha256} cycles with the precompile, BLAKE2b-256 {blake2b}, \
+         BLAKE3 {blake3}"
+    );
+    // Neither has a precompile
+    assert!(
+        blake2b > sha256 && blake3 > sha256,
+        "the SHA-256 precompile is not the cheapest"
+    );
+}
@@ -0,0 +1,108 @@
+//! Resuming from the checkpoint of a proving session that was interrupted after the setup.
+
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{HashableKey, Prover, ProverClient};
+
+use chacha_lib::PublicValues;
+use chacha_script::checkpoint::{Checkpoint, CheckpointError, CheckpointMeta};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+fn checkpoint_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("chacha-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn resume_after_interruption() {
+    let plaintext = b"interrupted plaintext";
+    let inputs = EncryptInputs::new([7; 32], [9; 12], plaintext);
+    let meta = CheckpointMeta {
+        nonce: inputs.nonce,
+        plaintext_hash: Sha256::digest(plaintext).into(),
+        commit_ciphertext_hash: false,
+        plaintext_size: plaintext.len(),
+    };
+    let client = ProverClient::builder().mock().build();
+    let (pk, vk) = client.setup(CHACHA_ELF);
+    let dir = checkpoint_dir("resume");
+
+    // The session is killed after saving, before the proof completes
+    {
+        let stdin = inputs.to_stdin();
+        Checkpoint::save(&dir, &stdin, &pk, &meta).unwrap();
+    }
+    assert!(Checkpoint::is_interrupted(&dir));
+
+    let (checkpoint, stdin, resumed_pk, resumed_meta) = Checkpoint::load(&dir).unwrap();
+    assert_eq!(resumed_meta, meta);
+    assert_eq!(stdin.buffer, inputs.to_stdin().buffer);
+    assert_eq!(resumed_pk.vk.bytes32(), vk.bytes32());
+
+    let proof = client.prove(&resumed_pk, &stdin).run().unwrap();
+    client.verify(&proof, &resumed_pk.vk).unwrap();
+    let public_values = PublicValues::parse(proof.public_values.as_slice()).unwrap();
+    assert_eq!(public_values.plaintext_hash, meta.plaintext_hash);
+
+    checkpoint.complete().unwrap();
+    assert!(!Checkpoint::is_interrupted(&dir));
+    assert!(!dir.exists());
+}
+
+#[test]
+fn incomplete_checkpoint_is_not_resumed() {
+    let dir = checkpoint_dir("incomplete");
+
+    // Killed while saving, before meta.json was written
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("stdin.bin"), b"partial").unwrap();
+    assert!(!Checkpoint::is_interrupted(&dir));
+    assert!(matches!(
+        Checkpoint::load(&dir),
+        Err(CheckpointError::NotFound(_))
+    ));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn completing_keeps_other_files_in_the_directory() {
+    // As with `--checkpoint-dir .`
+    let dir = checkpoint_dir("shared");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("input.txt"), b"not part of the checkpoint").unwrap();
+
+    let client = ProverClient::builder().mock().build();
+    let (pk, _) = client.setup(CHACHA_ELF);
+    let meta = CheckpointMeta {
+        nonce: [9; 12],
+        plaintext_hash: [0; 32],
+        commit_ciphertext_hash: false,
+        plaintext_size: 0,
+    };
+    let checkpoint = Checkpoint::save(&dir, &sp1_sdk::SP1Stdin::new(), &pk, &meta).unwrap();
+    checkpoint.complete().unwrap();
+
+    assert!(!Checkpoint::is_interrupted(&dir));
+    let left: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(left, ["input.txt"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn meta_without_the_plaintext_size_loads() {
+    // As saved before the plaintext size was recorded
+    let meta: CheckpointMeta = serde_json::from_str(&format!(
+        r#"{{"nonce":"{}","plaintext_hash":"{}","commit_ciphertext_hash":false}}"#,
+        hex::encode([9; 12]),
+        hex::encode([0; 32])
+    ))
+    .unwrap();
+    assert_eq!(meta.plaintext_size, 0);
+    assert_eq!(meta.nonce, [9; 12]);
+}
@@ -0,0 +1,93 @@
+//! Proving a stream in 5 chunks with `chunk_prove` and checking it with `chunk_verify`.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use chacha_lib::chacha;
+
+const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("chacha-chunk-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn check(output: &Output) -> String {
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    assert!(
+        output.status.success(),
+        "{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    stdout
+}
+
+fn verify(dir: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_chunk_verify"))
+        .arg("--dir")
+        .arg(dir)
+        .arg("--output")
+        .arg(dir.join("ciphertext.bin"))
+        .env("SP1_PROVER", "mock")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn five_chunks_prove_and_verify() {
+    let dir = temp_dir("five");
+    let plaintext: Vec<u8> = (0..4 * 64 + 10).map(|i| i as u8).collect();
+    let input = dir.join("plaintext.bin");
+    std::fs::write(&input, &plaintext).unwrap();
+
+    // 64 byte chunks, the last one shorter
+    let stdout = check(
+        &Command::new(env!("CARGO_BIN_EXE_chunk_prove"))
+            .arg("--input")
+            .arg(&input)
+            .arg("--output-dir")
+            .arg(&dir)
+            .args(["--chunk-size", "64"])
+            .env("SP1_PROVER", "mock")
+            .env("ENCRYPTION_KEY", KEY)
+            .output()
+            .unwrap(),
+    );
+    assert!(stdout.contains("Proved chunk 5/5"), "{stdout}");
+    let nonce: [u8; 12] = hex::decode(
+        stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("Nonce: "))
+            .unwrap(),
+    )
+    .unwrap()
+    .try_into()
+    .unwrap();
+
+    let stdout = check(&verify(&dir));
+    assert!(stdout.contains("from 5 chunks"), "{stdout}");
+    let mut expected = plaintext.clone();
+    let key: [u8; 32] = hex::decode(KEY).unwrap().try_into().unwrap();
+    chacha(&key, &nonce, &mut expected);
+    assert_eq!(std::fs::read(dir.join("ciphertext.bin")).unwrap(), expected);
+
+    // A missing last chunk is noticed from the committed count
+    let last = std::fs::read(dir.join("chunk_4.proof")).unwrap();
+    std::fs::remove_file(dir.join("chunk_4.proof")).unwrap();
+    let output = verify(&dir);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("chunk 4 is missing"), "{stderr}");
+
+    // As is an extra one
+    std::fs::write(dir.join("chunk_4.proof"), &last).unwrap();
+    std::fs::write(dir.join("chunk_5.proof"), &last).unwrap();
+    let output = verify(&dir);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("more chunk proofs than the 5"), "{stderr}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,50 @@
+//! `--commit-ciphertext-hash` commits `SHA256(ciphertext)` and says so in the public values.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::ProverClient;
+
+use chacha_lib::{chacha, CompressionType, PublicValues};
+use chacha_script::summary::ProofSummary;
+use chacha_script::upgrade::test_inputs;
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+#[test]
+fn committed_hash_is_the_sha256_of_the_ciphertext() {
+    let client = ProverClient::builder().mock().build();
+    let plaintext = b"ciphertext hash ciphertext hash ciphertext hash".repeat(4);
+
+    for compression in [CompressionType::None, CompressionType::Lz4] {
+        for plaintext in [&plaintext[..], &plaintext[..1], b""] {
+            let inputs = |commit_ciphertext_hash| EncryptInputs {
+                compression,
+                commit_ciphertext_hash,
+                plaintext,
+                ..test_inputs([1; 32], [2; 12])
+            };
+            // The program encrypts the compressed buffer
+            let mut ciphertext = inputs(false).compressed_plaintext();
+            chacha(&[1; 32], &[2; 12], &mut ciphertext);
+
+            let (output, _) = client
+                .execute(CHACHA_ELF, &inputs(true).to_stdin())
+                .run()
+                .unwrap();
+            let hashed = PublicValues::parse(output.as_slice()).unwrap();
+            assert_eq!(hashed.ciphertext_is_hash, Some(true));
+            assert_eq!(hashed.ciphertext, Sha256::digest(&ciphertext).as_slice());
+            let hashed_summary = ProofSummary::from_public_values(output.as_slice()).unwrap();
+
+            let (output, _) = client
+                .execute(CHACHA_ELF, &inputs(false).to_stdin())
+                .run()
+                .unwrap();
+            let full = PublicValues::parse(output.as_slice()).unwrap();
+            assert_eq!(full.ciphertext_is_hash, Some(false));
+            assert_eq!(full.ciphertext, ciphertext);
+
+            // Either way the summary shows the hash of the ciphertext
+            let full_summary = ProofSummary::from_public_values(output.as_slice()).unwrap();
+            assert_eq!(hashed_summary.ciphertext_hash, full_summary.ciphertext_hash);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! The reveal program, see `program/src/main_reveal.rs`.
+
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::commit_reveal::commit;
+use chacha_lib::{Key, Nonce};
+use chacha_script::REVEAL_ELF;
+
+#[test]
+fn reveals_the_committed_plaintext() {
+    let (key, nonce) = (Key([1; 32]), Nonce([2; 12]));
+    let commitment = commit(&key, &nonce, b"vote: yes");
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&key.0);
+    stdin.write_slice(&nonce.0);
+    stdin.write_slice(&commitment.ciphertext);
+
+    let client = ProverClient::builder().mock().build();
+    let (output, _) = client.execute(REVEAL_ELF, &stdin).run().unwrap();
+    let output = output.as_slice();
+    assert_eq!(output[..32], commitment.binding);
+    assert_eq!(output[32..], *b"vote: yes");
+}
@@ -0,0 +1,101 @@
+//! Concurrent use of one `ProverClient`, to catch shared state in the SDK that breaks under
+//! contention. Each thread encrypts a different input and checks its own output.
+
+use std::sync::Barrier;
+use std::thread;
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{CpuProver, Prover, ProverClient, SP1Stdin};
+
+use chacha_lib::{chacha, PublicValues};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+/// Inputs that differ per thread in key, nonce and plaintext.
+fn thread_inputs(i: usize) -> ([u8; 32], [u8; 12], Vec<u8>) {
+    let key = core::array::from_fn(|j| (i * 32 + j) as u8 ^ 0xa5);
+    let nonce = core::array::from_fn(|j| (i + j) as u8);
+    let plaintext = format!("plaintext of thread {i}")
+        .repeat(i + 1)
+        .into_bytes();
+    (key, nonce, plaintext)
+}
+
+fn stdin(key: [u8; 32], nonce: [u8; 12], plaintext: &[u8]) -> SP1Stdin {
+    EncryptInputs::new(key, nonce, plaintext).to_stdin()
+}
+
+/// Check the public values of thread `i` against its own inputs.
+fn check_output(i: usize, public_values: &[u8]) {
+    let (key, nonce, plaintext) = thread_inputs(i);
+    let public_values = PublicValues::parse(public_values).expect("public values too short");
+    assert_eq!(
+        public_values.plaintext_hash,
+        Sha256::digest(&plaintext).as_slice(),
+        "thread {i}"
+    );
+    let mut ciphertext = plaintext;
+    chacha(&key, &nonce, &mut ciphertext);
+    assert_eq!(public_values.ciphertext, ciphertext, "thread {i}");
+}
+
+#[test]
+fn concurrent_execute() {
+    const THREADS: usize = 4;
+    let client = ProverClient::builder().mock().build();
+    let barrier = Barrier::new(THREADS);
+
+    thread::scope(|s| {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|i| {
+                let (client, barrier) = (&client, &barrier);
+                s.spawn(move || {
+                    let (key, nonce, plaintext) = thread_inputs(i);
+                    let stdin = stdin(key, nonce, &plaintext);
+                    barrier.wait();
+                    let (output, _) = client.execute(CHACHA_ELF, &stdin).run().unwrap();
+                    check_output(i, output.as_slice());
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("thread panicked");
+        }
+    });
+}
+
+/// Prove `threads` inputs at once, verifying each proof and its public values.
+fn concurrent_prove(client: &CpuProver, threads: usize) {
+    let (pk, vk) = client.setup(CHACHA_ELF);
+    let barrier = Barrier::new(threads);
+
+    thread::scope(|s| {
+        let handles: Vec<_> = (0..threads)
+            .map(|i| {
+                let (pk, vk, barrier) = (&pk, &vk, &barrier);
+                s.spawn(move || {
+                    let (key, nonce, plaintext) = thread_inputs(i);
+                    let stdin = stdin(key, nonce, &plaintext);
+                    barrier.wait();
+                    let proof = client.prove(pk, &stdin).core().run().unwrap();
+                    client.verify(&proof, vk).unwrap();
+                    check_output(i, proof.public_values.as_slice());
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("thread panicked");
+        }
+    });
+}
+
+#[test]
+fn concurrent_mock_prove() {
+    concurrent_prove(&ProverClient::builder().mock().build(), 10);
+}
+
+/// Slow, run with `cargo test --release --test concurrent -- --ignored`.
+#[test]
+#[ignore]
+fn concurrent_cpu_prove() {
+    concurrent_prove(&ProverClient::builder().cpu().build(), 4);
+}
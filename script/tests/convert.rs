@@ -0,0 +1,57 @@
+//! Converting mock proofs between proof types.
+
+use sp1_sdk::{Prover, ProverClient};
+
+use chacha_lib::ProofType;
+use chacha_script::convert::{convert, proof_type, ConvertError};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+#[test]
+fn converts_to_every_later_type() {
+    let client = ProverClient::builder().mock().build();
+    let (pk, vk) = client.setup(CHACHA_ELF);
+    let stdin = EncryptInputs::new([3; 32], [5; 12], b"converted plaintext").to_stdin();
+    let core = client.prove(&pk, &stdin).core().run().unwrap();
+
+    for from in [ProofType::Core, ProofType::Compressed] {
+        let proof = convert(client.inner(), &pk, core.clone(), from).unwrap();
+        for to in [
+            ProofType::Core,
+            ProofType::Compressed,
+            ProofType::Groth16,
+            ProofType::Plonk,
+        ] {
+            let converted = convert(client.inner(), &pk, proof.clone(), to);
+            if to == ProofType::Core && from == ProofType::Compressed {
+                assert!(matches!(converted, Err(ConvertError::NeedsInputs { .. })));
+                continue;
+            }
+            let converted = converted.unwrap();
+            assert_eq!(proof_type(&converted.proof), to, "{from} to {to}");
+            assert_eq!(
+                converted.public_values.as_slice(),
+                core.public_values.as_slice()
+            );
+            client.verify(&converted, &vk).unwrap();
+        }
+    }
+}
+
+#[test]
+fn wrapped_proofs_need_the_inputs() {
+    let client = ProverClient::builder().mock().build();
+    let (pk, _) = client.setup(CHACHA_ELF);
+    let stdin = EncryptInputs::new([3; 32], [5; 12], b"converted plaintext").to_stdin();
+    let groth16 = client.prove(&pk, &stdin).groth16().run().unwrap();
+
+    for to in [ProofType::Core, ProofType::Compressed, ProofType::Plonk] {
+        let error = convert(client.inner(), &pk, groth16.clone(), to).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "a groth16 proof cannot be converted to {to} without the original inputs, \
+                 prove them again with --proof-type {to}"
+            )
+        );
+    }
+}
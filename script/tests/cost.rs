@@ -0,0 +1,76 @@
+//! Proving cost estimates against measured cycle counts and proving times.
+
+use std::time::Instant;
+
+use sp1_sdk::ProverClient;
+
+use chacha_lib::ProofType;
+use chacha_script::cost::{
+    estimate_proving_cost_usd, estimate_proving_minutes, instance_price_per_hour,
+};
+use chacha_script::estimate::estimate_cycles;
+use chacha_script::{run_proof, EncryptInputs, CHACHA_ELF};
+
+fn inputs(plaintext: &[u8]) -> EncryptInputs<'_> {
+    EncryptInputs::new(core::array::from_fn(|i| i as u8 ^ 0x5c), [0; 12], plaintext)
+}
+
+/// Whether `estimate` is at most twice or half of `actual`.
+fn within_2x(estimate: f64, actual: f64) -> bool {
+    (actual / 2.0..=actual * 2.0).contains(&estimate)
+}
+
+#[test]
+fn cost_scales_with_the_hourly_price() {
+    let l4 = instance_price_per_hour("L4").unwrap();
+    let a100 = instance_price_per_hour("a100").unwrap();
+    assert!(instance_price_per_hour("tpu").is_none());
+
+    let minutes = estimate_proving_minutes(64 * 1024, ProofType::Groth16);
+    let cost = estimate_proving_cost_usd(64 * 1024, ProofType::Groth16, l4);
+    assert!((cost - minutes / 60.0 * l4).abs() < 1e-9);
+    assert!(estimate_proving_cost_usd(64 * 1024, ProofType::Groth16, a100) > cost);
+    assert!(
+        estimate_proving_minutes(64 * 1024, ProofType::Plonk)
+            > estimate_proving_minutes(64 * 1024, ProofType::Core)
+    );
+}
+
+#[test]
+fn cycle_estimate_is_within_2x_of_executed_cycles() {
+    let client = ProverClient::builder().mock().build();
+    for size in [1024, 16 * 1024, 64 * 1024] {
+        let plaintext: Vec<u8> = (0..size).map(|i| i as u8).collect();
+        let (_, report) = client
+            .execute(CHACHA_ELF, &inputs(&plaintext).to_stdin())
+            .run()
+            .unwrap();
+        let (estimate, actual) = (estimate_cycles(size), report.total_instruction_count());
+        assert!(
+            within_2x(estimate as f64, actual as f64),
+            "{size} bytes: estimated {estimate} cycles, executed {actual}"
+        );
+    }
+}
+
+/// The proving time of the prover configured by `SP1_PROVER`, on a GPU to match
+/// `CYCLES_PER_MINUTE`.
+#[test]
+#[ignore = "proves on the configured prover, run with `SP1_PROVER=cuda cargo test -- --ignored`"]
+fn proving_time_estimate_is_within_2x_of_measured() {
+    let size = 64 * 1024;
+    let plaintext: Vec<u8> = (0..size).map(|i| i as u8).collect();
+    let client = ProverClient::from_env();
+    let (pk, _) = client.setup(CHACHA_ELF);
+    let stdin = inputs(&plaintext).to_stdin();
+    for proof_type in [ProofType::Core, ProofType::Groth16] {
+        let start = Instant::now();
+        run_proof(&client, &pk, &stdin, proof_type).unwrap();
+        let measured = start.elapsed().as_secs_f64() / 60.0;
+        let estimate = estimate_proving_minutes(size, proof_type);
+        assert!(
+            within_2x(estimate, measured),
+            "{proof_type}: estimated {estimate:.2} minutes, measured {measured:.2}"
+        );
+    }
+}
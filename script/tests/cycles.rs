@@ -0,0 +1,59 @@
+//! Linearity and regressions of the cycle count benchmark.
+
+use chacha_script::cycles::{plot, r_squared, regressions, CycleCounts, CycleRegression};
+use chacha_script::estimate::estimate_cycles;
+
+fn counts(points: &[(usize, u64)]) -> CycleCounts {
+    points.iter().copied().collect()
+}
+
+#[test]
+fn linear_counts_fit() {
+    let linear: CycleCounts = [1, 64, 1024, 4096, 65536]
+        .into_iter()
+        .map(|size| (size, estimate_cycles(size)))
+        .collect();
+    assert!((r_squared(&linear) - 1.0).abs() < 1e-12);
+
+    // A quadratic cost does not
+    let quadratic: CycleCounts = (1..=7).map(|i| (i, (i * i * i * i) as u64)).collect();
+    assert!(r_squared(&quadratic) < 0.99);
+
+    assert_eq!(r_squared(&counts(&[(1, 5), (2, 5)])), 1.0);
+}
+
+#[test]
+fn increases_above_the_threshold_regress() {
+    let baseline = counts(&[(1, 1000), (64, 2000), (1024, 40_000)]);
+    let current = counts(&[(1, 1050), (64, 2101), (1024, 30_000), (4096, 200_000)]);
+    assert_eq!(
+        regressions(&baseline, &current, 5.0),
+        [CycleRegression {
+            size: 64,
+            baseline: 2000,
+            current: 2101,
+        }]
+    );
+    assert!((regressions(&baseline, &current, 5.0)[0].percent() - 5.05).abs() < 1e-9);
+    assert!(regressions(&baseline, &baseline, 5.0).is_empty());
+}
+
+#[test]
+fn baseline_roundtrips_through_json() {
+    let baseline = counts(&[(1, 1000), (262144, 13_000_000)]);
+    let json = serde_json::to_string(&baseline).unwrap();
+    assert_eq!(json, r#"{"1":1000,"262144":13000000}"#);
+    assert_eq!(
+        serde_json::from_str::<CycleCounts>(&json).unwrap(),
+        baseline
+    );
+}
+
+#[test]
+fn plots_a_bar_per_size() {
+    let plot = plot(&counts(&[(1, 10), (64, 20)]), 4);
+    assert_eq!(
+        plot,
+        "       1 B           10 ##\n      64 B           20 ####\n"
+    );
+}
@@ -0,0 +1,32 @@
+//! `--deterministic-nonce` replaces the other ways of choosing a nonce.
+
+use clap::Parser;
+
+use chacha_script::pipeline::PipelineArgs;
+
+#[derive(Parser)]
+struct Cli {
+    #[clap(flatten)]
+    pipeline: PipelineArgs,
+}
+
+fn parse(args: &[&str]) -> Result<PipelineArgs, clap::Error> {
+    Cli::try_parse_from(["chacha"].iter().chain(args)).map(|cli| cli.pipeline)
+}
+
+#[test]
+fn deterministic_nonce_conflicts_with_fixed_nonces() {
+    assert!(
+        parse(&["--deterministic-nonce"])
+            .unwrap()
+            .deterministic_nonce
+    );
+    assert!(!parse(&[]).unwrap().deterministic_nonce);
+    assert!(parse(&[
+        "--deterministic-nonce",
+        "--nonce",
+        "000102030405060708090a0b"
+    ])
+    .is_err());
+    assert!(parse(&["--deterministic-nonce", "--nonce-from-env"]).is_err());
+}
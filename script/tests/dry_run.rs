@@ -0,0 +1,128 @@
+//! `--dry-run` reports on valid and invalid inputs.
+
+use std::path::PathBuf;
+
+use sp1_sdk::ProverClient;
+
+use chacha_lib::{CompressionType, ProofType};
+use chacha_script::dry_run::{
+    check_inputs, format_errors, DryRunInputs, DryRunSummary, OK_MESSAGE,
+};
+use chacha_script::{validate_prove_inputs, EncryptInputs, ValidationError, CHACHA_ELF};
+
+const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+fn input_file(contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("chacha-dry-run-{}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn valid_inputs_are_ready_to_prove() {
+    let path = input_file(b"dry run plaintext");
+    let valid = check_inputs(&DryRunInputs {
+        encryption_key: Some(KEY),
+        input: Some(&path),
+        disclose: &[(0, 3)],
+        ..Default::default()
+    })
+    .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(valid.plaintext, b"dry run plaintext");
+
+    let stdin = EncryptInputs {
+        commit_segments: true,
+        ..EncryptInputs::new(valid.key.unwrap(), [0; 12], &valid.plaintext)
+    }
+    .to_stdin();
+    let client = ProverClient::builder().mock().build();
+    let (_, report) = client.execute(CHACHA_ELF, &stdin).run().unwrap();
+
+    let summary = DryRunSummary::new(
+        valid.plaintext.len(),
+        CompressionType::None,
+        ProofType::Groth16,
+        report.total_instruction_count(),
+    );
+    let output = summary.to_string();
+    assert!(output.contains("Input:       17 bytes"), "{output}");
+    assert!(
+        output.contains(&format!(
+            "Cycles:      {}",
+            report.total_instruction_count()
+        )),
+        "{output}"
+    );
+    assert!(output.ends_with(OK_MESSAGE), "{output}");
+}
+
+#[test]
+fn every_invalid_input_is_reported() {
+    let errors = check_inputs(&DryRunInputs {
+        encryption_key: Some("not hex"),
+        input: Some("does/not/exist".as_ref()),
+        timestamp_key: Some("abcd"),
+        ..Default::default()
+    })
+    .unwrap_err();
+    assert_eq!(
+        format_errors(&errors),
+        "[ERROR] ENCRYPTION_KEY must be hex\n\
+         [ERROR] Input file does/not/exist does not exist\n\
+         [ERROR] --timestamp-key must be 32 hex encoded bytes"
+    );
+
+    let errors = check_inputs(&DryRunInputs {
+        encryption_key: Some(&"00".repeat(32)),
+        disclose: &[(0, 1), (0, usize::MAX)],
+        ..Default::default()
+    })
+    .unwrap_err();
+    assert_eq!(
+        format_errors(&errors),
+        format!(
+            "[ERROR] --disclose 0..{} is outside the {} byte input\n\
+             [ERROR] Weak key: all bytes are identical",
+            usize::MAX,
+            chacha_lib::INPUT_BYTES.len()
+        )
+    );
+
+    let errors = check_inputs(&DryRunInputs::default()).unwrap_err();
+    assert_eq!(
+        format_errors(&errors),
+        "[ERROR] ENCRYPTION_KEY is not set, set it or use --ephemeral-key"
+    );
+}
+
+#[test]
+fn validation_only_rejects_repeated_byte_keys() {
+    let plaintext = b"plaintext";
+    // The RFC 8439 test vector key, 00 01 .. 1f
+    let rfc_8439: Vec<u8> = (0..32).collect();
+    assert_eq!(
+        validate_prove_inputs(&rfc_8439, &[0; 12], plaintext),
+        Ok(())
+    );
+    let descending: Vec<u8> = (0..32).rev().collect();
+    assert_eq!(
+        validate_prove_inputs(&descending, &[0; 12], plaintext),
+        Ok(())
+    );
+
+    for byte in [0, 0x5a, 0xff] {
+        assert_eq!(
+            validate_prove_inputs(&[byte; 32], &[0; 12], plaintext),
+            Err(ValidationError::WeakKey("all bytes are identical"))
+        );
+    }
+    assert_eq!(
+        validate_prove_inputs(&rfc_8439[..31], &[0; 12], plaintext),
+        Err(ValidationError::KeyLength(31))
+    );
+    assert_eq!(
+        validate_prove_inputs(&rfc_8439, &[0; 8], plaintext),
+        Err(ValidationError::NonceLength(8))
+    );
+}
@@ -0,0 +1,66 @@
+//! The encryption program on empty and single byte plaintexts.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::ProverClient;
+
+use chacha_lib::public_values::PublicValues;
+use chacha_lib::{chacha, CompressionType};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+/// SHA256 of the empty string.
+const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+fn inputs(plaintext: &[u8]) -> EncryptInputs<'_> {
+    EncryptInputs::new([7; 32], [8; 12], plaintext)
+}
+
+fn execute(inputs: EncryptInputs) -> Vec<u8> {
+    let client = ProverClient::builder().mock().build();
+    let (output, _) = client
+        .execute(CHACHA_ELF, &inputs.to_stdin())
+        .run()
+        .expect("execution failed");
+    output.as_slice().to_vec()
+}
+
+#[test]
+fn empty_plaintext() {
+    let output = execute(inputs(&[]));
+    let public_values = PublicValues::parse(&output).unwrap();
+    assert_eq!(hex::encode(public_values.plaintext_hash), EMPTY_SHA256);
+    assert!(public_values.ciphertext.is_empty());
+
+    for inputs in [
+        EncryptInputs {
+            compression: CompressionType::Lz4,
+            ..inputs(&[])
+        },
+        EncryptInputs {
+            commit_segments: true,
+            ..inputs(&[])
+        },
+    ] {
+        let output = execute(inputs);
+        let public_values = PublicValues::parse(&output).unwrap();
+        assert_eq!(hex::encode(public_values.plaintext_hash), EMPTY_SHA256);
+    }
+
+    let output = execute(EncryptInputs {
+        commit_ciphertext_hash: true,
+        ..inputs(&[])
+    });
+    let public_values = PublicValues::parse(&output).unwrap();
+    assert_eq!(hex::encode(public_values.ciphertext), EMPTY_SHA256);
+}
+
+#[test]
+fn single_byte_plaintexts() {
+    for byte in [0x00, 0xFF] {
+        let output = execute(inputs(&[byte]));
+        let public_values = PublicValues::parse(&output).unwrap();
+        assert_eq!(public_values.plaintext_hash, Sha256::digest([byte])[..]);
+        let mut ciphertext = [byte];
+        chacha(&[7; 32], &[8; 12], &mut ciphertext);
+        assert_eq!(public_values.ciphertext, ciphertext);
+    }
+}
@@ -0,0 +1,48 @@
+//! `--ephemeral-key` encrypts under a new key on every run.
+
+use std::process::Command;
+
+/// The `zkVM -> <field>: 0x<hex>` line of an `--ephemeral-key --execute` run.
+fn committed(stdout: &str, field: &str) -> String {
+    let prefix = format!("zkVM -> {field}: 0x");
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .unwrap_or_else(|| panic!("no {field} in:\n{stdout}"))
+        .split_whitespace()
+        .next()
+        .unwrap()
+        .to_string()
+}
+
+/// Encrypt the same input under the same nonce, returning the committed key and ciphertext
+/// hashes.
+fn run() -> (String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_chacha"))
+        .args(["--execute", "--ephemeral-key"])
+        .args(["--nonce", "000000000000004a00000000"])
+        .env("SP1_PROVER", "mock")
+        .env_remove("ENCRYPTION_KEY")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        output.status.success(),
+        "{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("NOT logged or saved"), "{stderr}");
+    (
+        committed(&stdout, "key hash"),
+        committed(&stdout, "ciphertext hash"),
+    )
+}
+
+#[test]
+fn every_run_gives_a_different_ciphertext() {
+    let (first_key, first_ciphertext) = run();
+    let (second_key, second_ciphertext) = run();
+    assert_ne!(first_key, second_key);
+    assert_ne!(first_ciphertext, second_ciphertext);
+}
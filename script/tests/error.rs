@@ -0,0 +1,141 @@
+//! The errors of the script binaries and of turning proofs into EVM outputs.
+
+use std::io;
+
+use anyhow::Context;
+use sp1_prover::Groth16Bn254Proof;
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues, SP1PublicValues};
+
+use chacha_lib::public_values::HEADER_LEN;
+use chacha_lib::{ProofType, PROGRAM_VERSION};
+use chacha_script::error::ScriptError;
+use chacha_script::parse_public_values;
+use chacha_script::post::{proof_bytes, upgrade_calldata, verify_calldata};
+use chacha_script::storage::{load_proof, ProofFile};
+use chacha_script::summary::ProofSummary;
+
+fn groth16_proof(encoded_proof: &str) -> SP1ProofWithPublicValues {
+    SP1ProofWithPublicValues {
+        proof: SP1Proof::Groth16(Groth16Bn254Proof {
+            public_inputs: [String::new(), String::new()],
+            encoded_proof: encoded_proof.to_string(),
+            raw_proof: String::new(),
+            groth16_vkey_hash: [7; 32],
+        }),
+        public_values: SP1PublicValues::from(b"public values"),
+        sp1_version: String::new(),
+    }
+}
+
+#[test]
+fn proof_bytes_of_groth16_proofs() {
+    let proof = groth16_proof("0102");
+    assert_eq!(proof_bytes(&proof).unwrap(), [7, 7, 7, 7, 1, 2]);
+    // Mock proofs have no bytes
+    assert_eq!(proof_bytes(&groth16_proof("")).unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn abi_encode() {
+    let proof = groth16_proof("not hex");
+    assert!(matches!(
+        proof_bytes(&proof),
+        Err(ScriptError::AbiEncode(_))
+    ));
+    assert!(matches!(
+        verify_calldata(&proof, None),
+        Err(ScriptError::AbiEncode(_))
+    ));
+    assert!(matches!(
+        upgrade_calldata(&proof),
+        Err(ScriptError::AbiEncode(_))
+    ));
+}
+
+#[test]
+fn invalid_proof_type() {
+    let proof = SP1ProofWithPublicValues {
+        proof: SP1Proof::Core(Vec::new()),
+        ..groth16_proof("")
+    };
+    let e = verify_calldata(&proof, None).unwrap_err();
+    assert!(matches!(e, ScriptError::InvalidProofType(ProofType::Core)));
+    assert_eq!(
+        e.to_string(),
+        "a core proof cannot be verified on-chain, convert it to groth16"
+    );
+}
+
+#[test]
+fn invalid_public_values_length() {
+    let mut public_values = vec![0; 100];
+    public_values[0] = PROGRAM_VERSION;
+    let e = parse_public_values(&public_values).unwrap_err();
+    assert!(matches!(
+        e,
+        ScriptError::InvalidPublicValuesLength {
+            got: 100,
+            expected: HEADER_LEN
+        }
+    ));
+    assert_eq!(
+        e.to_string(),
+        format!("the public values are 100 bytes, expected at least {HEADER_LEN}")
+    );
+    assert!(matches!(
+        ProofSummary::from_public_values(&public_values),
+        Err(ScriptError::InvalidPublicValuesLength { got: 100, .. })
+    ));
+}
+
+#[test]
+fn verification_failed() {
+    let e = ScriptError::VerificationFailed("invalid proof".to_string());
+    assert_eq!(e.to_string(), "failed to verify proof: invalid proof");
+}
+
+#[test]
+fn proof_file_not_found() {
+    let path = std::env::temp_dir().join("sp1-chacha-error-test-missing.bin");
+    let e = load_proof(&path).unwrap_err();
+    assert!(matches!(&e, ScriptError::ProofFileNotFound(p) if *p == path));
+    assert_eq!(e.to_string(), format!("no proof at {}", path.display()));
+}
+
+#[test]
+fn io() {
+    let path = std::env::temp_dir().join("sp1-chacha-error-test-garbage.bin");
+    std::fs::write(&path, b"not a proof").unwrap();
+    let e = load_proof(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(&e, ScriptError::Io(e) if e.kind() == io::ErrorKind::InvalidData));
+
+    let e = ScriptError::from(io::Error::other("disk full"));
+    assert_eq!(e.to_string(), "disk full");
+}
+
+#[test]
+fn other() {
+    let e: ScriptError = std::fs::read(std::env::temp_dir().join("sp1-chacha-error-test-none"))
+        .context("Failed to read input file")
+        .unwrap_err()
+        .into();
+    assert!(matches!(e, ScriptError::Other(_)));
+    // The context and its cause, also what `main` prints as it is the `Debug` output
+    assert!(
+        e.to_string().starts_with("Failed to read input file: "),
+        "{e}"
+    );
+    assert_eq!(format!("{e:?}"), e.to_string());
+}
+
+#[test]
+fn saved_proofs_load() {
+    let proof = groth16_proof("0102");
+    let path = std::env::temp_dir().join("sp1-chacha-error-test-proof.bin");
+    ProofFile::from_proof(&proof).save(&path).unwrap();
+    let loaded = load_proof(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.public_values.as_slice(), b"public values");
+    assert_eq!(proof_bytes(&loaded).unwrap(), [7, 7, 7, 7, 1, 2]);
+}
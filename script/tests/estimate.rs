@@ -0,0 +1,57 @@
+//! `--check-gpu-memory` against mocked NVML queries.
+
+use nvml_wrapper::error::NvmlError;
+
+use chacha_script::estimate::{
+    check_available_gpu_memory, estimate_gpu_memory_mb, GpuMemoryError, GpuMemoryQuery,
+    InsufficientGpuMemory, RECOMMENDED_GPU_MEMORY_MB,
+};
+
+/// A GPU with this much free memory, or no NVML driver.
+struct MockGpu(Option<u64>);
+
+impl GpuMemoryQuery for MockGpu {
+    fn free_memory_mb(&self) -> Result<u64, NvmlError> {
+        self.0.ok_or(NvmlError::DriverNotLoaded)
+    }
+}
+
+const INPUT_SIZE: usize = 1536 * 1024;
+
+#[test]
+fn enough_memory_passes() {
+    let required_mb = estimate_gpu_memory_mb(INPUT_SIZE);
+    assert_eq!(
+        check_available_gpu_memory(&MockGpu(Some(required_mb)), INPUT_SIZE).unwrap(),
+        required_mb
+    );
+    assert_eq!(
+        check_available_gpu_memory(&MockGpu(Some(RECOMMENDED_GPU_MEMORY_MB)), INPUT_SIZE).unwrap(),
+        RECOMMENDED_GPU_MEMORY_MB
+    );
+}
+
+#[test]
+fn too_little_memory_recommends_a_larger_gpu() {
+    let required_mb = estimate_gpu_memory_mb(INPUT_SIZE);
+    let error =
+        check_available_gpu_memory(&MockGpu(Some(required_mb - 1)), INPUT_SIZE).unwrap_err();
+    assert!(
+        matches!(
+            error,
+            GpuMemoryError::Insufficient(InsufficientGpuMemory { available_mb, .. })
+                if available_mb == required_mb - 1
+        ),
+        "{error}"
+    );
+    assert!(error.to_string().contains("at least 20 GB"), "{error}");
+}
+
+#[test]
+fn failed_query_is_an_error() {
+    let error = check_available_gpu_memory(&MockGpu(None), INPUT_SIZE).unwrap_err();
+    assert!(
+        matches!(error, GpuMemoryError::Query(NvmlError::DriverNotLoaded)),
+        "{error}"
+    );
+}
@@ -0,0 +1,58 @@
+//! `--nonce-from-env` encrypts under the nonce of `CHACHA_NONCE_HEX`.
+//!
+//! Its own test binary, as it sets a variable of the environment every test thread shares.
+
+use clap::Parser;
+use sp1_sdk::ProverClient;
+
+use chacha_lib::{chacha, compute_nullifier, Key, Nonce, PublicValues};
+use chacha_script::pipeline::{fixed_nonce, PipelineArgs, FIXED_NONCE_VAR};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+#[derive(Parser)]
+struct Cli {
+    #[clap(flatten)]
+    pipeline: PipelineArgs,
+}
+
+fn parse(args: &[&str]) -> Result<PipelineArgs, clap::Error> {
+    Cli::try_parse_from(["chacha"].iter().chain(args)).map(|cli| cli.pipeline)
+}
+
+#[test]
+fn nonce_from_env_conflicts_with_nonce() {
+    assert!(parse(&["--nonce-from-env"]).unwrap().nonce_from_env);
+    assert!(!parse(&[]).unwrap().nonce_from_env);
+    assert!(parse(&["--nonce-from-env", "--nonce", "000102030405060708090a0b"]).is_err());
+}
+
+#[test]
+fn fixed_nonce_is_committed() {
+    let nonce = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+    // SAFETY: the other test of this binary does not read the variable
+    unsafe { std::env::set_var(FIXED_NONCE_VAR, "000102030405060708090a0b") };
+    assert_eq!(fixed_nonce(), Ok(nonce));
+
+    let key = [1; 32];
+    let stdin =
+        EncryptInputs::new(key, fixed_nonce().unwrap(), b"reproducible plaintext").to_stdin();
+    let client = ProverClient::builder().mock().build();
+    let (output, _) = client.execute(CHACHA_ELF, &stdin).run().unwrap();
+    let public_values = PublicValues::parse(output.as_slice()).unwrap();
+    assert_eq!(
+        public_values.nullifier,
+        compute_nullifier(&Key(key), &Nonce(nonce))
+    );
+    let mut ciphertext = *b"reproducible plaintext";
+    chacha(&key, &nonce, &mut ciphertext);
+    assert_eq!(public_values.ciphertext, ciphertext);
+
+    // SAFETY: as above
+    unsafe { std::env::set_var(FIXED_NONCE_VAR, "too short") };
+    assert!(fixed_nonce().unwrap_err().starts_with(FIXED_NONCE_VAR));
+    unsafe { std::env::remove_var(FIXED_NONCE_VAR) };
+    assert_eq!(
+        fixed_nonce(),
+        Err("--nonce-from-env needs CHACHA_NONCE_HEX to be set".to_string())
+    );
+}
@@ -0,0 +1,93 @@
+//! Folded stacks of an execution report for `inferno-flamegraph`.
+
+use sp1_sdk::ExecutionReport;
+
+use chacha_script::flamegraph::{export_flamegraph, flamegraph_stacks, opcode_category};
+
+/// A report of these opcode and syscall counts.
+fn report(opcodes: &[(&str, u64)], syscalls: &[(&str, u64)]) -> ExecutionReport {
+    let mut report = ExecutionReport::default();
+    for &(name, count) in opcodes {
+        let (_, value) = report
+            .opcode_counts
+            .iter_mut()
+            .find(|(opcode, _)| opcode.to_string() == name)
+            .unwrap();
+        *value = count;
+    }
+    for &(name, count) in syscalls {
+        let (_, value) = report
+            .syscall_counts
+            .iter_mut()
+            .find(|(syscall, _)| syscall.to_string() == name)
+            .unwrap();
+        *value = count;
+    }
+    report
+}
+
+/// Whether `line` is `frame;frame;... count` with frames inferno can split.
+fn is_folded(line: &str) -> bool {
+    let Some((stack, count)) = line.rsplit_once(' ') else {
+        return false;
+    };
+    count.parse::<u64>().is_ok()
+        && stack
+            .split(';')
+            .all(|frame| !frame.is_empty() && !frame.contains(char::is_whitespace))
+}
+
+#[test]
+fn stacks_are_folded_by_category() {
+    let report = report(
+        &[
+            ("add", 500),
+            ("xor", 300),
+            ("lw", 80),
+            ("bne", 40),
+            ("ecall", 12),
+        ],
+        &[("SHA_EXTEND", 4), ("SHA_COMPRESS", 4), ("WRITE", 3)],
+    );
+    let stacks = flamegraph_stacks(&report);
+    assert_eq!(
+        stacks,
+        "alu;add 500\n\
+         alu;xor 300\n\
+         branch;bne 40\n\
+         load;lw 80\n\
+         system;ecall 1\n\
+         system;ecall;sha_compress 4\n\
+         system;ecall;sha_extend 4\n\
+         system;ecall;write 3\n"
+    );
+
+    assert!(stacks.lines().all(is_folded), "{stacks}");
+    let total: u64 = stacks
+        .lines()
+        .map(|line| line.rsplit_once(' ').unwrap().1.parse::<u64>().unwrap())
+        .sum();
+    assert_eq!(total, report.total_instruction_count());
+}
+
+#[test]
+fn empty_report_has_no_stacks() {
+    assert_eq!(flamegraph_stacks(&ExecutionReport::default()), "");
+}
+
+#[test]
+fn every_rv32im_opcode_has_a_category() {
+    for (opcode, _) in ExecutionReport::default().opcode_counts.iter() {
+        let name = opcode.to_string();
+        assert_ne!(opcode_category(&name), "other", "{name}");
+    }
+}
+
+#[test]
+fn exports_to_a_file() {
+    let report = report(&[("add", 7)], &[]);
+    let path = std::env::temp_dir().join("sp1-chacha-flamegraph-test.stacks");
+    export_flamegraph(&report, &path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "alu;add 7\n");
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,54 @@
+//! The format-preserving encryption program, see `program/src/main_fpe.rs`.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::fpe::{chacha_fpe_decrypt, chacha_fpe_encrypt};
+use chacha_lib::{Key, Nonce};
+use chacha_script::FPE_ELF;
+
+const DIGITS: &str = "0123456789";
+
+fn stdin(plaintext: &str, alphabet: &str) -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&[1; 32]);
+    stdin.write_slice(&[2; 12]);
+    stdin.write_slice(plaintext.as_bytes());
+    stdin.write_slice(alphabet.as_bytes());
+    stdin
+}
+
+#[test]
+fn proves_the_encryption() {
+    let plaintext = "4111-1111-1111-1111";
+    let client = ProverClient::builder().mock().build();
+    let (output, report) = client
+        .execute(FPE_ELF, &stdin(plaintext, DIGITS))
+        .run()
+        .unwrap();
+    println!("FPE: {} cycles", report.cycle_tracker["fpe"]);
+
+    let output = output.as_slice();
+    assert_eq!(output[..32], Sha256::digest([1; 32])[..]);
+    assert_eq!(output[32..64], Sha256::digest(DIGITS)[..]);
+    assert_eq!(output[64..96], Sha256::digest(plaintext)[..]);
+    let ciphertext = std::str::from_utf8(&output[96..]).unwrap();
+    let (key, nonce) = (Key([1; 32]), Nonce([2; 12]));
+    assert_eq!(
+        ciphertext,
+        chacha_fpe_encrypt(&key, &nonce, plaintext, DIGITS)
+    );
+    assert_eq!(
+        chacha_fpe_decrypt(&key, &nonce, ciphertext, DIGITS),
+        plaintext
+    );
+}
+
+#[test]
+fn rejects_an_invalid_alphabet() {
+    let client = ProverClient::builder().mock().build();
+    assert!(client
+        .execute(FPE_ELF, &stdin("1234", "0123456789012"))
+        .run()
+        .is_err());
+}
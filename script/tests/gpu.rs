@@ -0,0 +1,124 @@
+//! Splitting a batch across GPUs, with mocked GPU detection.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use chacha_script::gpu::{
+    assign_proofs_to_gpus, cuda_env, moongate_server, prove_on_gpus, GpuInfo, ProveTask,
+};
+
+fn gpu(index: u32, free_memory_mb: u64) -> GpuInfo {
+    GpuInfo {
+        index,
+        name: format!("Mock GPU {index}"),
+        total_memory_mb: 24 * 1024,
+        free_memory_mb,
+    }
+}
+
+fn task(name: &str, size_bytes: usize) -> ProveTask {
+    ProveTask {
+        input: PathBuf::from(name),
+        size_bytes,
+    }
+}
+
+#[test]
+fn equal_tasks_are_spread_evenly() {
+    let tasks: Vec<_> = (0..8).map(|i| task(&format!("{i}.bin"), 1024)).collect();
+    let gpus = [gpu(0, 24 * 1024), gpu(1, 24 * 1024), gpu(2, 24 * 1024)];
+    let assignments = assign_proofs_to_gpus(&tasks, &gpus);
+    let counts: Vec<_> = assignments.iter().map(|a| a.tasks.len()).collect();
+    assert_eq!(counts, [3, 3, 2]);
+    let mut all: Vec<_> = assignments.iter().flat_map(|a| a.tasks.clone()).collect();
+    all.sort();
+    assert_eq!(all, (0..8).collect::<Vec<_>>());
+}
+
+#[test]
+fn large_tasks_are_balanced_against_small_ones() {
+    let tasks = [
+        task("small-1", 1024),
+        task("large", 1024 * 1024),
+        task("small-2", 1024),
+        task("small-3", 1024),
+    ];
+    let gpus = [gpu(0, 24 * 1024), gpu(1, 24 * 1024)];
+    let assignments = assign_proofs_to_gpus(&tasks, &gpus);
+    assert_eq!(assignments[0].tasks, [1]);
+    assert_eq!(assignments[1].tasks, [0, 2, 3]);
+}
+
+#[test]
+fn tasks_only_go_to_gpus_with_enough_memory() {
+    // ~14 GB for 1 MB, see estimate_gpu_memory_mb
+    let tasks = [task("a", 1024 * 1024), task("b", 1024 * 1024)];
+    let gpus = [gpu(0, 13 * 1024), gpu(1, 16 * 1024)];
+    let assignments = assign_proofs_to_gpus(&tasks, &gpus);
+    assert!(assignments[0].tasks.is_empty());
+    assert_eq!(assignments[1].tasks, [0, 1]);
+
+    // Too large for both, so given to the one most likely to manage
+    let tasks = [task("huge", 64 * 1024 * 1024)];
+    let assignments = assign_proofs_to_gpus(&tasks, &gpus);
+    assert_eq!(assignments[1].tasks, [0]);
+    assert!(assign_proofs_to_gpus(&tasks, &[]).is_empty());
+}
+
+#[test]
+fn each_gpu_proves_its_tasks_on_its_own_thread() {
+    let tasks: Vec<_> = (0..6).map(|i| task(&format!("{i}.bin"), 1024)).collect();
+    let gpus = [gpu(0, 24 * 1024), gpu(1, 24 * 1024), gpu(2, 24 * 1024)];
+    let assignments = assign_proofs_to_gpus(&tasks, &gpus);
+
+    let start = Instant::now();
+    let results = prove_on_gpus(&tasks, &assignments, |gpu, task| {
+        std::thread::sleep(Duration::from_millis(200));
+        if task.input.ends_with("4.bin") {
+            return Err("out of memory".to_string());
+        }
+        let thread = std::thread::current().name().unwrap().to_string();
+        Ok((gpu.index, thread))
+    });
+    // 2 tasks per GPU in parallel, rather than 6 in turn
+    assert!(start.elapsed() < Duration::from_millis(1000));
+
+    for (i, result) in results.into_iter().enumerate() {
+        let gpu = assignments
+            .iter()
+            .find(|a| a.tasks.contains(&i))
+            .unwrap()
+            .gpu
+            .index;
+        match result.unwrap() {
+            Ok(proved) => assert_eq!(proved, (gpu, format!("gpu-{gpu}"))),
+            Err(e) => {
+                assert_eq!(i, 4);
+                assert_eq!(e, "out of memory");
+            }
+        }
+    }
+}
+
+#[test]
+fn moongate_servers_see_one_gpu() {
+    let gpu = gpu(2, 24 * 1024);
+    assert_eq!(
+        cuda_env(&gpu),
+        [
+            ("CUDA_DEVICE_ORDER", "PCI_BUS_ID".to_string()),
+            ("CUDA_VISIBLE_DEVICES", "2".to_string())
+        ]
+    );
+    let command = moongate_server(&gpu, 3002);
+    let args: Vec<_> = command
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect();
+    assert_eq!(command.get_program(), "docker");
+    assert!(args
+        .windows(2)
+        .any(|w| w == ["-e", "CUDA_VISIBLE_DEVICES=2"]));
+    assert!(args.windows(2).any(|w| w == ["-p", "3002:3000"]));
+    assert!(args.windows(2).any(|w| w == ["--name", "sp1-gpu-2"]));
+}
@@ -0,0 +1,67 @@
+//! The program refuses inputs that were changed after their HMAC was computed.
+
+use sp1_sdk::ProverClient;
+
+use chacha_lib::input_auth::{hmac_key_hash, INPUT_HMAC_FAILED};
+use chacha_lib::{CompressionType, PublicValues};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+fn inputs(plaintext: &[u8]) -> EncryptInputs<'_> {
+    EncryptInputs {
+        input_hmac_key: Some([9; 32]),
+        ..EncryptInputs::new([1; 32], [2; 12], plaintext)
+    }
+}
+
+#[test]
+fn tampered_inputs_commit_the_sentinel() {
+    let client = ProverClient::builder().mock().build();
+    let honest = inputs(b"attack at dawn").to_stdin();
+    let (output, _) = client.execute(CHACHA_ELF, &honest).run().unwrap();
+    let public_values = PublicValues::parse(output.as_slice()).unwrap();
+    assert_eq!(public_values.input_hmac_key_hash, hmac_key_hash(&[9; 32]));
+
+    // Without authentication the key hash is all zeros
+    let unauthenticated = EncryptInputs::new([1; 32], [2; 12], b"attack at dawn").to_stdin();
+    let (output, _) = client.execute(CHACHA_ELF, &unauthenticated).run().unwrap();
+    let public_values = PublicValues::parse(output.as_slice()).unwrap();
+    assert_eq!(public_values.input_hmac_key_hash, [0; 32]);
+
+    // The plaintext changed after the HMAC of the honest inputs was written
+    let mut tampered = inputs(b"attack at dusk").to_stdin();
+    tampered.buffer.pop();
+    tampered.buffer.push(honest.buffer.last().unwrap().clone());
+    let (output, _) = client.execute(CHACHA_ELF, &tampered).run().unwrap();
+    assert_eq!(output.as_slice(), INPUT_HMAC_FAILED);
+    assert!(PublicValues::parse(output.as_slice()).is_none());
+}
+
+#[test]
+fn tampered_options_commit_the_sentinel() {
+    let client = ProverClient::builder().mock().build();
+    let honest = inputs(b"attack at dawn").to_stdin();
+    let options = [
+        // Index of the input in the order the program reads them, and a changed input
+        (
+            2,
+            EncryptInputs {
+                compression: CompressionType::Lz4,
+                ..inputs(b"attack at dawn")
+            },
+        ),
+        (
+            7,
+            EncryptInputs {
+                lineage: Some([0x11; 32]),
+                ..inputs(b"attack at dawn")
+            },
+        ),
+    ];
+    for (index, changed) in options {
+        let mut tampered = honest.clone();
+        tampered.buffer[index] = changed.to_stdin().buffer[index].clone();
+        assert_ne!(tampered.buffer, honest.buffer);
+        let (output, _) = client.execute(CHACHA_ELF, &tampered).run().unwrap();
+        assert_eq!(output.as_slice(), INPUT_HMAC_FAILED, "input {index}");
+    }
+}
@@ -0,0 +1,125 @@
+//! Content addressing of proofs, and uploading them to a mock IPFS node.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Multipart, Query, State};
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use sp1_sdk::{Prover, ProverClient, SP1ProofWithPublicValues};
+
+use chacha_script::storage::ipfs::{ipfs_download, ipfs_upload, raw_cid, IpfsError};
+use chacha_script::storage::ProofFile;
+use chacha_script::upgrade::test_inputs;
+use chacha_script::CHACHA_ELF;
+
+fn mock_proof() -> SP1ProofWithPublicValues {
+    let client = ProverClient::builder().mock().build();
+    let (pk, _) = client.setup(CHACHA_ELF);
+    client
+        .prove(&pk, &test_inputs([1; 32], [2; 12]).to_stdin())
+        .groth16()
+        .run()
+        .unwrap()
+}
+
+#[test]
+fn raw_cid_matches_kubo() {
+    // `ipfs add --cid-version 1 --raw-leaves`
+    assert_eq!(
+        raw_cid(b"hello world"),
+        "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+    );
+    assert_eq!(
+        raw_cid(b""),
+        "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+    );
+}
+
+#[test]
+fn same_proof_has_same_cid() {
+    let proof = mock_proof();
+    assert_eq!(
+        raw_cid(&ProofFile::from_proof(&proof).bytes),
+        raw_cid(&ProofFile::from_proof(&proof).bytes)
+    );
+}
+
+/// Blocks by CID, and whether the node serves them tampered with.
+#[derive(Default)]
+struct Node {
+    blocks: HashMap<String, Vec<u8>>,
+    pinned: Vec<String>,
+    tamper: bool,
+}
+
+type SharedNode = Arc<Mutex<Node>>;
+
+async fn add(
+    State(node): State<SharedNode>,
+    Query(query): Query<HashMap<String, String>>,
+    mut multipart: Multipart,
+) -> Json<serde_json::Value> {
+    let field = multipart.next_field().await.unwrap().unwrap();
+    let bytes = field.bytes().await.unwrap().to_vec();
+    let cid = raw_cid(&bytes);
+    let mut node = node.lock().unwrap();
+    if query["pin"] == "true" {
+        node.pinned.push(cid.clone());
+    }
+    node.blocks.insert(cid.clone(), bytes);
+    Json(serde_json::json!({ "Name": "proof.bin", "Hash": cid }))
+}
+
+async fn cat(
+    State(node): State<SharedNode>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Vec<u8>, StatusCode> {
+    let node = node.lock().unwrap();
+    let mut bytes = node
+        .blocks
+        .get(&query["arg"])
+        .cloned()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    if node.tamper {
+        bytes[0] ^= 1;
+    }
+    Ok(bytes)
+}
+
+/// Serve a mock Kubo RPC API, returning its endpoint.
+async fn spawn_node(node: SharedNode) -> String {
+    let app = Router::new()
+        .route("/api/v0/add", post(add))
+        .route("/api/v0/cat", post(cat))
+        .with_state(node);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    endpoint
+}
+
+#[tokio::test]
+async fn uploads_to_mock_node() {
+    let node = SharedNode::default();
+    let endpoint = spawn_node(node.clone()).await;
+
+    let file = ProofFile::from_proof(&mock_proof());
+    let cid = ipfs_upload(&endpoint, &file, true).await.unwrap();
+    assert_eq!(cid, raw_cid(&file.bytes));
+    assert_eq!(node.lock().unwrap().pinned, std::slice::from_ref(&cid));
+    assert_eq!(ipfs_download(&endpoint, &cid).await.unwrap(), file);
+
+    node.lock().unwrap().tamper = true;
+    let error = ipfs_download(&endpoint, &cid).await.unwrap_err();
+    assert!(
+        matches!(&error, IpfsError::CidMismatch { expected, .. } if *expected == cid),
+        "{error}"
+    );
+
+    let error = ipfs_download(&endpoint, &raw_cid(b"missing"))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, IpfsError::Http(_)), "{error}");
+}
@@ -0,0 +1,51 @@
+//! Share files of `keygen --key-split`, read back by `chacha --key-shares`.
+
+use chacha_lib::Key;
+use chacha_script::key_shares::{read_key_shares, share_file_name, write_shares, KeySplit};
+
+#[test]
+fn key_split_parses() {
+    assert_eq!(
+        "5/3".parse(),
+        Ok(KeySplit {
+            shares: 5,
+            threshold: 3
+        })
+    );
+    assert_eq!(
+        "5/6".parse::<KeySplit>(),
+        Err("the threshold must be between 1 and 5, got 6".to_string())
+    );
+    assert!("5/0".parse::<KeySplit>().is_err());
+    assert!("256/3".parse::<KeySplit>().is_err());
+    assert!("5".parse::<KeySplit>().is_err());
+}
+
+#[test]
+fn share_files_reconstruct_the_key() {
+    let dir = std::env::temp_dir().join("sp1-chacha-key-shares-test");
+    let key = Key::random();
+    let split = KeySplit {
+        shares: 5,
+        threshold: 3,
+    };
+    let paths = write_shares(&key, split, &dir).unwrap();
+    assert_eq!(paths.len(), 5);
+    assert_eq!(paths[0], dir.join(share_file_name(1)));
+
+    assert_eq!(read_key_shares(&paths[2..]), Ok(key.clone()));
+    assert_eq!(
+        read_key_shares(&[paths[4].clone(), paths[0].clone(), paths[2].clone()]),
+        Ok(key)
+    );
+    assert_eq!(
+        read_key_shares(&paths[..2]),
+        Err("2 key shares, 3 are needed".to_string())
+    );
+
+    let missing = dir.join(share_file_name(9));
+    assert!(read_key_shares(&[missing])
+        .unwrap_err()
+        .starts_with("Failed to read"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
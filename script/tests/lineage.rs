@@ -0,0 +1,42 @@
+//! A three stage pipeline, each stage proven with the lineage commitment of the one before.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::ProverClient;
+
+use chacha_lib::lineage::GENESIS_LINEAGE_HASH;
+use chacha_lib::{lineage_commitment, PublicValues};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+#[test]
+fn stages_chain_their_commitments() {
+    let client = ProverClient::builder().mock().build();
+    let stages: [&[u8]; 3] = [b"raw records", b"cleaned records", b"aggregated records"];
+
+    let mut lineage_hash = GENESIS_LINEAGE_HASH;
+    let mut chain = vec![];
+    for (i, plaintext) in stages.into_iter().enumerate() {
+        let stdin = EncryptInputs {
+            lineage: Some(lineage_hash),
+            ..EncryptInputs::new([1; 32], [i as u8; 12], plaintext)
+        }
+        .to_stdin();
+        let (output, _) = client.execute(CHACHA_ELF, &stdin).run().unwrap();
+        let public_values = PublicValues::parse(output.as_slice()).unwrap();
+        assert_eq!(
+            public_values.lineage_commitment,
+            lineage_commitment(&lineage_hash, &public_values.plaintext_hash),
+            "stage {i}"
+        );
+        chain.push((lineage_hash, public_values.plaintext_hash));
+        lineage_hash = public_values.lineage_commitment;
+    }
+
+    // A verifier holding the last commitment and each stage's hashes walks back to the start
+    let mut commitment = lineage_hash;
+    for ((parent, plaintext_hash), plaintext) in chain.iter().zip(stages).rev() {
+        assert_eq!(*plaintext_hash, <[u8; 32]>::from(Sha256::digest(plaintext)));
+        assert_eq!(commitment, lineage_commitment(parent, plaintext_hash));
+        commitment = *parent;
+    }
+    assert_eq!(commitment, GENESIS_LINEAGE_HASH);
+}
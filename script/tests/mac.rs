@@ -0,0 +1,32 @@
+//! The Encrypt-then-MAC program, see `program/src/main_mac.rs`.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::mac::{chacha_with_hmac, open_with_hmac};
+use chacha_lib::{Key, Nonce};
+use chacha_script::MAC_ELF;
+
+#[test]
+fn commits_the_tag() {
+    let (key, nonce) = (Key([1; 32]), Nonce([2; 12]));
+    let plaintext = b"authenticated plaintext";
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&key.0);
+    stdin.write_slice(&nonce.0);
+    stdin.write_slice(plaintext);
+
+    let client = ProverClient::builder().mock().build();
+    let (output, _) = client.execute(MAC_ELF, &stdin).run().unwrap();
+    let output = output.as_slice();
+    let (ciphertext, tag) = chacha_with_hmac(&key, &nonce, plaintext);
+    assert_eq!(output[..32], Sha256::digest(plaintext)[..]);
+    assert_eq!(output[32..64], tag);
+    assert_eq!(output[64..], ciphertext);
+
+    let tag = output[32..64].try_into().unwrap();
+    assert_eq!(
+        open_with_hmac(&key, &nonce, &output[64..], &tag).unwrap(),
+        plaintext
+    );
+}
@@ -0,0 +1,117 @@
+//! Pushing the metrics of a proving run to a mock Prometheus Pushgateway.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::put;
+use axum::Router;
+
+use chacha_lib::ProofType;
+use chacha_script::metrics::push::{push_metrics, MetricsError};
+use chacha_script::metrics::ProveMetrics;
+
+/// The job, content type and body of every push.
+type Pushes = Arc<Mutex<Vec<(String, String, String)>>>;
+
+async fn record(
+    State(pushes): State<Pushes>,
+    Path(job): Path<String>,
+    headers: HeaderMap,
+    body: String,
+) -> StatusCode {
+    let content_type = headers["content-type"].to_str().unwrap().to_string();
+    pushes.lock().unwrap().push((job, content_type, body));
+    StatusCode::OK
+}
+
+/// Serve a mock Pushgateway, returning its URL.
+async fn spawn_gateway(pushes: Pushes) -> String {
+    let app = Router::new()
+        .route("/metrics/job/{job}", put(record))
+        .route(
+            "/broken/metrics/job/{job}",
+            put(|| async { StatusCode::BAD_REQUEST }),
+        )
+        .with_state(pushes);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    url
+}
+
+fn metrics(success: bool) -> ProveMetrics {
+    ProveMetrics {
+        cycles: 123_456,
+        prove_duration: Duration::from_millis(2500),
+        input_size: 1024,
+        proof_type: ProofType::Groth16,
+        success,
+    }
+}
+
+#[test]
+fn encodes_every_field() {
+    let text = metrics(true).encode().unwrap();
+    for line in [
+        "# TYPE sp1_chacha_cycles gauge",
+        "sp1_chacha_cycles{proof_type=\"groth16\"} 123456",
+        "sp1_chacha_prove_duration_seconds{proof_type=\"groth16\"} 2.5",
+        "sp1_chacha_input_bytes{proof_type=\"groth16\"} 1024",
+        "sp1_chacha_prove_success{proof_type=\"groth16\"} 1",
+    ] {
+        assert!(
+            text.lines().any(|l| l == line),
+            "{line} missing from\n{text}"
+        );
+    }
+    let text = metrics(false).encode().unwrap();
+    assert!(text.contains("sp1_chacha_prove_success{proof_type=\"groth16\"} 0"));
+}
+
+#[tokio::test]
+async fn pushes_to_mock_gateway() {
+    let pushes = Pushes::default();
+    let gateway = spawn_gateway(pushes.clone()).await;
+
+    push_metrics(&gateway, "nightly-batch", &metrics(true))
+        .await
+        .unwrap();
+    push_metrics(&format!("{gateway}/"), "nightly-batch", &metrics(false))
+        .await
+        .unwrap();
+
+    let pushes = pushes.lock().unwrap();
+    assert_eq!(pushes.len(), 2);
+    for (job, content_type, body) in pushes.iter() {
+        assert_eq!(job, "nightly-batch");
+        assert_eq!(content_type, "text/plain; version=0.0.4");
+        for name in [
+            "sp1_chacha_cycles",
+            "sp1_chacha_prove_duration_seconds",
+            "sp1_chacha_input_bytes",
+            "sp1_chacha_prove_success",
+            "proof_type=\"groth16\"",
+        ] {
+            assert!(body.contains(name), "{name} missing from\n{body}");
+        }
+    }
+    assert!(pushes[1]
+        .2
+        .contains("sp1_chacha_prove_success{proof_type=\"groth16\"} 0"));
+}
+
+#[tokio::test]
+async fn failed_pushes_are_errors() {
+    let gateway = spawn_gateway(Pushes::default()).await;
+    let error = push_metrics(&format!("{gateway}/broken"), "job", &metrics(true))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetricsError::Http(_)), "{error}");
+
+    let error = push_metrics("not a url", "job", &metrics(true))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MetricsError::Url(_)), "{error}");
+}
@@ -0,0 +1,96 @@
+//! Migrating proof files to the current format.
+
+use sp1_prover::Groth16Bn254Proof;
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues, SP1PublicValues};
+
+use chacha_lib::envelope::{ProofEnvelope, ENVELOPE_VERSION};
+use chacha_lib::ProofType;
+use chacha_script::migrate::{
+    detect_version, migrate, migrate_v1_to_v2, MigrateError, SP1_VERSION_EXTENSION,
+};
+use chacha_script::storage::ProofFile;
+
+const PROGRAM_HASH: [u8; 32] = [7; 32];
+
+/// The bytes of a v1 proof file, as `SP1ProofWithPublicValues::save` writes them.
+fn v1_proof() -> (SP1ProofWithPublicValues, Vec<u8>) {
+    let proof = SP1ProofWithPublicValues {
+        proof: SP1Proof::Groth16(Groth16Bn254Proof {
+            public_inputs: ["1".to_string(), "2".to_string()],
+            encoded_proof: "abcd".to_string(),
+            raw_proof: "ef".to_string(),
+            groth16_vkey_hash: [3; 32],
+        }),
+        public_values: SP1PublicValues::from(&[0x42; 100]),
+        sp1_version: "v4.0.0-rc.3".to_string(),
+    };
+    let bytes = ProofFile::from_proof(&proof).bytes;
+    (proof, bytes)
+}
+
+#[test]
+fn v1_migrates_to_a_valid_envelope() {
+    let (proof, v1) = v1_proof();
+    assert_eq!(detect_version(&v1), Ok(1));
+
+    let envelope = migrate_v1_to_v2(&v1, PROGRAM_HASH).unwrap();
+    assert_eq!(envelope.version, ENVELOPE_VERSION);
+    assert_eq!(envelope.proof_type, ProofType::Groth16);
+    assert_eq!(envelope.program_hash, PROGRAM_HASH);
+    assert_eq!(envelope.public_values, proof.public_values.as_slice());
+    assert_eq!(
+        envelope.extensions[SP1_VERSION_EXTENSION],
+        b"v4.0.0-rc.3".to_vec()
+    );
+    let migrated: SP1Proof = bincode::deserialize(&envelope.proof_bytes).unwrap();
+    assert_eq!(
+        bincode::serialize(&migrated).unwrap(),
+        bincode::serialize(&proof.proof).unwrap()
+    );
+
+    // The written file parses as an envelope of the current version
+    let v2 = migrate(&v1, Some(1), 2, PROGRAM_HASH).unwrap();
+    assert_eq!(detect_version(&v2), Ok(2));
+    let parsed = ProofEnvelope::from_json(std::str::from_utf8(&v2).unwrap()).unwrap();
+    assert_eq!(parsed.proof_bytes, envelope.proof_bytes);
+    assert_eq!(parsed.extensions, envelope.extensions);
+}
+
+#[test]
+fn unsupported_migrations_are_rejected() {
+    let (_, v1) = v1_proof();
+    let v2 = migrate(&v1, None, 2, PROGRAM_HASH).unwrap();
+    assert_eq!(
+        migrate(&v2, Some(1), 2, PROGRAM_HASH),
+        Err(MigrateError::VersionMismatch {
+            expected: 1,
+            found: 2
+        })
+    );
+    assert_eq!(
+        migrate(&v2, None, 3, PROGRAM_HASH),
+        Err(MigrateError::UnsupportedMigration { from: 2, to: 3 })
+    );
+    assert_eq!(
+        migrate(&v1, None, 1, PROGRAM_HASH),
+        Err(MigrateError::UnsupportedMigration { from: 1, to: 1 })
+    );
+}
+
+#[test]
+fn invalid_files_are_rejected() {
+    assert_eq!(detect_version(b""), Err(MigrateError::UnknownFormat));
+    assert_eq!(
+        detect_version(b"proof: 0x1234"),
+        Err(MigrateError::UnknownFormat)
+    );
+    assert!(matches!(
+        detect_version(b"{\"version\": 2}"),
+        Err(MigrateError::InvalidEnvelope(_))
+    ));
+    let (_, v1) = v1_proof();
+    assert!(matches!(
+        migrate_v1_to_v2(&v1[..v1.len() / 2], PROGRAM_HASH),
+        Err(MigrateError::InvalidProof(_))
+    ));
+}
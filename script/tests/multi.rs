@@ -0,0 +1,107 @@
+//! One plaintext encrypted for several recipients, see `program/src/main_multi.rs`.
+
+use std::process::Command;
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::chacha;
+use chacha_script::MULTI_ELF;
+
+const PLAINTEXT: &[u8] = b"one plaintext for several recipients";
+
+fn recipients() -> Vec<([u8; 32], [u8; 12])> {
+    (1..=3u8).map(|i| ([i; 32], [i + 10; 12])).collect()
+}
+
+fn execute(keys: &[([u8; 32], [u8; 12])]) -> Vec<u8> {
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&(keys.len() as u32));
+    for (key, nonce) in keys {
+        stdin.write_slice(key);
+        stdin.write_slice(nonce);
+    }
+    stdin.write_slice(PLAINTEXT);
+    let client = ProverClient::builder().mock().build();
+    let (output, _) = client.execute(MULTI_ELF, &stdin).run().unwrap();
+    output.to_vec()
+}
+
+#[test]
+fn each_recipient_gets_its_own_ciphertext() {
+    let keys = recipients();
+    let public_values = execute(&keys);
+    let (plaintext_hash, pairs) = public_values.split_at(32);
+    assert_eq!(plaintext_hash, Sha256::digest(PLAINTEXT).as_slice());
+    assert_eq!(pairs.len(), 64 * keys.len());
+
+    let mut ciphertext_hashes = Vec::new();
+    for (i, ((key, nonce), pair)) in keys.iter().zip(pairs.chunks(64)).enumerate() {
+        let mut ciphertext = PLAINTEXT.to_vec();
+        chacha(key, nonce, &mut ciphertext);
+        assert_eq!(&pair[..32], Sha256::digest(key).as_slice(), "recipient {i}");
+        assert_eq!(
+            &pair[32..],
+            Sha256::digest(&ciphertext).as_slice(),
+            "recipient {i}"
+        );
+        ciphertext_hashes.push(pair[32..].to_vec());
+    }
+    ciphertext_hashes.sort_unstable();
+    ciphertext_hashes.dedup();
+    assert_eq!(ciphertext_hashes.len(), keys.len());
+}
+
+#[test]
+fn no_recipients_commit_only_the_plaintext_hash() {
+    assert_eq!(execute(&[]), Sha256::digest(PLAINTEXT).as_slice());
+}
+
+#[test]
+fn binary_writes_each_recipients_ciphertext() {
+    let dir = std::env::temp_dir().join(format!("chacha-multi-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("input.txt");
+    std::fs::write(&input, PLAINTEXT).unwrap();
+
+    let keys: Vec<[u8; 32]> = recipients().into_iter().map(|(key, _)| key).collect();
+    let recipients: Vec<String> = keys.iter().map(hex::encode).collect();
+    let output = Command::new(env!("CARGO_BIN_EXE_multi_encrypt"))
+        .args(["--execute", "--recipients", &recipients.join(",")])
+        .arg("--input")
+        .arg(&input)
+        .arg("--output-dir")
+        .arg(&dir)
+        .env("SP1_PROVER", "mock")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        output.status.success(),
+        "{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    for (i, key) in keys.iter().enumerate() {
+        // Recipient <i>: key hash 0x<hex>, nonce <hex>, ciphertext <path>
+        let line = stdout
+            .lines()
+            .find(|line| line.starts_with(&format!("Recipient {i}:")))
+            .unwrap_or_else(|| panic!("no recipient {i} in:\n{stdout}"));
+        let nonce = line
+            .split("nonce ")
+            .nth(1)
+            .unwrap()
+            .split(',')
+            .next()
+            .unwrap();
+        let nonce: [u8; 12] = hex::decode(nonce).unwrap().try_into().unwrap();
+
+        let mut decrypted = std::fs::read(dir.join(format!("ciphertext_{i}.bin"))).unwrap();
+        assert_ne!(decrypted, PLAINTEXT);
+        chacha(key, &nonce, &mut decrypted);
+        assert_eq!(decrypted, PLAINTEXT, "recipient {i}");
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
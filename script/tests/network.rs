@@ -0,0 +1,178 @@
+//! Requesting and polling proofs against a mock prover network.
+
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use sp1_sdk::network::B256;
+use sp1_sdk::{Prover, ProverClient, SP1ProofWithPublicValues, SP1ProvingKey, SP1Stdin};
+
+use chacha_lib::ProofType;
+use chacha_script::network::{
+    request_with_retry, wait_for_proof, NetworkProveError, ProofNetwork, RequestStatus,
+};
+use chacha_script::upgrade::test_inputs;
+use chacha_script::CHACHA_ELF;
+
+/// Replies to the requests and status checks in order, then with the proof.
+struct MockNetwork {
+    requests: Mutex<Vec<anyhow::Result<B256>>>,
+    statuses: Mutex<Vec<anyhow::Result<RequestStatus>>>,
+    proof: SP1ProofWithPublicValues,
+    requested: Mutex<Vec<ProofType>>,
+}
+
+impl MockNetwork {
+    fn new(
+        requests: Vec<anyhow::Result<B256>>,
+        statuses: Vec<anyhow::Result<RequestStatus>>,
+        proof: SP1ProofWithPublicValues,
+    ) -> Self {
+        MockNetwork {
+            requests: Mutex::new(requests),
+            statuses: Mutex::new(statuses),
+            proof,
+            requested: Mutex::default(),
+        }
+    }
+}
+
+impl ProofNetwork for MockNetwork {
+    async fn request(
+        &self,
+        _pk: &SP1ProvingKey,
+        _stdin: &SP1Stdin,
+        proof_type: ProofType,
+    ) -> anyhow::Result<B256> {
+        self.requested.lock().unwrap().push(proof_type);
+        let mut requests = self.requests.lock().unwrap();
+        if requests.is_empty() {
+            Ok(B256::repeat_byte(1))
+        } else {
+            requests.remove(0)
+        }
+    }
+
+    async fn status(&self, request_id: B256) -> anyhow::Result<RequestStatus> {
+        assert_eq!(request_id, B256::repeat_byte(1));
+        let mut statuses = self.statuses.lock().unwrap();
+        if statuses.is_empty() {
+            Ok(RequestStatus::Fulfilled(Box::new(self.proof.clone())))
+        } else {
+            statuses.remove(0)
+        }
+    }
+}
+
+fn setup() -> (SP1ProvingKey, SP1Stdin, SP1ProofWithPublicValues) {
+    let client = ProverClient::builder().mock().build();
+    let (pk, _) = client.setup(CHACHA_ELF);
+    let stdin = test_inputs([1; 32], [2; 12]).to_stdin();
+    let proof = client.prove(&pk, &stdin).groth16().run().unwrap();
+    (pk, stdin, proof)
+}
+
+#[tokio::test]
+async fn retries_failed_requests_then_waits_for_the_proof() {
+    let (pk, stdin, proof) = setup();
+    let network = MockNetwork::new(
+        vec![
+            Err(anyhow::anyhow!("unavailable")),
+            Err(anyhow::anyhow!("unavailable")),
+        ],
+        vec![
+            Ok(RequestStatus::Pending(
+                "FULFILLMENT_STATUS_ASSIGNED".to_string(),
+            )),
+            Err(anyhow::anyhow!("connection reset")),
+        ],
+        proof.clone(),
+    );
+
+    let request_id =
+        request_with_retry(&network, &pk, &stdin, ProofType::Groth16, 3, Duration::ZERO)
+            .await
+            .unwrap();
+    assert_eq!(*network.requested.lock().unwrap(), [ProofType::Groth16; 3]);
+
+    let fulfilled = wait_for_proof(&network, request_id, Duration::ZERO)
+        .await
+        .unwrap();
+    assert_eq!(
+        fulfilled.public_values.as_slice(),
+        proof.public_values.as_slice()
+    );
+    assert!(network.statuses.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn gives_up_after_the_last_attempt() {
+    let (pk, stdin, proof) = setup();
+    let network = MockNetwork::new(
+        vec![
+            Err(anyhow::anyhow!("unavailable")),
+            Err(anyhow::anyhow!("unavailable")),
+            Err(anyhow::anyhow!("unauthorized")),
+        ],
+        vec![],
+        proof,
+    );
+
+    let error = request_with_retry(&network, &pk, &stdin, ProofType::Core, 3, Duration::ZERO)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        NetworkProveError::Request {
+            attempts: 3,
+            error: "unauthorized".to_string()
+        }
+    );
+}
+
+#[tokio::test]
+async fn stops_polling_a_failed_request() {
+    let (_, _, proof) = setup();
+    let network = MockNetwork::new(
+        vec![],
+        vec![
+            Ok(RequestStatus::Pending(
+                "FULFILLMENT_STATUS_REQUESTED".to_string(),
+            )),
+            Ok(RequestStatus::Failed("unexecutable".to_string())),
+        ],
+        proof,
+    );
+
+    let error = wait_for_proof(&network, B256::repeat_byte(1), Duration::ZERO)
+        .await
+        .unwrap_err();
+    assert_eq!(error, NetworkProveError::Failed("unexecutable".to_string()));
+}
+
+/// Run `network_prove` with `ENCRYPTION_KEY` set to `key`, returning its stderr.
+fn network_prove_with_key(key: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_network_prove"))
+        .args(["--network-key", "unused"])
+        .env("ENCRYPTION_KEY", key)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    String::from_utf8(output.stderr).unwrap()
+}
+
+#[test]
+fn invalid_keys_are_rejected_before_connecting() {
+    let stderr = network_prove_with_key(&"ab".repeat(16));
+    assert!(
+        stderr.contains("Error: Key must be exactly 32 bytes, got 16"),
+        "{stderr}"
+    );
+    let stderr = network_prove_with_key(&"00".repeat(32));
+    assert!(
+        stderr.contains("Error: Weak key: all bytes are identical"),
+        "{stderr}"
+    );
+    let stderr = network_prove_with_key("not hex");
+    assert!(stderr.contains("ENCRYPTION_KEY must be hex"), "{stderr}");
+}
@@ -0,0 +1,35 @@
+//! The Pedersen commitment program, see `program/src/main_pedersen.rs`.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::chacha;
+use chacha_lib::pedersen::{pedersen_verify, PedersenCommitment};
+use chacha_script::PEDERSEN_ELF;
+
+const PLAINTEXT: &[u8] = b"vote: yes";
+
+#[test]
+fn commits_to_the_plaintext_twice() {
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&[1; 32]);
+    stdin.write_slice(&[2; 12]);
+    stdin.write_slice(PLAINTEXT);
+    stdin.write_slice(&[3; 32]);
+    let client = ProverClient::builder().mock().build();
+    let (output, report) = client.execute(PEDERSEN_ELF, &stdin).run().unwrap();
+    println!(
+        "Pedersen commitment: {} cycles",
+        report.cycle_tracker["pedersen"]
+    );
+
+    let output = output.as_slice();
+    let mut ciphertext = PLAINTEXT.to_vec();
+    chacha(&[1; 32], &[2; 12], &mut ciphertext);
+    assert_eq!(output[..32], Sha256::digest(PLAINTEXT)[..]);
+    let commitment = PedersenCommitment(output[32..64].try_into().unwrap());
+    assert!(pedersen_verify(&commitment, PLAINTEXT, &[3; 32]));
+    assert!(!pedersen_verify(&commitment, b"vote: no", &[3; 32]));
+    assert_eq!(output[64..96], Sha256::digest(&ciphertext)[..]);
+    assert_eq!(output[96..], ciphertext);
+}
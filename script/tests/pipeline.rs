@@ -0,0 +1,117 @@
+//! `CHACHA_PROVE_*` environment variables apply when their flag is not set.
+//!
+//! A single test, as the environment is shared by every thread of the test binary.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use chacha_lib::ProofType;
+use chacha_script::pipeline::{
+    env_key, PipelineArgs, CHAIN_ID_VAR, INPUT_FILE_VAR, KEY_VAR, NONCE_VAR, OUTPUT_FILE_VAR,
+    TYPE_VAR,
+};
+
+#[derive(Parser)]
+struct Cli {
+    #[clap(flatten)]
+    pipeline: PipelineArgs,
+}
+
+fn parse(args: &[&str]) -> PipelineArgs {
+    Cli::try_parse_from(["chacha"].iter().chain(args))
+        .unwrap()
+        .pipeline
+}
+
+#[test]
+fn env_vars_apply_unless_flags_are_set() {
+    let defaults = parse(&[]);
+
+    // SAFETY: no other thread of this test binary touches the environment
+    unsafe {
+        std::env::set_var(KEY_VAR, "11".repeat(32));
+        std::env::set_var("ENCRYPTION_KEY", "22".repeat(32));
+        std::env::set_var(NONCE_VAR, "000102030405060708090a0b");
+        std::env::set_var(INPUT_FILE_VAR, "pipeline/input.bin");
+        std::env::set_var(TYPE_VAR, "plonk");
+        std::env::set_var(CHAIN_ID_VAR, "11155111");
+        std::env::set_var(OUTPUT_FILE_VAR, "pipeline/proof.bin");
+    }
+
+    assert_eq!(env_key(), Some((KEY_VAR, "11".repeat(32))));
+    assert_eq!(
+        parse(&[]),
+        PipelineArgs {
+            input: Some(PathBuf::from("pipeline/input.bin")),
+            nonce: Some([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]),
+            nonce_from_env: false,
+            deterministic_nonce: false,
+            proof_type: ProofType::Plonk,
+            chain_id: Some(11155111),
+            output: Some(PathBuf::from("pipeline/proof.bin")),
+        }
+    );
+
+    // Flags take precedence
+    let flags = parse(&[
+        "--input",
+        "flag.bin",
+        "--nonce",
+        "0b0a09080706050403020100",
+        "--proof-type",
+        "core",
+        "--chain-id",
+        "1",
+        "--output",
+        "flag-proof.bin",
+    ]);
+    assert_eq!(
+        flags,
+        PipelineArgs {
+            input: Some(PathBuf::from("flag.bin")),
+            nonce: Some([11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0]),
+            nonce_from_env: false,
+            deterministic_nonce: false,
+            proof_type: ProofType::Core,
+            chain_id: Some(1),
+            output: Some(PathBuf::from("flag-proof.bin")),
+        }
+    );
+
+    // SAFETY: as above
+    unsafe {
+        std::env::set_var(NONCE_VAR, "too short");
+        std::env::remove_var(KEY_VAR);
+    }
+    assert!(Cli::try_parse_from(["chacha"]).is_err());
+    assert_eq!(env_key(), Some(("ENCRYPTION_KEY", "22".repeat(32))));
+
+    // SAFETY: as above
+    unsafe {
+        for var in [
+            "ENCRYPTION_KEY",
+            NONCE_VAR,
+            INPUT_FILE_VAR,
+            TYPE_VAR,
+            CHAIN_ID_VAR,
+            OUTPUT_FILE_VAR,
+        ] {
+            std::env::remove_var(var);
+        }
+    }
+    assert_eq!(env_key(), None);
+    assert_eq!(parse(&[]), defaults);
+    assert_eq!(
+        defaults,
+        PipelineArgs {
+            input: None,
+            nonce: None,
+            nonce_from_env: false,
+            deterministic_nonce: false,
+            proof_type: ProofType::Groth16,
+            chain_id: None,
+            output: None,
+        }
+    );
+}
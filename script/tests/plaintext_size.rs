@@ -0,0 +1,30 @@
+//! The committed plaintext size, which is the length before compression.
+
+use sp1_sdk::ProverClient;
+
+use chacha_lib::{CompressionType, PublicValues};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+#[test]
+fn committed_size_is_the_plaintext_length() {
+    let client = ProverClient::builder().mock().build();
+    let plaintext = b"plaintext size plaintext size plaintext size".repeat(4);
+
+    for compression in [CompressionType::None, CompressionType::Lz4] {
+        for input_plaintext in [&plaintext[..], &plaintext[..1], b""] {
+            let stdin = EncryptInputs {
+                compression,
+                ..EncryptInputs::new([1; 32], [2; 12], input_plaintext)
+            }
+            .to_stdin();
+            let (output, _) = client.execute(CHACHA_ELF, &stdin).run().unwrap();
+            let public_values = PublicValues::parse(output.as_slice()).unwrap();
+            assert_eq!(
+                public_values.plaintext_size,
+                Some(input_plaintext.len() as u64),
+                "{compression:?}, {} bytes",
+                input_plaintext.len()
+            );
+        }
+    }
+}
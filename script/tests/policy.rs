@@ -0,0 +1,63 @@
+//! The policy program, see `program/src/main_policy.rs`.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::chacha;
+use chacha_script::POLICY_ELF;
+
+const POLICY: &str = r#"{"and": [{"eq": ["role", "admin"]}, {"lt": ["time", 1767225600]}]}"#;
+const PLAINTEXT: &[u8] = b"access controlled plaintext";
+
+fn stdin(attributes: &str) -> SP1Stdin {
+    let mut ciphertext = PLAINTEXT.to_vec();
+    chacha(&[1; 32], &[2; 12], &mut ciphertext);
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&[1; 32]);
+    stdin.write_slice(&[2; 12]);
+    stdin.write_slice(&ciphertext);
+    stdin.write_slice(POLICY.as_bytes());
+    stdin.write_slice(attributes.as_bytes());
+    stdin
+}
+
+#[test]
+fn decrypts_when_the_policy_is_satisfied() {
+    let attributes = r#"{"role": "admin", "time": 1700000000, "salt": "9b1d5ef0"}"#;
+    let client = ProverClient::builder().mock().build();
+    let (output, report) = client
+        .execute(POLICY_ELF, &stdin(attributes))
+        .run()
+        .unwrap();
+    println!(
+        "Policy evaluation: {} cycles",
+        report.cycle_tracker["policy"]
+    );
+
+    let output = output.as_slice();
+    let mut ciphertext = PLAINTEXT.to_vec();
+    chacha(&[1; 32], &[2; 12], &mut ciphertext);
+    assert_eq!(output[..32], Sha256::digest(POLICY)[..]);
+    assert_eq!(output[32..64], Sha256::digest(attributes)[..]);
+    assert_eq!(output[64..96], Sha256::digest(&ciphertext)[..]);
+    assert_eq!(output[96..], Sha256::digest(PLAINTEXT)[..]);
+}
+
+#[test]
+fn refuses_when_the_policy_is_not_satisfied() {
+    let client = ProverClient::builder().mock().build();
+    for attributes in [
+        r#"{"role": "guest", "time": 1700000000}"#,
+        r#"{"role": "admin", "time": 1767225600}"#,
+        // Missing attribute
+        r#"{"role": "admin"}"#,
+    ] {
+        assert!(
+            client
+                .execute(POLICY_ELF, &stdin(attributes))
+                .run()
+                .is_err(),
+            "{attributes}"
+        );
+    }
+}
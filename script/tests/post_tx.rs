@@ -0,0 +1,234 @@
+//! Encoding and posting proofs to `SP1Chacha`.
+//!
+//! The anvil test needs `anvil` on the `PATH` and the contracts built with `forge build`:
+//!
+//! ```shell
+//! (cd contracts && forge build) && cargo test --release --test post_tx -- --ignored
+//! ```
+
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::node_bindings::Anvil;
+use alloy::primitives::{keccak256, Address, B256, U256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::sol_types::{SolCall, SolValue};
+use sha2::{Digest, Sha256};
+use sp1_sdk::{HashableKey, Prover, ProverClient, SP1ProofWithPublicValues};
+
+use chacha_lib::{chacha, PublicValues, PROGRAM_VERSION};
+use chacha_script::post::{
+    post_tx, verify_calldata, ExpectationError, ExpectedHashes, ISP1Chacha, PostError,
+};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+/// A mock Groth16 proof and the verification key hash of the program.
+fn mock_proof() -> (SP1ProofWithPublicValues, B256) {
+    let client = ProverClient::builder().mock().build();
+    let (pk, vk) = client.setup(CHACHA_ELF);
+    let stdin = EncryptInputs::new([1; 32], [2; 12], b"posted plaintext").to_stdin();
+    let proof = client.prove(&pk, &stdin).groth16().run().unwrap();
+    (proof, vk.bytes32().parse().unwrap())
+}
+
+#[test]
+fn calldata_calls_verify_encryption() {
+    let (proof, _) = mock_proof();
+
+    let calldata = verify_calldata(&proof, None).unwrap();
+    assert_eq!(calldata[..4], ISP1Chacha::verifyEncryptionCall::SELECTOR);
+    let call = ISP1Chacha::verifyEncryptionCall::abi_decode(&calldata, true).unwrap();
+    assert_eq!(call.publicValues[..], *proof.public_values.as_slice());
+    assert_eq!(call.proofBytes, proof.bytes());
+
+    let calldata = verify_calldata(&proof, Some(b"ciphertext")).unwrap();
+    let call = ISP1Chacha::verifyEncryptionWithCiphertextCall::abi_decode(&calldata, true).unwrap();
+    assert_eq!(call.ciphertext[..], *b"ciphertext");
+}
+
+/// `bytes` ABI encoded as the tail of a dynamic argument: its length, then the bytes padded to
+/// 32 byte words.
+fn abi_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = U256::from(bytes.len()).to_be_bytes::<32>().to_vec();
+    encoded.extend(bytes);
+    encoded.resize(32 + bytes.len().div_ceil(32) * 32, 0);
+    encoded
+}
+
+#[test]
+fn calldata_matches_manual_encoding() {
+    let (proof, _) = mock_proof();
+    let public_values = proof.public_values.as_slice();
+    let proof_bytes = proof.bytes();
+
+    let mut expected = keccak256("verifyEncryption(bytes,bytes)")[..4].to_vec();
+    let public_values = abi_bytes(public_values);
+    // Offsets of the two arguments from the start of the arguments
+    expected.extend(U256::from(64).to_be_bytes::<32>());
+    expected.extend(U256::from(64 + public_values.len()).to_be_bytes::<32>());
+    expected.extend(public_values);
+    expected.extend(abi_bytes(&proof_bytes));
+    assert_eq!(verify_calldata(&proof, None).unwrap(), expected);
+}
+
+#[test]
+fn expected_hashes_are_checked() {
+    let (proof, _) = mock_proof();
+    let public_values = proof.public_values.as_slice();
+    let plaintext_hash: [u8; 32] = Sha256::digest(b"posted plaintext").into();
+    let mut ciphertext = *b"posted plaintext";
+    chacha(&[1; 32], &[2; 12], &mut ciphertext);
+    let ciphertext_hash: [u8; 32] = Sha256::digest(ciphertext).into();
+
+    let expected = ExpectedHashes {
+        plaintext_hash: Some(plaintext_hash),
+        ciphertext_hash: Some(ciphertext_hash),
+    };
+    assert_eq!(expected.check(public_values, false), Ok(()));
+    assert_eq!(
+        ExpectedHashes::default().check(public_values, false),
+        Ok(())
+    );
+
+    let wrong = ExpectedHashes {
+        ciphertext_hash: Some(plaintext_hash),
+        ..expected
+    };
+    assert_eq!(
+        wrong.check(public_values, false),
+        Err(ExpectationError::Mismatch {
+            field: "ciphertext hash",
+            expected: plaintext_hash,
+            actual: ciphertext_hash,
+        })
+    );
+    let wrong = ExpectedHashes {
+        plaintext_hash: Some([0; 32]),
+        ..expected
+    };
+    assert!(matches!(
+        wrong.check(public_values, false),
+        Err(ExpectationError::Mismatch {
+            field: "plaintext hash",
+            ..
+        })
+    ));
+    assert_eq!(
+        expected.check(&public_values[..10], false),
+        Err(ExpectationError::PublicValues)
+    );
+
+    // With --commit-ciphertext-hash the committed hash is compared as is
+    let header_len = public_values.len() - ciphertext.len();
+    let mut hashed = public_values[..header_len].to_vec();
+    hashed.extend(ciphertext_hash);
+    assert_eq!(
+        PublicValues::parse(&hashed).unwrap().ciphertext,
+        ciphertext_hash
+    );
+    assert_eq!(expected.check(&hashed, true), Ok(()));
+    assert_eq!(
+        expected.check(public_values, true),
+        Err(ExpectationError::PublicValues)
+    );
+}
+
+/// Deploy `contract` from its `forge build` artifact.
+async fn deploy(
+    provider: &impl Provider,
+    file: &str,
+    contract: &str,
+    constructor_args: Vec<u8>,
+) -> Address {
+    let path = format!("../contracts/out/{file}/{contract}.json");
+    let artifact: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path}: {e}, run forge build")),
+    )
+    .unwrap();
+    let mut code = hex::decode(
+        artifact["bytecode"]["object"]
+            .as_str()
+            .unwrap()
+            .trim_start_matches("0x"),
+    )
+    .unwrap();
+    code.extend(constructor_args);
+
+    let tx = TransactionRequest::default().with_deploy_code(code);
+    let receipt = provider
+        .send_transaction(tx)
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    receipt.contract_address.unwrap()
+}
+
+#[tokio::test]
+#[ignore = "needs anvil and the forge build artifacts"]
+async fn posts_to_anvil() {
+    let (proof, vkey) = mock_proof();
+    let anvil = Anvil::new().spawn();
+    let private_key = hex::encode(anvil.keys()[0].to_bytes());
+    let signer = PrivateKeySigner::from(anvil.keys()[0].clone());
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer))
+        .on_http(anvil.endpoint_url());
+
+    let verifier = deploy(&provider, "MockVerifier.sol", "MockVerifier", vec![]).await;
+    // A uint8 is ABI encoded as a full word, like a uint256
+    let args = (verifier, U256::from(PROGRAM_VERSION), vkey).abi_encode_params();
+    let contract = deploy(&provider, "SP1Chacha.sol", "SP1Chacha", args).await;
+
+    let calldata = verify_calldata(&proof, None).unwrap();
+    let mut sent = None;
+    let posted = post_tx(
+        &anvil.endpoint(),
+        contract,
+        &private_key,
+        calldata.clone(),
+        Some(anvil.chain_id()),
+        1,
+        |tx_hash| sent = Some(tx_hash),
+    )
+    .await
+    .unwrap();
+    assert_eq!(sent, Some(posted.tx_hash));
+    assert!(posted.block_number.is_some());
+    assert!(posted.gas_used > 21_000);
+    assert!(posted.effective_gas_price > 0);
+
+    let error = post_tx(
+        &anvil.endpoint(),
+        contract,
+        &private_key,
+        calldata.clone(),
+        Some(1),
+        1,
+        |_| panic!("sent to the wrong chain"),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(error, PostError::ChainId { expected: 1, .. }),
+        "{error}"
+    );
+
+    // The nullifier is used now
+    let error = post_tx(
+        &anvil.endpoint(),
+        contract,
+        &private_key,
+        calldata,
+        None,
+        1,
+        |_| {},
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(error, PostError::Rpc(_) | PostError::Reverted(_)),
+        "{error}"
+    );
+}
@@ -0,0 +1,43 @@
+//! Comparing opcode histograms against a baseline.
+
+use chacha_script::profile::{compare, markdown_summary, OpcodeChange, OpcodeHistogram};
+
+fn histogram(counts: &[(&str, u64)]) -> OpcodeHistogram {
+    counts
+        .iter()
+        .map(|&(opcode, count)| (opcode.to_string(), count))
+        .collect()
+}
+
+#[test]
+fn reports_changes_above_threshold() {
+    let baseline = histogram(&[("add", 1000), ("xor", 500), ("lw", 200), ("ecall", 10)]);
+    let current = histogram(&[("add", 1100), ("xor", 400), ("lw", 230), ("mul", 5)]);
+
+    let changes = compare(&baseline, &current, 10.0);
+    let opcodes: Vec<_> = changes.iter().map(|c| c.opcode.as_str()).collect();
+    // add is exactly 10% and not reported
+    assert_eq!(opcodes, ["mul", "ecall", "xor", "lw"]);
+    assert_eq!(
+        changes[2],
+        OpcodeChange {
+            opcode: "xor".to_string(),
+            baseline: 500,
+            current: 400,
+        }
+    );
+
+    let summary = markdown_summary(&changes, &baseline, &current, 10.0);
+    assert!(summary.contains("took 1735 instructions, 1710 on main"));
+    assert!(summary.contains("| mul | 0 | 5 | new |"));
+    assert!(summary.contains("| ecall | 10 | 0 | -100.0% |"));
+    assert!(summary.contains("| xor | 500 | 400 | -20.0% |"));
+    assert!(summary.contains("| lw | 200 | 230 | +15.0% |"));
+}
+
+#[test]
+fn identical_histograms_have_no_changes() {
+    let baseline = histogram(&[("add", 1000), ("xor", 500)]);
+    assert!(compare(&baseline, &baseline, 10.0).is_empty());
+    assert!(compare(&OpcodeHistogram::new(), &OpcodeHistogram::new(), 0.0).is_empty());
+}
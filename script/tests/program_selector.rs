@@ -0,0 +1,134 @@
+//! `--program` names and the ELF and verification key of each program.
+
+use chacha_script::{
+    select_elf, solidity_vkeys, ProgramSelector, AES_ELF, AGGREGATE_ELF, BLAKE2B_ELF, CHACHA_ELF,
+    CHUNK_ELF, FPE_ELF, KDF_ELF, MAC_ELF, MULTI_ELF, NOISE_ELF, PEDERSEN_ELF, POLICY_ELF,
+    REKEY_ELF, REVEAL_ELF, ROTATE_ELF, SEGMENT_ELF, SHA512_ELF, SIGNAL_ELF, SSH_ELF, TIMELOCK_ELF,
+    TLS13_ELF, UPGRADE_ELF, VERIFIABLE_ELF, WIREGUARD_ELF,
+};
+
+#[test]
+fn names_roundtrip() {
+    for program in ProgramSelector::ALL {
+        assert_eq!(program.to_string().parse::<ProgramSelector>(), Ok(program));
+        assert_eq!(
+            program
+                .to_string()
+                .to_uppercase()
+                .parse::<ProgramSelector>(),
+            Ok(program)
+        );
+    }
+    assert_eq!(ProgramSelector::default(), ProgramSelector::Encrypt);
+
+    let error = "decrypt".parse::<ProgramSelector>().unwrap_err();
+    assert!(
+        error.starts_with("unknown program `decrypt`, expected encrypt|reencrypt|"),
+        "{error}"
+    );
+}
+
+#[test]
+fn each_program_selects_its_elf() {
+    let elfs = [
+        (ProgramSelector::Encrypt, CHACHA_ELF),
+        (ProgramSelector::Reencrypt, ROTATE_ELF),
+        (ProgramSelector::Verifiable, VERIFIABLE_ELF),
+        (ProgramSelector::Multi, MULTI_ELF),
+        (ProgramSelector::Chunk, CHUNK_ELF),
+        (ProgramSelector::Wireguard, WIREGUARD_ELF),
+        (ProgramSelector::Signal, SIGNAL_ELF),
+        (ProgramSelector::Ssh, SSH_ELF),
+        (ProgramSelector::Noise, NOISE_ELF),
+        (ProgramSelector::Tls13, TLS13_ELF),
+        (ProgramSelector::Kdf, KDF_ELF),
+        (ProgramSelector::Upgrade, UPGRADE_ELF),
+        (ProgramSelector::Blake2b, BLAKE2B_ELF),
+        (ProgramSelector::Sha512, SHA512_ELF),
+        (ProgramSelector::Mac, MAC_ELF),
+        (ProgramSelector::Reveal, REVEAL_ELF),
+        (ProgramSelector::Policy, POLICY_ELF),
+        (ProgramSelector::Timelock, TIMELOCK_ELF),
+        (ProgramSelector::Segment, SEGMENT_ELF),
+        (ProgramSelector::Fpe, FPE_ELF),
+        (ProgramSelector::Pedersen, PEDERSEN_ELF),
+        (ProgramSelector::Aggregate, AGGREGATE_ELF),
+        (ProgramSelector::Rekey, REKEY_ELF),
+        (ProgramSelector::Aes, AES_ELF),
+    ];
+    assert_eq!(elfs.len(), ProgramSelector::ALL.len());
+    for (program, elf) in elfs {
+        assert_eq!(select_elf(program), elf, "{program}");
+    }
+}
+
+#[test]
+fn solidity_has_a_constant_per_program() {
+    let vkeys = [
+        (ProgramSelector::Encrypt, format!("0x{}", "01".repeat(32))),
+        (ProgramSelector::Tls13, format!("0x{}", "02".repeat(32))),
+    ];
+    let solidity = solidity_vkeys(&vkeys, None);
+    assert!(
+        solidity.starts_with("// SPDX-License-Identifier: MIT\n"),
+        "{solidity}"
+    );
+    assert!(solidity.contains("library ProgramVKeys {\n"), "{solidity}");
+    assert!(
+        solidity.contains(&format!(
+            "    bytes32 internal constant ENCRYPT = 0x{};\n",
+            "01".repeat(32)
+        )),
+        "{solidity}"
+    );
+    assert!(
+        solidity.contains(&format!(
+            "    bytes32 internal constant TLS13 = 0x{};\n",
+            "02".repeat(32)
+        )),
+        "{solidity}"
+    );
+    assert!(solidity.ends_with("}\n"), "{solidity}");
+    assert!(!solidity.contains("MAX_PLAINTEXT_SIZE"), "{solidity}");
+}
+
+#[test]
+fn solidity_checks_the_max_plaintext_size() {
+    let vkeys = [(ProgramSelector::Encrypt, format!("0x{}", "01".repeat(32)))];
+    let solidity = solidity_vkeys(&vkeys, Some(1024));
+    for line in [
+        "    uint64 internal constant MAX_PLAINTEXT_SIZE = 1024;\n",
+        "    function checkPlaintextSize(uint64 committedSize) internal pure {\n",
+        "        require(committedSize <= MAX_PLAINTEXT_SIZE, \"Plaintext too large\");\n",
+    ] {
+        assert!(solidity.contains(line), "{solidity}");
+    }
+    assert!(solidity.ends_with("    }\n}\n"), "{solidity}");
+}
+
+#[test]
+fn chacha_only_flags_conflict_with_other_programs() {
+    let rejected = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_chacha"))
+            .arg("--execute")
+            .args(args)
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(2), "{args:?}: {stderr}");
+        assert!(stderr.contains("cannot be used with"), "{args:?}: {stderr}");
+    };
+    for mode in ["--blake2b", "--sha512", "--fips-mode"] {
+        for flag in [
+            &["--commit-ciphertext-hash"][..],
+            &["--dry-run"],
+            &["--ipfs"],
+            &["--compress", "lz4"],
+            &["--hmac-key", &"00".repeat(32)],
+        ] {
+            rejected(&[&[mode][..], flag].concat());
+        }
+    }
+    rejected(&["--blake2b", "--sha512"]);
+    rejected(&["--sha512", "--fips-mode"]);
+}
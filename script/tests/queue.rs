@@ -0,0 +1,120 @@
+//! The SQLite proving job queue.
+
+use chacha_script::queue::{JobQueue, JobState, SCHEMA_VERSION};
+
+#[test]
+fn jobs_run_in_insertion_order() {
+    let queue = JobQueue::open_in_memory().unwrap();
+    let first = queue.push(b"first", "ip:127.0.0.1").unwrap();
+    let second = queue.push(b"second", "key:0011223344556677").unwrap();
+    assert_eq!(queue.depth().unwrap(), 2);
+
+    let (id, input, requester) = queue.next_pending().unwrap().unwrap();
+    assert_eq!(
+        (id, input.as_slice(), requester.as_str()),
+        (first, &b"first"[..], "ip:127.0.0.1")
+    );
+    assert_eq!(
+        queue.status(first).unwrap().unwrap().state,
+        JobState::Running
+    );
+    assert_eq!(queue.depth().unwrap(), 1);
+
+    let (id, _, _) = queue.next_pending().unwrap().unwrap();
+    assert_eq!(id, second);
+    assert!(queue.next_pending().unwrap().is_none());
+    assert_eq!(queue.depth().unwrap(), 0);
+}
+
+#[test]
+fn finished_jobs_keep_their_result() {
+    let queue = JobQueue::open_in_memory().unwrap();
+    let ok = queue.push(b"ok", "ip:127.0.0.1").unwrap();
+    let failed = queue.push(b"failed", "ip:127.0.0.1").unwrap();
+    queue.next_pending().unwrap();
+    queue.next_pending().unwrap();
+    queue.complete(ok, "{\"proof\":\"00\"}").unwrap();
+    queue.fail(failed, "out of memory").unwrap();
+
+    let status = queue.status(ok).unwrap().unwrap();
+    assert_eq!(status.state, JobState::Complete);
+    assert_eq!(status.result.as_deref(), Some("{\"proof\":\"00\"}"));
+    let status = queue.status(failed).unwrap().unwrap();
+    assert_eq!(status.state, JobState::Failed);
+    assert_eq!(status.result.as_deref(), Some("out of memory"));
+
+    assert!(queue.status(uuid::Uuid::new_v4()).unwrap().is_none());
+}
+
+#[test]
+fn interrupted_jobs_are_queued_again_on_open() {
+    let path = std::env::temp_dir().join(format!("chacha-queue-{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let queue = JobQueue::open(&path).unwrap();
+    let interrupted = queue.push(b"interrupted", "ip:127.0.0.1").unwrap();
+    let done = queue.push(b"done", "ip:127.0.0.1").unwrap();
+    queue.next_pending().unwrap();
+    queue.next_pending().unwrap();
+    queue.complete(done, "{}").unwrap();
+    drop(queue);
+
+    let queue = JobQueue::open(&path).unwrap();
+    assert_eq!(
+        queue.status(interrupted).unwrap().unwrap().state,
+        JobState::Pending
+    );
+    assert_eq!(
+        queue.status(done).unwrap().unwrap().state,
+        JobState::Complete
+    );
+    let (id, _, _) = queue.next_pending().unwrap().unwrap();
+    assert_eq!(id, interrupted);
+    drop(queue);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn queue_without_requesters_is_migrated() {
+    let path = std::env::temp_dir().join(format!("chacha-queue-v1-{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    // The schema of version 1, before `requester`
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE jobs (
+            id          TEXT PRIMARY KEY,
+            seq         INTEGER NOT NULL,
+            state       TEXT NOT NULL,
+            input       BLOB NOT NULL,
+            created_at  INTEGER NOT NULL,
+            started_at  INTEGER,
+            finished_at INTEGER,
+            result      TEXT
+        );
+        INSERT INTO jobs (id, seq, state, input, created_at)
+        VALUES ('00000000-0000-0000-0000-000000000001', 1, 'Pending', x'6f6c64', 0);",
+    )
+    .unwrap();
+    drop(conn);
+
+    let queue = JobQueue::open(&path).unwrap();
+    let new = queue.push(b"new", "ip:127.0.0.1").unwrap();
+    let (_, input, requester) = queue.next_pending().unwrap().unwrap();
+    assert_eq!(
+        (input.as_slice(), requester.as_str()),
+        (&b"old"[..], "unknown")
+    );
+    let (id, _, requester) = queue.next_pending().unwrap().unwrap();
+    assert_eq!((id, requester.as_str()), (new, "ip:127.0.0.1"));
+    drop(queue);
+
+    // Migrated once, then opened as is
+    let version: i64 = rusqlite::Connection::open(&path)
+        .unwrap()
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(version, SCHEMA_VERSION);
+    JobQueue::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,63 @@
+//! The re-keying program commits the same plaintext hash as the encryption it re-keys, see
+//! `program/src/main_rekey.rs`.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::rekey::Rekey;
+use chacha_lib::{chacha, PublicValues};
+use chacha_script::upgrade::test_inputs;
+use chacha_script::{CHACHA_ELF, REKEY_ELF};
+
+const OLD_KEY: [u8; 32] = [1; 32];
+const OLD_NONCE: [u8; 12] = [2; 12];
+const NEW_KEY: [u8; 32] = [3; 32];
+const NEW_NONCE: [u8; 12] = [4; 12];
+
+fn stdin(old_key_hash: &[u8], ciphertext: &[u8]) -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(old_key_hash);
+    stdin.write_slice(&OLD_KEY);
+    stdin.write_slice(&OLD_NONCE);
+    stdin.write_slice(&NEW_KEY);
+    stdin.write_slice(&NEW_NONCE);
+    stdin.write_slice(ciphertext);
+    stdin
+}
+
+#[test]
+fn rekeys_an_encryption() {
+    let client = ProverClient::builder().mock().build();
+    let (output, _) = client
+        .execute(CHACHA_ELF, &test_inputs(OLD_KEY, OLD_NONCE).to_stdin())
+        .run()
+        .unwrap();
+    let encryption = PublicValues::parse(output.as_slice()).unwrap();
+
+    let (output, _) = client
+        .execute(
+            REKEY_ELF,
+            &stdin(&encryption.key_hash, encryption.ciphertext),
+        )
+        .run()
+        .unwrap();
+    let rekey = Rekey::parse(output.as_slice()).unwrap();
+    assert_eq!(rekey.old_key_hash, encryption.key_hash);
+    assert_eq!(rekey.new_key_hash[..], Sha256::digest(NEW_KEY)[..]);
+    assert_eq!(rekey.plaintext_hash, encryption.plaintext_hash);
+
+    let mut new = chacha_lib::INPUT_BYTES.to_vec();
+    chacha(&NEW_KEY, &NEW_NONCE, &mut new);
+    assert_eq!(rekey.ciphertext_hash[..], Sha256::digest(&new)[..]);
+}
+
+#[test]
+fn old_key_must_match_its_commitment() {
+    let client = ProverClient::builder().mock().build();
+    let mut ciphertext = b"stored record".to_vec();
+    chacha(&OLD_KEY, &OLD_NONCE, &mut ciphertext);
+    let result = client
+        .execute(REKEY_ELF, &stdin(&Sha256::digest([9; 32]), &ciphertext))
+        .run();
+    assert!(result.is_err());
+}
@@ -0,0 +1,158 @@
+//! Relaying one proof to several chains.
+//!
+//! The anvil test needs `anvil` on the `PATH` and the contracts built with `forge build`:
+//!
+//! ```shell
+//! (cd contracts && forge build) && cargo test --release --test relay -- --ignored
+//! ```
+
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::node_bindings::{Anvil, AnvilInstance};
+use alloy::primitives::{Address, B256, U256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::sol_types::SolValue;
+use sp1_sdk::{HashableKey, Prover, ProverClient};
+
+use chacha_lib::PROGRAM_VERSION;
+use chacha_script::post::{verify_calldata, PostError};
+use chacha_script::relay::{relay, RelayChain, RelayConfig};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+
+#[test]
+fn config_parses() {
+    let config: RelayConfig = serde_json::from_str(
+        r#"{
+            "chains": [
+                {
+                    "name": "optimism",
+                    "rpc_url": "https://mainnet.optimism.io",
+                    "contract": "0x00000000000000000000000000000000000000aa",
+                    "chain_id": 10
+                },
+                {
+                    "name": "local",
+                    "rpc_url": "http://localhost:8545",
+                    "contract": "0x00000000000000000000000000000000000000bb"
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.chains,
+        [
+            RelayChain {
+                name: "optimism".to_string(),
+                rpc_url: "https://mainnet.optimism.io".to_string(),
+                contract: Address::with_last_byte(0xaa),
+                chain_id: Some(10),
+            },
+            RelayChain {
+                name: "local".to_string(),
+                rpc_url: "http://localhost:8545".to_string(),
+                contract: Address::with_last_byte(0xbb),
+                chain_id: None,
+            },
+        ]
+    );
+}
+
+/// Deploy `SP1Chacha` with a `MockVerifier` from their `forge build` artifacts.
+async fn deploy(anvil: &AnvilInstance, vkey: B256) -> Address {
+    let signer = PrivateKeySigner::from(anvil.keys()[0].clone());
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer))
+        .on_http(anvil.endpoint_url());
+    let mut address = Address::ZERO;
+    for (file, contract, constructor_args) in [
+        ("MockVerifier.sol", "MockVerifier", vec![]),
+        (
+            "SP1Chacha.sol",
+            "SP1Chacha",
+            (address, U256::from(PROGRAM_VERSION), vkey).abi_encode_params(),
+        ),
+    ] {
+        let path = format!("../contracts/out/{file}/{contract}.json");
+        let artifact: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("{path}: {e}, run forge build")),
+        )
+        .unwrap();
+        let mut code = hex::decode(
+            artifact["bytecode"]["object"]
+                .as_str()
+                .unwrap()
+                .trim_start_matches("0x"),
+        )
+        .unwrap();
+        code.extend(constructor_args);
+        let tx = TransactionRequest::default().with_deploy_code(code);
+        let receipt = provider
+            .send_transaction(tx)
+            .await
+            .unwrap()
+            .get_receipt()
+            .await
+            .unwrap();
+        address = receipt.contract_address.unwrap();
+    }
+    address
+}
+
+#[tokio::test]
+#[ignore = "needs anvil and the forge build artifacts"]
+async fn relays_to_anvil_chains() {
+    let client = ProverClient::builder().mock().build();
+    let (pk, vk) = client.setup(CHACHA_ELF);
+    let stdin = EncryptInputs::new([1; 32], [2; 12], b"relayed plaintext").to_stdin();
+    let proof = client.prove(&pk, &stdin).groth16().run().unwrap();
+    let calldata = verify_calldata(&proof, None).unwrap();
+    let vkey: B256 = vk.bytes32().parse().unwrap();
+
+    let optimism = Anvil::new().chain_id(10).spawn();
+    let arbitrum = Anvil::new().chain_id(42161).spawn();
+    // Anvil funds the same accounts on every chain
+    let private_key = hex::encode(optimism.keys()[0].to_bytes());
+    let chains = [
+        RelayChain {
+            name: "optimism".to_string(),
+            rpc_url: optimism.endpoint(),
+            contract: deploy(&optimism, vkey).await,
+            chain_id: Some(10),
+        },
+        RelayChain {
+            name: "arbitrum".to_string(),
+            rpc_url: arbitrum.endpoint(),
+            contract: deploy(&arbitrum, vkey).await,
+            // Misconfigured, which is only warned about
+            chain_id: Some(1),
+        },
+        RelayChain {
+            name: "down".to_string(),
+            rpc_url: "http://127.0.0.1:1".to_string(),
+            contract: Address::ZERO,
+            chain_id: None,
+        },
+    ];
+
+    let relayed = relay(&chains, &private_key, &calldata, 1).await;
+    let names: Vec<_> = relayed.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["optimism", "arbitrum", "down"]);
+    assert_eq!(relayed[0].other_chain_id, None);
+    assert!(relayed[0].result.as_ref().unwrap().block_number.is_some());
+    assert_eq!(relayed[1].other_chain_id, Some(42161));
+    assert!(relayed[1].result.is_ok());
+    assert!(matches!(relayed[2].result, Err(PostError::Rpc(_))));
+
+    // Every chain has spent the nullifier now
+    let relayed = relay(&chains[..2], &private_key, &calldata, 1).await;
+    for relayed in relayed {
+        assert!(
+            matches!(relayed.result, Err(PostError::Reverted(_))),
+            "{}",
+            relayed.name
+        );
+    }
+}
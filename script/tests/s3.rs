@@ -0,0 +1,70 @@
+//! Proof files, and uploading them to S3.
+//!
+//! The S3 test needs localstack, or any other S3 compatible endpoint:
+//!
+//! ```shell
+//! docker run -d -p 4566:4566 localstack/localstack
+//! AWS_ENDPOINT_URL=http://localhost:4566 AWS_ACCESS_KEY_ID=test AWS_SECRET_ACCESS_KEY=test \
+//!     AWS_REGION=us-east-1 cargo test --release --test s3 -- --ignored
+//! ```
+
+use sp1_sdk::{Prover, ProverClient, SP1ProofWithPublicValues};
+
+use chacha_script::storage::s3::{download_proof, s3_client, upload_proof, S3Error};
+use chacha_script::storage::ProofFile;
+use chacha_script::upgrade::test_inputs;
+use chacha_script::CHACHA_ELF;
+
+fn mock_proof() -> SP1ProofWithPublicValues {
+    let client = ProverClient::builder().mock().build();
+    let (pk, _) = client.setup(CHACHA_ELF);
+    client
+        .prove(&pk, &test_inputs([1; 32], [2; 12]).to_stdin())
+        .groth16()
+        .run()
+        .unwrap()
+}
+
+#[test]
+fn proof_file_matches_saved_proof() {
+    let proof = mock_proof();
+    let path = std::env::temp_dir().join(format!("chacha-proof-file-{}", std::process::id()));
+    proof.save(&path).unwrap();
+
+    let file = ProofFile::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(file, ProofFile::from_proof(&proof));
+    let loaded = file.to_proof().unwrap();
+    assert_eq!(
+        loaded.public_values.as_slice(),
+        proof.public_values.as_slice()
+    );
+    assert_eq!(loaded.bytes(), proof.bytes());
+}
+
+#[tokio::test]
+#[ignore = "needs localstack"]
+async fn uploads_to_localstack() {
+    let bucket = format!("chacha-proofs-{}", std::process::id());
+    s3_client()
+        .await
+        .create_bucket()
+        .bucket(&bucket)
+        .send()
+        .await
+        .unwrap();
+
+    let file = ProofFile::from_proof(&mock_proof());
+    upload_proof(&file, &bucket, "proofs/mock.bin")
+        .await
+        .unwrap();
+    assert_eq!(
+        download_proof(&bucket, "proofs/mock.bin").await.unwrap(),
+        file
+    );
+
+    let error = download_proof(&bucket, "proofs/missing.bin")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, S3Error::Download(_)), "{error}");
+}
@@ -0,0 +1,96 @@
+//! The segment program reveals one plaintext segment, see `program/src/main_segment.rs`.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::merkle::{leaf_hash, merkle_prove, merkle_verify};
+use chacha_lib::selective::{segments_root, SegmentPublicValues, SEGMENT_SIZE};
+use chacha_lib::{chacha, PublicValues};
+use chacha_script::upgrade::test_inputs;
+use chacha_script::{EncryptInputs, CHACHA_ELF, SEGMENT_ELF};
+
+/// 8 segments, the last one shorter.
+fn plaintext() -> Vec<u8> {
+    (0..SEGMENT_SIZE * 7 + 5).map(|i| i as u8).collect()
+}
+
+fn stdin(reveal_index: Option<u32>) -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&[1; 32]);
+    stdin.write_slice(&[2; 12]);
+    stdin.write_slice(&plaintext());
+    stdin.write(&reveal_index);
+    stdin
+}
+
+#[test]
+fn reveals_only_the_requested_segment() {
+    let plaintext = plaintext();
+    let mut ciphertext = plaintext.clone();
+    chacha(&[1; 32], &[2; 12], &mut ciphertext);
+    let client = ProverClient::builder().mock().build();
+
+    for index in [0, 5, 7] {
+        let (output, _) = client
+            .execute(SEGMENT_ELF, &stdin(Some(index)))
+            .run()
+            .unwrap();
+        let values = SegmentPublicValues::parse(output.as_slice()).unwrap();
+        assert_eq!(values.plaintext_hash[..], Sha256::digest(&plaintext)[..]);
+        assert_eq!(values.segments_root, segments_root(&plaintext));
+        assert_eq!(values.ciphertext_hash[..], Sha256::digest(&ciphertext)[..]);
+
+        let (revealed_index, segment) = values.revealed.unwrap();
+        assert_eq!(revealed_index, index);
+        let index = index as usize;
+        assert_eq!(segment, plaintext.chunks(SEGMENT_SIZE).nth(index).unwrap());
+
+        // The opening a verifier checks against the committed root, e.g. with `verifyChunk`
+        let leaves: Vec<_> = plaintext.chunks(SEGMENT_SIZE).map(leaf_hash).collect();
+        let proof = merkle_prove(&leaves, index);
+        assert!(merkle_verify(
+            &values.segments_root,
+            &leaf_hash(segment),
+            &proof
+        ));
+    }
+}
+
+#[test]
+fn reveals_nothing_without_an_index() {
+    let client = ProverClient::builder().mock().build();
+    let (output, _) = client.execute(SEGMENT_ELF, &stdin(None)).run().unwrap();
+    let values = SegmentPublicValues::parse(output.as_slice()).unwrap();
+    assert_eq!(values.revealed, None);
+
+    assert!(client.execute(SEGMENT_ELF, &stdin(Some(8))).run().is_err());
+}
+
+#[test]
+fn encryption_program_commits_the_segments_root() {
+    let client = ProverClient::builder().mock().build();
+    let plaintext = plaintext();
+    for plaintext in [&plaintext[..], &plaintext[..SEGMENT_SIZE], &[0x42], &[]] {
+        for commit_segments in [true, false] {
+            let stdin = EncryptInputs {
+                commit_segments,
+                plaintext,
+                ..test_inputs([1; 32], [2; 12])
+            }
+            .to_stdin();
+            let (output, _) = client.execute(CHACHA_ELF, &stdin).run().unwrap();
+            let values = PublicValues::parse(output.as_slice()).unwrap();
+            let expected = if commit_segments {
+                segments_root(plaintext)
+            } else {
+                [0; 32]
+            };
+            assert_eq!(
+                values.segments_root,
+                expected,
+                "{} bytes, commit_segments {commit_segments}",
+                plaintext.len()
+            );
+        }
+    }
+}
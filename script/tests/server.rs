@@ -0,0 +1,172 @@
+//! Probes and rate limits of the proving server, without a prover.
+
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::num::NonZeroU32;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use chacha_script::queue::JobQueue;
+use chacha_script::rate_limit::{RateLimits, API_KEY_HEADER};
+use chacha_script::server::{router, AppState};
+
+/// Serve the routes on a free port, returning their base URL.
+async fn spawn_server(state: Arc<AppState>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let app = router(state).into_make_service_with_connect_info::<SocketAddr>();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    url
+}
+
+fn state() -> Arc<AppState> {
+    limited_state(RateLimits::new(None, None, None, HashSet::new()))
+}
+
+fn limited_state(rate_limits: RateLimits) -> Arc<AppState> {
+    Arc::new(AppState::new(
+        JobQueue::open_in_memory().unwrap(),
+        rate_limits,
+        None,
+    ))
+}
+
+#[tokio::test]
+async fn ready_once_the_prover_is_set_up() {
+    let state = state();
+    let url = spawn_server(state.clone()).await;
+    let client = reqwest::Client::new();
+
+    let ready = client.get(format!("{url}/ready")).send().await.unwrap();
+    assert_eq!(ready.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    let health = client.get(format!("{url}/health")).send().await.unwrap();
+    assert_eq!(health.status(), reqwest::StatusCode::OK);
+    let health: serde_json::Value = health.json().await.unwrap();
+    assert_eq!(health["status"], "ok");
+    assert_eq!(health["prover_ready"], false);
+    assert_eq!(health["version"], env!("CARGO_PKG_VERSION"));
+
+    state.prover_ready.store(true, Ordering::Release);
+    let ready = client.get(format!("{url}/ready")).send().await.unwrap();
+    assert_eq!(ready.status(), reqwest::StatusCode::OK);
+    let health: serde_json::Value = client
+        .get(format!("{url}/health"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(health["prover_ready"], true);
+}
+
+#[tokio::test]
+async fn health_reports_the_queue_depth() {
+    let state = state();
+    let url = spawn_server(state.clone()).await;
+    let client = reqwest::Client::new();
+
+    for plaintext in ["first", "second"] {
+        let job = client
+            .post(format!("{url}/prove"))
+            .body(plaintext)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(job.status(), reqwest::StatusCode::OK);
+    }
+    let health: serde_json::Value = client
+        .get(format!("{url}/health"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(health["queue_depth"], 2);
+}
+
+/// The status of a `GET /metrics` with `api_key`, which is rate limited like `POST /prove`.
+async fn metrics_status(
+    client: &reqwest::Client,
+    url: &str,
+    api_key: Option<&str>,
+) -> reqwest::StatusCode {
+    let mut request = client.get(format!("{url}/metrics"));
+    if let Some(api_key) = api_key {
+        request = request.header(API_KEY_HEADER, api_key);
+    }
+    request.send().await.unwrap().status()
+}
+
+#[tokio::test]
+async fn request_over_the_key_limit_is_rejected() {
+    let per_key = 3;
+    let url = spawn_server(limited_state(RateLimits::new(
+        NonZeroU32::new(1),
+        NonZeroU32::new(per_key),
+        None,
+        HashSet::from(["alice".to_string()]),
+    )))
+    .await;
+    let client = reqwest::Client::new();
+
+    for _ in 0..per_key {
+        assert_eq!(
+            metrics_status(&client, &url, Some("alice")).await,
+            reqwest::StatusCode::OK
+        );
+    }
+    let rejected = client
+        .get(format!("{url}/metrics"))
+        .header(API_KEY_HEADER, "alice")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(rejected.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = rejected.headers()[reqwest::header::RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(
+        (1..=60).contains(&retry_after),
+        "Retry-After: {retry_after}"
+    );
+
+    // Probes are never limited
+    let health = client.get(format!("{url}/health")).send().await.unwrap();
+    assert_eq!(health.status(), reqwest::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn unknown_keys_share_the_ip_limit() {
+    let per_ip = 2;
+    let url = spawn_server(limited_state(RateLimits::new(
+        NonZeroU32::new(per_ip),
+        NonZeroU32::new(10),
+        None,
+        HashSet::from(["alice".to_string()]),
+    )))
+    .await;
+    let client = reqwest::Client::new();
+
+    // A new key on every request still counts against the IP
+    assert_eq!(
+        metrics_status(&client, &url, None).await,
+        reqwest::StatusCode::OK
+    );
+    assert_eq!(
+        metrics_status(&client, &url, Some("mallory-1")).await,
+        reqwest::StatusCode::OK
+    );
+    assert_eq!(
+        metrics_status(&client, &url, Some("mallory-2")).await,
+        reqwest::StatusCode::TOO_MANY_REQUESTS
+    );
+    // An allowed key from the same IP has its own limit
+    assert_eq!(
+        metrics_status(&client, &url, Some("alice")).await,
+        reqwest::StatusCode::OK
+    );
+}
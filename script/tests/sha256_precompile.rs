@@ -0,0 +1,26 @@
+//! Cycles spent hashing 1 KiB with and without the SP1 SHA-256 precompile, see
+//! `program/src/main_sha256_bench.rs`.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_script::SHA256_BENCH_ELF;
+
+#[test]
+fn precompile_saves_10x_cycles() {
+    let input = [0x61u8; 1024];
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&input);
+
+    let client = ProverClient::builder().mock().build();
+    let (output, report) = client.execute(SHA256_BENCH_ELF, &stdin).run().unwrap();
+    assert_eq!(output.as_slice(), Sha256::digest(input).as_slice());
+
+    let precompile = report.cycle_tracker["sha256-precompile"];
+    let software = report.cycle_tracker["sha256-software"];
+    println!("SHA-256 of 1 KiB: {precompile} cycles with the precompile, {software} without");
+    assert!(
+        software >= 10 * precompile,
+        "precompile saves less than 10x: {precompile} vs {software} cycles"
+    );
+}
@@ -0,0 +1,45 @@
+//! The SHA-512 program, and the cycles of SHA-512 next to the SHA-256 precompile, see
+//! `program/src/main_sha256_bench.rs`.
+
+use sha2::{Digest, Sha512};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::chacha;
+use chacha_lib::public_values::Sha512PublicValues;
+use chacha_script::{SHA256_BENCH_ELF, SHA512_ELF};
+
+#[test]
+fn commits_sha512() {
+    let key = [1u8; 32];
+    let nonce = [2u8; 12];
+    let plaintext = b"plaintext hashed with sha-512";
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&key);
+    stdin.write_slice(&nonce);
+    stdin.write_slice(plaintext);
+
+    let client = ProverClient::builder().mock().build();
+    let (output, _) = client.execute(SHA512_ELF, &stdin).run().unwrap();
+    let output = Sha512PublicValues::parse(output.as_slice()).unwrap();
+    assert_eq!(output.plaintext_hash[..], Sha512::digest(plaintext)[..]);
+    let mut ciphertext = plaintext.to_vec();
+    chacha(&key, &nonce, &mut ciphertext);
+    assert_eq!(output.ciphertext, ciphertext);
+}
+
+#[test]
+fn sha512_cycles() {
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&[0x61u8; 1024]);
+
+    let client = ProverClient::builder().mock().build();
+    let (_, report) = client.execute(SHA256_BENCH_ELF, &stdin).run().unwrap();
+
+    let sha256 = report.cycle_tracker["sha256-precompile"];
+    let sha512 = report.cycle_tracker["sha512"];
+    println!(
+        "Hashing 1 KiB: SHA-256 {sha256} cycles with the precompile, SHA-512 {sha512}, {:.1}x",
+        sha512 as f64 / sha256 as f64
+    );
+    assert!(sha512 > sha256, "SHA-512 has no precompile");
+}
@@ -0,0 +1,92 @@
+//! Byte layout of the encryption program's public values.
+//!
+//! On-chain verifiers parse the public values at fixed offsets, so any change to their layout
+//! fails these tests until the snapshots in `tests/snapshots` are updated on purpose with
+//! `cargo insta review`. Such a change also needs a new `PROGRAM_VERSION`.
+//!
+//! Every optional commitment has its own snapshot. Zstandard is left out, its output depends on
+//! the version of the linked libzstd.
+
+use chacha_lib::{key_from_hex_const, CompressionType};
+use chacha_script::{EncryptInputs, CHACHA_ELF};
+use sp1_sdk::ProverClient;
+
+const KEY: [u8; 32] =
+    key_from_hex_const("5e1f0d3b9a2c4e6f8071a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f7");
+const NONCE: [u8; 12] = *b"snapshot-n01";
+const PLAINTEXT: &[u8] = b"sp1-chacha public values snapshot: sp1-chacha public values snapshot";
+
+fn inputs() -> EncryptInputs<'static> {
+    EncryptInputs::new(KEY, NONCE, PLAINTEXT)
+}
+
+/// Hex encoded public values of executing the program.
+fn public_values(inputs: EncryptInputs) -> String {
+    let client = ProverClient::builder().mock().build();
+    let (output, _) = client
+        .execute(CHACHA_ELF, &inputs.to_stdin())
+        .run()
+        .expect("execution failed");
+    hex::encode(output.as_slice())
+}
+
+#[test]
+fn plain() {
+    insta::assert_snapshot!(public_values(inputs()));
+}
+
+#[test]
+fn lz4() {
+    insta::assert_snapshot!(public_values(EncryptInputs {
+        compression: CompressionType::Lz4,
+        ..inputs()
+    }));
+}
+
+#[test]
+fn segments() {
+    insta::assert_snapshot!(public_values(EncryptInputs {
+        commit_segments: true,
+        ..inputs()
+    }));
+}
+
+#[test]
+fn ciphertext_hash() {
+    insta::assert_snapshot!(public_values(EncryptInputs {
+        commit_ciphertext_hash: true,
+        ..inputs()
+    }));
+}
+
+#[test]
+fn timestamp() {
+    insta::assert_snapshot!(public_values(EncryptInputs {
+        timestamp: Some(vec![0x42; 72]),
+        ..inputs()
+    }));
+}
+
+#[test]
+fn custody() {
+    insta::assert_snapshot!(public_values(EncryptInputs {
+        custody: Some(([0x24; 16], 1_700_000_000)),
+        ..inputs()
+    }));
+}
+
+#[test]
+fn lineage() {
+    insta::assert_snapshot!(public_values(EncryptInputs {
+        lineage: Some([0x11; 32]),
+        ..inputs()
+    }));
+}
+
+#[test]
+fn input_hmac() {
+    insta::assert_snapshot!(public_values(EncryptInputs {
+        input_hmac_key: Some([0x33; 32]),
+        ..inputs()
+    }));
+}
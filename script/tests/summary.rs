@@ -0,0 +1,41 @@
+//! Snapshot of the `ProofSummary` table printed by the `chacha` binary.
+
+use chacha_lib::ProofType;
+use chacha_script::summary::ProofSummary;
+
+const SNAPSHOT: &str = "\
+┌─────────────────┬────────────────────────────────────────────────────────────────────┐
+│ Field           ┆ Value                                                              │
+╞═════════════════╪════════════════════════════════════════════════════════════════════╡
+│ Program version ┆ 1                                                                  │
+│ Proof type      ┆ groth16                                                            │
+│ Plaintext hash  ┆ 0x1111111111111111111111111111111111111111111111111111111111111111 │
+│ Plaintext size  ┆ 68 bytes                                                           │
+│ Ciphertext hash ┆ 0x2222222222222222222222222222222222222222222222222222222222222222 │
+│ Nonce           ┆ 0x333333333333333333333333                                         │
+│ Nullifier       ┆ 0x4444444444444444444444444444444444444444444444444444444444444444 │
+│ Program hash    ┆ -                                                                  │
+│ Chain ID        ┆ 1                                                                  │
+│ Expiry          ┆ -                                                                  │
+│ Proof size      ┆ 1234 bytes                                                         │
+│ IPFS CID        ┆ -                                                                  │
+└─────────────────┴────────────────────────────────────────────────────────────────────┘";
+
+#[test]
+fn display_snapshot() {
+    let summary = ProofSummary {
+        version: 1,
+        proof_type: Some(ProofType::Groth16),
+        plaintext_hash: format!("0x{}", "11".repeat(32)),
+        plaintext_size: Some(68),
+        ciphertext_hash: format!("0x{}", "22".repeat(32)),
+        nonce: Some(format!("0x{}", "33".repeat(12))),
+        nullifier: format!("0x{}", "44".repeat(32)),
+        program_hash: None,
+        chain_id: Some(1),
+        expiry: None,
+        proof_size_bytes: Some(1234),
+        ipfs_cid: None,
+    };
+    assert_eq!(summary.to_string(), SNAPSHOT);
+}
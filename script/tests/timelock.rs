@@ -0,0 +1,56 @@
+//! The time-lock program, see `program/src/main_timelock.rs`.
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{ProverClient, SP1Stdin};
+
+use chacha_lib::chacha;
+use chacha_lib::timelock::{BlockHeader, TimelockPublicValues};
+use chacha_script::TIMELOCK_ELF;
+
+const KEY: [u8; 32] = [1; 32];
+const PLAINTEXT: &[u8] = b"sealed until block 1000";
+
+fn stdin(unlock_block: u64, header: &BlockHeader) -> (SP1Stdin, Vec<u8>) {
+    let mut ciphertext = PLAINTEXT.to_vec();
+    chacha(&KEY, &[2; 12], &mut ciphertext);
+    let mut stdin = SP1Stdin::new();
+    stdin.write_slice(&KEY);
+    stdin.write_slice(&[2; 12]);
+    stdin.write_slice(&ciphertext);
+    stdin.write(&unlock_block);
+    stdin.write_slice(&header.to_bytes());
+    (stdin, ciphertext)
+}
+
+fn header(number: u64) -> BlockHeader {
+    BlockHeader {
+        parent_hash: [7; 32],
+        number,
+        timestamp: 1_700_000_000 + number * 12,
+        state_root: [8; 32],
+    }
+}
+
+#[test]
+fn decrypts_at_the_unlock_block() {
+    let client = ProverClient::builder().mock().build();
+    for number in [1000, 1001] {
+        let (stdin, ciphertext) = stdin(1000, &header(number));
+        let (output, _) = client.execute(TIMELOCK_ELF, &stdin).run().unwrap();
+
+        let values = TimelockPublicValues::parse(output.as_slice()).unwrap();
+        assert_eq!(values.unlock_block, 1000);
+        assert_eq!(values.block_number, number);
+        assert_eq!(values.block_hash, header(number).hash());
+        assert_eq!(values.key_hash[..], Sha256::digest(KEY)[..]);
+        assert_eq!(values.ciphertext_hash[..], Sha256::digest(&ciphertext)[..]);
+        assert_eq!(values.plaintext, PLAINTEXT);
+    }
+}
+
+#[test]
+fn refuses_before_the_unlock_block() {
+    let client = ProverClient::builder().mock().build();
+    let (stdin, _) = stdin(1000, &header(999));
+    assert!(client.execute(TIMELOCK_ELF, &stdin).run().is_err());
+}
@@ -0,0 +1,112 @@
+//! Fetching signed timestamps from the `mock_time_oracle` binary.
+
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chacha_lib::envelope::ed25519_dalek::SigningKey;
+use chacha_script::timestamp::{fetch_timestamp, TimestampError};
+
+const SECRET: [u8; 32] = [5; 32];
+const NONCE: [u8; 32] = [8; 32];
+
+/// The oracle, killed on drop.
+struct Oracle {
+    child: Child,
+    url: String,
+}
+
+impl Drop for Oracle {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Start the oracle on a free port with the key `SECRET`, once it accepts connections.
+fn oracle() -> Oracle {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let child = Command::new(env!("CARGO_BIN_EXE_mock_time_oracle"))
+        .args(["--port", &port.to_string()])
+        .args(["--secret", &hex::encode(SECRET)])
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    let oracle = Oracle {
+        child,
+        url: format!("http://127.0.0.1:{port}/"),
+    };
+    for _ in 0..100 {
+        if TcpStream::connect(("127.0.0.1", port)).is_ok() {
+            return oracle;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    panic!("the oracle did not start on port {port}");
+}
+
+fn oracle_key() -> [u8; 32] {
+    SigningKey::from_bytes(&SECRET).verifying_key().to_bytes()
+}
+
+#[test]
+fn good_signature_is_accepted() {
+    let oracle = oracle();
+    let before = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let timestamp = fetch_timestamp(&oracle.url, &oracle_key(), &NONCE).unwrap();
+    let after = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    assert!((before.as_secs()..=after.as_secs()).contains(&timestamp.timestamp));
+    assert!(timestamp.verify(&oracle_key(), &NONCE).is_ok());
+
+    let bytes = timestamp.to_bytes();
+    assert_eq!(bytes[..8], timestamp.timestamp.to_le_bytes());
+    assert_eq!(bytes[8..], timestamp.signature);
+}
+
+#[test]
+fn bad_signature_is_rejected() {
+    let oracle = oracle();
+    let timestamp = fetch_timestamp(&oracle.url, &oracle_key(), &NONCE).unwrap();
+
+    let mut forged = timestamp.clone();
+    forged.signature[0] ^= 1;
+    assert!(forged.verify(&oracle_key(), &NONCE).is_err());
+
+    // The signature covers the time and the nonce
+    let mut later = timestamp.clone();
+    later.timestamp += 1;
+    assert!(later.verify(&oracle_key(), &NONCE).is_err());
+    assert!(timestamp.verify(&oracle_key(), &[9; 32]).is_err());
+}
+
+#[test]
+fn wrong_pinned_key_is_rejected() {
+    let oracle = oracle();
+    let other = SigningKey::from_bytes(&[6; 32]).verifying_key().to_bytes();
+    assert!(matches!(
+        fetch_timestamp(&oracle.url, &other, &NONCE),
+        Err(TimestampError::Signature(_))
+    ));
+}
+
+/// Against a live oracle, `TIMESTAMP_URL` with the pinned key `TIMESTAMP_KEY`.
+#[test]
+#[ignore = "needs a live time oracle"]
+fn live_oracle() {
+    let url = std::env::var("TIMESTAMP_URL").expect("TIMESTAMP_URL is not set");
+    let key: [u8; 32] =
+        hex::decode(std::env::var("TIMESTAMP_KEY").expect("TIMESTAMP_KEY is not set"))
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let timestamp = fetch_timestamp(&url, &key, &NONCE).unwrap();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    assert!(timestamp.timestamp.abs_diff(now) < 60);
+}
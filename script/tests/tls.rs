@@ -0,0 +1,118 @@
+//! Mutual TLS of the proving server with generated certificates, without a prover.
+
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axum_server::tls_rustls::RustlsConfig;
+use reqwest::{Certificate, Identity};
+
+use chacha_script::queue::JobQueue;
+use chacha_script::rate_limit::RateLimits;
+use chacha_script::server::{router, AppState};
+use chacha_script::tls::{generate_certs, server_config, DevCerts};
+
+fn temp_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("chacha-tls-{name}-{}", std::process::id()))
+}
+
+/// Serve the routes with mutual TLS on a free port, returning their base URL.
+fn spawn_server(dir: &Path) -> String {
+    let config = server_config(
+        &dir.join("ca.pem"),
+        &dir.join("server.pem"),
+        &dir.join("server.key"),
+    )
+    .unwrap();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let url = format!(
+        "https://localhost:{}",
+        listener.local_addr().unwrap().port()
+    );
+
+    let state = Arc::new(AppState::new(
+        JobQueue::open_in_memory().unwrap(),
+        RateLimits::new(None, None, None, HashSet::new()),
+        None,
+    ));
+    let app = router(state).into_make_service_with_connect_info::<SocketAddr>();
+    let server =
+        axum_server::from_tcp_rustls(listener, RustlsConfig::from_config(Arc::new(config)));
+    tokio::spawn(async move { server.serve(app).await.unwrap() });
+    url
+}
+
+fn client(certs: &DevCerts, identity: Option<&DevCerts>) -> reqwest::Client {
+    let mut client = reqwest::Client::builder()
+        .use_rustls_tls()
+        .add_root_certificate(Certificate::from_pem(certs.ca_cert.as_bytes()).unwrap());
+    if let Some(identity) = identity {
+        let pem = format!("{}{}", identity.client_cert, identity.client_key);
+        client = client.identity(Identity::from_pem(pem.as_bytes()).unwrap());
+    }
+    client.build().unwrap()
+}
+
+#[tokio::test]
+async fn accepts_clients_signed_by_the_ca() {
+    let dir = temp_dir("accept");
+    let certs = generate_certs(vec!["localhost".into()]).unwrap();
+    certs.write(&dir).unwrap();
+    let url = spawn_server(&dir);
+
+    let health = client(&certs, Some(&certs))
+        .get(format!("{url}/health"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(health.status(), reqwest::StatusCode::OK);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn rejects_clients_without_a_certificate() {
+    let dir = temp_dir("anonymous");
+    let certs = generate_certs(vec!["localhost".into()]).unwrap();
+    certs.write(&dir).unwrap();
+    let url = spawn_server(&dir);
+
+    let health = client(&certs, None)
+        .get(format!("{url}/health"))
+        .send()
+        .await;
+    assert!(health.is_err(), "{health:?}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn rejects_clients_signed_by_another_ca() {
+    let dir = temp_dir("other-ca");
+    let certs = generate_certs(vec!["localhost".into()]).unwrap();
+    certs.write(&dir).unwrap();
+    let url = spawn_server(&dir);
+
+    let other = generate_certs(vec!["localhost".into()]).unwrap();
+    let health = client(&certs, Some(&other))
+        .get(format!("{url}/health"))
+        .send()
+        .await;
+    assert!(health.is_err(), "{health:?}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn missing_certificate_files_are_reported() {
+    let dir = temp_dir("missing");
+    let error = server_config(
+        &dir.join("ca.pem"),
+        &dir.join("server.pem"),
+        &dir.join("server.key"),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("ca.pem"), "{error}");
+}
@@ -0,0 +1,109 @@
+//! A full upgrade cycle with mock proofs: both programs encrypt the test input, and the upgrade
+//! program links their verification keys.
+//!
+//! There is no older release in the tree, so the old program is simulated by a version 1 mock
+//! proof under the key of another program.
+
+use alloy::sol_types::SolCall;
+use sp1_sdk::{
+    HashableKey, Prover, ProverClient, SP1ProofMode, SP1ProofWithPublicValues, SP1PublicValues,
+};
+
+use chacha_lib::public_values::{HEADER_LEN, V1_HEADER_LEN};
+use chacha_lib::upgrade::{vkey_bytes32, Upgrade, UpgradeError};
+use chacha_lib::PROGRAM_VERSION;
+use chacha_script::post::{upgrade_calldata, ISP1Chacha};
+use chacha_script::upgrade::{test_inputs, upgrade_stdin, MigrateError};
+use chacha_script::{CHACHA_ELF, ROTATE_ELF, UPGRADE_ELF};
+
+/// Version 1 public values of the same encryption as `new`, which drop the custody hash and
+/// lineage commitment.
+fn v1_public_values(new: &[u8]) -> Vec<u8> {
+    let mut old = vec![1];
+    old.extend(&new[1..V1_HEADER_LEN]);
+    old.extend(&new[HEADER_LEN..]);
+    old
+}
+
+#[test]
+fn upgrade_cycle() {
+    let client = ProverClient::builder().mock().build();
+    let (new_pk, new_vk) = client.setup(CHACHA_ELF);
+    let (old_pk, old_vk) = client.setup(ROTATE_ELF);
+    let (upgrade_pk, upgrade_vk) = client.setup(UPGRADE_ELF);
+
+    let inputs = test_inputs([7; 32], [8; 12]);
+    let new = client
+        .prove(&new_pk, &inputs.to_stdin())
+        .compressed()
+        .run()
+        .unwrap();
+    let old = SP1ProofWithPublicValues::create_mock_proof(
+        &old_pk,
+        SP1PublicValues::from(&v1_public_values(new.public_values.as_slice())),
+        SP1ProofMode::Compressed,
+        &new.sp1_version,
+    );
+
+    let stdin = upgrade_stdin(&old_vk, &old, &new_vk, &new).unwrap();
+    // Mock proofs do not verify, only the upgrade program's own checks run
+    let (output, _) = client
+        .execute(UPGRADE_ELF, &stdin)
+        .deferred_proof_verification(false)
+        .run()
+        .unwrap();
+    let upgrade = Upgrade::parse(output.as_slice()).unwrap();
+    assert_eq!(upgrade.old_version, 1);
+    assert_eq!(upgrade.new_version, PROGRAM_VERSION);
+    assert_eq!(upgrade.old_vkey, vkey_bytes32(&old_vk.hash_u32()));
+    assert_eq!(upgrade.new_vkey, vkey_bytes32(&new_vk.hash_u32()));
+    // The committed keys are the ones SP1Chacha registers
+    assert_eq!(upgrade.new_vkey, new_vk.bytes32_raw());
+    assert_eq!(
+        format!("0x{}", hex::encode(upgrade.new_vkey)),
+        new_vk.bytes32()
+    );
+
+    // The mock upgrade proof submitted to SP1Chacha.upgrade
+    let proof = SP1ProofWithPublicValues::create_mock_proof(
+        &upgrade_pk,
+        output,
+        SP1ProofMode::Groth16,
+        &new.sp1_version,
+    );
+    client.verify(&proof, &upgrade_vk).unwrap();
+    let call =
+        ISP1Chacha::upgradeCall::abi_decode(&upgrade_calldata(&proof).unwrap(), true).unwrap();
+    assert_eq!(call.publicValues[..], upgrade.to_bytes());
+}
+
+#[test]
+fn mismatching_proofs_are_rejected() {
+    let client = ProverClient::builder().mock().build();
+    let (pk, vk) = client.setup(CHACHA_ELF);
+    let new = client
+        .prove(&pk, &test_inputs([7; 32], [8; 12]).to_stdin())
+        .compressed()
+        .run()
+        .unwrap();
+    let other = client
+        .prove(&pk, &test_inputs([9; 32], [8; 12]).to_stdin())
+        .compressed()
+        .run()
+        .unwrap();
+    let old = SP1ProofWithPublicValues::create_mock_proof(
+        &pk,
+        SP1PublicValues::from(&v1_public_values(other.public_values.as_slice())),
+        SP1ProofMode::Compressed,
+        &new.sp1_version,
+    );
+    assert!(matches!(
+        upgrade_stdin(&vk, &old, &vk, &new),
+        Err(MigrateError::Upgrade(UpgradeError::Mismatch("nullifier")))
+    ));
+
+    assert!(matches!(
+        upgrade_stdin(&vk, &new, &vk, &new),
+        Err(MigrateError::Upgrade(UpgradeError::NotNewer { .. }))
+    ));
+}
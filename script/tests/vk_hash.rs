@@ -0,0 +1,16 @@
+//! `compute_vk_hash` is the key the `vkey` binary prints.
+
+use sp1_sdk::{HashableKey, Prover, ProverClient};
+
+use chacha_script::{compute_vk_hash, CHACHA_ELF, ROTATE_ELF};
+
+#[test]
+fn matches_the_vkey_binary() {
+    let (_, vk) = ProverClient::builder().cpu().build().setup(CHACHA_ELF);
+    let vk_hash = compute_vk_hash(CHACHA_ELF);
+    assert_eq!(format!("0x{}", hex::encode(vk_hash)), vk.bytes32());
+
+    // Cached, and per ELF
+    assert_eq!(compute_vk_hash(CHACHA_ELF), vk_hash);
+    assert_ne!(compute_vk_hash(ROTATE_ELF), vk_hash);
+}
@@ -0,0 +1,70 @@
+//! The `vk_registry` binary refuses to replace the key of a released version without `--force`.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use sp1_sdk::{HashableKey, Prover, ProverClient};
+
+use chacha_lib::vk_registry::VkRegistry;
+use chacha_lib::PROGRAM_VERSION;
+use chacha_script::CHACHA_ELF;
+
+fn registry_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "chacha-vk-registry-{name}-{}.json",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn run(registry: &Path, force: bool) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_vk_registry"));
+    command.arg("--registry").arg(registry);
+    if force {
+        command.arg("--force");
+    }
+    command.output().unwrap()
+}
+
+fn read(registry: &Path) -> VkRegistry {
+    VkRegistry::from_json(&std::fs::read_to_string(registry).unwrap()).unwrap()
+}
+
+#[test]
+fn registers_the_current_key() {
+    let (_, vk) = ProverClient::builder().mock().build().setup(CHACHA_ELF);
+    let path = registry_file("new");
+
+    let output = run(&path, false);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(read(&path).get(PROGRAM_VERSION), Some(&vk.bytes32_raw()));
+
+    // Running again keeps the file as it is
+    let json = std::fs::read_to_string(&path).unwrap();
+    let output = run(&path, false);
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("already registered"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), json);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn replacing_a_released_key_needs_force() {
+    let (_, vk) = ProverClient::builder().mock().build().setup(CHACHA_ELF);
+    let path = registry_file("force");
+    let mut registry = VkRegistry::new();
+    registry.insert(PROGRAM_VERSION, [0xaa; 32]);
+    std::fs::write(&path, registry.to_json()).unwrap();
+
+    let output = run(&path, false);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("pass --force to replace it"), "{stderr}");
+    assert_eq!(read(&path), registry);
+
+    let output = run(&path, true);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(read(&path).get(PROGRAM_VERSION), Some(&vk.bytes32_raw()));
+    std::fs::remove_file(&path).unwrap();
+}
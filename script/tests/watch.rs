@@ -0,0 +1,186 @@
+//! Proving the files arriving in a watched directory, with a stand-in prover.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use sp1_prover::Groth16Bn254Proof;
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues, SP1PublicValues};
+
+use chacha_script::storage::load_proof;
+use chacha_script::watch::{proof_path, sidecar_path, watch, Processed, ProofSidecar, WatchDirs};
+
+/// A proof whose public values are the plaintext, failing for plaintexts starting with `fail`.
+fn fake_prove(plaintext: &[u8], _nonce: [u8; 12]) -> Result<SP1ProofWithPublicValues, String> {
+    if plaintext.starts_with(b"fail") {
+        return Err("proving failed".to_string());
+    }
+    Ok(SP1ProofWithPublicValues {
+        proof: SP1Proof::Groth16(Groth16Bn254Proof {
+            public_inputs: [String::new(), String::new()],
+            encoded_proof: String::new(),
+            raw_proof: String::new(),
+            groth16_vkey_hash: [0; 32],
+        }),
+        public_values: SP1PublicValues::from(plaintext),
+        sp1_version: String::new(),
+    })
+}
+
+fn names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<_> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+#[test]
+fn proofs_appear_for_new_files() {
+    let root = std::env::temp_dir().join("sp1-chacha-watch-test");
+    let _ = std::fs::remove_dir_all(&root);
+    let dirs = WatchDirs {
+        input: root.join("incoming"),
+        output: root.join("proofs"),
+        processed: Some(root.join("done")),
+        error: Some(root.join("failed")),
+        delete_processed: false,
+    };
+    std::fs::create_dir_all(&dirs.input).unwrap();
+    // Proved on start
+    std::fs::write(dirs.input.join("existing.bin"), b"existing").unwrap();
+
+    let stop = AtomicBool::new(false);
+    let (tx, rx) = mpsc::channel();
+    std::thread::scope(|s| {
+        let watcher = s.spawn(|| {
+            watch(
+                &dirs,
+                fake_prove,
+                |processed| tx.send(processed).unwrap(),
+                &stop,
+            )
+        });
+        let next = || -> Processed { rx.recv_timeout(Duration::from_secs(10)).unwrap() };
+
+        let existing = next();
+        assert_eq!(existing.input, dirs.input.join("existing.bin"));
+
+        std::fs::write(dirs.input.join("sensor-1.bin"), b"reading 1").unwrap();
+        let sensor = next();
+        assert_eq!(
+            sensor.result,
+            Ok(proof_path(&dirs.output, &dirs.input.join("sensor-1.bin")))
+        );
+
+        // Moved in, as by writers that rename a finished temporary file
+        std::fs::write(root.join("sensor-2.bin"), b"reading 2").unwrap();
+        std::fs::rename(root.join("sensor-2.bin"), dirs.input.join("sensor-2.bin")).unwrap();
+        next().result.unwrap();
+
+        std::fs::write(dirs.input.join(".partial"), b"skipped").unwrap();
+        std::fs::write(dirs.input.join("broken.bin"), b"fail").unwrap();
+        let broken = next();
+        assert_eq!(broken.input, dirs.input.join("broken.bin"));
+        assert_eq!(broken.result, Err("proving failed".to_string()));
+
+        stop.store(true, Ordering::Relaxed);
+        watcher.join().unwrap().unwrap();
+    });
+
+    assert_eq!(
+        names(&dirs.output),
+        [
+            "existing.bin.proof.bin",
+            "existing.bin.proof.json",
+            "sensor-1.bin.proof.bin",
+            "sensor-1.bin.proof.json",
+            "sensor-2.bin.proof.bin",
+            "sensor-2.bin.proof.json",
+        ]
+    );
+    let proof = load_proof(&dirs.output.join("sensor-1.bin.proof.bin")).unwrap();
+    assert_eq!(proof.public_values.as_slice(), b"reading 1");
+    let sidecar = |name: &str| -> ProofSidecar {
+        let path = sidecar_path(&dirs.output, &dirs.input.join(name));
+        serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap()
+    };
+    // A fresh nonce per file
+    assert_ne!(sidecar("sensor-1.bin"), sidecar("sensor-2.bin"));
+    assert_eq!(
+        names(&dirs.processed.clone().unwrap()),
+        ["existing.bin", "sensor-1.bin", "sensor-2.bin"]
+    );
+    assert_eq!(names(&dirs.error.clone().unwrap()), ["broken.bin"]);
+    assert_eq!(names(&dirs.input), [".partial"]);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn output_dir_must_not_be_the_watched_dir() {
+    let dir = std::env::temp_dir().join("sp1-chacha-watch-test-same");
+    let dirs = WatchDirs {
+        input: dir.clone(),
+        output: dir.clone(),
+        processed: None,
+        error: None,
+        delete_processed: false,
+    };
+    let stop = AtomicBool::new(true);
+    let e = watch(&dirs, fake_prove, |_| {}, &stop).unwrap_err();
+    assert!(e.contains("is the watched directory"), "{e}");
+}
+
+/// Record the nonce each file is proved with, and watch until `count` files are processed.
+fn watch_files(dirs: &WatchDirs, count: usize) -> Vec<[u8; 12]> {
+    let stop = AtomicBool::new(false);
+    let mut nonces = Vec::new();
+    let mut processed = 0;
+    let prove = |plaintext: &[u8], nonce| {
+        nonces.push(nonce);
+        fake_prove(plaintext, nonce)
+    };
+    let on_processed = |processed_file: Processed| {
+        processed_file.result.unwrap();
+        processed += 1;
+        if processed == count {
+            stop.store(true, Ordering::Relaxed);
+        }
+    };
+    watch(dirs, prove, on_processed, &stop).unwrap();
+    nonces
+}
+
+#[test]
+fn proved_files_are_kept_unless_deletion_is_asked_for() {
+    let root = std::env::temp_dir().join(format!("chacha-watch-keep-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    let mut dirs = WatchDirs {
+        input: root.join("incoming"),
+        output: root.join("proofs"),
+        processed: None,
+        error: None,
+        delete_processed: false,
+    };
+    std::fs::create_dir_all(&dirs.input).unwrap();
+    std::fs::write(dirs.input.join("a.bin"), b"a").unwrap();
+
+    let nonces = watch_files(&dirs, 1);
+    assert_eq!(names(&dirs.input), ["a.bin"]);
+    let sidecar: ProofSidecar = serde_json::from_slice(
+        &std::fs::read(sidecar_path(&dirs.output, &dirs.input.join("a.bin"))).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(sidecar.nonce, nonces[0]);
+
+    dirs.delete_processed = true;
+    watch_files(&dirs, 1);
+    assert!(names(&dirs.input).is_empty());
+    assert_eq!(names(&dirs.output), ["a.bin.proof.bin", "a.bin.proof.json"]);
+    std::fs::remove_dir_all(&root).unwrap();
+}
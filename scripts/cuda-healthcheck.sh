@@ -0,0 +1,10 @@
+#!/usr/bin/env sh
+# Verify a CUDA device is visible inside the container.
+set -e
+
+if ! command -v nvidia-smi >/dev/null 2>&1; then
+    echo "nvidia-smi not found: is the NVIDIA Container Toolkit installed on the host?" >&2
+    exit 1
+fi
+
+nvidia-smi --query-gpu=name,memory.total --format=csv,noheader
@@ -0,0 +1,15 @@
+#!/usr/bin/env sh
+# Create a development CA with a server and a client certificate for the mutual TLS of the
+# proving server, in the given directory or certs/:
+#   ./scripts/gen_certs.sh [out-dir] [server-name...]
+# The server certificate is valid for localhost and 127.0.0.1 unless server names are given.
+set -e
+
+OUT_DIR="${1:-certs}"
+[ "$#" -gt 0 ] && shift
+
+if [ "$#" -gt 0 ]; then
+    cargo run --release -p chacha-script --bin gen_certs -- --out-dir "$OUT_DIR" --server-name "$@"
+else
+    cargo run --release -p chacha-script --bin gen_certs -- --out-dir "$OUT_DIR"
+fi
@@ -0,0 +1,36 @@
+#!/usr/bin/env sh
+# Start a proving server and run the client examples against it, all of them or those given:
+#   ./scripts/run-examples.sh [rust] [typescript] [python]
+# Proves with SP1_PROVER=mock unless set, the examples only exercise the API.
+set -e
+
+PORT="${PORT:-3000}"
+CLIENTS="${*:-rust typescript python}"
+export SERVER_URL="http://localhost:$PORT"
+export SP1_PROVER="${SP1_PROVER:-mock}"
+export ENCRYPTION_KEY="${ENCRYPTION_KEY:-$(openssl rand -hex 32)}"
+
+cargo build --release -p chacha-script --bin server
+cargo build --release -p chacha-client-example
+
+QUEUE_DB="$(mktemp)"
+target/release/server --port "$PORT" --queue-db "$QUEUE_DB" &
+SERVER_PID=$!
+trap 'kill $SERVER_PID; rm -f "$QUEUE_DB"' EXIT
+
+until curl --silent --fail "$SERVER_URL/health" >/dev/null; do
+    sleep 1
+done
+
+for client in $CLIENTS; do
+    echo "== $client client"
+    case "$client" in
+        rust) target/release/chacha-client-example ;;
+        typescript) (cd examples/typescript && npx --yes tsx client.ts) ;;
+        python) python3 examples/python/client.py ;;
+        *)
+            echo "unknown client $client, expected rust, typescript or python" >&2
+            exit 1
+            ;;
+    esac
+done